@@ -0,0 +1,112 @@
+//! Compute-unit baseline for the Smart Vault V2 benchmark
+//!
+//! `tests/compute_units.rs` simulates each instruction under program-test and
+//! compares the units consumed against `compute_units.txt`, which holds one
+//! `name units` pair per line.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Growth over the baseline tolerated before a case counts as a regression, in percent
+pub const DEFAULT_THRESHOLD_PCT: u64 = 5;
+
+/// Compute units per benchmark case, keyed by case name
+pub type Baseline = BTreeMap<String, u64>;
+
+/// Parse a baseline file; blank lines and `#` comments are skipped
+pub fn parse_baseline(text: &str) -> Result<Baseline, String> {
+    let mut baseline = Baseline::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(units), None) = (fields.next(), fields.next(), fields.next()) else {
+            return Err(format!("line {}: expected `name units`", number + 1));
+        };
+        let units = units
+            .parse()
+            .map_err(|_| format!("line {}: bad unit count {units:?}", number + 1))?;
+        baseline.insert(name.to_string(), units);
+    }
+    Ok(baseline)
+}
+
+/// Render a baseline in the format [`parse_baseline`] reads
+pub fn format_baseline(baseline: &Baseline) -> String {
+    let mut text = String::from("# Compute units per instruction; regenerate with CU_BENCH_UPDATE=1\n");
+    for (name, units) in baseline {
+        text.push_str(&format!("{name} {units}\n"));
+    }
+    text
+}
+
+/// A case that grew past the allowed threshold
+#[derive(Debug, PartialEq, Eq)]
+pub struct Regression {
+    pub name: String,
+    pub baseline: u64,
+    pub measured: u64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let growth = (self.measured - self.baseline) as f64 * 100.0 / self.baseline.max(1) as f64;
+        write!(f, "{}: {} -> {} CU (+{growth:.1}%)", self.name, self.baseline, self.measured)
+    }
+}
+
+/// Cases whose measured units exceed their baseline by more than `threshold_pct` percent
+///
+/// Cases missing from the baseline are new and never count as regressions.
+pub fn regressions(baseline: &Baseline, measured: &Baseline, threshold_pct: u64) -> Vec<Regression> {
+    measured
+        .iter()
+        .filter_map(|(name, &measured)| {
+            let &baseline = baseline.get(name)?;
+            let allowed = baseline as u128 * (100 + threshold_pct as u128);
+            (measured as u128 * 100 > allowed).then(|| Regression { name: name.clone(), baseline, measured })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline(cases: &[(&str, u64)]) -> Baseline {
+        cases.iter().map(|(name, units)| (name.to_string(), *units)).collect()
+    }
+
+    #[test]
+    fn baseline_round_trips() {
+        let units = baseline(&[("deposit", 9_000), ("batch_settle_10", 180_000)]);
+        assert_eq!(parse_baseline(&format_baseline(&units)).unwrap(), units);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_lines() {
+        assert!(parse_baseline("deposit").is_err());
+        assert!(parse_baseline("deposit lots").is_err());
+        assert!(parse_baseline("deposit 1 2").is_err());
+        assert_eq!(parse_baseline("\n# comment\n  withdraw 7  \n").unwrap(), baseline(&[("withdraw", 7)]));
+    }
+
+    #[test]
+    fn growth_at_threshold_is_allowed() {
+        let old = baseline(&[("bet_and_settle_win", 10_000)]);
+        assert!(regressions(&old, &baseline(&[("bet_and_settle_win", 10_500)]), 5).is_empty());
+        assert_eq!(
+            regressions(&old, &baseline(&[("bet_and_settle_win", 10_501)]), 5),
+            vec![Regression { name: "bet_and_settle_win".to_string(), baseline: 10_000, measured: 10_501 }]
+        );
+    }
+
+    #[test]
+    fn new_and_cheaper_cases_pass() {
+        let old = baseline(&[("deposit", 9_000)]);
+        let new = baseline(&[("deposit", 8_000), ("batch_settle_11", 200_000)]);
+        assert!(regressions(&old, &new, 0).is_empty());
+    }
+}
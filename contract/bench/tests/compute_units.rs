@@ -0,0 +1,301 @@
+//! Compute units consumed by each player, settlement and operator instruction
+//!
+//! Loads the SBF build, so run `anchor build` first, then
+//! `cargo test --manifest-path bench/Cargo.toml -- --nocapture`.
+//! Transactions are simulated, which skips signature checks and lets the
+//! hardcoded admin and multisig act without their keys. Every case runs
+//! against the same seeded state.
+//!
+//! * `CU_BENCH_UPDATE=1` rewrites `compute_units.txt` with the measured units
+//! * `CU_BENCH_THRESHOLD_PCT` sets the tolerated growth (default 5%)
+
+use anchor_lang::{AccountSerialize, Discriminator};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::transaction::Transaction;
+
+use smart_vault_v2::{
+    Blacklist, GameLimits, GlobalConfig, GlobalStats, HouseVault, PauseConfig, UserVault, BLACKLIST_SPACE,
+    GAME_CATEGORIES, GAME_LIMITS_SPACE, GLOBAL_CONFIG_SPACE, GLOBAL_CONFIG_VERSION, GLOBAL_STATS_VERSION, HOUSE_VERSION,
+    PAUSE_CONFIG_SPACE, VAULT_VERSION,
+};
+use sols_bet_bench::{format_baseline, parse_baseline, regressions, Baseline, DEFAULT_THRESHOLD_PCT};
+use sols_bet_sdk::instructions::{self, Settlement, SettlementExtras};
+use sols_bet_sdk::{pda, PROGRAM_ID};
+
+const BASELINE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/compute_units.txt");
+
+/// Largest batch measured; `batch_settle_1` through `batch_settle_10` are recorded
+const MAX_BATCH: usize = 10;
+
+fn admin() -> Pubkey {
+    "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap()
+}
+
+fn multisig() -> Pubkey {
+    "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap()
+}
+
+fn wallet(lamports: u64) -> Account {
+    Account::new(lamports, 0, &solana_sdk::system_program::ID)
+}
+
+/// Program-owned account holding `data`, zero-padded to `space` bytes
+fn program_account(mut data: Vec<u8>, space: usize, lamports: u64) -> Account {
+    data.resize(space, 0);
+    Account {
+        lamports: lamports.max(Rent::default().minimum_balance(space)),
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn borsh_account<T: AccountSerialize>(account: &T, space: usize) -> Account {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    program_account(data, space, 0)
+}
+
+fn zero_copy_account<T: Discriminator + bytemuck::Pod>(account: &T, lamports: u64) -> Account {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(account));
+    let space = data.len();
+    program_account(data, space, lamports)
+}
+
+fn seed_vault(program_test: &mut ProgramTest, owner: &Pubkey) {
+    let (address, bump) = pda::vault(owner);
+    let vault = UserVault {
+        owner: *owner,
+        bump,
+        locked_amount: 0,
+        active_games: 0,
+        accum_wager: 0,
+        version: VAULT_VERSION,
+        created_at: 0,
+        lifetime_wager: 0,
+        rate_window_start: 0,
+        rate_window_bets: 0,
+        transfer_window_start: 0,
+        transfer_window_total: 0,
+        region: 0,
+        terms_hash: [0; 32],
+        terms_accepted_at: 0,
+        free_spins: 0,
+        free_spin_value: 0,
+        loss_streak: 0,
+        win_window_start: 0,
+        win_window_net: 0,
+        daily_win_cap: 0,
+    };
+    program_test.add_account(*owner, wallet(10 * LAMPORTS_PER_SOL));
+    program_test.add_account(address, zero_copy_account(&vault, 10 * LAMPORTS_PER_SOL));
+}
+
+/// Global PDAs as the init instructions would leave them, with generous game limits
+fn seed_globals(program_test: &mut ProgramTest) {
+    program_test.add_account(admin(), wallet(100 * LAMPORTS_PER_SOL));
+    program_test.add_account(multisig(), wallet(100 * LAMPORTS_PER_SOL));
+
+    let house = HouseVault {
+        bump: pda::house_vault().1,
+        multisig_authority: multisig(),
+        admin_authority: admin(),
+        total_volume: 0,
+        version: HOUSE_VERSION,
+        open_exposure: 0,
+        category_volume: [0; GAME_CATEGORIES],
+    };
+    program_test.add_account(pda::house_vault().0, zero_copy_account(&house, 1_000 * LAMPORTS_PER_SOL));
+
+    let stats = GlobalStats {
+        total_users: 0,
+        total_bets: 0,
+        total_volume: 0,
+        biggest_win: 0,
+        biggest_win_vault: Pubkey::default(),
+        bump: pda::global_stats().1,
+        settle_calls: 0,
+        batch_calls: 0,
+        deposit_calls: 0,
+        withdraw_calls: 0,
+        version: GLOBAL_STATS_VERSION,
+    };
+    program_test.add_account(pda::global_stats().0, zero_copy_account(&stats, 0));
+
+    let pause = PauseConfig {
+        multisig_authority: multisig(),
+        admin_authority: admin(),
+        flags: 0,
+        maintenance_start_time: 0,
+        maintenance_duration_hours: 0,
+        version: PAUSE_CONFIG_VERSION,
+        bump: pda::pause_config().1,
+    };
+    program_test.add_account(pda::pause_config().0, borsh_account(&pause, PAUSE_CONFIG_SPACE));
+
+    let config = GlobalConfig {
+        event_seq: 0,
+        bump: pda::global_config().1,
+        version: GLOBAL_CONFIG_VERSION,
+        min_deposit: 0,
+        min_stake: 0,
+    };
+    program_test.add_account(pda::global_config().0, borsh_account(&config, GLOBAL_CONFIG_SPACE));
+
+    let limits = GameLimits {
+        default_max_multiplier: 10_000,
+        max_unstaked_payout: 100 * LAMPORTS_PER_SOL,
+        max_bets_per_second: 0,
+        max_transaction_payout: 0,
+        max_daily_net_win: 0,
+        payout_stream_threshold: 0,
+        payout_stream_tranches: 0,
+        payout_stream_interval_secs: 0,
+        games: Vec::new(),
+        bump: pda::game_limits().1,
+    };
+    program_test.add_account(pda::game_limits().0, borsh_account(&limits, GAME_LIMITS_SPACE));
+
+    let blacklist = Blacklist { addresses: Vec::new(), bump: pda::blacklist().1 };
+    program_test.add_account(pda::blacklist().0, borsh_account(&blacklist, BLACKLIST_SPACE));
+}
+
+/// Simulate `instruction` with `payer` as fee payer and return the units it consumed
+async fn measure(context: &mut ProgramTestContext, name: &str, payer: &Pubkey, instruction: Instruction) -> u64 {
+    let message = Message::new_with_blockhash(
+        &[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), instruction],
+        Some(payer),
+        &context.last_blockhash,
+    );
+    let simulation = context
+        .banks_client
+        .simulate_transaction(Transaction::new_unsigned(message))
+        .await
+        .unwrap();
+    let details = simulation.simulation_details.expect("simulation details");
+    if let Some(Err(err)) = simulation.result {
+        panic!("{name} failed: {err}\n{}", details.logs.join("\n"));
+    }
+    details.units_consumed
+}
+
+fn settlement(owner: Pubkey, index: usize, stake: u64, payout: u64) -> Settlement {
+    Settlement {
+        owner,
+        stake,
+        payout,
+        bet_id: format!("bench-{index}"),
+        game_id: 1,
+        category: 0,
+        gem_data: vec![0; 7],
+    }
+}
+
+#[tokio::test]
+async fn compute_units_within_baseline() {
+    if std::env::var_os("SBF_OUT_DIR").is_none() && std::env::var_os("BPF_OUT_DIR").is_none() {
+        std::env::set_var("SBF_OUT_DIR", concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy"));
+    }
+
+    let players: Vec<Pubkey> = (0..MAX_BATCH).map(|_| Pubkey::new_unique()).collect();
+    let newcomer = Pubkey::new_unique();
+
+    let mut program_test = ProgramTest::new("smart_vault_v2", PROGRAM_ID, None);
+    program_test.prefer_bpf(true);
+    seed_globals(&mut program_test);
+    for player in &players {
+        seed_vault(&mut program_test, player);
+    }
+    program_test.add_account(newcomer, wallet(10 * LAMPORTS_PER_SOL));
+    let mut context = program_test.start_with_context().await;
+
+    let player = players[0];
+    let stake = LAMPORTS_PER_SOL / 10;
+    let mut cases: Vec<(String, Pubkey, Instruction)> = vec![
+        ("initialize_vault".into(), newcomer, instructions::initialize_vault(&newcomer)),
+        ("migrate_vault".into(), player, instructions::migrate_vault(&player)),
+        ("deposit".into(), player, instructions::deposit(&player, LAMPORTS_PER_SOL)),
+        ("withdraw".into(), player, instructions::withdraw(&player, LAMPORTS_PER_SOL)),
+        (
+            "bet_and_settle_loss".into(),
+            admin(),
+            instructions::bet_and_settle(&admin(), &settlement(player, 0, stake, 0), SettlementExtras::default()),
+        ),
+        (
+            "bet_and_settle_win".into(),
+            admin(),
+            instructions::bet_and_settle(&admin(), &settlement(player, 0, stake, 2 * stake), SettlementExtras::default()),
+        ),
+        (
+            "bet_and_settle_receipt".into(),
+            admin(),
+            instructions::bet_and_settle(
+                &admin(),
+                &settlement(player, 0, stake, 2 * stake),
+                SettlementExtras { receipt: true, ..SettlementExtras::default() },
+            ),
+        ),
+    ];
+    for size in 1..=MAX_BATCH {
+        // Alternate wins and losses so both settlement directions are exercised
+        let settlements: Vec<Settlement> = players[..size]
+            .iter()
+            .enumerate()
+            .map(|(i, owner)| settlement(*owner, i, stake, if i % 2 == 0 { 2 * stake } else { 0 }))
+            .collect();
+        cases.push((format!("batch_settle_{size}"), admin(), instructions::batch_settle(&admin(), &settlements)));
+    }
+    cases.extend([
+        ("get_vault_info".into(), player, instructions::get_vault_info(&player)),
+        ("get_house_info".into(), player, instructions::get_house_info()),
+        ("get_pause_status".into(), player, instructions::get_pause_status()),
+        ("start_maintenance_pause".into(), admin(), instructions::start_maintenance_pause(&admin())),
+        ("emergency_pause".into(), multisig(), instructions::emergency_pause(&multisig())),
+        ("set_game_limit".into(), multisig(), instructions::set_game_limit(&multisig(), 1, 500)),
+        ("set_bet_rate_limit".into(), multisig(), instructions::set_bet_rate_limit(&multisig(), 5)),
+        (
+            "set_transaction_payout_limit".into(),
+            multisig(),
+            instructions::set_transaction_payout_limit(&multisig(), 100 * LAMPORTS_PER_SOL),
+        ),
+        ("add_to_blacklist".into(), multisig(), instructions::add_to_blacklist(&multisig(), newcomer)),
+        ("change_authority".into(), multisig(), instructions::change_authority(&multisig(), None, Some(admin()))),
+    ]);
+
+    let mut measured = Baseline::new();
+    for (name, payer, instruction) in cases {
+        let units = measure(&mut context, &name, &payer, instruction).await;
+        println!("{name:<32} {units:>8} CU");
+        measured.insert(name, units);
+    }
+
+    let baseline = match std::fs::read_to_string(BASELINE_PATH) {
+        Ok(text) => parse_baseline(&text).unwrap(),
+        Err(_) => Baseline::new(),
+    };
+    if baseline.is_empty() || std::env::var_os("CU_BENCH_UPDATE").is_some() {
+        std::fs::write(BASELINE_PATH, format_baseline(&measured)).unwrap();
+        println!("baseline written to {BASELINE_PATH}");
+        return;
+    }
+
+    let threshold = std::env::var("CU_BENCH_THRESHOLD_PCT")
+        .ok()
+        .map(|pct| pct.parse().expect("CU_BENCH_THRESHOLD_PCT must be a whole number"))
+        .unwrap_or(DEFAULT_THRESHOLD_PCT);
+    let regressed = regressions(&baseline, &measured, threshold);
+    assert!(
+        regressed.is_empty(),
+        "compute units regressed by more than {threshold}%:\n{}",
+        regressed.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+    );
+}
@@ -0,0 +1,1049 @@
+//! `sols-bet` – operator CLI for the Smart Vault V2 program
+//!
+//! Wraps the SDK builders so operators and multisig signers can act without
+//! the web backend. With `--print-only`, instructions are printed instead of
+//! sent, ready to be pasted into a multisig proposal. With `--nonce`, each
+//! transaction advances a durable nonce instead of using a recent blockhash,
+//! so signing may take longer than a blockhash stays valid.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::{hash, Hash};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::{v0, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+
+use sols_bet_sdk::constants::{
+    DEFAULT_ANNOUNCEMENT_HOURS, DEFAULT_HEARTBEAT_INTERVAL_SECS, DEFAULT_MAX_BETS_PER_SECOND,
+    DEFAULT_STATS_SNAPSHOT_INTERVAL_SECS, DEFAULT_UNSTAKE_COOLDOWN_SECS,
+};
+use sols_bet_sdk::events::bet_id_hash;
+use sols_bet_sdk::instructions::{self, BatchBudget, Settlement, SettlementExtras};
+use sols_bet_sdk::{durable_nonce, lookup_table, sns};
+use sols_bet_sdk::{
+    bet_id_for, decode_account, needs_migration, pda, terms_hash, upgrade_buffer_hash, GameLimits, GlobalConfig,
+    GlobalStats, HealthMonitor, HouseVault, PauseConfig, PvpBet, PvpQueue, RebateTier, StakingConfig,
+    UpgradeGovernance, VaultRegistry, WithdrawalQueue, AUDIT_PAGE_COUNT, GAME_CATEGORY_LIVE, GAME_CATEGORY_ORIGINALS,
+    GAME_CATEGORY_SLOTS, MAX_BATCH_CREDIT, MAX_COMPUTE_UNIT_LIMIT, MAX_GEMS_PER_SETTLEMENT, MAX_REBATE_BPS,
+    PAUSE_CONFIG_VERSION,
+};
+
+#[derive(Parser)]
+#[command(name = "sols-bet", version, about = "Operator CLI for the Smart Vault V2 program")]
+struct Cli {
+    /// RPC endpoint
+    #[arg(long, env = "SOLANA_RPC_URL", default_value = "https://api.devnet.solana.com")]
+    url: String,
+
+    /// Signer keypair file (admin, multisig member or funder)
+    #[arg(long, env = "SOLANA_KEYPAIR")]
+    keypair: Option<PathBuf>,
+
+    /// Print the instructions instead of sending them
+    #[arg(long)]
+    print_only: bool,
+
+    /// Durable nonce account, advanced by the signer, to use instead of a recent blockhash
+    #[arg(long)]
+    nonce: Option<Pubkey>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the HouseVault PDA
+    InitHouse,
+    /// Start a maintenance pause, or an emergency pause with --emergency
+    Pause {
+        #[arg(long)]
+        emergency: bool,
+    },
+    /// Lift all pauses
+    Unpause,
+    /// Repack a pause config written before the bitflag layout
+    MigratePauseConfig,
+    /// Bring the house vault to the current layout
+    MigrateHouseVault,
+    /// Bring the global config to the current layout
+    MigrateGlobalConfig,
+    /// Bring the global stats to the current layout
+    MigrateGlobalStats,
+    /// Set the smallest deposit and non-zero stake accepted, in lamports (0 = no minimum)
+    SetMinimums {
+        #[arg(long, default_value_t = 0)]
+        min_deposit: u64,
+        #[arg(long, default_value_t = 0)]
+        min_stake: u64,
+    },
+    /// Show pause flags and house vault state
+    Status,
+    /// Resolve a .sol name to its owner and vault
+    Resolve {
+        domain: String,
+    },
+    /// Settle a single bet
+    Settle {
+        #[arg(long)]
+        owner: Pubkey,
+        #[arg(long)]
+        stake: u64,
+        #[arg(long)]
+        payout: u64,
+        #[arg(long)]
+        bet_id: String,
+        #[arg(long)]
+        game_id: u64,
+        /// Game category the stake counts toward: slots, live or originals
+        #[arg(long, default_value = "slots")]
+        category: String,
+        /// Seven comma-separated gem counts
+        #[arg(long, default_value = "0,0,0,0,0,0,0")]
+        gems: String,
+        /// Trophy tree to mint into if the win clears the trophy threshold
+        #[arg(long)]
+        trophy_tree: Option<Pubkey>,
+    },
+    /// Settle bets from a CSV file (owner,stake,payout,bet_id,game_id,gems,category)
+    BatchSettle {
+        #[arg(long)]
+        csv: PathBuf,
+        /// Compute units each transaction may request; batches are packed up to it
+        #[arg(long, default_value_t = MAX_COMPUTE_UNIT_LIMIT)]
+        compute_units: u32,
+        /// Address lookup table to compile the transactions against
+        #[arg(long)]
+        lookup_table: Option<Pubkey>,
+    },
+    /// Credit promo lamports to many vaults from a CSV file (owner,amount)
+    BatchCredit {
+        #[arg(long)]
+        csv: PathBuf,
+    },
+    /// Credit free spins of one nominal stake to a vault
+    FreeSpins {
+        #[arg(long)]
+        owner: Pubkey,
+        #[arg(long)]
+        spins: u32,
+        /// Nominal stake of each spin, in lamports
+        #[arg(long)]
+        value: u64,
+    },
+    /// Create the signer's durable nonce account for use with --nonce
+    CreateNonceAccount,
+    /// Create an address lookup table holding the shared settlement accounts
+    CreateLookupTable,
+    /// Add the vaults of the given owners to an address lookup table
+    ExtendLookupTable {
+        #[arg(long)]
+        table: Pubkey,
+        #[arg(long = "owner", required = true)]
+        owners: Vec<Pubkey>,
+    },
+    /// Create the empty blacklist
+    InitBlacklist,
+    /// Create the empty vault registry
+    InitVaultRegistry,
+    /// Create the stats snapshot ring buffer
+    InitStatsSnapshots {
+        /// Minimum seconds between snapshots
+        #[arg(long, default_value_t = DEFAULT_STATS_SNAPSHOT_INTERVAL_SECS)]
+        interval_secs: u32,
+    },
+    /// Create the trophy config (min multiplier in hundredths, 100 = 1x, 0 = off)
+    InitTrophyConfig {
+        /// Bubblegum tree whose delegate is the trophy config PDA
+        #[arg(long)]
+        tree: Pubkey,
+        #[arg(long)]
+        min_multiplier: u32,
+        /// Metadata URI the bet details are appended to
+        #[arg(long)]
+        base_uri: String,
+    },
+    /// Change the trophy tree, threshold or metadata URI
+    SetTrophyConfig {
+        #[arg(long)]
+        tree: Pubkey,
+        #[arg(long)]
+        min_multiplier: u32,
+        #[arg(long)]
+        base_uri: String,
+    },
+    /// Create the bridge config for Wormhole deposits
+    InitBridge {
+        /// Wormhole core bridge program that posts verified VAAs
+        #[arg(long)]
+        core_bridge: Pubkey,
+    },
+    /// Trust a deposit contract on another chain, or stop trusting it with --remove
+    BridgeEmitter {
+        /// Wormhole chain id
+        #[arg(long)]
+        chain: u16,
+        /// Emitter address in hex; 20-byte EVM addresses are left-padded
+        #[arg(long)]
+        address: String,
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Create the responsible-gambling config, or change its floors with --update
+    RgConfig {
+        /// Shortest cool-off a player may choose, in seconds
+        #[arg(long)]
+        min_cool_off_secs: i64,
+        /// Shortest gap between raises of a self-set limit, in seconds
+        #[arg(long)]
+        min_limit_raise_interval_secs: i64,
+        #[arg(long)]
+        update: bool,
+    },
+    /// Create the dispute config, or change the window with --update (0 = disputes off)
+    DisputeWindow {
+        window_secs: i64,
+        #[arg(long)]
+        update: bool,
+    },
+    /// Rule on a player's challenge, paying --award of the frozen stake to their vault
+    ResolveDispute {
+        #[arg(long)]
+        owner: Pubkey,
+        #[arg(long)]
+        bet_id: String,
+        #[arg(long)]
+        award: u64,
+    },
+    /// Create the pity boost on gems after a losing streak, or change it with --update
+    Pity {
+        /// Consecutive losses before the boost; 0 disables it
+        min_losses: u16,
+        /// Applied to the awarded gems (100 = 1x)
+        multiplier: u16,
+        #[arg(long)]
+        update: bool,
+    },
+    /// Create the PvP config with this rake in basis points, or change it with --update
+    PvpRake {
+        rake_bps: u16,
+        #[arg(long)]
+        update: bool,
+    },
+    /// Create the insurance fund with these premium and refund rates in basis points, or change them with --update
+    Insurance {
+        premium_bps: u16,
+        refund_bps: u16,
+        #[arg(long)]
+        update: bool,
+    },
+    /// Create the queue listing open PvP challenges
+    InitPvpQueue,
+    /// Create the queue ordering withdrawals requested during pauses
+    InitWithdrawalQueue,
+    /// Drop the queued withdrawal at the head of the queue without paying it (owner or multisig)
+    DropWithdrawal { owner: Pubkey },
+    /// Refund every queued PvP challenge past its expiry
+    ExpirePvp,
+    /// Settle an accepted PvP bet for --winner (the creator or opponent wallet), or as a draw without it
+    SettlePvp {
+        #[arg(long)]
+        creator: Pubkey,
+        #[arg(long)]
+        opponent: Pubkey,
+        #[arg(long)]
+        bet_id: String,
+        #[arg(long)]
+        winner: Option<Pubkey>,
+    },
+    /// Create the compliance config with KYC gating off
+    InitCompliance,
+    /// Require a KYC attestation for withdrawals above this many lamports (0 = off)
+    KycThreshold {
+        threshold: u64,
+    },
+    /// Emit LargeTransfer events for transfers above this many lamports (0 = off)
+    LargeTransferThreshold {
+        threshold: u64,
+    },
+    /// Emit every audit page as an AuditPage event, one transaction per page
+    AuditExport,
+    /// Screen deposits above the threshold through an external program, or stop screening
+    Screening {
+        #[arg(long, required_unless_present = "disable")]
+        program: Option<Pubkey>,
+        #[arg(long, default_value_t = 0)]
+        threshold: u64,
+        #[arg(long, conflicts_with = "program")]
+        disable: bool,
+    },
+    /// Cap stakes from a region for one game (or all with no --game-id), barring it with 0, or drop the rule
+    RegionRule {
+        #[arg(long)]
+        region: u8,
+        #[arg(long)]
+        game_id: Option<u64>,
+        #[arg(long, required_unless_present = "remove")]
+        max_stake: Option<u64>,
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Assign a vault to a jurisdiction
+    VaultRegion {
+        #[arg(long)]
+        owner: Pubkey,
+        #[arg(long)]
+        region: u8,
+    },
+    /// Publish a terms-of-service document, identified by its SHA-256
+    SetTerms {
+        #[arg(long)]
+        file: PathBuf,
+        /// Require acceptance of these terms before a vault's next staked bet
+        #[arg(long)]
+        required: bool,
+    },
+    /// Approve a KYC attestor, or revoke its approval with --remove
+    Attestor {
+        attestor: Pubkey,
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Create the empty registry of game programs allowed to settle via CPI
+    InitGamePrograms,
+    /// Allow a game program to settle via CPI, or revoke it with --remove
+    GameProgram {
+        program: Pubkey,
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Block a wallet from vault creation, deposits and bets, or lift the block with --remove
+    Blacklist {
+        #[arg(long)]
+        address: Pubkey,
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Create the payout-bound table (multipliers in hundredths, 100 = 1x)
+    InitGameLimits {
+        #[arg(long)]
+        default_max_multiplier: u32,
+        /// Largest payout allowed when the stake was deducted earlier
+        #[arg(long)]
+        max_unstaked_payout: u64,
+        /// Settlements allowed per vault per second (0 = unlimited)
+        #[arg(long, default_value_t = DEFAULT_MAX_BETS_PER_SECOND)]
+        max_bets_per_second: u32,
+        /// Summed payouts allowed per transaction (0 = unlimited)
+        #[arg(long, default_value_t = 0)]
+        max_transaction_payout: u64,
+        /// Net winnings allowed per vault per 24h before bets are refused (0 = unlimited)
+        #[arg(long, default_value_t = 0)]
+        max_daily_net_win: u64,
+    },
+    /// Cap the summed payouts of all settlements in one transaction (0 = unlimited)
+    SetTransactionPayoutLimit {
+        #[arg(long)]
+        max_transaction_payout: u64,
+    },
+    /// Cap settlements per vault per second (0 = unlimited)
+    SetBetRateLimit {
+        #[arg(long)]
+        max_bets_per_second: u32,
+    },
+    /// Stream the part of payouts above --threshold in equal tranches (threshold 0 = off)
+    SetPayoutStream {
+        #[arg(long)]
+        threshold: u64,
+        #[arg(long, default_value_t = 0)]
+        tranches: u16,
+        /// Seconds between tranches
+        #[arg(long, default_value_t = 0)]
+        interval_secs: i64,
+    },
+    /// Cap each vault's net winnings per 24h, or one vault's with --owner (0 = unlimited, or the global cap)
+    SetDailyWinCap {
+        #[arg(long)]
+        max_daily_net_win: u64,
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Set a game's maximum payout multiplier (0 removes it)
+    SetGameLimit {
+        #[arg(long)]
+        game_id: u64,
+        #[arg(long)]
+        max_multiplier: u32,
+    },
+    /// Publish an originals game's rules, or change them with --update
+    GameConfig {
+        #[arg(long)]
+        game_id: u64,
+        #[arg(long)]
+        house_edge_bps: u16,
+        /// Fair top multiplier before the edge (100 = 1x)
+        #[arg(long)]
+        max_multiplier: u32,
+        #[arg(long, default_value_t = 0)]
+        min_stake: u64,
+        /// 0 leaves stakes uncapped
+        #[arg(long, default_value_t = 0)]
+        max_stake: u64,
+        #[arg(long)]
+        update: bool,
+    },
+    /// Create a game's tiered jackpot, drawn with randomness from the given oracle
+    InitJackpot {
+        #[arg(long)]
+        game_id: u64,
+        #[arg(long)]
+        vrf_authority: Pubkey,
+    },
+    /// Configure one jackpot tier of a game: 0 mini, 1 major, 2 grand
+    JackpotTier {
+        #[arg(long)]
+        game_id: u64,
+        #[arg(long)]
+        tier: u8,
+        #[arg(long)]
+        contribution_bps: u16,
+        /// Pot the tier restarts from after a hit, topped up from the house
+        #[arg(long)]
+        seed: u64,
+        /// A draw hits once in this many; 0 disables the tier
+        #[arg(long)]
+        odds: u32,
+    },
+    /// Publish the on-chain announcement banner (creating the account on first use)
+    Announce {
+        /// Where frontends fetch the message text
+        #[arg(long)]
+        uri: String,
+        /// Local copy of the message; its sha256 is stored for verification
+        #[arg(long)]
+        message_file: PathBuf,
+        /// 0 = info, 1 = warning, 2 = critical
+        #[arg(long, default_value_t = 0)]
+        severity: u8,
+        /// Hours until the banner expires
+        #[arg(long, default_value_t = DEFAULT_ANNOUNCEMENT_HOURS)]
+        hours: i64,
+    },
+    /// Take the announcement banner down
+    ClearAnnouncement,
+    /// Rotate the multisig and/or admin authority
+    ChangeAuthority {
+        #[arg(long)]
+        new_multisig: Option<Pubkey>,
+        #[arg(long)]
+        new_admin: Option<Pubkey>,
+    },
+    /// Create the upgrade governance account, or change the required notice with --update
+    UpgradeDelay {
+        /// Slots an announced upgrade must wait before it may be deployed
+        min_delay_slots: u64,
+        #[arg(long)]
+        update: bool,
+    },
+    /// Hand the program's upgrade authority to the multisig recorded in the HouseVault
+    TransferUpgradeAuthority,
+    /// Announce the upgrade in a loader buffer, hashing the buffer's program bytes
+    AnnounceUpgrade {
+        buffer: Pubkey,
+        /// Slots from now until the upgrade may be deployed; leave room for multisig signing
+        #[arg(long)]
+        delay_slots: u64,
+    },
+    /// Withdraw the pending upgrade announcement
+    CancelUpgrade,
+    /// Record the latest deployment against the pending announcement
+    RecordUpgrade,
+    /// Create the health monitor, or change its operator and interval with --update
+    HealthMonitor {
+        /// Key expected to send heartbeats
+        operator: Pubkey,
+        /// Longest gap between heartbeats before the program counts as degraded
+        #[arg(long, default_value_t = DEFAULT_HEARTBEAT_INTERVAL_SECS)]
+        interval_secs: i64,
+        #[arg(long)]
+        update: bool,
+    },
+    /// Send the operator heartbeat; the keypair must be the monitor's operator
+    Heartbeat,
+    /// Create the platform-token staking config
+    InitStaking {
+        /// Platform token mint
+        mint: Pubkey,
+        /// Token account of the mint owned by the staking config PDA, holding every stake
+        escrow: Pubkey,
+        /// Wait between requesting an unstake and withdrawing it
+        #[arg(long, default_value_t = DEFAULT_UNSTAKE_COOLDOWN_SECS)]
+        cooldown_secs: i64,
+    },
+    /// Replace the staking rebate tiers (multisig)
+    RebateTiers {
+        /// `min_staked:rebate_bps` pairs in ascending order, e.g. 1000000:10 5000000:25
+        tiers: Vec<String>,
+        #[arg(long, default_value_t = DEFAULT_UNSTAKE_COOLDOWN_SECS)]
+        cooldown_secs: i64,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+
+    if let Command::Status = cli.command {
+        return print_status(&rpc);
+    }
+    if let Command::Resolve { domain } = &cli.command {
+        return print_resolved(&rpc, domain);
+    }
+
+    let signer = load_keypair(cli.keypair.as_ref())?;
+    let authority = signer.pubkey();
+
+    let lookup_tables = match &cli.command {
+        Command::BatchSettle { lookup_table: Some(table), .. } => vec![fetch_lookup_table(&rpc, table)?],
+        _ => Vec::new(),
+    };
+
+    let batches: Vec<Vec<Instruction>> = match &cli.command {
+        Command::InitHouse => vec![vec![instructions::initialize_house(&authority)]],
+        Command::Pause { emergency: true } => vec![vec![instructions::emergency_pause(&authority)]],
+        Command::Pause { emergency: false } => vec![vec![instructions::start_maintenance_pause(&authority)]],
+        Command::Unpause => vec![vec![instructions::unpause(&authority)]],
+        Command::MigratePauseConfig => vec![vec![instructions::migrate_pause_config(&authority)]],
+        Command::MigrateHouseVault => vec![vec![instructions::migrate_house_vault(&authority)]],
+        Command::MigrateGlobalConfig => vec![vec![instructions::migrate_global_config(&authority)]],
+        Command::MigrateGlobalStats => vec![vec![instructions::migrate_global_stats(&authority)]],
+        Command::SetMinimums { min_deposit, min_stake } => {
+            vec![vec![instructions::set_minimums(&authority, *min_deposit, *min_stake)]]
+        }
+        Command::Settle { owner, stake, payout, bet_id, game_id, category, gems, trophy_tree } => {
+            let settlement = Settlement {
+                owner: *owner,
+                stake: *stake,
+                payout: *payout,
+                bet_id: bet_id_for(bet_id),
+                game_id: *game_id,
+                category: parse_category(category)?,
+                gem_data: parse_gems(gems)?,
+            };
+            let limits: GameLimits = decode_account(&rpc.get_account_data(&pda::game_limits().0)?)?;
+            let token_stake = pda::token_stake(&pda::vault(owner).0).0;
+            let extras = SettlementExtras {
+                trophy_tree: *trophy_tree,
+                stream: limits.split_payout(*payout).1 > 0,
+                staking: rpc.get_account_data(&pda::staking_config().0).is_ok()
+                    && rpc.get_account_data(&token_stake).is_ok(),
+                ..SettlementExtras::default()
+            };
+            vec![vec![instructions::bet_and_settle(&authority, &settlement, extras)]]
+        }
+        Command::BatchSettle { csv, compute_units, .. } => instructions::pack_batch_settle(
+            &authority,
+            &read_settlements(csv)?,
+            BatchBudget { compute_units: *compute_units, ..BatchBudget::default() },
+            &lookup_tables,
+        ),
+        Command::BatchCredit { csv } => read_credits(csv)?
+            .chunks(MAX_BATCH_CREDIT)
+            .map(|chunk| vec![instructions::batch_credit(&authority, chunk)])
+            .collect(),
+        Command::FreeSpins { owner, spins, value } => {
+            vec![vec![instructions::credit_free_spins(&authority, owner, *spins, *value)]]
+        }
+        Command::CreateNonceAccount => {
+            let lamports = rpc.get_minimum_balance_for_rent_exemption(durable_nonce::NONCE_ACCOUNT_LEN)?;
+            let (ixs, nonce) = durable_nonce::create(&authority, lamports);
+            println!("nonce account: {nonce}");
+            vec![ixs]
+        }
+        Command::CreateLookupTable => {
+            let slot = rpc.get_slot_with_commitment(CommitmentConfig::finalized())?;
+            let (create, table) = lookup_table::create(&authority, slot);
+            println!("lookup table: {table}");
+            let extend = lookup_table::extend(&table, &authority, &lookup_table::shared_addresses());
+            std::iter::once(create).chain(extend).map(|ix| vec![ix]).collect()
+        }
+        Command::ExtendLookupTable { table, owners } => {
+            let missing = lookup_table::missing_vaults(&fetch_lookup_table(&rpc, table)?, owners);
+            if missing.is_empty() {
+                println!("lookup table already holds these vaults or is full");
+            }
+            lookup_table::extend(table, &authority, &missing).into_iter().map(|ix| vec![ix]).collect()
+        }
+        Command::InitBlacklist => vec![vec![instructions::initialize_blacklist(&authority)]],
+        Command::InitVaultRegistry => vec![vec![instructions::initialize_vault_registry(&authority)]],
+        Command::InitStatsSnapshots { interval_secs } => {
+            vec![vec![instructions::initialize_stats_snapshots(&authority, *interval_secs)]]
+        }
+        Command::InitTrophyConfig { tree, min_multiplier, base_uri } => {
+            vec![vec![instructions::initialize_trophy_config(&authority, *tree, *min_multiplier, base_uri.clone())]]
+        }
+        Command::SetTrophyConfig { tree, min_multiplier, base_uri } => {
+            vec![vec![instructions::set_trophy_config(&authority, *tree, *min_multiplier, base_uri.clone())]]
+        }
+        Command::InitBridge { core_bridge } => {
+            vec![vec![instructions::initialize_bridge_config(&authority, *core_bridge)]]
+        }
+        Command::BridgeEmitter { chain, address, remove: false } => {
+            vec![vec![instructions::add_bridge_emitter(&authority, *chain, parse_emitter(address)?)]]
+        }
+        Command::BridgeEmitter { chain, address, remove: true } => {
+            vec![vec![instructions::remove_bridge_emitter(&authority, *chain, parse_emitter(address)?)]]
+        }
+        Command::RgConfig { min_cool_off_secs: cool_off, min_limit_raise_interval_secs: interval, update: false } => {
+            vec![vec![instructions::initialize_rg_config(&authority, *cool_off, *interval)]]
+        }
+        Command::RgConfig { min_cool_off_secs: cool_off, min_limit_raise_interval_secs: interval, update: true } => {
+            vec![vec![instructions::set_rg_config(&authority, *cool_off, *interval)]]
+        }
+        Command::DisputeWindow { window_secs, update: false } => {
+            vec![vec![instructions::initialize_dispute_config(&authority, *window_secs)]]
+        }
+        Command::DisputeWindow { window_secs, update: true } => {
+            vec![vec![instructions::set_dispute_window(&authority, *window_secs)]]
+        }
+        Command::ResolveDispute { owner, bet_id, award } => {
+            let hash = bet_id_hash(&bet_id_for(bet_id));
+            vec![vec![instructions::resolve_dispute(&authority, owner, &hash, *award)]]
+        }
+        Command::PvpRake { rake_bps, update: false } => {
+            vec![vec![instructions::initialize_pvp_config(&authority, *rake_bps)]]
+        }
+        Command::PvpRake { rake_bps, update: true } => vec![vec![instructions::set_pvp_rake(&authority, *rake_bps)]],
+        Command::Pity { min_losses, multiplier, update } => {
+            let build = if *update { instructions::set_pity_config } else { instructions::initialize_pity_config };
+            vec![vec![build(&authority, *min_losses, *multiplier)]]
+        }
+        Command::Insurance { premium_bps, refund_bps, update: false } => {
+            vec![vec![instructions::initialize_insurance_fund(&authority, *premium_bps, *refund_bps)]]
+        }
+        Command::Insurance { premium_bps, refund_bps, update: true } => {
+            vec![vec![instructions::set_insurance_terms(&authority, *premium_bps, *refund_bps)]]
+        }
+        Command::InitPvpQueue => vec![vec![instructions::initialize_pvp_queue(&authority)]],
+        Command::InitWithdrawalQueue => vec![vec![instructions::initialize_withdrawal_queue(&authority)]],
+        Command::DropWithdrawal { owner } => vec![vec![instructions::drop_withdrawal_request(&authority, owner)]],
+        Command::ExpirePvp => {
+            let queue: PvpQueue = decode_account(&rpc.get_account_data(&pda::pvp_queue().0)?)?;
+            let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_secs() as i64;
+            let mut batches = Vec::new();
+            for challenge in &queue.challenges {
+                let bet: PvpBet = decode_account(&rpc.get_account_data(challenge)?)?;
+                if bet.is_expired(now) {
+                    batches.push(vec![instructions::expire_pvp_bet(&bet.creator, &bet.bet_id_hash)]);
+                }
+            }
+            println!("{} of {} queued challenges expired", batches.len(), queue.challenges.len());
+            batches
+        }
+        Command::SettlePvp { creator, opponent, bet_id, winner } => {
+            let hash = bet_id_hash(&bet_id_for(bet_id));
+            vec![vec![instructions::settle_pvp_bet(&authority, creator, opponent, &hash, winner.as_ref())]]
+        }
+        Command::InitCompliance => vec![vec![instructions::initialize_compliance_config(&authority)]],
+        Command::KycThreshold { threshold } => vec![vec![instructions::set_kyc_threshold(&authority, *threshold)]],
+        Command::LargeTransferThreshold { threshold } => {
+            vec![vec![instructions::set_large_transfer_threshold(&authority, *threshold)]]
+        }
+        Command::AuditExport => (0..AUDIT_PAGE_COUNT).map(|page| vec![instructions::export_audit_page(page)]).collect(),
+        Command::Screening { program, threshold, .. } => {
+            vec![vec![instructions::set_deposit_screening(&authority, *program, *threshold)]]
+        }
+        Command::RegionRule { region, game_id, remove: true, .. } => {
+            vec![vec![instructions::remove_region_rule(&authority, *region, *game_id)]]
+        }
+        Command::RegionRule { region, game_id, max_stake, remove: false } => {
+            let max_stake = max_stake.context("--max-stake is required")?;
+            vec![vec![instructions::set_region_rule(&authority, *region, *game_id, max_stake)]]
+        }
+        Command::VaultRegion { owner, region } => {
+            vec![vec![instructions::set_vault_region(&authority, owner, *region)]]
+        }
+        Command::SetTerms { file, required } => {
+            let document = std::fs::read(file).with_context(|| format!("reading {}", file.display()))?;
+            let hash = terms_hash(&document);
+            println!("terms hash: {}", hash.iter().map(|b| format!("{b:02x}")).collect::<String>());
+            vec![vec![instructions::set_terms(&authority, hash, *required)]]
+        }
+        Command::Attestor { attestor, remove: false } => vec![vec![instructions::add_attestor(&authority, *attestor)]],
+        Command::Attestor { attestor, remove: true } => {
+            vec![vec![instructions::remove_attestor(&authority, *attestor)]]
+        }
+        Command::InitGamePrograms => vec![vec![instructions::initialize_game_programs(&authority)]],
+        Command::GameProgram { program, remove: false } => vec![vec![instructions::add_game_program(&authority, *program)]],
+        Command::GameProgram { program, remove: true } => {
+            vec![vec![instructions::remove_game_program(&authority, *program)]]
+        }
+        Command::Blacklist { address, remove: false } => vec![vec![instructions::add_to_blacklist(&authority, *address)]],
+        Command::Blacklist { address, remove: true } => {
+            vec![vec![instructions::remove_from_blacklist(&authority, *address)]]
+        }
+        Command::InitGameLimits {
+            default_max_multiplier,
+            max_unstaked_payout,
+            max_bets_per_second,
+            max_transaction_payout,
+            max_daily_net_win,
+        } => vec![vec![instructions::initialize_game_limits(
+            &authority,
+            *default_max_multiplier,
+            *max_unstaked_payout,
+            *max_bets_per_second,
+            *max_transaction_payout,
+            *max_daily_net_win,
+        )]],
+        Command::SetTransactionPayoutLimit { max_transaction_payout } => {
+            vec![vec![instructions::set_transaction_payout_limit(&authority, *max_transaction_payout)]]
+        }
+        Command::SetBetRateLimit { max_bets_per_second } => {
+            vec![vec![instructions::set_bet_rate_limit(&authority, *max_bets_per_second)]]
+        }
+        Command::SetPayoutStream { threshold, tranches, interval_secs } => {
+            vec![vec![instructions::set_payout_stream(&authority, *threshold, *tranches, *interval_secs)]]
+        }
+        Command::SetDailyWinCap { max_daily_net_win, owner: None } => {
+            vec![vec![instructions::set_daily_win_cap(&authority, *max_daily_net_win)]]
+        }
+        Command::SetDailyWinCap { max_daily_net_win, owner: Some(owner) } => {
+            vec![vec![instructions::set_vault_win_cap(&authority, owner, *max_daily_net_win)]]
+        }
+        Command::SetGameLimit { game_id, max_multiplier } => {
+            vec![vec![instructions::set_game_limit(&authority, *game_id, *max_multiplier)]]
+        }
+        Command::GameConfig { game_id, house_edge_bps, max_multiplier, min_stake, max_stake, update } => {
+            let build = if *update { instructions::set_game_config } else { instructions::initialize_game_config };
+            vec![vec![build(&authority, *game_id, *house_edge_bps, *max_multiplier, *min_stake, *max_stake)]]
+        }
+        Command::InitJackpot { game_id, vrf_authority } => {
+            vec![vec![instructions::initialize_jackpot(&authority, *game_id, *vrf_authority)]]
+        }
+        Command::JackpotTier { game_id, tier, contribution_bps, seed, odds } => {
+            vec![vec![instructions::set_jackpot_tier(&authority, *game_id, *tier, *contribution_bps, *seed, *odds)]]
+        }
+        Command::Announce { uri, message_file, severity, hours } => {
+            let message = std::fs::read(message_file)
+                .with_context(|| format!("reading {}", message_file.display()))?;
+            let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?.as_secs() as i64;
+            let mut ixs = Vec::new();
+            if rpc.get_account_data(&pda::announcement().0).is_err() {
+                ixs.push(instructions::initialize_announcement(&authority));
+            }
+            ixs.push(instructions::set_announcement(
+                &authority,
+                uri.clone(),
+                hash(&message).to_bytes(),
+                *severity,
+                now + hours * 3600,
+            ));
+            vec![ixs]
+        }
+        Command::ClearAnnouncement => vec![vec![instructions::clear_announcement(&authority)]],
+        Command::ChangeAuthority { new_multisig, new_admin } => {
+            if new_multisig.is_none() && new_admin.is_none() {
+                bail!("nothing to change: pass --new-multisig and/or --new-admin");
+            }
+            vec![vec![instructions::change_authority(&authority, *new_multisig, *new_admin)]]
+        }
+        Command::UpgradeDelay { min_delay_slots, update: false } => {
+            vec![vec![instructions::initialize_upgrade_governance(&authority, *min_delay_slots)]]
+        }
+        Command::UpgradeDelay { min_delay_slots, update: true } => {
+            vec![vec![instructions::set_upgrade_delay(&authority, *min_delay_slots)]]
+        }
+        Command::TransferUpgradeAuthority => {
+            let house: HouseVault = decode_account(&rpc.get_account_data(&pda::house_vault().0)?)?;
+            vec![vec![instructions::transfer_upgrade_authority(&authority, &house.multisig_authority)]]
+        }
+        Command::AnnounceUpgrade { buffer, delay_slots } => {
+            let data = rpc.get_account_data(buffer).with_context(|| format!("fetching buffer {buffer}"))?;
+            let earliest_upgrade_slot = rpc.get_slot()? + delay_slots;
+            let buffer_hash = upgrade_buffer_hash(&data);
+            println!("buffer_hash: {}", Hash::new_from_array(buffer_hash));
+            vec![vec![instructions::announce_upgrade(&authority, buffer, buffer_hash, earliest_upgrade_slot)]]
+        }
+        Command::CancelUpgrade => vec![vec![instructions::cancel_upgrade(&authority)]],
+        Command::RecordUpgrade => vec![vec![instructions::record_upgrade()]],
+        Command::HealthMonitor { operator, interval_secs, update: false } => {
+            vec![vec![instructions::initialize_health_monitor(&authority, operator, *interval_secs)]]
+        }
+        Command::HealthMonitor { operator, interval_secs, update: true } => {
+            vec![vec![instructions::set_heartbeat_config(&authority, operator, *interval_secs)]]
+        }
+        Command::Heartbeat => vec![vec![instructions::operator_heartbeat(&authority)]],
+        Command::InitStaking { mint, escrow, cooldown_secs } => {
+            vec![vec![instructions::initialize_staking_config(&authority, mint, escrow, *cooldown_secs)]]
+        }
+        Command::RebateTiers { tiers, cooldown_secs } => {
+            let tiers = tiers.iter().map(|tier| parse_rebate_tier(tier)).collect::<Result<Vec<_>>>()?;
+            vec![vec![instructions::set_staking_tiers(&authority, *cooldown_secs, tiers)]]
+        }
+        Command::Status | Command::Resolve { .. } => unreachable!(),
+    };
+
+    for ixs in batches {
+        let ixs = match &cli.nonce {
+            Some(nonce) => durable_nonce::with_advance(nonce, &authority, &ixs),
+            None => ixs,
+        };
+        if cli.print_only {
+            ixs.iter().for_each(print_instruction);
+        } else {
+            let blockhash = match &cli.nonce {
+                Some(nonce) => fetch_nonce(&rpc, nonce)?,
+                None => rpc.get_latest_blockhash()?,
+            };
+            send(&rpc, &signer, &ixs, &lookup_tables, blockhash)?;
+        }
+    }
+    Ok(())
+}
+
+fn load_keypair(path: Option<&PathBuf>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path.clone(),
+        None => PathBuf::from(std::env::var("HOME").context("HOME not set")?).join(".config/solana/id.json"),
+    };
+    read_keypair_file(&path).map_err(|err| anyhow::anyhow!("reading keypair {}: {err}", path.display()))
+}
+
+/// Send `ixs` as a legacy transaction, or as v0 when lookup tables are given
+///
+/// `blockhash` is a recent blockhash, or the stored nonce when `ixs` start by advancing one.
+fn send(
+    rpc: &RpcClient,
+    signer: &Keypair,
+    ixs: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<()> {
+    let signature = if lookup_tables.is_empty() {
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&signer.pubkey()), &[signer], blockhash);
+        rpc.send_and_confirm_transaction(&tx)?
+    } else {
+        let message = v0::Message::try_compile(&signer.pubkey(), ixs, lookup_tables, blockhash)?;
+        let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), &[signer])?;
+        rpc.send_and_confirm_transaction(&tx)?
+    };
+    println!("{signature}");
+    Ok(())
+}
+
+fn fetch_lookup_table(rpc: &RpcClient, table: &Pubkey) -> Result<AddressLookupTableAccount> {
+    let data = rpc.get_account_data(table).with_context(|| format!("fetching lookup table {table}"))?;
+    lookup_table::decode(*table, &data).map_err(|err| anyhow::anyhow!("decoding lookup table {table}: {err}"))
+}
+
+/// Stored hash of a durable nonce account
+fn fetch_nonce(rpc: &RpcClient, nonce: &Pubkey) -> Result<Hash> {
+    let data = rpc.get_account_data(nonce).with_context(|| format!("fetching nonce account {nonce}"))?;
+    let (_authority, hash) =
+        durable_nonce::decode(&data).with_context(|| format!("{nonce} is not an initialized nonce account"))?;
+    Ok(hash)
+}
+
+fn print_instruction(ix: &Instruction) {
+    println!("program: {}", ix.program_id);
+    for meta in &ix.accounts {
+        println!(
+            "  {} {}{}",
+            meta.pubkey,
+            if meta.is_writable { "w" } else { "r" },
+            if meta.is_signer { "s" } else { "" },
+        );
+    }
+    println!("data (base58): {}", bs58::encode(&ix.data).into_string());
+}
+
+fn print_status(rpc: &RpcClient) -> Result<()> {
+    let pause: PauseConfig = decode_account(&rpc.get_account_data(&pda::pause_config().0)?)?;
+    let house_address = pda::house_vault().0;
+    let house_data = rpc.get_account_data(&house_address)?;
+    let house: HouseVault = decode_account(&house_data)?;
+    let house_balance = rpc.get_balance(&house_address)?;
+
+    if pause.version < PAUSE_CONFIG_VERSION {
+        println!("pause_config:        legacy layout, run migrate-pause-config");
+    }
+    if needs_migration::<HouseVault>(&house_data) {
+        println!("house_vault:         legacy layout, run migrate-house-vault");
+    }
+    println!("emergency_pause:     {}", pause.emergency_pause());
+    println!("maintenance_pause:   {}", pause.maintenance_pause());
+    if pause.maintenance_pause() {
+        println!("maintenance_started: {}", pause.maintenance_start_time);
+        println!("maintenance_hours:   {}", pause.maintenance_duration_hours);
+    }
+    println!("house_vault:         {house_address}");
+    println!("house_balance:       {house_balance} lamports");
+    println!("total_volume:        {} lamports", { house.total_volume });
+    println!("open_exposure:       {} lamports", { house.open_exposure });
+    let [slots, live, originals] = house.category_volume;
+    println!("category_volume:     slots {slots}, live {live}, originals {originals} lamports");
+    println!("multisig_authority:  {}", house.multisig_authority);
+    println!("admin_authority:     {}", house.admin_authority);
+
+    if let Ok(data) = rpc.get_account_data(&pda::global_config().0) {
+        if needs_migration::<GlobalConfig>(&data) {
+            println!("global_config:       legacy layout, run migrate-global-config");
+        } else {
+            let config: GlobalConfig = decode_account(&data)?;
+            println!("min_deposit:         {} lamports", config.min_deposit);
+            println!("min_stake:           {} lamports", config.min_stake);
+        }
+    }
+
+    if let Ok(data) = rpc.get_account_data(&pda::global_stats().0) {
+        if needs_migration::<GlobalStats>(&data) {
+            println!("global_stats:        legacy layout, run migrate-global-stats");
+        } else {
+            let stats: GlobalStats = decode_account(&data)?;
+            println!("total_users:         {}", { stats.total_users });
+            println!("total_bets:          {}", { stats.total_bets });
+            println!("biggest_win:         {} lamports ({})", { stats.biggest_win }, stats.biggest_win_vault);
+            println!("settle_calls:        {}", { stats.settle_calls });
+            println!("batch_calls:         {}", { stats.batch_calls });
+            println!("deposit_calls:       {}", { stats.deposit_calls });
+            println!("withdraw_calls:      {}", { stats.withdraw_calls });
+        }
+    }
+    if let Ok(data) = rpc.get_account_data(&pda::vault_registry().0) {
+        let registry: VaultRegistry = decode_account(&data)?;
+        println!("registered_vaults:   {} on {} pages", registry.count, registry.pages);
+    }
+    if let Ok(data) = rpc.get_account_data(&pda::health_monitor().0) {
+        let monitor: HealthMonitor = decode_account(&data)?;
+        let now = rpc.get_block_time(rpc.get_slot()?)?;
+        println!("degraded:            {}", monitor.is_degraded(now));
+        let (last, interval, operator) = (monitor.last_heartbeat, monitor.interval_secs, monitor.operator);
+        println!("last_heartbeat:      {last} (every {interval}s by {operator})");
+    }
+    if let Ok(data) = rpc.get_account_data(&pda::withdrawal_queue().0) {
+        let queue: WithdrawalQueue = decode_account(&data)?;
+        println!("queued_withdrawals:  {} (next ticket {})", queue.tail - queue.head, queue.head);
+    }
+    if let Ok(data) = rpc.get_account_data(&pda::staking_config().0) {
+        let config: StakingConfig = decode_account(&data)?;
+        let tiers: Vec<String> =
+            config.tiers.iter().map(|tier| format!("{}:{}", tier.min_staked, tier.rebate_bps)).collect();
+        println!("rebate_tiers:        [{}] (unstake cooldown {}s)", tiers.join(", "), config.cooldown_secs);
+    }
+    if let Ok(data) = rpc.get_account_data(&pda::upgrade_governance().0) {
+        let governance: UpgradeGovernance = decode_account(&data)?;
+        println!("upgrade_delay:       {} slots", governance.min_delay_slots);
+        if governance.buffer != Pubkey::default() {
+            println!("pending_upgrade:     {} at slot {}", governance.buffer, governance.earliest_upgrade_slot);
+            println!("pending_hash:        {}", Hash::new_from_array(governance.buffer_hash));
+        }
+    }
+    Ok(())
+}
+
+fn print_resolved(rpc: &RpcClient, domain: &str) -> Result<()> {
+    let name_account = sns::domain_key(domain)?;
+    let data = rpc.get_account_data(&name_account).with_context(|| format!("fetching name account of {domain}"))?;
+    println!("name_account:  {name_account}");
+    println!("owner:         {}", sns::owner(&data)?);
+    println!("vault:         {}", sns::vault(&data)?);
+    Ok(())
+}
+
+fn parse_gems(gems: &str) -> Result<Vec<u8>> {
+    let gem_data = gems
+        .split([',', ';'])
+        .map(|count| count.trim().parse::<u8>().with_context(|| format!("invalid gem count {count:?}")))
+        .collect::<Result<Vec<_>>>()?;
+    if gem_data.len() != 7 {
+        bail!("expected 7 gem counts, got {}", gem_data.len());
+    }
+    for (gem_type, (count, max)) in gem_data.iter().zip(MAX_GEMS_PER_SETTLEMENT).enumerate() {
+        if *count > max {
+            bail!("gem type {gem_type} is limited to {max} per settlement, got {count}");
+        }
+    }
+    Ok(gem_data)
+}
+
+/// `GAME_CATEGORY_*` code of a category name
+fn parse_category(category: &str) -> Result<u8> {
+    match category.trim() {
+        "slots" => Ok(GAME_CATEGORY_SLOTS),
+        "live" => Ok(GAME_CATEGORY_LIVE),
+        "originals" => Ok(GAME_CATEGORY_ORIGINALS),
+        other => bail!("unknown game category {other:?}, expected slots, live or originals"),
+    }
+}
+
+/// Rebate tier from `min_staked:rebate_bps`
+fn parse_rebate_tier(tier: &str) -> Result<RebateTier> {
+    let (min_staked, rebate_bps) =
+        tier.split_once(':').with_context(|| format!("expected min_staked:bps, got {tier:?}"))?;
+    let min_staked = min_staked.trim().parse().with_context(|| format!("invalid stake {min_staked:?}"))?;
+    let rebate_bps: u16 = rebate_bps.trim().parse().with_context(|| format!("invalid rebate {rebate_bps:?}"))?;
+    if rebate_bps > MAX_REBATE_BPS {
+        bail!("rebate is limited to {MAX_REBATE_BPS} bps, got {rebate_bps}");
+    }
+    Ok(RebateTier { min_staked, rebate_bps })
+}
+
+/// Wormhole emitter address from hex, left-padding 20-byte EVM addresses to 32 bytes
+fn parse_emitter(hex: &str) -> Result<[u8; 32]> {
+    let hex = hex.trim_start_matches("0x");
+    if !matches!(hex.len(), 40 | 64) {
+        bail!("expected a 20- or 32-byte hex address, got {hex:?}");
+    }
+    let mut address = [0u8; 32];
+    let offset = 32 - hex.len() / 2;
+    for (i, byte) in address[offset..].iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).with_context(|| format!("invalid hex address {hex:?}"))?;
+    }
+    Ok(address)
+}
+
+/// Read `owner,stake,payout,bet_id,game_id,gems,category` rows; gems are `;`-separated
+fn read_settlements(path: &PathBuf) -> Result<Vec<Settlement>> {
+    let mut reader = csv::Reader::from_path(path).with_context(|| format!("opening {}", path.display()))?;
+    reader
+        .records()
+        .enumerate()
+        .map(|(row, record)| {
+            let record = record?;
+            let field = |i: usize| record.get(i).with_context(|| format!("row {}: missing column {i}", row + 1));
+            Ok(Settlement {
+                owner: field(0)?.trim().parse().with_context(|| format!("row {}: bad owner", row + 1))?,
+                stake: field(1)?.trim().parse()?,
+                payout: field(2)?.trim().parse()?,
+                bet_id: bet_id_for(field(3)?.trim()),
+                game_id: field(4)?.trim().parse()?,
+                category: parse_category(field(6)?)?,
+                gem_data: parse_gems(field(5)?)?,
+            })
+        })
+        .collect()
+}
+
+/// Read `owner,amount` rows
+fn read_credits(path: &PathBuf) -> Result<Vec<(Pubkey, u64)>> {
+    let mut reader = csv::Reader::from_path(path).with_context(|| format!("opening {}", path.display()))?;
+    reader
+        .records()
+        .enumerate()
+        .map(|(row, record)| {
+            let record = record?;
+            let field = |i: usize| record.get(i).with_context(|| format!("row {}: missing column {i}", row + 1));
+            let owner = field(0)?.trim().parse().with_context(|| format!("row {}: bad owner", row + 1))?;
+            let amount = field(1)?.trim().parse().with_context(|| format!("row {}: bad amount", row + 1))?;
+            Ok((owner, amount))
+        })
+        .collect()
+}
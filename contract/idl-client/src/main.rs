@@ -0,0 +1,38 @@
+//! Smart Vault V2 client generated from the published IDL
+//!
+//! `declare_program!` reads `idls/smart_vault_v2.json` and generates the
+//! program's accounts, instruction builders and CPI helpers, so a service can
+//! talk to the deployed program without depending on its crate. Regenerate the
+//! IDL after changing the program, then build this crate to prove the client
+//! still compiles against it:
+//!
+//! ```text
+//! cd src && anchor idl build -o ../idls/smart_vault_v2.json && cd ..
+//! cargo run --manifest-path idl-client/Cargo.toml
+//! ```
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+declare_program!(smart_vault_v2);
+
+use smart_vault_v2::accounts::DisputeConfig;
+use smart_vault_v2::client::{accounts, args};
+
+fn main() {
+    let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+    let dispute_config = Pubkey::find_program_address(&[b"dispute_config"], &smart_vault_v2::ID).0;
+
+    let ix = Instruction {
+        program_id: smart_vault_v2::ID,
+        accounts: accounts::SetDisputeWindow { dispute_config, authority: multisig }.to_account_metas(None),
+        data: args::SetDisputeWindow { window_secs: 3600 }.data(),
+    };
+    println!("set_dispute_window: {} accounts, {} data bytes", ix.accounts.len(), ix.data.len());
+
+    let mut data = Vec::new();
+    DisputeConfig { window_secs: 3600, bump: 255 }.try_serialize(&mut data).unwrap();
+    let decoded = DisputeConfig::try_deserialize(&mut &data[..]).unwrap();
+    println!("dispute_config: window {}s", decoded.window_secs);
+}
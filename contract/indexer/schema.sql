@@ -0,0 +1,316 @@
+-- Smart Vault V2 event tables. Every row is keyed by the on-chain event
+-- sequence number, so replays and backfills are idempotent.
+
+CREATE TABLE IF NOT EXISTS deposits (
+    seq          BIGINT PRIMARY KEY,
+    signature    TEXT        NOT NULL,
+    slot         BIGINT      NOT NULL,
+    user_pubkey  TEXT        NOT NULL,
+    amount       BIGINT      NOT NULL,
+    new_balance  BIGINT      NOT NULL,
+    indexed_at   TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS deposits_user_idx ON deposits (user_pubkey);
+-- Solana Pay or order reference the deposit was tagged with (base58), NULL when it had none
+ALTER TABLE deposits ADD COLUMN IF NOT EXISTS reference TEXT;
+CREATE INDEX IF NOT EXISTS deposits_reference_idx ON deposits (reference);
+
+CREATE TABLE IF NOT EXISTS withdrawals (
+    seq          BIGINT PRIMARY KEY,
+    signature    TEXT        NOT NULL,
+    slot         BIGINT      NOT NULL,
+    user_pubkey  TEXT        NOT NULL,
+    amount       BIGINT      NOT NULL,
+    destination  TEXT        NOT NULL,
+    indexed_at   TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS withdrawals_user_idx ON withdrawals (user_pubkey);
+
+CREATE TABLE IF NOT EXISTS bets (
+    seq          BIGINT PRIMARY KEY,
+    signature    TEXT        NOT NULL,
+    slot         BIGINT      NOT NULL,
+    vault        TEXT        NOT NULL,
+    bet_id_hash  BYTEA       NOT NULL,  -- first 16 bytes of keccak(bet_id)
+    game_id      BIGINT      NOT NULL,
+    stake        BIGINT      NOT NULL,
+    payout       BIGINT      NOT NULL,
+    gem_data     BYTEA       NOT NULL,
+    indexed_at   TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS bets_vault_idx ON bets (vault);
+CREATE INDEX IF NOT EXISTS bets_bet_id_idx ON bets (bet_id_hash);
+
+CREATE TABLE IF NOT EXISTS gem_awards (
+    seq              BIGINT PRIMARY KEY,
+    signature        TEXT        NOT NULL,
+    slot             BIGINT      NOT NULL,
+    vault            TEXT        NOT NULL,
+    gems             BYTEA       NOT NULL,
+    boost_multiplier INTEGER     NOT NULL,
+    indexed_at       TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS gem_awards_vault_idx ON gem_awards (vault);
+
+-- Transfers above the compliance threshold, for AML review
+CREATE TABLE IF NOT EXISTS large_transfers (
+    seq           BIGINT PRIMARY KEY,
+    signature     TEXT        NOT NULL,
+    slot          BIGINT      NOT NULL,
+    vault         TEXT        NOT NULL,
+    kind          SMALLINT    NOT NULL,  -- 0 deposit, 1 withdrawal, 2 payout
+    amount        BIGINT      NOT NULL,
+    window_start  BIGINT      NOT NULL,  -- unix time the vault's 24h window opened
+    window_total  BIGINT      NOT NULL,
+    indexed_at    TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS large_transfers_vault_idx ON large_transfers (vault);
+
+-- Jurisdiction changes made by the admin; the latest row per vault is current
+CREATE TABLE IF NOT EXISTS vault_regions (
+    seq              BIGINT PRIMARY KEY,
+    signature        TEXT        NOT NULL,
+    slot             BIGINT      NOT NULL,
+    vault            TEXT        NOT NULL,
+    previous_region  SMALLINT    NOT NULL,
+    region           SMALLINT    NOT NULL,
+    indexed_at       TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS vault_regions_vault_idx ON vault_regions (vault, seq);
+
+-- Player challenges of settled bets; award stays NULL until the multisig rules
+CREATE TABLE IF NOT EXISTS disputes (
+    seq           BIGINT PRIMARY KEY,  -- of the DisputeFiled event
+    signature     TEXT        NOT NULL,
+    slot          BIGINT      NOT NULL,
+    vault         TEXT        NOT NULL,
+    bet_id_hash   BYTEA       NOT NULL,
+    amount        BIGINT      NOT NULL,
+    award         BIGINT,
+    resolved_seq  BIGINT,
+    indexed_at    TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS disputes_bet_idx ON disputes (vault, bet_id_hash);
+
+-- Side bets settled with a parent bet in `bets`, in the parent's order
+CREATE TABLE IF NOT EXISTS side_bets (
+    seq                 BIGINT PRIMARY KEY,
+    signature           TEXT        NOT NULL,
+    slot                BIGINT      NOT NULL,
+    vault               TEXT        NOT NULL,
+    parent_bet_id_hash  BYTEA       NOT NULL,
+    side_index          SMALLINT    NOT NULL,
+    game_id             BIGINT      NOT NULL,
+    stake               BIGINT      NOT NULL,
+    payout              BIGINT      NOT NULL,
+    indexed_at          TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS side_bets_parent_idx ON side_bets (parent_bet_id_hash);
+
+-- Followers' copies of a leader's bet; the leader's own bet is in `bets`
+CREATE TABLE IF NOT EXISTS mirrored_bets (
+    seq             BIGINT PRIMARY KEY,
+    signature       TEXT        NOT NULL,
+    slot            BIGINT      NOT NULL,
+    vault           TEXT        NOT NULL,
+    leader_vault    TEXT        NOT NULL,
+    bet_id_hash     BYTEA       NOT NULL,
+    game_id         BIGINT      NOT NULL,
+    stake           BIGINT      NOT NULL,
+    payout          BIGINT      NOT NULL,
+    indexed_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS mirrored_bets_leader_idx ON mirrored_bets (leader_vault, bet_id_hash);
+
+-- Tournament bracket matches as the settler posts them
+CREATE TABLE IF NOT EXISTS tournament_matches (
+    seq             BIGINT PRIMARY KEY,
+    signature       TEXT        NOT NULL,
+    slot            BIGINT      NOT NULL,
+    tournament_id   BIGINT      NOT NULL,
+    round           SMALLINT    NOT NULL,
+    match_index     INTEGER     NOT NULL,
+    winner          TEXT        NOT NULL,
+    loser           TEXT        NOT NULL,
+    indexed_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS tournament_matches_tournament_idx ON tournament_matches (tournament_id, round);
+
+-- Final standings of paid-out tournaments, one row per entrant in seeding order; 0 is the champion
+CREATE TABLE IF NOT EXISTS tournament_standings (
+    seq             BIGINT      NOT NULL,
+    seed            SMALLINT    NOT NULL,
+    signature       TEXT        NOT NULL,
+    slot            BIGINT      NOT NULL,
+    tournament_id   BIGINT      NOT NULL,
+    game_id         BIGINT      NOT NULL,
+    vault           TEXT        NOT NULL,
+    standing        SMALLINT    NOT NULL,
+    prize           BIGINT      NOT NULL,
+    indexed_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (seq, seed)
+);
+CREATE INDEX IF NOT EXISTS tournament_standings_vault_idx ON tournament_standings (vault);
+
+-- Jackpot hits; tier 0 is the mini, 1 the major and 2 the grand
+CREATE TABLE IF NOT EXISTS jackpot_awards (
+    seq             BIGINT PRIMARY KEY,
+    signature       TEXT        NOT NULL,
+    slot            BIGINT      NOT NULL,
+    vault           TEXT        NOT NULL,
+    bet_id_hash     BYTEA       NOT NULL,
+    game_id         BIGINT      NOT NULL,
+    tier            SMALLINT    NOT NULL,
+    amount          BIGINT      NOT NULL,
+    indexed_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS jackpot_awards_game_idx ON jackpot_awards (game_id, tier);
+
+-- Wins above the streaming threshold; `streamed` is paid in `tranches` by claim_payout_tranches
+CREATE TABLE IF NOT EXISTS payout_schedules (
+    seq             BIGINT PRIMARY KEY,
+    signature       TEXT        NOT NULL,
+    slot            BIGINT      NOT NULL,
+    vault           TEXT        NOT NULL,
+    bet_id_hash     BYTEA       NOT NULL,
+    paid_now        BIGINT      NOT NULL,
+    streamed        BIGINT      NOT NULL,
+    tranches        INTEGER     NOT NULL,
+    interval_secs   BIGINT      NOT NULL,
+    indexed_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Tranche claims against a payout schedule; `claimed` is the running total after this claim
+CREATE TABLE IF NOT EXISTS payout_tranches (
+    seq             BIGINT PRIMARY KEY,
+    signature       TEXT        NOT NULL,
+    slot            BIGINT      NOT NULL,
+    vault           TEXT        NOT NULL,
+    bet_id_hash     BYTEA       NOT NULL,
+    amount          BIGINT      NOT NULL,
+    claimed         BIGINT      NOT NULL,
+    total           BIGINT      NOT NULL,
+    indexed_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS payout_tranches_bet_idx ON payout_tranches (vault, bet_id_hash);
+
+-- House rebates on settlements by vaults with staked platform tokens; `wagered` includes side bets
+CREATE TABLE IF NOT EXISTS staking_rebates (
+    seq             BIGINT PRIMARY KEY,
+    signature       TEXT        NOT NULL,
+    slot            BIGINT      NOT NULL,
+    vault           TEXT        NOT NULL,
+    bet_id_hash     BYTEA       NOT NULL,
+    wagered         BIGINT      NOT NULL,
+    rebate_bps      INTEGER     NOT NULL,
+    rebate          BIGINT      NOT NULL,
+    indexed_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Upgrades the multisig announced; buffer_hash is the sha256 of the buffer's program bytes
+CREATE TABLE IF NOT EXISTS upgrade_announcements (
+    seq                    BIGINT PRIMARY KEY,
+    signature              TEXT        NOT NULL,
+    slot                   BIGINT      NOT NULL,
+    buffer                 TEXT        NOT NULL,
+    buffer_hash            BYTEA       NOT NULL,
+    earliest_upgrade_slot  BIGINT      NOT NULL,
+    indexed_at             TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Recorded deployments; buffer_hash and earliest_upgrade_slot are NULL when none was announced
+CREATE TABLE IF NOT EXISTS program_upgrades (
+    seq                    BIGINT PRIMARY KEY,
+    signature              TEXT        NOT NULL,
+    slot                   BIGINT      NOT NULL,
+    buffer_hash            BYTEA,
+    earliest_upgrade_slot  BIGINT,
+    deployed_slot          BIGINT      NOT NULL,
+    on_schedule            BOOLEAN     NOT NULL,
+    indexed_at             TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Settled head-to-head bets; winner is NULL for a draw
+CREATE TABLE IF NOT EXISTS pvp_bets (
+    seq             BIGINT PRIMARY KEY,
+    signature       TEXT        NOT NULL,
+    slot            BIGINT      NOT NULL,
+    bet_id_hash     BYTEA       NOT NULL,
+    game_id         BIGINT      NOT NULL,
+    creator_vault   TEXT        NOT NULL,
+    opponent_vault  TEXT        NOT NULL,
+    winner          TEXT,
+    stake           BIGINT      NOT NULL,
+    rake            BIGINT      NOT NULL,
+    indexed_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS pvp_bets_creator_idx ON pvp_bets (creator_vault);
+CREATE INDEX IF NOT EXISTS pvp_bets_opponent_idx ON pvp_bets (opponent_vault);
+
+-- Seats of settled table rounds, one row per seat in seating order; rake is the whole round's
+CREATE TABLE IF NOT EXISTS table_seats (
+    seq         BIGINT      NOT NULL,
+    seat        SMALLINT    NOT NULL,
+    signature   TEXT        NOT NULL,
+    slot        BIGINT      NOT NULL,
+    round_id    BIGINT      NOT NULL,
+    game_id     BIGINT      NOT NULL,
+    vault       TEXT        NOT NULL,
+    committed   BIGINT      NOT NULL,
+    payout      BIGINT      NOT NULL,
+    rake        BIGINT      NOT NULL,
+    indexed_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (seq, seat)
+);
+CREATE INDEX IF NOT EXISTS table_seats_vault_idx ON table_seats (vault);
+
+-- Refunds paid on insured pending bets that lost, next to their settlement in `bets`
+CREATE TABLE IF NOT EXISTS insurance_refunds (
+    seq          BIGINT PRIMARY KEY,
+    signature    TEXT        NOT NULL,
+    slot         BIGINT      NOT NULL,
+    vault        TEXT        NOT NULL,
+    bet_id_hash  BYTEA       NOT NULL,
+    stake        BIGINT      NOT NULL,
+    premium      BIGINT      NOT NULL,
+    refund       BIGINT      NOT NULL,
+    indexed_at   TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS insurance_refunds_vault_idx ON insurance_refunds (vault);
+
+-- Latest account state streamed from the Geyser feed. Rows are only replaced
+-- by updates from the same or a later slot; every change is announced on the
+-- `balances` channel for LISTEN-ing frontends.
+
+CREATE TABLE IF NOT EXISTS vault_balances (
+    vault          TEXT PRIMARY KEY,
+    owner          TEXT        NOT NULL,
+    lamports       BIGINT      NOT NULL,
+    locked_amount  BIGINT      NOT NULL,
+    slot           BIGINT      NOT NULL,
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS vault_balances_owner_idx ON vault_balances (owner);
+
+CREATE TABLE IF NOT EXISTS house_balance (
+    address        TEXT PRIMARY KEY,
+    lamports       BIGINT      NOT NULL,
+    total_volume   BIGINT      NOT NULL,
+    slot           BIGINT      NOT NULL,
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE OR REPLACE FUNCTION notify_balance() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify('balances', json_build_object('table', TG_TABLE_NAME, 'row', row_to_json(NEW))::text);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS vault_balances_notify ON vault_balances;
+CREATE TRIGGER vault_balances_notify AFTER INSERT OR UPDATE ON vault_balances
+    FOR EACH ROW EXECUTE FUNCTION notify_balance();
+
+DROP TRIGGER IF EXISTS house_balance_notify ON house_balance;
+CREATE TRIGGER house_balance_notify AFTER INSERT OR UPDATE ON house_balance
+    FOR EACH ROW EXECUTE FUNCTION notify_balance();
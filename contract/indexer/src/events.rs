@@ -0,0 +1,47 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+    EncodedTransaction, UiInstruction, UiMessage,
+};
+
+pub use sols_bet_sdk::events::{decode_event, ProgramEvent};
+
+/// Extract program events from the inner instructions of a confirmed transaction
+pub fn events_from_transaction(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    program_id: &Pubkey,
+) -> Vec<ProgramEvent> {
+    let Some(meta) = tx.transaction.meta.as_ref() else {
+        return Vec::new();
+    };
+    let EncodedTransaction::Json(ui_tx) = &tx.transaction.transaction else {
+        return Vec::new();
+    };
+    let UiMessage::Raw(message) = &ui_tx.message else {
+        return Vec::new();
+    };
+
+    // Static keys first, then any keys loaded from address lookup tables
+    let mut account_keys = message.account_keys.clone();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        account_keys.extend(loaded.writable.iter().cloned());
+        account_keys.extend(loaded.readonly.iter().cloned());
+    }
+    let program_id = program_id.to_string();
+
+    let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions else {
+        return Vec::new();
+    };
+
+    inner_instructions
+        .iter()
+        .flat_map(|inner| inner.instructions.iter())
+        .filter_map(|ix| match ix {
+            UiInstruction::Compiled(compiled) => Some(compiled),
+            _ => None,
+        })
+        .filter(|ix| account_keys.get(ix.program_id_index as usize) == Some(&program_id))
+        .filter_map(|ix| bs58::decode(&ix.data).into_vec().ok())
+        .filter_map(|data| decode_event(&data))
+        .collect()
+}
@@ -0,0 +1,221 @@
+//! Yellowstone gRPC account feed
+//!
+//! Streams `UserVault` and `HouseVault` account writes straight from a Geyser
+//! node, so balances reach Postgres (and `LISTEN balances` subscribers) within
+//! a slot instead of after a transaction fetch. `PauseConfig` writes are
+//! watched as well so pause changes can be alerted on. Only the subset of
+//! `geyser.proto` the indexer uses is declared below; prost skips the rest.
+
+use std::collections::HashMap;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{bail, Context, Result};
+use futures::channel::mpsc;
+use futures::SinkExt;
+use smart_vault_v2::{HouseVault, PauseConfig, UserVault};
+use sols_bet_sdk::vault_needs_migration;
+use solana_sdk::pubkey::Pubkey;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{ClientTlsConfig, Endpoint};
+
+use crate::metrics::Metrics;
+use crate::store::Store;
+use crate::webhooks::Notifier;
+
+const SUBSCRIBE_PATH: &str = "/geyser.Geyser/Subscribe";
+
+mod proto {
+    use std::collections::HashMap;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeRequest {
+        #[prost(map = "string, message", tag = "1")]
+        pub accounts: HashMap<String, SubscribeRequestFilterAccounts>,
+        #[prost(enumeration = "CommitmentLevel", optional, tag = "6")]
+        pub commitment: Option<i32>,
+        #[prost(message, optional, tag = "9")]
+        pub ping: Option<SubscribeRequestPing>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeRequestFilterAccounts {
+        #[prost(string, repeated, tag = "2")]
+        pub account: Vec<String>,
+        #[prost(string, repeated, tag = "3")]
+        pub owner: Vec<String>,
+        #[prost(message, repeated, tag = "4")]
+        pub filters: Vec<SubscribeRequestFilterAccountsFilter>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeRequestFilterAccountsFilter {
+        #[prost(message, optional, tag = "1")]
+        pub memcmp: Option<SubscribeRequestFilterAccountsFilterMemcmp>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeRequestFilterAccountsFilterMemcmp {
+        #[prost(uint64, tag = "1")]
+        pub offset: u64,
+        #[prost(bytes = "vec", optional, tag = "2")]
+        pub bytes: Option<Vec<u8>>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeRequestPing {
+        #[prost(int32, tag = "1")]
+        pub id: i32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeUpdate {
+        #[prost(string, repeated, tag = "1")]
+        pub filters: Vec<String>,
+        #[prost(message, optional, tag = "2")]
+        pub account: Option<SubscribeUpdateAccount>,
+        #[prost(message, optional, tag = "6")]
+        pub ping: Option<SubscribeUpdatePing>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeUpdateAccount {
+        #[prost(message, optional, tag = "1")]
+        pub account: Option<SubscribeUpdateAccountInfo>,
+        #[prost(uint64, tag = "2")]
+        pub slot: u64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeUpdateAccountInfo {
+        #[prost(bytes = "vec", tag = "1")]
+        pub pubkey: Vec<u8>,
+        #[prost(uint64, tag = "2")]
+        pub lamports: u64,
+        #[prost(bytes = "vec", tag = "6")]
+        pub data: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SubscribeUpdatePing {}
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum CommitmentLevel {
+        Processed = 0,
+        Confirmed = 1,
+        Finalized = 2,
+    }
+}
+
+/// Geyser endpoint settings
+pub struct GeyserConfig {
+    pub url: String,
+    pub x_token: Option<String>,
+}
+
+/// Subscribe to the program's vault accounts and persist every update until the stream ends
+pub async fn run(
+    config: &GeyserConfig,
+    program_id: &Pubkey,
+    store: &Store,
+    metrics: &Metrics,
+    notifier: Option<&Notifier>,
+) -> Result<()> {
+    let mut endpoint = Endpoint::from_shared(config.url.clone()).context("parsing GEYSER_URL")?;
+    if config.url.starts_with("https") {
+        endpoint = endpoint.tls_config(ClientTlsConfig::new())?;
+    }
+    let mut grpc = tonic::client::Grpc::new(endpoint.connect().await.context("connecting to Geyser")?);
+    grpc.ready().await?;
+
+    // Requests are streamed so pings can be answered on the same call
+    let (mut requests, request_stream) = mpsc::channel(8);
+    requests.send(subscribe_request(program_id)).await?;
+
+    let mut request = tonic::Request::new(request_stream);
+    if let Some(token) = &config.x_token {
+        request.metadata_mut().insert("x-token", token.parse().context("invalid GEYSER_X_TOKEN")?);
+    }
+    let codec: ProstCodec<proto::SubscribeRequest, proto::SubscribeUpdate> = ProstCodec::default();
+    let mut updates = grpc
+        .streaming(request, PathAndQuery::from_static(SUBSCRIBE_PATH), codec)
+        .await?
+        .into_inner();
+
+    log::info!("Geyser feed connected to {}", config.url);
+    while let Some(update) = updates.message().await? {
+        if update.ping.is_some() {
+            let pong = proto::SubscribeRequest {
+                ping: Some(proto::SubscribeRequestPing { id: 1 }),
+                ..Default::default()
+            };
+            requests.send(pong).await?;
+            continue;
+        }
+        let Some(proto::SubscribeUpdateAccount { account: Some(info), slot }) = update.account else {
+            continue;
+        };
+        if let Err(err) = record_account(store, metrics, notifier, &info, slot).await {
+            log::error!("Failed to record account update at slot {slot}: {err:#}");
+            metrics.errors.with_label_values(&["geyser"]).inc();
+        }
+    }
+    bail!("Geyser stream closed")
+}
+
+/// One filter per account type, matched on owner and Anchor discriminator
+fn subscribe_request(program_id: &Pubkey) -> proto::SubscribeRequest {
+    let filter = |discriminator: [u8; 8]| proto::SubscribeRequestFilterAccounts {
+        account: vec![],
+        owner: vec![program_id.to_string()],
+        filters: vec![proto::SubscribeRequestFilterAccountsFilter {
+            memcmp: Some(proto::SubscribeRequestFilterAccountsFilterMemcmp {
+                offset: 0,
+                bytes: Some(discriminator.to_vec()),
+            }),
+        }],
+    };
+    proto::SubscribeRequest {
+        accounts: HashMap::from([
+            ("vaults".to_string(), filter(UserVault::DISCRIMINATOR)),
+            ("house".to_string(), filter(HouseVault::DISCRIMINATOR)),
+            ("pause".to_string(), filter(PauseConfig::DISCRIMINATOR)),
+        ]),
+        commitment: Some(proto::CommitmentLevel::Confirmed as i32),
+        ping: None,
+    }
+}
+
+async fn record_account(
+    store: &Store,
+    metrics: &Metrics,
+    notifier: Option<&Notifier>,
+    info: &proto::SubscribeUpdateAccountInfo,
+    slot: u64,
+) -> Result<()> {
+    let address = Pubkey::try_from(info.pubkey.as_slice()).map_err(|_| anyhow::anyhow!("bad pubkey"))?;
+    match info.data.get(..8) {
+        // Legacy vaults are shorter than the zero-copy layout until `migrate_vault` grows them
+        Some(tag) if tag == UserVault::DISCRIMINATOR && !vault_needs_migration(&info.data) => {
+            let vault = UserVault::try_deserialize(&mut &info.data[..])?;
+            store.record_vault_balance(&address, &vault, info.lamports, slot).await
+        }
+        Some(tag) if tag == HouseVault::DISCRIMINATOR => {
+            let house = HouseVault::try_deserialize(&mut &info.data[..])?;
+            metrics.house_balance.set(info.lamports as i64);
+            if let Some(notifier) = notifier {
+                notifier.on_house_balance(info.lamports, slot);
+            }
+            store.record_house_balance(&address, &house, info.lamports, slot).await
+        }
+        Some(tag) if tag == PauseConfig::DISCRIMINATOR => {
+            let pause = PauseConfig::try_deserialize(&mut &info.data[..])?;
+            if let Some(notifier) = notifier {
+                notifier.on_pause_config(&pause, slot);
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
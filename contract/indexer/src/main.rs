@@ -0,0 +1,202 @@
+//! Smart Vault V2 indexer
+//!
+//! Subscribes to program logs, fetches each matching transaction, decodes the
+//! `emit_cpi!` events from its inner instructions and writes them to Postgres.
+//!
+//! Configuration (environment):
+//! * `RPC_URL`      – HTTP RPC endpoint used to fetch transactions
+//! * `WS_URL`       – websocket endpoint used for the log subscription
+//! * `DATABASE_URL` – Postgres connection string
+//! * `PROGRAM_ID`   – optional override of the program address
+//! * `GEYSER_URL`   – optional Yellowstone gRPC endpoint; enables the live balance feed
+//! * `GEYSER_X_TOKEN` – optional auth token for `GEYSER_URL`
+//! * `METRICS_ADDR` – listen address of the `/metrics` endpoint (default `0.0.0.0:9101`)
+//! * `WEBHOOK_URLS` – optional comma-separated alert webhooks, see [`webhooks`]
+
+mod events;
+mod geyser;
+mod metrics;
+mod store;
+mod webhooks;
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcTransactionConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::geyser::GeyserConfig;
+use crate::metrics::Metrics;
+use crate::store::Store;
+use crate::webhooks::{Notifier, WebhookConfig};
+
+/// Delay before re-subscribing after the websocket drops
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+struct Config {
+    rpc_url: String,
+    ws_url: String,
+    database_url: String,
+    program_id: Pubkey,
+    geyser: Option<GeyserConfig>,
+    metrics_addr: String,
+    webhooks: Option<WebhookConfig>,
+}
+
+impl Config {
+    fn from_env() -> Result<Self> {
+        let program_id = match std::env::var("PROGRAM_ID") {
+            Ok(id) => Pubkey::from_str(&id).context("parsing PROGRAM_ID")?,
+            Err(_) => smart_vault_v2::ID,
+        };
+        Ok(Self {
+            rpc_url: std::env::var("RPC_URL").context("RPC_URL not set")?,
+            ws_url: std::env::var("WS_URL").context("WS_URL not set")?,
+            database_url: std::env::var("DATABASE_URL").context("DATABASE_URL not set")?,
+            program_id,
+            geyser: std::env::var("GEYSER_URL").ok().map(|url| GeyserConfig {
+                url,
+                x_token: std::env::var("GEYSER_X_TOKEN").ok(),
+            }),
+            metrics_addr: std::env::var("METRICS_ADDR").unwrap_or_else(|_| "0.0.0.0:9101".to_string()),
+            webhooks: WebhookConfig::from_env()?,
+        })
+    }
+}
+
+/// Tracks the highest event sequence number seen so gaps can be reported for backfill
+struct SeqTracker {
+    last_seq: Option<u64>,
+    metrics: Arc<Metrics>,
+}
+
+impl SeqTracker {
+    fn new(metrics: Arc<Metrics>) -> Self {
+        Self { last_seq: None, metrics }
+    }
+
+    fn observe(&mut self, seq: u64) {
+        if let Some(last) = self.last_seq {
+            if seq > last + 1 {
+                log::warn!("Missed events {}..={}; backfill required", last + 1, seq - 1);
+                self.metrics.missed_events.inc_by(seq - last - 1);
+            }
+            if seq <= last {
+                return;
+            }
+        }
+        self.last_seq = Some(seq);
+        self.metrics.last_seq.set(seq as i64);
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    env_logger::init();
+    let mut config = Config::from_env()?;
+    let store = Arc::new(Store::connect(&config.database_url).await?);
+    let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+    let metrics = Arc::new(Metrics::new()?);
+    metrics.serve(&config.metrics_addr)?;
+    let mut tracker = SeqTracker::new(metrics.clone());
+    let notifier = config.webhooks.take().map(Notifier::new).transpose()?.map(Arc::new);
+
+    if let Some(geyser_config) = config.geyser.take() {
+        let store = store.clone();
+        let metrics = metrics.clone();
+        let notifier = notifier.clone();
+        let program_id = config.program_id;
+        tokio::spawn(async move {
+            loop {
+                let result = geyser::run(&geyser_config, &program_id, &store, &metrics, notifier.as_deref()).await;
+                if let Err(err) = result {
+                    log::error!("Geyser feed failed: {err:#}");
+                    metrics.errors.with_label_values(&["geyser"]).inc();
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+    }
+
+    log::info!("Indexing program {}", config.program_id);
+    loop {
+        if let Err(err) = run(&config, &rpc, &store, &mut tracker, notifier.as_deref()).await {
+            log::error!("Subscription failed: {err:#}");
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Consume the log subscription until it ends
+async fn run(
+    config: &Config,
+    rpc: &RpcClient,
+    store: &Store,
+    tracker: &mut SeqTracker,
+    notifier: Option<&Notifier>,
+) -> Result<()> {
+    let pubsub = PubsubClient::new(&config.ws_url).await?;
+    let (mut logs, unsubscribe) = pubsub
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![config.program_id.to_string()]),
+            RpcTransactionLogsConfig { commitment: Some(CommitmentConfig::confirmed()) },
+        )
+        .await?;
+
+    while let Some(response) = logs.next().await {
+        if response.value.err.is_some() {
+            continue;
+        }
+        let signature = response.value.signature;
+        if let Err(err) = index_transaction(config, rpc, store, tracker, notifier, &signature).await {
+            log::error!("Failed to index {signature}: {err:#}");
+            tracker.metrics.errors.with_label_values(&["transaction"]).inc();
+        }
+    }
+
+    unsubscribe().await;
+    Ok(())
+}
+
+/// Fetch a transaction, decode its events and persist them
+async fn index_transaction(
+    config: &Config,
+    rpc: &RpcClient,
+    store: &Store,
+    tracker: &mut SeqTracker,
+    notifier: Option<&Notifier>,
+    signature: &str,
+) -> Result<()> {
+    let tx = rpc
+        .get_transaction_with_config(
+            &Signature::from_str(signature)?,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?;
+
+    for event in events::events_from_transaction(&tx, &config.program_id) {
+        tracker.observe(event.seq());
+        store.record(signature, tx.slot, &event).await?;
+        tracker.metrics.events.with_label_values(&[event.name()]).inc();
+        if let Some(notifier) = notifier {
+            notifier.on_event(signature, &event);
+        }
+    }
+    if let Some(block_time) = tx.block_time {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        tracker.metrics.event_lag.set(now - block_time);
+    }
+    Ok(())
+}
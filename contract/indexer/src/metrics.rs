@@ -0,0 +1,62 @@
+use std::thread;
+
+use anyhow::{anyhow, Result};
+use prometheus::{Encoder, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+
+/// Indexer metrics exported for Prometheus
+pub struct Metrics {
+    registry: Registry,
+    pub events: IntCounterVec,      // Events persisted, by event name (BetSettled = settlements)
+    pub errors: IntCounterVec,      // Failures by stage (transaction, geyser)
+    pub missed_events: IntCounter,  // Sequence numbers skipped between observed events
+    pub last_seq: IntGauge,         // Highest event sequence number seen
+    pub event_lag: IntGauge,        // Seconds between block time and indexing of the last transaction
+    pub house_balance: IntGauge,    // House vault lamports from the Geyser feed
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self> {
+        let metrics = Self {
+            registry: Registry::new(),
+            events: IntCounterVec::new(Opts::new("indexer_events_total", "Events persisted"), &["event"])?,
+            errors: IntCounterVec::new(Opts::new("indexer_errors_total", "Indexing failures"), &["stage"])?,
+            missed_events: IntCounter::new("indexer_missed_events_total", "Event sequence gaps")?,
+            last_seq: IntGauge::new("indexer_last_seq", "Highest event sequence number seen")?,
+            event_lag: IntGauge::new("indexer_event_lag_seconds", "Block time to indexing delay")?,
+            house_balance: IntGauge::new("indexer_house_balance_lamports", "House vault balance")?,
+        };
+        metrics.registry.register(Box::new(metrics.events.clone()))?;
+        metrics.registry.register(Box::new(metrics.errors.clone()))?;
+        metrics.registry.register(Box::new(metrics.missed_events.clone()))?;
+        metrics.registry.register(Box::new(metrics.last_seq.clone()))?;
+        metrics.registry.register(Box::new(metrics.event_lag.clone()))?;
+        metrics.registry.register(Box::new(metrics.house_balance.clone()))?;
+        Ok(metrics)
+    }
+
+    /// Serve the registry in Prometheus text format at `http://{addr}/metrics`
+    pub fn serve(&self, addr: &str) -> Result<()> {
+        let server = tiny_http::Server::http(addr).map_err(|err| anyhow!("binding metrics on {addr}: {err}"))?;
+        let registry = self.registry.clone();
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let response = if request.url() == "/metrics" {
+                    let encoder = TextEncoder::new();
+                    let mut body = Vec::new();
+                    if let Err(err) = encoder.encode(&registry.gather(), &mut body) {
+                        log::error!("encoding metrics: {err}");
+                    }
+                    let content_type = tiny_http::Header::from_bytes("Content-Type", encoder.format_type())
+                        .expect("static header");
+                    tiny_http::Response::from_data(body).with_header(content_type)
+                } else {
+                    tiny_http::Response::from_data(b"not found".to_vec()).with_status_code(404)
+                };
+                if let Err(err) = request.respond(response) {
+                    log::debug!("metrics response failed: {err}");
+                }
+            }
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,448 @@
+use anyhow::{Context, Result};
+use smart_vault_v2::{HouseVault, UserVault};
+use solana_sdk::pubkey::Pubkey;
+use tokio_postgres::{Client, NoTls};
+
+use crate::events::ProgramEvent;
+
+/// Postgres sink for decoded program events
+pub struct Store {
+    client: Client,
+}
+
+impl Store {
+    /// Connect and apply the schema (idempotent)
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let (client, connection) = tokio_postgres::connect(database_url, NoTls)
+            .await
+            .context("connecting to Postgres")?;
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                log::error!("Postgres connection closed: {err}");
+            }
+        });
+
+        client
+            .batch_execute(include_str!("../schema.sql"))
+            .await
+            .context("applying schema")?;
+        Ok(Self { client })
+    }
+
+    /// Persist one event; events already stored under the same sequence number are skipped
+    pub async fn record(&self, signature: &str, slot: u64, event: &ProgramEvent) -> Result<()> {
+        let seq = event.seq() as i64;
+        let slot = slot as i64;
+
+        match event {
+            ProgramEvent::Deposited(e) => {
+                let reference = (e.reference != [0; 32]).then(|| Pubkey::new_from_array(e.reference).to_string());
+                self.client
+                    .execute(
+                        "INSERT INTO deposits (seq, signature, slot, user_pubkey, amount, new_balance, reference)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.user.to_string(),
+                            &(e.amount as i64),
+                            &(e.new_balance as i64),
+                            &reference,
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::Withdrawn(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO withdrawals (seq, signature, slot, user_pubkey, amount, destination)
+                         VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (seq) DO NOTHING",
+                        &[&seq, &signature, &slot, &e.user.to_string(), &(e.amount as i64), &e.destination.to_string()],
+                    )
+                    .await?;
+            }
+            ProgramEvent::BetSettled(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO bets (seq, signature, slot, vault, bet_id_hash, game_id, stake, payout, gem_data)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.vault.to_string(),
+                            &&e.bet_id_hash[..],
+                            &(e.game_id as i64),
+                            &(e.stake as i64),
+                            &(e.payout as i64),
+                            &&e.gem_data[..],
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::GemsCredited(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO gem_awards (seq, signature, slot, vault, gems, boost_multiplier)
+                         VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (seq) DO NOTHING",
+                        &[&seq, &signature, &slot, &e.vault.to_string(), &&e.gems[..], &(e.boost_multiplier as i32)],
+                    )
+                    .await?;
+            }
+            ProgramEvent::LargeTransfer(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO large_transfers
+                             (seq, signature, slot, vault, kind, amount, window_start, window_total)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.vault.to_string(),
+                            &(e.kind as i16),
+                            &(e.amount as i64),
+                            &e.window_start,
+                            &(e.window_total as i64),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::VaultRegionSet(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO vault_regions (seq, signature, slot, vault, previous_region, region)
+                         VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.vault.to_string(),
+                            &(e.previous_region as i16),
+                            &(e.region as i16),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::DisputeFiled(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO disputes (seq, signature, slot, vault, bet_id_hash, amount)
+                         VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (seq) DO NOTHING",
+                        &[&seq, &signature, &slot, &e.vault.to_string(), &&e.bet_id_hash[..], &(e.amount as i64)],
+                    )
+                    .await?;
+            }
+            ProgramEvent::DisputeResolved(e) => {
+                self.client
+                    .execute(
+                        "UPDATE disputes SET award = $1, resolved_seq = $2
+                         WHERE vault = $3 AND bet_id_hash = $4 AND resolved_seq IS NULL",
+                        &[&(e.award as i64), &seq, &e.vault.to_string(), &&e.bet_id_hash[..]],
+                    )
+                    .await?;
+            }
+            ProgramEvent::SideBetSettled(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO side_bets
+                         (seq, signature, slot, vault, parent_bet_id_hash, side_index, game_id, stake, payout)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.vault.to_string(),
+                            &&e.parent_bet_id_hash[..],
+                            &(e.index as i16),
+                            &(e.game_id as i64),
+                            &(e.stake as i64),
+                            &(e.payout as i64),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::BetMirrored(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO mirrored_bets
+                         (seq, signature, slot, vault, leader_vault, bet_id_hash, game_id, stake, payout)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.vault.to_string(),
+                            &e.leader.to_string(),
+                            &&e.bet_id_hash[..],
+                            &(e.game_id as i64),
+                            &(e.stake as i64),
+                            &(e.payout as i64),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::TournamentMatchSettled(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO tournament_matches
+                         (seq, signature, slot, tournament_id, round, match_index, winner, loser)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &(e.tournament_id as i64),
+                            &(e.round as i16),
+                            &(e.match_index as i32),
+                            &e.winner.to_string(),
+                            &e.loser.to_string(),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::TournamentPrizesPaid(e) => {
+                for (seed, result) in e.standings.iter().enumerate() {
+                    self.client
+                        .execute(
+                            "INSERT INTO tournament_standings
+                             (seq, seed, signature, slot, tournament_id, game_id, vault, standing, prize)
+                             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT (seq, seed) DO NOTHING",
+                            &[
+                                &seq,
+                                &(seed as i16),
+                                &signature,
+                                &slot,
+                                &(e.tournament_id as i64),
+                                &(e.game_id as i64),
+                                &result.vault.to_string(),
+                                &(result.standing as i16),
+                                &(result.prize as i64),
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            ProgramEvent::JackpotAwarded(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO jackpot_awards (seq, signature, slot, vault, bet_id_hash, game_id, tier, amount)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.vault.to_string(),
+                            &&e.bet_id_hash[..],
+                            &(e.game_id as i64),
+                            &(e.tier as i16),
+                            &(e.amount as i64),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::UpgradeAnnounced(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO upgrade_announcements
+                         (seq, signature, slot, buffer, buffer_hash, earliest_upgrade_slot)
+                         VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.buffer.to_string(),
+                            &&e.buffer_hash[..],
+                            &(e.earliest_upgrade_slot as i64),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::UpgradeRecorded(e) => {
+                let announced = e.buffer_hash != [0; 32];
+                let buffer_hash = announced.then_some(&e.buffer_hash[..]);
+                let earliest_upgrade_slot = announced.then_some(e.earliest_upgrade_slot as i64);
+                self.client
+                    .execute(
+                        "INSERT INTO program_upgrades
+                         (seq, signature, slot, buffer_hash, earliest_upgrade_slot, deployed_slot, on_schedule)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &buffer_hash,
+                            &earliest_upgrade_slot,
+                            &(e.deployed_slot as i64),
+                            &e.on_schedule,
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::PayoutScheduled(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO payout_schedules
+                         (seq, signature, slot, vault, bet_id_hash, paid_now, streamed, tranches, interval_secs)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.vault.to_string(),
+                            &&e.bet_id_hash[..],
+                            &(e.paid_now as i64),
+                            &(e.streamed as i64),
+                            &(e.tranches as i32),
+                            &e.interval_secs,
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::PayoutTrancheClaimed(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO payout_tranches (seq, signature, slot, vault, bet_id_hash, amount, claimed, total)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.vault.to_string(),
+                            &&e.bet_id_hash[..],
+                            &(e.amount as i64),
+                            &(e.claimed as i64),
+                            &(e.total as i64),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::StakingRebatePaid(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO staking_rebates
+                         (seq, signature, slot, vault, bet_id_hash, wagered, rebate_bps, rebate)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.vault.to_string(),
+                            &&e.bet_id_hash[..],
+                            &(e.wagered as i64),
+                            &(e.rebate_bps as i32),
+                            &(e.rebate as i64),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::PvpBetSettled(e) => {
+                let winner = (e.winner != Pubkey::default()).then(|| e.winner.to_string());
+                self.client
+                    .execute(
+                        "INSERT INTO pvp_bets (seq, signature, slot, bet_id_hash, game_id,
+                             creator_vault, opponent_vault, winner, stake, rake)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &&e.bet_id_hash[..],
+                            &(e.game_id as i64),
+                            &e.creator_vault.to_string(),
+                            &e.opponent_vault.to_string(),
+                            &winner,
+                            &(e.stake as i64),
+                            &(e.rake as i64),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::InsuranceRefunded(e) => {
+                self.client
+                    .execute(
+                        "INSERT INTO insurance_refunds
+                         (seq, signature, slot, vault, bet_id_hash, stake, premium, refund)
+                         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (seq) DO NOTHING",
+                        &[
+                            &seq,
+                            &signature,
+                            &slot,
+                            &e.vault.to_string(),
+                            &&e.bet_id_hash[..],
+                            &(e.stake as i64),
+                            &(e.premium as i64),
+                            &(e.refund as i64),
+                        ],
+                    )
+                    .await?;
+            }
+            ProgramEvent::TableRoundSettled(e) => {
+                for (seat, result) in e.seats.iter().enumerate() {
+                    self.client
+                        .execute(
+                            "INSERT INTO table_seats
+                             (seq, seat, signature, slot, round_id, game_id, vault, committed, payout, rake)
+                             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) ON CONFLICT (seq, seat) DO NOTHING",
+                            &[
+                                &seq,
+                                &(seat as i16),
+                                &signature,
+                                &slot,
+                                &(e.round_id as i64),
+                                &(e.game_id as i64),
+                                &result.vault.to_string(),
+                                &(result.committed as i64),
+                                &(result.payout as i64),
+                                &(e.rake as i64),
+                            ],
+                        )
+                        .await?;
+                }
+            }
+            ProgramEvent::GemBurned(_) | ProgramEvent::PromoClawback(_) | ProgramEvent::AuditPage(_) => {
+                log::debug!("{} event {seq} not persisted", event.name());
+            }
+        }
+        Ok(())
+    }
+
+    /// Upsert a vault's streamed balance unless a later slot is already stored
+    pub async fn record_vault_balance(&self, address: &Pubkey, vault: &UserVault, lamports: u64, slot: u64) -> Result<()> {
+        self.client
+            .execute(
+                "INSERT INTO vault_balances (vault, owner, lamports, locked_amount, slot)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (vault) DO UPDATE SET
+                     lamports = EXCLUDED.lamports, locked_amount = EXCLUDED.locked_amount,
+                     slot = EXCLUDED.slot, updated_at = now()
+                 WHERE vault_balances.slot <= EXCLUDED.slot",
+                &[
+                    &address.to_string(),
+                    &vault.owner.to_string(),
+                    &(lamports as i64),
+                    &(vault.locked_amount as i64),
+                    &(slot as i64),
+                ],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Upsert the house vault's streamed balance unless a later slot is already stored
+    pub async fn record_house_balance(&self, address: &Pubkey, house: &HouseVault, lamports: u64, slot: u64) -> Result<()> {
+        self.client
+            .execute(
+                "INSERT INTO house_balance (address, lamports, total_volume, slot)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (address) DO UPDATE SET
+                     lamports = EXCLUDED.lamports, total_volume = EXCLUDED.total_volume,
+                     slot = EXCLUDED.slot, updated_at = now()
+                 WHERE house_balance.slot <= EXCLUDED.slot",
+                &[&address.to_string(), &(lamports as i64), &(house.total_volume as i64), &(slot as i64)],
+            )
+            .await?;
+        Ok(())
+    }
+}
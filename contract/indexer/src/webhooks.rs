@@ -0,0 +1,175 @@
+//! Webhook notifications for events the ops team needs to see immediately
+//!
+//! Alerts are POSTed as JSON to every configured URL. The body carries a
+//! human-readable `content` line (so Discord webhooks render it directly)
+//! next to the structured `kind` and `data` fields for bots.
+//!
+//! Pause and reserve alerts are driven by the Geyser feed and fire on state
+//! transitions only, not on every account write.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use smart_vault_v2::PauseConfig;
+use solana_sdk::native_token::lamports_to_sol;
+
+use crate::events::ProgramEvent;
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct WebhookConfig {
+    pub urls: Vec<String>,
+    pub big_win_lamports: u64,            // Minimum net win that triggers a big-win alert
+    pub min_reserve_lamports: Option<u64>, // House balance below this is a reserve breach
+}
+
+impl WebhookConfig {
+    /// `WEBHOOK_URLS` (comma-separated), `WEBHOOK_BIG_WIN_LAMPORTS`, `WEBHOOK_MIN_RESERVE_LAMPORTS`
+    pub fn from_env() -> Result<Option<Self>> {
+        let Ok(urls) = std::env::var("WEBHOOK_URLS") else {
+            return Ok(None);
+        };
+        let big_win_lamports = match std::env::var("WEBHOOK_BIG_WIN_LAMPORTS") {
+            Ok(value) => value.parse().context("parsing WEBHOOK_BIG_WIN_LAMPORTS")?,
+            Err(_) => 10_000_000_000,
+        };
+        let min_reserve_lamports = match std::env::var("WEBHOOK_MIN_RESERVE_LAMPORTS") {
+            Ok(value) => Some(value.parse().context("parsing WEBHOOK_MIN_RESERVE_LAMPORTS")?),
+            Err(_) => None,
+        };
+        Ok(Some(Self {
+            urls: urls.split(',').map(|url| url.trim().to_string()).filter(|url| !url.is_empty()).collect(),
+            big_win_lamports,
+            min_reserve_lamports,
+        }))
+    }
+}
+
+/// Last observed state, so transition alerts fire once
+#[derive(Default)]
+struct AlertState {
+    emergency_paused: Option<bool>,
+    below_reserve: bool,
+}
+
+pub struct Notifier {
+    config: WebhookConfig,
+    client: reqwest::Client,
+    state: Mutex<AlertState>,
+}
+
+impl Notifier {
+    pub fn new(config: WebhookConfig) -> Result<Self> {
+        Ok(Self {
+            config,
+            client: reqwest::Client::builder().timeout(SEND_TIMEOUT).build()?,
+            state: Mutex::default(),
+        })
+    }
+
+    /// Check a decoded program event for alert conditions
+    pub fn on_event(&self, signature: &str, event: &ProgramEvent) {
+        match event {
+            ProgramEvent::BetSettled(e) => {
+                let net_win = e.payout.saturating_sub(e.stake);
+                if net_win >= self.config.big_win_lamports {
+                    self.send(
+                        "big_win",
+                        format!(
+                            "Big win: {} SOL on game {} (stake {} SOL)",
+                            lamports_to_sol(e.payout),
+                            e.game_id,
+                            lamports_to_sol(e.stake)
+                        ),
+                        json!({
+                            "vault": e.vault.to_string(),
+                            "bet_id_hash": hex(&e.bet_id_hash),
+                            "game_id": e.game_id,
+                            "stake": e.stake,
+                            "payout": e.payout,
+                            "seq": e.seq,
+                            "signature": signature,
+                        }),
+                    );
+                }
+            }
+            ProgramEvent::JackpotAwarded(e) => self.send(
+                "jackpot_hit",
+                format!("Jackpot hit: {} SOL from tier {} on game {}", lamports_to_sol(e.amount), e.tier, e.game_id),
+                json!({
+                    "vault": e.vault.to_string(),
+                    "bet_id_hash": hex(&e.bet_id_hash),
+                    "game_id": e.game_id,
+                    "tier": e.tier,
+                    "amount": e.amount,
+                    "seq": e.seq,
+                    "signature": signature,
+                }),
+            ),
+            _ => {}
+        }
+    }
+
+    /// Alert when the emergency pause is switched on or off
+    pub fn on_pause_config(&self, config: &PauseConfig, slot: u64) {
+        let previous = self.state.lock().unwrap().emergency_paused.replace(config.emergency_pause());
+        // The first observation only establishes the baseline, unless it is already paused
+        let changed = match previous {
+            Some(previous) => previous != config.emergency_pause(),
+            None => config.emergency_pause(),
+        };
+        if changed {
+            let content = if config.emergency_pause() {
+                "Emergency pause ACTIVATED"
+            } else {
+                "Emergency pause lifted"
+            };
+            self.send(
+                "emergency_pause",
+                content.to_string(),
+                json!({ "emergency_pause": config.emergency_pause(), "slot": slot }),
+            );
+        }
+    }
+
+    /// Alert when the house balance crosses below the configured reserve
+    pub fn on_house_balance(&self, lamports: u64, slot: u64) {
+        let Some(min_reserve) = self.config.min_reserve_lamports else {
+            return;
+        };
+        let below = lamports < min_reserve;
+        let was_below = std::mem::replace(&mut self.state.lock().unwrap().below_reserve, below);
+        if below && !was_below {
+            self.send(
+                "reserve_breach",
+                format!(
+                    "House reserve breach: {} SOL (minimum {} SOL)",
+                    lamports_to_sol(lamports),
+                    lamports_to_sol(min_reserve)
+                ),
+                json!({ "house_lamports": lamports, "min_reserve_lamports": min_reserve, "slot": slot }),
+            );
+        }
+    }
+
+    /// Deliver in the background so slow endpoints never stall indexing
+    fn send(&self, kind: &'static str, content: String, data: Value) {
+        let body = json!({ "content": content, "kind": kind, "data": data });
+        for url in &self.config.urls {
+            let request = self.client.post(url).json(&body);
+            let url = url.clone();
+            tokio::spawn(async move {
+                match request.send().await.and_then(|response| response.error_for_status()) {
+                    Ok(_) => log::info!("Webhook {kind} delivered to {url}"),
+                    Err(err) => log::error!("Webhook {kind} to {url} failed: {err}"),
+                }
+            });
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
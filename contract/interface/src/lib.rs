@@ -0,0 +1,79 @@
+//! CPI interface for game programs that settle bets through Smart Vault V2
+//!
+//! Depend on this crate instead of copying IDL structs into a game program.
+//! The `authority` of a settlement is either the admin, co-signing the outer
+//! transaction, or the game program's own [`GAME_AUTHORITY_SEED`] PDA, signed
+//! with `invoke_signed`, once the multisig has registered the program.
+//!
+//! * [`cpi`] – settlement helpers and their account structs
+//! * [`pda`] – address derivation for the accounts the CPI expects
+//! * account types, errors and limits re-exported from the program
+
+pub use smart_vault_v2::{
+    bet_id_hash, Blacklist, GameConfig, GameLimits, GamePrograms, GlobalConfig, GlobalStats, HouseVault, Jackpot,
+    PauseConfig, SideBet, UserVault, VaultError, GAME_AUTHORITY_SEED, GAME_CATEGORY_LIVE, GAME_CATEGORY_ORIGINALS,
+    GAME_CATEGORY_SLOTS, HOUSE_VERSION, ID, MAX_BET_ID_LEN, MAX_SIDE_BETS, PAUSE_CONFIG_VERSION, VAULT_VERSION,
+};
+pub use sols_bet_sdk::pda;
+
+pub mod cpi {
+    use anchor_lang::prelude::*;
+
+    pub use smart_vault_v2::cpi::accounts::BetAndSettle;
+
+    /// Outcome of one bet, settled against the vault in the CPI accounts
+    #[derive(Clone, Debug)]
+    pub struct BetResult {
+        pub stake: u64,
+        pub payout: u64,
+        /// At most [`crate::MAX_BET_ID_LEN`] bytes
+        pub bet_id: String,
+        pub game_id: u64,
+        /// `GAME_CATEGORY_*` code the stake counts toward in the house's volume breakdown
+        pub category: u8,
+        /// Gems awarded per type, common to legendary
+        pub gem_data: [u8; 7],
+    }
+
+    /// Settle `bet` into `ctx.accounts.vault`
+    ///
+    /// The optional bonus, gem inventory, loss-back, pity, receipt and trophy
+    /// accounts may be left `None`; `game_programs` is required unless the
+    /// admin signs.
+    /// `game_config` and `jackpot` are the game's config and jackpot addresses,
+    /// whether or not they exist. The per-transaction payout limit counts each
+    /// CPI settlement on its own, as it cannot see sibling CPIs.
+    pub fn bet_and_settle<'info>(ctx: CpiContext<'_, '_, '_, 'info, BetAndSettle<'info>>, bet: BetResult) -> Result<()> {
+        smart_vault_v2::cpi::bet_and_settle(
+            ctx,
+            bet.stake,
+            bet.payout,
+            bet.bet_id,
+            bet.game_id,
+            bet.category,
+            bet.gem_data.to_vec(),
+        )
+    }
+
+    /// Settle `bet` and up to [`crate::MAX_SIDE_BETS`] side bets in one call
+    ///
+    /// Side bets share the parent's vault and bet id and count toward the same
+    /// per-transaction payout limit. Pass each side bet's
+    /// [`pda::game_config`](crate::pda::game_config) in order as remaining accounts.
+    pub fn bet_and_settle_with_side_bets<'info>(
+        ctx: CpiContext<'_, '_, '_, 'info, BetAndSettle<'info>>,
+        bet: BetResult,
+        side_bets: Vec<crate::SideBet>,
+    ) -> Result<()> {
+        smart_vault_v2::cpi::bet_and_settle_with_side_bets(
+            ctx,
+            bet.stake,
+            bet.payout,
+            bet.bet_id,
+            bet.game_id,
+            bet.category,
+            bet.gem_data.to_vec(),
+            side_bets,
+        )
+    }
+}
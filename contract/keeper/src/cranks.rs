@@ -0,0 +1,377 @@
+//! Permissionless cranks
+//!
+//! Each crank scans chain state and returns the instructions that are due;
+//! the keeper sends each one in its own transaction so a single stale
+//! account cannot block the rest.
+
+use std::collections::HashSet;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::Result;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use sols_bet_sdk::constants::USER_VAULT_SPACE;
+use sols_bet_sdk::{instructions, lookup_table};
+use sols_bet_sdk::{
+    decode_account, pda, BetReceipt, DepositBonus, DisputeConfig, HealthMonitor, LossBack, PauseConfig, PayoutSchedule,
+    PendingBet, PvpBet, StatsSnapshots, UserVault, VaultRegistry, VaultRegistryEntry, WithdrawalQueue,
+    WithdrawalRequest, PROGRAM_ID, RECEIPT_RETENTION_SECS, VAULT_REGISTRY_PAGE_LEN,
+};
+
+pub trait Crank {
+    fn name(&self) -> &'static str;
+
+    /// Instructions due at unix time `now`
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>>;
+}
+
+/// All cranks the keeper runs, in execution order; `keeper` signs where a signer is required
+///
+/// `lookup_table`, when set, is kept stocked with active vaults and must be owned by `keeper`.
+pub fn all(keeper: &Pubkey, lookup_table: Option<Pubkey>) -> Vec<Box<dyn Crank>> {
+    let mut cranks: Vec<Box<dyn Crank>> = vec![
+        Box::new(MaintenanceResume),
+        Box::new(AutomationTick),
+        Box::new(ExpiredPendingBets),
+        Box::new(ExpiredPvpBets),
+        Box::new(QueuedWithdrawals),
+        Box::new(Heartbeat { operator: *keeper }),
+        Box::new(ExpiredBonuses),
+        Box::new(LossBackPayouts),
+        Box::new(PayoutTranches),
+        Box::new(ReceiptSweeper),
+        Box::new(VaultRegistration { payer: *keeper }),
+    ];
+    if let Some(table) = lookup_table {
+        cranks.push(Box::new(LookupTableVaults { table, authority: *keeper }));
+    }
+    cranks
+}
+
+/// Persist the end of an elapsed maintenance pause
+pub struct MaintenanceResume;
+
+impl Crank for MaintenanceResume {
+    fn name(&self) -> &'static str {
+        "maintenance_resume"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        let pause: PauseConfig = decode_account(&rpc.get_account_data(&pda::pause_config().0)?)?;
+        let window_end = i64::from(pause.maintenance_start_time) + i64::from(pause.maintenance_duration_hours) * 3600;
+        if pause.maintenance_pause() && now >= window_end {
+            Ok(vec![instructions::resume_after_maintenance()])
+        } else {
+            Ok(vec![])
+        }
+    }
+}
+
+/// Record a stats snapshot once its interval has elapsed
+///
+/// Idle until `initialize_stats_snapshots` has run.
+pub struct AutomationTick;
+
+impl Crank for AutomationTick {
+    fn name(&self) -> &'static str {
+        "automation_tick"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        let Ok(data) = rpc.get_account_data(&pda::stats_snapshots().0) else {
+            return Ok(vec![]);
+        };
+        let snapshots: StatsSnapshots = decode_account(&data)?;
+        if now >= snapshots.last_recorded.saturating_add(i64::from(snapshots.interval_secs)) {
+            Ok(vec![instructions::automation_tick()])
+        } else {
+            Ok(vec![])
+        }
+    }
+}
+
+/// Release the stakes of pending bets left unsettled past their `expires_at`
+pub struct ExpiredPendingBets;
+
+impl Crank for ExpiredPendingBets {
+    fn name(&self) -> &'static str {
+        "expired_pending_bets"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        let mut due = Vec::new();
+        for (_, bet) in program_accounts::<PendingBet>(rpc)? {
+            if now < bet.expires_at {
+                continue;
+            }
+            let vault: UserVault = decode_account(&rpc.get_account_data(&bet.vault)?)?;
+            due.push(instructions::expire_pending_bet(
+                &bet.payer,
+                &vault.owner,
+                &bet.bet_id_hash,
+                bet.insurance_refund > 0,
+            ));
+        }
+        Ok(due)
+    }
+}
+
+/// Refund PvP challenges nobody accepted before they expired, clearing them from the queue
+pub struct ExpiredPvpBets;
+
+impl Crank for ExpiredPvpBets {
+    fn name(&self) -> &'static str {
+        "expired_pvp_bets"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        Ok(program_accounts::<PvpBet>(rpc)?
+            .into_iter()
+            .filter(|(_, bet)| bet.accepted_at == 0 && bet.is_expired(now))
+            .map(|(_, bet)| instructions::expire_pvp_bet(&bet.creator, &bet.bet_id_hash))
+            .collect())
+    }
+}
+
+/// Honor withdrawals requested during a pause, in ticket order, once operations resume
+///
+/// Stops at the first gap in the tickets; the instructions go out in order,
+/// and the program rejects any request that is not at the head of the queue.
+pub struct QueuedWithdrawals;
+
+impl Crank for QueuedWithdrawals {
+    fn name(&self) -> &'static str {
+        "queued_withdrawals"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        let Ok(data) = rpc.get_account_data(&pda::withdrawal_queue().0) else {
+            return Ok(vec![]);
+        };
+        let queue: WithdrawalQueue = decode_account(&data)?;
+        let pause: PauseConfig = decode_account(&rpc.get_account_data(&pda::pause_config().0)?)?;
+        if queue.head == queue.tail || pause.is_paused(now) {
+            return Ok(vec![]);
+        }
+        let mut requests = program_accounts::<WithdrawalRequest>(rpc)?;
+        requests.sort_by_key(|(_, request)| request.ticket);
+        let mut due = Vec::new();
+        for (ticket, (_, request)) in (queue.head..).zip(requests) {
+            if request.ticket != ticket {
+                break;
+            }
+            let vault: UserVault = decode_account(&rpc.get_account_data(&request.vault)?)?;
+            due.push(instructions::process_withdrawal_request(&vault.owner));
+        }
+        Ok(due)
+    }
+}
+
+/// Send the operator heartbeat when `operator` is the configured one, otherwise flag a missed heartbeat
+///
+/// Idle until `initialize_health_monitor` has run. Heartbeats go out at half
+/// the interval so one slow pass does not mark the program degraded.
+pub struct Heartbeat {
+    operator: Pubkey,
+}
+
+impl Crank for Heartbeat {
+    fn name(&self) -> &'static str {
+        "heartbeat"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        let Ok(data) = rpc.get_account_data(&pda::health_monitor().0) else {
+            return Ok(vec![]);
+        };
+        let monitor: HealthMonitor = decode_account(&data)?;
+        if monitor.operator == self.operator {
+            if now - monitor.last_heartbeat >= monitor.interval_secs / 2 || monitor.degraded {
+                return Ok(vec![instructions::operator_heartbeat(&self.operator)]);
+            }
+        } else if monitor.heartbeat_overdue(now) && !monitor.degraded {
+            return Ok(vec![instructions::check_health()]);
+        }
+        Ok(vec![])
+    }
+}
+
+/// Forfeit expired deposit bonuses and close cleared ones
+pub struct ExpiredBonuses;
+
+impl Crank for ExpiredBonuses {
+    fn name(&self) -> &'static str {
+        "expired_bonuses"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        Ok(program_accounts::<DepositBonus>(rpc)?
+            .into_iter()
+            .filter(|(_, bonus)| bonus.cleared || now >= bonus.expires_at)
+            .map(|(_, bonus)| instructions::forfeit_bonus(&bonus.vault))
+            .collect())
+    }
+}
+
+/// Pay out loss-back balances whose promo window has closed, or carried over from an earlier one
+pub struct LossBackPayouts;
+
+impl Crank for LossBackPayouts {
+    fn name(&self) -> &'static str {
+        "loss_back_payouts"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        Ok(program_accounts::<LossBack>(rpc)?
+            .into_iter()
+            .filter(|(_, loss_back)| loss_back.claimable(now) > 0)
+            .map(|(_, loss_back)| instructions::claim_loss_back(&loss_back.vault))
+            .collect())
+    }
+}
+
+/// Pay the tranches of streamed wins that have fallen due
+pub struct PayoutTranches;
+
+impl Crank for PayoutTranches {
+    fn name(&self) -> &'static str {
+        "payout_tranches"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        Ok(program_accounts::<PayoutSchedule>(rpc)?
+            .into_iter()
+            .filter(|(_, schedule)| schedule.claimable(now) > 0)
+            .map(|(_, schedule)| {
+                instructions::claim_payout_tranches(&schedule.vault, &schedule.bet_id_hash, &schedule.rent_payer)
+            })
+            .collect())
+    }
+}
+
+/// Receipts closed per `sweep_bet_receipts` transaction
+const RECEIPTS_PER_SWEEP: usize = 12;
+
+/// Close bet receipts past the dispute window in batches, returning the rent to each vault
+pub struct ReceiptSweeper;
+
+impl Crank for ReceiptSweeper {
+    fn name(&self) -> &'static str {
+        "receipt_sweeper"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        // Mirrors the program: receipts are kept for the full retention period until disputes are configured
+        let window_secs = match rpc.get_account_data(&pda::dispute_config().0) {
+            Ok(data) => decode_account::<DisputeConfig>(&data)?.window_secs,
+            Err(_) => RECEIPT_RETENTION_SECS,
+        };
+        let expired: Vec<(Pubkey, [u8; 16])> = program_accounts::<BetReceipt>(rpc)?
+            .into_iter()
+            .filter(|(_, receipt)| now - receipt.settled_at > window_secs)
+            .map(|(_, receipt)| (receipt.vault, receipt.bet_id_hash))
+            .collect();
+        Ok(expired.chunks(RECEIPTS_PER_SWEEP).map(instructions::sweep_bet_receipts).collect())
+    }
+}
+
+/// List vaults missing from the vault registry, opening pages as they fill
+///
+/// Idle until `initialize_vault_registry` has run. Instructions must land in
+/// order; after a failure the next pass recomputes pages from chain state.
+pub struct VaultRegistration {
+    payer: Pubkey,
+}
+
+impl Crank for VaultRegistration {
+    fn name(&self) -> &'static str {
+        "vault_registration"
+    }
+
+    fn due(&self, rpc: &RpcClient, _now: i64) -> Result<Vec<Instruction>> {
+        let Ok(data) = rpc.get_account_data(&pda::vault_registry().0) else {
+            return Ok(vec![]);
+        };
+        let registry: VaultRegistry = decode_account(&data)?;
+        let registered: HashSet<Pubkey> = program_accounts::<VaultRegistryEntry>(rpc)?
+            .into_iter()
+            .map(|(_, entry)| entry.vault)
+            .collect();
+        let size = RpcFilterType::DataSize(USER_VAULT_SPACE as u64);
+
+        let (mut count, mut pages) = (registry.count, registry.pages);
+        let mut due = Vec::new();
+        for (address, vault) in program_accounts_filtered::<UserVault>(rpc, vec![size])? {
+            if registered.contains(&address) {
+                continue;
+            }
+            let page = (count / VAULT_REGISTRY_PAGE_LEN as u64) as u32;
+            if page == pages {
+                due.push(instructions::open_registry_page(&self.payer, page));
+                pages += 1;
+            }
+            due.push(instructions::register_vault(&self.payer, &vault.owner, page));
+            count += 1;
+        }
+        Ok(due)
+    }
+}
+
+/// Seconds since its last settlement within which a vault counts as active
+const ACTIVE_VAULT_SECS: i64 = 24 * 3600;
+
+/// Add recently active vaults to the settlement lookup table, highest lifetime wager first
+pub struct LookupTableVaults {
+    table: Pubkey,
+    authority: Pubkey,
+}
+
+impl Crank for LookupTableVaults {
+    fn name(&self) -> &'static str {
+        "lookup_table_vaults"
+    }
+
+    fn due(&self, rpc: &RpcClient, now: i64) -> Result<Vec<Instruction>> {
+        let table = lookup_table::decode(self.table, &rpc.get_account_data(&self.table)?)?;
+        // Only current-layout vaults decode; legacy ones are left until they migrate
+        let size = RpcFilterType::DataSize(USER_VAULT_SPACE as u64);
+        let mut active: Vec<UserVault> = program_accounts_filtered::<UserVault>(rpc, vec![size])?
+            .into_iter()
+            .map(|(_, vault)| vault)
+            .filter(|vault| now - vault.rate_window_start < ACTIVE_VAULT_SECS)
+            .collect();
+        active.sort_by_key(|vault| std::cmp::Reverse(vault.lifetime_wager));
+        let owners: Vec<Pubkey> = active.iter().map(|vault| vault.owner).collect();
+        Ok(lookup_table::extend(&self.table, &self.authority, &lookup_table::missing_vaults(&table, &owners)))
+    }
+}
+
+/// Fetch and decode every program account of type `T`, filtered by discriminator
+fn program_accounts<T: AccountDeserialize + Discriminator>(rpc: &RpcClient) -> Result<Vec<(Pubkey, T)>> {
+    program_accounts_filtered(rpc, Vec::new())
+}
+
+/// [`program_accounts`] narrowed by additional RPC filters
+fn program_accounts_filtered<T: AccountDeserialize + Discriminator>(
+    rpc: &RpcClient,
+    mut filters: Vec<RpcFilterType>,
+) -> Result<Vec<(Pubkey, T)>> {
+    filters.insert(0, RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &T::DISCRIMINATOR)));
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    rpc.get_program_accounts_with_config(&PROGRAM_ID, config)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, decode_account(&account.data)?)))
+        .collect()
+}
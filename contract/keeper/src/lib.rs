@@ -0,0 +1,4 @@
+//! Crank logic of `sols-bet-keeper`, a library so end-to-end tests can drive it
+//! against a local validator (see `test-harness`)
+
+pub mod cranks;
@@ -0,0 +1,122 @@
+//! `sols-bet-keeper` – runs the program's permissionless cranks
+//!
+//! Every interval the keeper asks each crank what is due and sends one
+//! transaction per instruction, paying fees from its own keypair. RPC calls
+//! are retried with exponential backoff; per-crank counters, pending work
+//! and the house balance are exported for Prometheus.
+//!
+//! Configuration (environment):
+//! * `RPC_URL` – JSON-RPC endpoint (default devnet)
+//! * `KEEPER_KEYPAIR` – fee payer keypair file
+//! * `KEEPER_INTERVAL_SECS` – seconds between passes (default 60)
+//! * `KEEPER_MAX_ATTEMPTS` – attempts per RPC call before giving up (default 5)
+//! * `METRICS_ADDR` – listen address of the `/metrics` endpoint (default `0.0.0.0:9100`)
+//! * `KEEPER_LOOKUP_TABLE` – settlement lookup table owned by the keeper to add active vaults to (optional)
+
+mod metrics;
+mod retry;
+
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+use sols_bet_keeper::cranks::{self, Crank};
+use sols_bet_sdk::pda;
+
+use metrics::Metrics;
+use retry::Backoff;
+
+struct Config {
+    rpc_url: String,
+    keypair: String,
+    interval: Duration,
+    backoff: Backoff,
+    metrics_addr: String,
+    lookup_table: Option<Pubkey>,
+}
+
+impl Config {
+    fn from_env() -> Result<Self> {
+        let var = |name: &str, default: &str| std::env::var(name).unwrap_or_else(|_| default.to_string());
+        Ok(Self {
+            rpc_url: var("RPC_URL", "https://api.devnet.solana.com"),
+            keypair: std::env::var("KEEPER_KEYPAIR").context("KEEPER_KEYPAIR not set")?,
+            interval: Duration::from_secs(var("KEEPER_INTERVAL_SECS", "60").parse().context("KEEPER_INTERVAL_SECS")?),
+            backoff: Backoff {
+                max_attempts: var("KEEPER_MAX_ATTEMPTS", "5").parse().context("KEEPER_MAX_ATTEMPTS")?,
+                ..Backoff::default()
+            },
+            metrics_addr: var("METRICS_ADDR", "0.0.0.0:9100"),
+            lookup_table: std::env::var("KEEPER_LOOKUP_TABLE")
+                .ok()
+                .map(|table| table.parse().context("KEEPER_LOOKUP_TABLE"))
+                .transpose()?,
+        })
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let config = Config::from_env()?;
+    let payer = read_keypair_file(&config.keypair)
+        .map_err(|err| anyhow::anyhow!("reading keypair {}: {err}", config.keypair))?;
+    let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+    let cranks = cranks::all(&payer.pubkey(), config.lookup_table);
+    let metrics = Metrics::new()?;
+    metrics.serve(&config.metrics_addr)?;
+
+    log::info!("keeper {} running {} cranks every {:?}", payer.pubkey(), cranks.len(), config.interval);
+    loop {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        for crank in &cranks {
+            run_crank(crank.as_ref(), &rpc, &payer, &config.backoff, &metrics, now);
+        }
+        match rpc.get_balance(&pda::house_vault().0) {
+            Ok(lamports) => metrics.house_balance.set(lamports as i64),
+            Err(err) => log::warn!("reading house balance: {err}"),
+        }
+        thread::sleep(config.interval);
+    }
+}
+
+fn run_crank(crank: &dyn Crank, rpc: &RpcClient, payer: &Keypair, backoff: &Backoff, metrics: &Metrics, now: i64) {
+    let name = crank.name();
+    metrics.runs.with_label_values(&[name]).inc();
+    let retries = metrics.retries.with_label_values(&[name]);
+
+    let due = match backoff.run(|| crank.due(rpc, now), |_| retries.inc()) {
+        Ok(due) => due,
+        Err(err) => {
+            log::error!("{name}: scan failed: {err:#}");
+            metrics.scan_errors.with_label_values(&[name]).inc();
+            return;
+        }
+    };
+    metrics.pending.with_label_values(&[name]).set(due.len() as i64);
+
+    for ix in due {
+        match backoff.run(|| send(rpc, payer, &ix), |_| retries.inc()) {
+            Ok(signature) => {
+                log::info!("{name}: {signature}");
+                metrics.sent.with_label_values(&[name]).inc();
+            }
+            Err(err) => {
+                log::error!("{name}: giving up: {err:#}");
+                metrics.failed.with_label_values(&[name]).inc();
+            }
+        }
+    }
+}
+
+fn send(rpc: &RpcClient, payer: &Keypair, ix: &Instruction) -> Result<String> {
+    let blockhash = rpc.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(std::slice::from_ref(ix), Some(&payer.pubkey()), &[payer], blockhash);
+    Ok(rpc.send_and_confirm_transaction(&tx)?.to_string())
+}
@@ -0,0 +1,66 @@
+use std::thread;
+
+use anyhow::{anyhow, Result};
+use prometheus::{Encoder, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder};
+
+/// Keeper metrics, labelled by crank name where applicable
+pub struct Metrics {
+    registry: Registry,
+    pub runs: IntCounterVec,        // Scans performed
+    pub scan_errors: IntCounterVec, // Scans that failed after retries
+    pub sent: IntCounterVec,        // Transactions confirmed
+    pub failed: IntCounterVec,      // Transactions abandoned after retries
+    pub retries: IntCounterVec,     // Retried RPC calls, scans and sends alike
+    pub pending: IntGaugeVec,       // Instructions due at the last scan
+    pub house_balance: IntGauge,    // House vault lamports at the last pass
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new();
+        let counter = |name: &str, help: &str| -> Result<IntCounterVec> {
+            let metric = IntCounterVec::new(Opts::new(name, help), &["crank"])?;
+            registry.register(Box::new(metric.clone()))?;
+            Ok(metric)
+        };
+        let metrics = Self {
+            runs: counter("keeper_crank_runs_total", "Crank scans performed")?,
+            scan_errors: counter("keeper_crank_scan_errors_total", "Crank scans that failed after retries")?,
+            sent: counter("keeper_tx_sent_total", "Crank transactions confirmed")?,
+            failed: counter("keeper_tx_failed_total", "Crank transactions abandoned after retries")?,
+            retries: counter("keeper_retries_total", "Retried RPC calls")?,
+            pending: IntGaugeVec::new(Opts::new("keeper_pending", "Instructions due at the last scan"), &["crank"])?,
+            house_balance: IntGauge::new("keeper_house_balance_lamports", "House vault balance")?,
+            registry,
+        };
+        metrics.registry.register(Box::new(metrics.pending.clone()))?;
+        metrics.registry.register(Box::new(metrics.house_balance.clone()))?;
+        Ok(metrics)
+    }
+
+    /// Serve the registry in Prometheus text format at `http://{addr}/metrics`
+    pub fn serve(&self, addr: &str) -> Result<()> {
+        let server = tiny_http::Server::http(addr).map_err(|err| anyhow!("binding metrics on {addr}: {err}"))?;
+        let registry = self.registry.clone();
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let response = if request.url() == "/metrics" {
+                    let encoder = TextEncoder::new();
+                    let mut body = Vec::new();
+                    if let Err(err) = encoder.encode(&registry.gather(), &mut body) {
+                        log::error!("encoding metrics: {err}");
+                    }
+                    let content_type = tiny_http::Header::from_bytes("Content-Type", encoder.format_type())
+                        .expect("static header");
+                    tiny_http::Response::from_data(body).with_header(content_type)
+                } else {
+                    tiny_http::Response::from_data(b"not found".to_vec()).with_status_code(404)
+                };
+                if let Err(err) = request.respond(response) {
+                    log::debug!("metrics response failed: {err}");
+                }
+            }
+        });
+        Ok(())
+    }
+}
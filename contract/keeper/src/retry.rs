@@ -0,0 +1,44 @@
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Result;
+
+/// Exponential backoff between attempts of a fallible RPC operation
+#[derive(Clone, Copy, Debug)]
+pub struct Backoff {
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Backoff {
+    /// Run `op` until it succeeds or attempts run out, doubling the delay each time.
+    /// `on_retry` is called before every retry with the failed attempt number.
+    pub fn run<T>(&self, mut op: impl FnMut() -> Result<T>, mut on_retry: impl FnMut(u32)) -> Result<T> {
+        let mut delay = self.initial_delay;
+        let mut attempt = 1;
+        loop {
+            match op() {
+                Ok(value) => return Ok(value),
+                Err(err) if attempt >= self.max_attempts => return Err(err),
+                Err(err) => {
+                    log::warn!("attempt {attempt}/{} failed: {err:#}; retrying in {delay:?}", self.max_attempts);
+                    on_retry(attempt);
+                    thread::sleep(delay);
+                    delay = (delay * 2).min(self.max_delay);
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
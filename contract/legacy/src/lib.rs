@@ -0,0 +1,851 @@
+use anchor_lang::prelude::*;
+use solana_program::pubkey;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+
+// The legacy program shipped as four variants of one source file. They now share
+// the state, errors and handlers below; a Cargo feature picks the instruction set.
+//
+// * `place-bet`      – place_bet and settle_game as two transactions
+// * `bet-and-settle` – bet_and_settle in one transaction
+// * `batch-settle`   – batch_settle over many vaults
+// * `airdrop`        – bet_and_settle with gem rolls (default)
+const _: () = assert!(
+    cfg!(feature = "place-bet") as u8
+        + cfg!(feature = "bet-and-settle") as u8
+        + cfg!(feature = "batch-settle") as u8
+        + cfg!(feature = "airdrop") as u8
+        == 1,
+    "enable exactly one of the place-bet, bet-and-settle, batch-settle and airdrop features"
+);
+
+declare_id!("9yWzBLvPQxyezB9LvRqGEZHG4aQMBKuXzGPNxQRqxDXj"); // replace with actual program ID on deployment
+
+// Define your AUTHORITY_PUBKEY clearly:
+pub const AUTHORITY_PUBKEY: Pubkey = pubkey!("CBKPbzTqdz4TMa1qoGCAokuSASGkAXtKZ9EWovwnSSfG");
+
+#[account]
+pub struct UserVault {
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub locked_amount: u64,
+    pub active_games: u32,
+    #[cfg(feature = "airdrop")]
+    pub accum_wager: u64, // Accumulated effective wager (lamports scale)
+}
+
+#[cfg(not(feature = "airdrop"))]
+pub const VAULT_SPACE: usize = 8 + 32 + 1 + 8 + 4;
+#[cfg(feature = "airdrop")]
+pub const VAULT_SPACE: usize = 8 + 32 + 1 + 8 + 4 + 8;
+
+#[account]
+pub struct HouseVault {
+    pub bump: u8, // PDA bump for the house vault
+                  // (No other data needed; this account’s lamports represent the house’s balance)
+}
+
+// Wager that earns one gem roll (0.1 SOL)
+#[cfg(feature = "airdrop")]
+pub const GEM_ROLL_THRESHOLD: u64 = 100_000_000;
+
+// Most rolls one settlement performs; leftover wager carries to the next bet
+#[cfg(feature = "airdrop")]
+pub const MAX_GEM_ROLLS: u32 = 100;
+
+// Rolls drawn from one keccak hash (8 bytes each)
+#[cfg(feature = "airdrop")]
+const ROLLS_PER_HASH: usize = 4;
+
+// Cumulative award odds per gem type within the 300/1000 base award window:
+// Garnet 15%, Amethyst 8%, Topaz 4%, Sapphire 2%, Emerald 0.7%, Ruby 0.2%, Diamond 0.1%
+#[cfg(feature = "airdrop")]
+const GEM_SUB_PROBS: [u64; 7] = [150, 230, 270, 290, 297, 299, 300];
+
+// NEW: 7 Gem types (rarity order: common to legendary)
+#[cfg(feature = "airdrop")]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum GemType {
+    Garnet,     // Common
+    Amethyst,
+    Topaz,
+    Sapphire,
+    Emerald,
+    Ruby,
+    Diamond,    // Legendary
+}
+
+#[cfg(feature = "place-bet")]
+#[program]
+pub mod smart_vault {
+    use super::*;
+
+    /// Initialize a new UserVault PDA for the user.
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        handlers::initialize_vault(ctx)
+    }
+
+    /// Initialize the global HouseVault PDA (run once by the operator/admin).
+    pub fn initialize_house(ctx: Context<InitializeHouse>) -> Result<()> {
+        handlers::initialize_house(ctx)
+    }
+
+    /// Deposit SOL into the user's vault.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        handlers::deposit(ctx, amount)
+    }
+
+    /// Withdraw SOL from the vault back to the user's wallet.
+    /// Only allowed if no active games are in progress.
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        handlers::withdraw(ctx, amount)
+    }
+
+    /// ------------------------------------------------------------------------
+    /// 1.  Player places a bet
+    ///     - `stake` is moved from the user's vault to the house vault
+    ///     - that stake is also tracked in `locked_amount`
+    /// ------------------------------------------------------------------------
+    pub fn place_bet(ctx: Context<PlaceBet>, stake: u64) -> Result<()> {
+        require!(stake > 0, VaultError::InvalidAmount);
+
+        let vault = &mut ctx.accounts.vault;
+        let vault_info = vault.to_account_info();
+        let house_info = ctx.accounts.house_vault.to_account_info();
+
+        // Only the authorised backend can call
+        require!(
+            ctx.accounts.authority.key() == AUTHORITY_PUBKEY,
+            VaultError::Unauthorized
+        );
+
+        // Make sure the user has the SOL available
+        let available = (**vault_info.lamports.borrow()).saturating_sub(vault.locked_amount);
+        require!(available >= stake, VaultError::InsufficientFunds);
+
+        // Lock funds and transfer to house vault
+        vault.locked_amount = vault
+            .locked_amount
+            .checked_add(stake)
+            .ok_or(VaultError::Overflow)?;
+        vault.active_games += 1;
+
+        **vault_info.try_borrow_mut_lamports()? -= stake;
+        **house_info.try_borrow_mut_lamports()? += stake;
+
+        msg!("Bet placed: {} lamports locked and sent to house", stake);
+        Ok(())
+    }
+
+    /// Settle a completed game round.
+    ///
+    /// * `stake`  – lamports that were locked when the bet was placed
+    /// * `payout` – total lamports the player should receive (0 if they lost,
+    ///              stake + profit if they won, stake if push/refund).
+    pub fn settle_game(ctx: Context<SettleGame>, stake: u64, payout: u64) -> Result<()> {
+        // --- Account & state checks -------------------------------------------------
+        require!(stake > 0, VaultError::InvalidAmount);
+        let vault       = &mut ctx.accounts.vault;
+        let vault_info  = vault.to_account_info();
+        let house_info  = ctx.accounts.house_vault.to_account_info();
+
+        require!(ctx.accounts.authority.key() == AUTHORITY_PUBKEY, VaultError::Unauthorized);
+        require!(vault.active_games > 0,                             VaultError::NoActiveGame);
+        require!(vault.locked_amount >= stake,                       VaultError::SettlementMismatch);
+
+        // --- Common processing  -----------------------------------------------------
+        // 1.  Unlock the stake in bookkeeping (same line for every outcome)
+        vault.locked_amount -= stake;
+        vault.active_games  -= 1;
+
+        // 2.  Funds movement
+        //
+        //      • If payout == 0 → player lost → stake already in house vault, nothing else to do.
+        //      • If payout  > 0 → house must pay `payout` to player (includes stake).
+        //
+        if payout > 0 {
+            require!(**house_info.lamports.borrow() >= payout, VaultError::HouseInsufficient);
+            **house_info.try_borrow_mut_lamports()? -= payout;
+            **vault_info.try_borrow_mut_lamports()? += payout;
+            msg!("Player paid out {} lamports (stake {}, profit {})",
+                payout, stake, payout.saturating_sub(stake));
+        } else {
+            // loss – stake remains in house_vault
+            msg!("Player lost, house keeps stake {}", stake);
+        }
+
+        Ok(())
+    }
+
+    /// Pay additional winnings that were not part of the original stake.
+    /// Does **not** touch `locked_amount` or `active_games`.
+    pub fn credit_win(ctx: Context<CreditWin>, amount: u64) -> Result<()> {
+        handlers::credit_win(ctx, amount)
+    }
+
+    /// Take an additional loss that was not part of the original stake.
+    /// Does **not** touch `locked_amount` or `active_games`.
+    pub fn debit_loss(ctx: Context<DebitLoss>, amount: u64) -> Result<()> {
+        handlers::debit_loss(ctx, amount)
+    }
+}
+
+#[cfg(feature = "bet-and-settle")]
+#[program]
+pub mod smart_vault {
+    use super::*;
+
+    /// Initialize a new UserVault PDA for the user.
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        handlers::initialize_vault(ctx)
+    }
+
+    /// Initialize the global HouseVault PDA (run once by the operator/admin).
+    pub fn initialize_house(ctx: Context<InitializeHouse>) -> Result<()> {
+        handlers::initialize_house(ctx)
+    }
+
+    /// Deposit SOL into the user's vault.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        handlers::deposit(ctx, amount)
+    }
+
+    /// Withdraw SOL from the vault back to the user's wallet.
+    /// Only allowed if no active games are in progress.
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        handlers::withdraw(ctx, amount)
+    }
+
+    /// Atomic bet + settle in one go
+    ///
+    /// * `stake`   – lamports staked
+    /// * `payout` – lamports to give back (0 ➜ player lost)
+    pub fn bet_and_settle(
+        ctx: Context<BetAndSettle>,
+        stake: u64,
+        payout: u64,
+    ) -> Result<()> {
+        handlers::settle_round(&mut ctx.accounts.vault, &ctx.accounts.house_vault, stake, payout)
+    }
+
+    /// Pay additional winnings that were not part of the original stake.
+    /// Does **not** touch `locked_amount` or `active_games`.
+    pub fn credit_win(ctx: Context<CreditWin>, amount: u64) -> Result<()> {
+        handlers::credit_win(ctx, amount)
+    }
+
+    /// Take an additional loss that was not part of the original stake.
+    /// Does **not** touch `locked_amount` or `active_games`.
+    pub fn debit_loss(ctx: Context<DebitLoss>, amount: u64) -> Result<()> {
+        handlers::debit_loss(ctx, amount)
+    }
+}
+
+#[cfg(feature = "batch-settle")]
+#[program]
+pub mod smart_vault {
+    use super::*;
+
+    /// Initialize a new UserVault PDA for the user.
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        handlers::initialize_vault(ctx)
+    }
+
+    /// Initialize the global HouseVault PDA (run once by the operator/admin).
+    pub fn initialize_house(ctx: Context<InitializeHouse>) -> Result<()> {
+        handlers::initialize_house(ctx)
+    }
+
+    /// Deposit SOL into the user's vault.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        handlers::deposit(ctx, amount)
+    }
+
+    /// Withdraw SOL from the vault back to the user's wallet.
+    /// Only allowed if no active games are in progress.
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        handlers::withdraw(ctx, amount)
+    }
+
+    // ────────────────────────────────────────────────────────────────────────────
+    //  batch_settle ─ settle many users in one transaction
+    //
+    //  • `users[i]`   = owner pubkey of the i-th user vault
+    //  • `profits[i]` = net profit for that user
+    //                   >0 → house pays player
+    //                   <0 → player’s locked stake moves to house
+    //                   0  → no lamport movement
+    //
+    //  All user vault PDAs must be passed in `remaining_accounts` in the
+    //  *same order* as the `users` vector (writable, not signer).
+    //
+    //  compute-units  ~ 35k  + 2k × (#users)
+    //  fee            sig (5k) + CU_price × CU
+    // ────────────────────────────────────────────────────────────────────────────
+    pub fn batch_settle(
+        ctx: Context<BatchSettle>,
+        users: Vec<Pubkey>,
+        profits: Vec<i64>,      // same length as users
+    ) -> Result<()> {
+        require!(
+            users.len() == profits.len(),
+            VaultError::InvalidAmount      // reuse existing error enum
+        );
+
+        let house_info = ctx.accounts.house_vault.to_account_info();
+        let remaining  = &ctx.remaining_accounts;
+
+        // Each user vault must be provided as a remaining account
+        require!(
+            remaining.len() == users.len(),
+            VaultError::InvalidAmount
+        );
+
+        let rent = Rent::get()?;
+
+        // Validate every item before any lamports move
+        for (i, user_pk) in users.iter().enumerate() {
+            // Vault PDA must be [b"vault", user_pk]
+            let (expected_pda, _bump) =
+                Pubkey::find_program_address(&[b"vault", user_pk.as_ref()], ctx.program_id);
+
+            let vault_info = remaining[i].to_account_info();
+            require!(vault_info.key() == expected_pda, VaultError::Unauthorized);
+            require!(vault_info.is_writable,           VaultError::Unauthorized);
+            // A repeated vault would make the balance checks below see stale balances
+            require!(!users[..i].contains(user_pk),    VaultError::DuplicateVault);
+
+            // Loss must be covered by the vault without dropping below rent exemption
+            let lamports = loss_lamports(profits[i])?;
+            if lamports > 0 {
+                let remaining_balance = vault_info
+                    .lamports()
+                    .checked_sub(lamports)
+                    .ok_or(VaultError::InsufficientFunds)?;
+                require!(
+                    remaining_balance >= rent.minimum_balance(vault_info.data_len()),
+                    VaultError::BelowRentExemption
+                );
+            }
+        }
+
+        for (i, user_pk) in users.iter().enumerate() {
+            let vault_info = remaining[i].to_account_info();
+            let delta = profits[i];
+
+            // Loss => move lamports from player vault TO house
+            if delta < 0 {
+                let lamports = loss_lamports(delta)?;
+                move_lamports(&vault_info, &house_info, lamports)?;
+                msg!("User {:?} lost {} lamports", user_pk, lamports);
+
+            // Win  => move lamports from house TO player vault
+            } else if delta > 0 {
+                let lamports = win_lamports(delta)?;
+                require!(
+                    **house_info.lamports.borrow() >= lamports,
+                    VaultError::HouseInsufficient
+                );
+                move_lamports(&house_info, &vault_info, lamports)?;
+                msg!("User {:?} won {} lamports", user_pk, lamports);
+            } else {
+                // delta == 0 -> nothing to move
+                msg!("User {:?} net 0 lamports", user_pk);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "airdrop")]
+#[program]
+pub mod smart_vault {
+    use super::*;
+    use solana_program::keccak;
+
+    /// Initialize a new UserVault PDA for the user.
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        handlers::initialize_vault(ctx)
+    }
+
+    /// Initialize the global HouseVault PDA (run once by the operator/admin).
+    pub fn initialize_house(ctx: Context<InitializeHouse>) -> Result<()> {
+        handlers::initialize_house(ctx)
+    }
+
+    /// Deposit SOL into the user's vault.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        handlers::deposit(ctx, amount)
+    }
+
+    /// Withdraw SOL from the vault back to the user's wallet.
+    /// Only allowed if no active games are in progress.
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        handlers::withdraw(ctx, amount)
+    }
+
+    /// Atomic bet + settle in one go
+    ///
+    /// * `stake`   – lamports staked
+    /// * `payout` – lamports to give back (0 ➜ player lost)
+    /// * `multiplier` – rank boost (100=1x, 200=2x)
+    pub fn bet_and_settle(
+        ctx: Context<BetAndSettle>,
+        stake: u64,
+        payout: u64,
+        multiplier: u16,  // NEW: 100-300
+    ) -> Result<()> {
+        require!((50..=300).contains(&multiplier), VaultError::InvalidMultiplier);
+        handlers::settle_round(&mut ctx.accounts.vault, &ctx.accounts.house_vault, stake, payout)?;
+        let vault = &mut ctx.accounts.vault;
+
+        // NEW: Gem awarding logic
+        let effective_wager = stake;  // Use full stake for gem calculation
+        vault.accum_wager += effective_wager;
+
+        // Capped roll count is known up front; wager beyond the cap stays accumulated
+        let rolls = (vault.accum_wager / GEM_ROLL_THRESHOLD).min(MAX_GEM_ROLLS as u64) as u32;
+        vault.accum_wager -= rolls as u64 * GEM_ROLL_THRESHOLD;
+
+        // Create bindings to avoid temporary value issues
+        let instruction_account = ctx.accounts.instruction_sysvar.to_account_info();
+        let instruction_data = instruction_account.data.borrow();
+        let slot_bytes = ctx.accounts.clock.slot.to_le_bytes();
+        let wager_bytes = effective_wager.to_le_bytes();
+
+        let base_seed_data = [
+            &instruction_data[..32],
+            &slot_bytes[..],
+            &wager_bytes[..],
+        ];
+        let base_hash_bytes = keccak::hashv(&base_seed_data).to_bytes();
+
+        // Base nothing: 700/1000 = 70%
+        // Awards 300/1000 = 30% base, scaled by multiplier (e.g., 1.2x → 360/1000 awards)
+        let base_award_prob = 300u64;  // Out of 1000
+        let effective_award_prob = base_award_prob * (multiplier as u64) / 100;
+        let nothing_prob = 1000 - effective_award_prob.min(1000);  // Cap at 100%
+
+        // Gems awarded per type, indexed like GemType
+        let mut gem_counts = [0u8; 7];
+        let mut roll_count = 0u32;
+        let mut hash_index = 0u32;
+        while roll_count < rolls {
+            // One hash yields ROLLS_PER_HASH rolls of 8 bytes each
+            let roll_hash_bytes = keccak::hashv(&[&base_hash_bytes[..], &hash_index.to_le_bytes()[..]]).to_bytes();
+            hash_index += 1;
+
+            for chunk in roll_hash_bytes.chunks_exact(8).take(ROLLS_PER_HASH) {
+                if roll_count == rolls {
+                    break;
+                }
+                roll_count += 1;
+
+                let roll = u64::from_le_bytes(chunk.try_into().unwrap()) % 1000;  // 0-999 for finer %
+                if roll < nothing_prob {
+                    continue;
+                }
+
+                // Within award window (effective_award_prob): scale to base 300 and pick the gem
+                let award_roll = (roll - nothing_prob) * 300 / effective_award_prob;
+                let gem = GEM_SUB_PROBS.iter().position(|&p| award_roll < p).unwrap_or(6);
+                gem_counts[gem] = gem_counts[gem].saturating_add(1);
+            }
+        }
+
+        let gems_awarded: u32 = gem_counts.iter().map(|&count| count as u32).sum();
+        if gems_awarded > 0 {
+            emit!(GemsAwarded {
+                user: vault.owner,
+                gems: gem_counts,
+                effective_wager_per_roll: GEM_ROLL_THRESHOLD,
+                num_rolls: roll_count,
+                multiplier_applied: multiplier,
+            });
+            msg!("{} gems awarded over {} rolls with {}x multiplier", gems_awarded, roll_count, multiplier as f32 / 100.0);
+        }
+
+        Ok(())
+    }
+
+    /// Pay additional winnings that were not part of the original stake.
+    /// Does **not** touch `locked_amount` or `active_games`.
+    pub fn credit_win(ctx: Context<CreditWin>, amount: u64) -> Result<()> {
+        handlers::credit_win(ctx, amount)
+    }
+
+    /// Take an additional loss that was not part of the original stake.
+    /// Does **not** touch `locked_amount` or `active_games`.
+    pub fn debit_loss(ctx: Context<DebitLoss>, amount: u64) -> Result<()> {
+        handlers::debit_loss(ctx, amount)
+    }
+}
+
+/// Instruction bodies shared by every variant
+pub mod handlers {
+    use super::*;
+
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.user.key();
+        vault.bump = ctx.bumps.vault;
+        vault.locked_amount = 0;
+        vault.active_games = 0;
+        #[cfg(feature = "airdrop")]
+        {
+            vault.accum_wager = 0;
+        }
+        Ok(())
+    }
+
+    pub fn initialize_house(ctx: Context<InitializeHouse>) -> Result<()> {
+        let house_vault = &mut ctx.accounts.house_vault;
+        house_vault.bump = ctx.bumps.house_vault;
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        let user = &ctx.accounts.user; // user wallet (signer)
+        let vault_info = ctx.accounts.vault.to_account_info();
+
+        // Transfer lamports from user to vault using system program CPI
+        invoke(
+            &system_instruction::transfer(
+                &user.key(),       // from user
+                &vault_info.key(), // to vault PDA
+                amount,
+            ),
+            &[
+                user.to_account_info().clone(),
+                vault_info.clone(),
+                ctx.accounts.system_program.to_account_info().clone(),
+            ],
+        )?;
+        // (Anchor will ensure `user` signed, so the transfer is authorized)
+
+        msg!("Deposit completed: {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        let vault = &mut ctx.accounts.vault;
+        let user_info = ctx.accounts.owner.to_account_info();
+        let vault_info = vault.to_account_info();
+
+        // Ensure the user can withdraw (no ongoing games locking funds)
+        require!(vault.active_games == 0, VaultError::GamesInProgress);
+        // Ensure vault has enough balance to withdraw the requested amount
+        require!(
+            **vault_info.lamports.borrow() >= amount,
+            VaultError::InsufficientFunds
+        );
+
+        // Transfer lamports from vault PDA to user's wallet
+        **vault_info.try_borrow_mut_lamports()? -= amount;
+        **user_info.try_borrow_mut_lamports()? += amount;
+        // (We manipulate lamports directly because vault is program-owned)
+
+        msg!("Withdraw completed: {} lamports", amount);
+        Ok(())
+    }
+
+    /// Lock `stake`, hand it to the house and pay `payout` back in the same instruction
+    pub fn settle_round(
+        vault: &mut Account<UserVault>,
+        house_vault: &Account<HouseVault>,
+        stake: u64,
+        payout: u64,
+    ) -> Result<()> {
+        require!(stake > 0, VaultError::InvalidAmount);
+        let vault_info  = vault.to_account_info();
+        let house_info  = house_vault.to_account_info();
+
+        // 1. make sure player has stake free
+        let available = (**vault_info.lamports.borrow()).saturating_sub(vault.locked_amount);
+        require!(available >= stake, VaultError::InsufficientFunds);
+
+        // 2. lock stake and move to house
+        vault.locked_amount = vault
+            .locked_amount
+            .checked_add(stake)
+            .ok_or(VaultError::Overflow)?;
+        **vault_info.try_borrow_mut_lamports()? -= stake;
+        **house_info.try_borrow_mut_lamports()? += stake;
+
+        // 3. immediately settle the round
+        vault.locked_amount -= stake;      // unlock
+        // payout == stake + profit OR stake (refund) OR 0 (loss)
+        if payout > 0 {
+            require!(**house_info.lamports.borrow() >= payout, VaultError::HouseInsufficient);
+            **house_info.try_borrow_mut_lamports()? -= payout;
+            **vault_info.try_borrow_mut_lamports()? += payout;
+        }
+        // no else – stake already with house
+
+        // active_games net-zero change (never >1)
+        msg!("Round settled in one tx: stake {}, payout {}", stake, payout);
+        Ok(())
+    }
+
+    pub fn credit_win(ctx: Context<CreditWin>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        require!(ctx.accounts.authority.key() == AUTHORITY_PUBKEY, VaultError::Unauthorized);
+
+        let house_info = ctx.accounts.house_vault.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+
+        require!(**house_info.lamports.borrow() >= amount, VaultError::HouseInsufficient);
+
+        **house_info.try_borrow_mut_lamports()? -= amount;
+        **vault_info.try_borrow_mut_lamports()? += amount;
+
+        msg!("Bonus win credited: {} lamports", amount);
+        Ok(())
+    }
+
+    pub fn debit_loss(ctx: Context<DebitLoss>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        require!(ctx.accounts.authority.key() == AUTHORITY_PUBKEY, VaultError::Unauthorized);
+
+        let house_info = ctx.accounts.house_vault.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+
+        require!(**vault_info.lamports.borrow() >= amount, VaultError::InsufficientFunds);
+
+        **vault_info.try_borrow_mut_lamports()? -= amount;
+        **house_info.try_borrow_mut_lamports()? += amount;
+
+        msg!("Loss debited: {} lamports", amount);
+        Ok(())
+    }
+}
+
+/// Lamports a player loses for a negative `profit` (0 for wins and pushes)
+///
+/// `i64::MIN` has no positive counterpart, so it is rejected instead of wrapping.
+pub fn loss_lamports(profit: i64) -> Result<u64> {
+    if profit >= 0 {
+        return Ok(0);
+    }
+    let loss = profit.checked_neg().ok_or(VaultError::Overflow)?;
+    u64::try_from(loss).map_err(|_| error!(VaultError::Overflow))
+}
+
+/// Lamports the house pays for a positive `profit` (0 for losses and pushes)
+pub fn win_lamports(profit: i64) -> Result<u64> {
+    u64::try_from(profit.max(0)).map_err(|_| error!(VaultError::Overflow))
+}
+
+/// Move lamports between program-owned accounts with checked arithmetic on both sides
+pub fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let from_balance = from.lamports().checked_sub(amount).ok_or(VaultError::InsufficientFunds)?;
+    let to_balance = to.lamports().checked_add(amount).ok_or(VaultError::Overflow)?;
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+    Ok(())
+}
+
+// Contexts for instructions:
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(init, seeds=[b"vault", user.key().as_ref()], bump, payer=user, space=VAULT_SPACE)]
+    pub vault: Account<'info, UserVault>,
+    #[account(mut)]
+    pub user: Signer<'info>, // user paying for account creation
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeHouse<'info> {
+    #[account(init, seeds=[b"house_vault"], bump, payer=admin, space=8 + 1)]
+    pub house_vault: Account<'info, HouseVault>,
+    #[account(mut)]
+    pub admin: Signer<'info>, // casino operator initializing the house account
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, UserVault>,
+    /// CHECK: user's wallet, matched against vault.owner
+    #[account(signer)]
+    pub owner: AccountInfo<'info>, // user's wallet (must match vault.owner)
+    #[account(mut)]
+    pub user: Signer<'info>, // same as owner, for Anchor context
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, has_one = owner)]
+    pub vault: Account<'info, UserVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>, // user withdrawing (must be vault owner)
+                              // no system_program needed for direct lamport transfer
+}
+
+#[cfg(feature = "place-bet")]
+#[derive(Accounts)]
+pub struct PlaceBet<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, UserVault>, // player’s PDA
+    #[account(mut)]
+    pub house_vault: Account<'info, HouseVault>, // house PDA
+    /// CHECK: authority is compared to constant, so no data is read
+    #[account(signer, address = AUTHORITY_PUBKEY)]
+    pub authority: AccountInfo<'info>, // casino server
+}
+
+#[cfg(feature = "place-bet")]
+#[derive(Accounts)]
+pub struct SettleGame<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, UserVault>,
+    #[account(mut)]
+    pub house_vault: Account<'info, HouseVault>,
+    /// CHECK: same authority check
+    #[account(signer, address = AUTHORITY_PUBKEY)]
+    pub authority: AccountInfo<'info>,
+}
+
+#[cfg(any(feature = "bet-and-settle", feature = "airdrop"))]
+#[derive(Accounts)]
+pub struct BetAndSettle<'info> {
+    #[account(mut, seeds=[b"vault", vault.owner.key().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, UserVault>,
+    #[account(mut, seeds=[b"house_vault"], bump = house_vault.bump)]
+    pub house_vault: Account<'info, HouseVault>,
+    /// CHECK: hard-coded backend signer
+    #[account(signer, address = AUTHORITY_PUBKEY)]
+    pub authority: AccountInfo<'info>,
+    /// CHECK: Solana sysvar for randomness
+    #[cfg(feature = "airdrop")]
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::id())]
+    pub instruction_sysvar: AccountInfo<'info>,
+    #[cfg(feature = "airdrop")]
+    pub clock: Sysvar<'info, Clock>,
+}
+
+#[cfg(feature = "batch-settle")]
+#[derive(Accounts)]
+pub struct BatchSettle<'info> {
+    /// House vault PDA (writable, hot funds)
+    #[account(mut)]
+    pub house_vault: Account<'info, HouseVault>,
+
+    /// CPI signer (casino authority = system wallet)
+    /// CHECK: compared to constant
+    #[account(signer, address = AUTHORITY_PUBKEY)]
+    pub authority: AccountInfo<'info>,
+}
+
+/// Accounts for `credit_win`
+#[derive(Accounts)]
+pub struct CreditWin<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, UserVault>,       // player vault PDA
+    #[account(mut)]
+    pub house_vault: Account<'info, HouseVault>,// house PDA
+    /// CHECK: only the hard-coded authority may sign
+    #[account(signer, address = AUTHORITY_PUBKEY)]
+    pub authority: AccountInfo<'info>,
+}
+
+/// Accounts for `debit_loss`
+#[derive(Accounts)]
+pub struct DebitLoss<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, UserVault>,       // player vault PDA
+    #[account(mut)]
+    pub house_vault: Account<'info, HouseVault>,// house PDA
+    /// CHECK: only the hard-coded authority may sign
+    #[account(signer, address = AUTHORITY_PUBKEY)]
+    pub authority: AccountInfo<'info>,
+}
+
+// Event
+#[cfg(feature = "airdrop")]
+#[event]
+pub struct GemsAwarded {
+    pub user: Pubkey,
+    pub gems: [u8; 7],  // Gems awarded per type, Garnet to Diamond
+    pub effective_wager_per_roll: u64,
+    pub num_rolls: u32,
+    pub multiplier_applied: u16,  // For verification
+}
+
+// `#[error_code]` ignores `cfg` on variants, so each variant appends its own
+// errors after the shared ones and keeps the codes it was deployed with.
+macro_rules! vault_error {
+    ($($(#[$attr:meta])* $variant:ident,)*) => {
+        #[error_code]
+        pub enum VaultError {
+            #[msg("Invalid amount specified")]
+            InvalidAmount,
+            #[msg("Withdrawal not allowed: games in progress")]
+            GamesInProgress,
+            #[msg("Insufficient funds for this operation")]
+            InsufficientFunds,
+            #[msg("No active game to settle")]
+            NoActiveGame,
+            #[msg("Mismatched locked amount for settlement")]
+            SettlementMismatch,
+            #[msg("Unauthorized caller")]
+            Unauthorized,
+            #[msg("House vault has insufficient funds")]
+            HouseInsufficient,
+            #[msg("Arithmetic overflow")]
+            Overflow,
+            $($(#[$attr])* $variant,)*
+        }
+    };
+}
+
+#[cfg(any(feature = "place-bet", feature = "bet-and-settle"))]
+vault_error! {}
+
+#[cfg(feature = "batch-settle")]
+vault_error! {
+    #[msg("Vault would fall below rent exemption")]
+    BelowRentExemption,
+    #[msg("The same vault appears more than once in the batch")]
+    DuplicateVault,
+}
+
+#[cfg(feature = "airdrop")]
+vault_error! {
+    #[msg("Invalid multiplier specified (must be 50-300)")]
+    InvalidMultiplier,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loss_of_most_negative_profit_is_rejected() {
+        assert_eq!(loss_lamports(i64::MIN).unwrap_err(), VaultError::Overflow.into());
+    }
+
+    #[test]
+    fn loss_boundaries() {
+        assert_eq!(loss_lamports(i64::MIN + 1).unwrap(), i64::MAX as u64);
+        assert_eq!(loss_lamports(-1).unwrap(), 1);
+        assert_eq!(loss_lamports(0).unwrap(), 0);
+        assert_eq!(loss_lamports(1).unwrap(), 0);
+        assert_eq!(loss_lamports(i64::MAX).unwrap(), 0);
+    }
+
+    #[test]
+    fn win_boundaries() {
+        assert_eq!(win_lamports(i64::MAX).unwrap(), i64::MAX as u64);
+        assert_eq!(win_lamports(1).unwrap(), 1);
+        assert_eq!(win_lamports(0).unwrap(), 0);
+        assert_eq!(win_lamports(-1).unwrap(), 0);
+        assert_eq!(win_lamports(i64::MIN).unwrap(), 0);
+    }
+}
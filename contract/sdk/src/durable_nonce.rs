@@ -0,0 +1,73 @@
+//! Durable nonces for transactions signed over more than a blockhash lifetime
+//!
+//! Multisig ceremonies routinely outlive the ~60 seconds a recent blockhash is
+//! valid. A transaction that starts by advancing a nonce account uses the
+//! nonce's stored hash instead, and stays valid until the nonce is advanced.
+//! No program instruction depends on its position in the transaction, so
+//! prepending the advance is safe for every builder in [`crate::instructions`].
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::Hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::message::Message;
+use anchor_lang::solana_program::{system_instruction, system_program};
+
+/// Seed of the nonce account [`create`] derives from its authority
+pub const NONCE_SEED: &str = "sols-bet-nonce";
+
+/// Size of an initialized nonce account
+pub const NONCE_ACCOUNT_LEN: usize = 80;
+
+/// Nonce account `authority` controls, at `create_with_seed(authority, NONCE_SEED, system_program)`
+pub fn address(authority: &Pubkey) -> Pubkey {
+    Pubkey::create_with_seed(authority, NONCE_SEED, &system_program::ID).expect("seed is short enough")
+}
+
+/// Create and initialize [`address`]`(authority)`, funded by `authority` with `lamports` (rent-exempt minimum or more)
+///
+/// Only `authority` signs; no separate nonce keypair is needed.
+pub fn create(authority: &Pubkey, lamports: u64) -> (Vec<Instruction>, Pubkey) {
+    let nonce = address(authority);
+    let ixs = system_instruction::create_nonce_account_with_seed(
+        authority,
+        &nonce,
+        authority,
+        NONCE_SEED,
+        authority,
+        lamports,
+    );
+    (ixs, nonce)
+}
+
+/// `instructions` preceded by the advance of `nonce_account`; sign with the stored nonce as blockhash
+pub fn with_advance(nonce_account: &Pubkey, nonce_authority: &Pubkey, instructions: &[Instruction]) -> Vec<Instruction> {
+    let mut ixs = vec![system_instruction::advance_nonce_account(nonce_account, nonce_authority)];
+    ixs.extend_from_slice(instructions);
+    ixs
+}
+
+/// Legacy message for `instructions` paid by `payer` and kept valid by `nonce_account`
+pub fn message(
+    payer: &Pubkey,
+    nonce_account: &Pubkey,
+    nonce_authority: &Pubkey,
+    instructions: &[Instruction],
+    nonce: Hash,
+) -> Message {
+    let mut message = Message::new_with_nonce(instructions.to_vec(), Some(payer), nonce_account, nonce_authority);
+    message.recent_blockhash = nonce;
+    message
+}
+
+/// Authority and stored nonce of raw nonce account data, or `None` if it is not initialized
+///
+/// Layout (bincode): u32 version, u32 state (1 = initialized), authority,
+/// durable nonce, lamports per signature.
+pub fn decode(data: &[u8]) -> Option<(Pubkey, Hash)> {
+    if data.len() < NONCE_ACCOUNT_LEN || data[4..8] != 1u32.to_le_bytes() {
+        return None;
+    }
+    let authority = Pubkey::try_from(&data[8..40]).ok()?;
+    let nonce = Hash::new(&data[40..72]);
+    Some((authority, nonce))
+}
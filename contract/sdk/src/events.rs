@@ -0,0 +1,178 @@
+//! Decoding of the program's `emit_cpi!` events
+//!
+//! Events use a fixed layout that starts with a version byte. Payloads from
+//! an unknown [`EVENT_VERSION`] are skipped, not misread. Version 1 differs
+//! only in `Deposited`, which lacked the trailing reference; those are still
+//! decoded, with a zeroed reference, so older history backfills.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+
+pub use smart_vault_v2::{
+    bet_id_hash, AuditEntry, AuditPage, BetMirrored, BetSettled, Deposited, DisputeFiled, DisputeResolved, GemBurned,
+    GemsCredited, InsuranceRefunded, JackpotAwarded, LargeTransfer, PayoutScheduled, PayoutTrancheClaimed,
+    PromoClawback, PvpBetSettled, SideBetSettled, StakingRebatePaid, TableRoundSettled, TableSeatResult,
+    TournamentMatchSettled, TournamentPrizesPaid, TournamentStanding, UpgradeAnnounced, UpgradeRecorded, VaultRegionSet,
+    Withdrawn, EVENT_VERSION, TRANSFER_DEPOSIT, TRANSFER_PAYOUT, TRANSFER_WITHDRAWAL,
+};
+
+/// Every event the program emits through `emit_cpi!`
+pub enum ProgramEvent {
+    Deposited(Deposited),
+    Withdrawn(Withdrawn),
+    BetSettled(BetSettled),
+    GemsCredited(GemsCredited),
+    GemBurned(GemBurned),
+    PromoClawback(PromoClawback),
+    LargeTransfer(LargeTransfer),
+    VaultRegionSet(VaultRegionSet),
+    DisputeFiled(DisputeFiled),
+    DisputeResolved(DisputeResolved),
+    AuditPage(AuditPage),
+    PvpBetSettled(PvpBetSettled),
+    SideBetSettled(SideBetSettled),
+    InsuranceRefunded(InsuranceRefunded),
+    TableRoundSettled(TableRoundSettled),
+    BetMirrored(BetMirrored),
+    TournamentMatchSettled(TournamentMatchSettled),
+    TournamentPrizesPaid(TournamentPrizesPaid),
+    JackpotAwarded(JackpotAwarded),
+    UpgradeAnnounced(UpgradeAnnounced),
+    UpgradeRecorded(UpgradeRecorded),
+    PayoutScheduled(PayoutScheduled),
+    PayoutTrancheClaimed(PayoutTrancheClaimed),
+    StakingRebatePaid(StakingRebatePaid),
+}
+
+impl ProgramEvent {
+    /// Event name as declared in the program
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProgramEvent::Deposited(_) => "Deposited",
+            ProgramEvent::Withdrawn(_) => "Withdrawn",
+            ProgramEvent::BetSettled(_) => "BetSettled",
+            ProgramEvent::GemsCredited(_) => "GemsCredited",
+            ProgramEvent::GemBurned(_) => "GemBurned",
+            ProgramEvent::PromoClawback(_) => "PromoClawback",
+            ProgramEvent::LargeTransfer(_) => "LargeTransfer",
+            ProgramEvent::VaultRegionSet(_) => "VaultRegionSet",
+            ProgramEvent::DisputeFiled(_) => "DisputeFiled",
+            ProgramEvent::DisputeResolved(_) => "DisputeResolved",
+            ProgramEvent::AuditPage(_) => "AuditPage",
+            ProgramEvent::PvpBetSettled(_) => "PvpBetSettled",
+            ProgramEvent::SideBetSettled(_) => "SideBetSettled",
+            ProgramEvent::InsuranceRefunded(_) => "InsuranceRefunded",
+            ProgramEvent::TableRoundSettled(_) => "TableRoundSettled",
+            ProgramEvent::BetMirrored(_) => "BetMirrored",
+            ProgramEvent::TournamentMatchSettled(_) => "TournamentMatchSettled",
+            ProgramEvent::TournamentPrizesPaid(_) => "TournamentPrizesPaid",
+            ProgramEvent::JackpotAwarded(_) => "JackpotAwarded",
+            ProgramEvent::UpgradeAnnounced(_) => "UpgradeAnnounced",
+            ProgramEvent::UpgradeRecorded(_) => "UpgradeRecorded",
+            ProgramEvent::PayoutScheduled(_) => "PayoutScheduled",
+            ProgramEvent::PayoutTrancheClaimed(_) => "PayoutTrancheClaimed",
+            ProgramEvent::StakingRebatePaid(_) => "StakingRebatePaid",
+        }
+    }
+
+    /// Global sequence number stamped by the program
+    pub fn seq(&self) -> u64 {
+        match self {
+            ProgramEvent::Deposited(e) => e.seq,
+            ProgramEvent::Withdrawn(e) => e.seq,
+            ProgramEvent::BetSettled(e) => e.seq,
+            ProgramEvent::GemsCredited(e) => e.seq,
+            ProgramEvent::GemBurned(e) => e.seq,
+            ProgramEvent::PromoClawback(e) => e.seq,
+            ProgramEvent::LargeTransfer(e) => e.seq,
+            ProgramEvent::VaultRegionSet(e) => e.seq,
+            ProgramEvent::DisputeFiled(e) => e.seq,
+            ProgramEvent::DisputeResolved(e) => e.seq,
+            ProgramEvent::AuditPage(e) => e.seq,
+            ProgramEvent::PvpBetSettled(e) => e.seq,
+            ProgramEvent::SideBetSettled(e) => e.seq,
+            ProgramEvent::InsuranceRefunded(e) => e.seq,
+            ProgramEvent::TableRoundSettled(e) => e.seq,
+            ProgramEvent::BetMirrored(e) => e.seq,
+            ProgramEvent::TournamentMatchSettled(e) => e.seq,
+            ProgramEvent::TournamentPrizesPaid(e) => e.seq,
+            ProgramEvent::JackpotAwarded(e) => e.seq,
+            ProgramEvent::UpgradeAnnounced(e) => e.seq,
+            ProgramEvent::UpgradeRecorded(e) => e.seq,
+            ProgramEvent::PayoutScheduled(e) => e.seq,
+            ProgramEvent::PayoutTrancheClaimed(e) => e.seq,
+            ProgramEvent::StakingRebatePaid(e) => e.seq,
+        }
+    }
+}
+
+/// Decode the data of a self-CPI event instruction
+///
+/// Layout: 8-byte event tag, 8-byte event discriminator, version byte, fixed body.
+pub fn decode_event(data: &[u8]) -> Option<ProgramEvent> {
+    if data.len() < 17 || data[..8] != EVENT_IX_TAG_LE || !(1..=EVENT_VERSION).contains(&data[16]) {
+        return None;
+    }
+    let (discriminator, mut body) = (&data[8..16], &data[16..]);
+
+    let event = match discriminator {
+        d if d == Deposited::DISCRIMINATOR && data[16] == 1 => {
+            let padded = [body, &[0u8; 32]].concat();
+            ProgramEvent::Deposited(Deposited::deserialize(&mut padded.as_slice()).ok()?)
+        }
+        d if d == Deposited::DISCRIMINATOR => ProgramEvent::Deposited(Deposited::deserialize(&mut body).ok()?),
+        d if d == Withdrawn::DISCRIMINATOR => ProgramEvent::Withdrawn(Withdrawn::deserialize(&mut body).ok()?),
+        d if d == BetSettled::DISCRIMINATOR => ProgramEvent::BetSettled(BetSettled::deserialize(&mut body).ok()?),
+        d if d == GemsCredited::DISCRIMINATOR => ProgramEvent::GemsCredited(GemsCredited::deserialize(&mut body).ok()?),
+        d if d == GemBurned::DISCRIMINATOR => ProgramEvent::GemBurned(GemBurned::deserialize(&mut body).ok()?),
+        d if d == PromoClawback::DISCRIMINATOR => ProgramEvent::PromoClawback(PromoClawback::deserialize(&mut body).ok()?),
+        d if d == LargeTransfer::DISCRIMINATOR => ProgramEvent::LargeTransfer(LargeTransfer::deserialize(&mut body).ok()?),
+        d if d == VaultRegionSet::DISCRIMINATOR => {
+            ProgramEvent::VaultRegionSet(VaultRegionSet::deserialize(&mut body).ok()?)
+        }
+        d if d == DisputeFiled::DISCRIMINATOR => ProgramEvent::DisputeFiled(DisputeFiled::deserialize(&mut body).ok()?),
+        d if d == DisputeResolved::DISCRIMINATOR => {
+            ProgramEvent::DisputeResolved(DisputeResolved::deserialize(&mut body).ok()?)
+        }
+        d if d == AuditPage::DISCRIMINATOR => ProgramEvent::AuditPage(AuditPage::deserialize(&mut body).ok()?),
+        d if d == PvpBetSettled::DISCRIMINATOR => {
+            ProgramEvent::PvpBetSettled(PvpBetSettled::deserialize(&mut body).ok()?)
+        }
+        d if d == SideBetSettled::DISCRIMINATOR => {
+            ProgramEvent::SideBetSettled(SideBetSettled::deserialize(&mut body).ok()?)
+        }
+        d if d == InsuranceRefunded::DISCRIMINATOR => {
+            ProgramEvent::InsuranceRefunded(InsuranceRefunded::deserialize(&mut body).ok()?)
+        }
+        d if d == TableRoundSettled::DISCRIMINATOR => {
+            ProgramEvent::TableRoundSettled(TableRoundSettled::deserialize(&mut body).ok()?)
+        }
+        d if d == BetMirrored::DISCRIMINATOR => ProgramEvent::BetMirrored(BetMirrored::deserialize(&mut body).ok()?),
+        d if d == TournamentMatchSettled::DISCRIMINATOR => {
+            ProgramEvent::TournamentMatchSettled(TournamentMatchSettled::deserialize(&mut body).ok()?)
+        }
+        d if d == TournamentPrizesPaid::DISCRIMINATOR => {
+            ProgramEvent::TournamentPrizesPaid(TournamentPrizesPaid::deserialize(&mut body).ok()?)
+        }
+        d if d == JackpotAwarded::DISCRIMINATOR => {
+            ProgramEvent::JackpotAwarded(JackpotAwarded::deserialize(&mut body).ok()?)
+        }
+        d if d == UpgradeAnnounced::DISCRIMINATOR => {
+            ProgramEvent::UpgradeAnnounced(UpgradeAnnounced::deserialize(&mut body).ok()?)
+        }
+        d if d == UpgradeRecorded::DISCRIMINATOR => {
+            ProgramEvent::UpgradeRecorded(UpgradeRecorded::deserialize(&mut body).ok()?)
+        }
+        d if d == PayoutScheduled::DISCRIMINATOR => {
+            ProgramEvent::PayoutScheduled(PayoutScheduled::deserialize(&mut body).ok()?)
+        }
+        d if d == PayoutTrancheClaimed::DISCRIMINATOR => {
+            ProgramEvent::PayoutTrancheClaimed(PayoutTrancheClaimed::deserialize(&mut body).ok()?)
+        }
+        d if d == StakingRebatePaid::DISCRIMINATOR => {
+            ProgramEvent::StakingRebatePaid(StakingRebatePaid::deserialize(&mut body).ok()?)
+        }
+        _ => return None,
+    };
+    Some(event)
+}
@@ -0,0 +1,2457 @@
+//! Instruction builders
+//!
+//! Each builder derives the PDAs it needs and returns a ready-to-sign
+//! [`Instruction`]. Signers are passed explicitly; nothing is fetched from chain.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::address_lookup_table::AddressLookupTableAccount;
+use anchor_lang::solana_program::hash::Hash;
+use anchor_lang::solana_program::message::{v0, Message, VersionedMessage};
+use anchor_lang::solana_program::{bpf_loader_upgradeable, ed25519_program, system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+
+use smart_vault_v2::{
+    accounts, bet_id_hash, instruction, withdraw_intent_message, Jackpot, PendingBetTerms, RebateTier, SideBet,
+    BATCH_SETTLE_BASE_CU, BATCH_SETTLE_ITEM_CU, ID, MAX_BATCH_SETTLE, MAX_COMPUTE_UNIT_LIMIT, SPL_TOKEN_PROGRAM_ID,
+};
+
+use crate::pda;
+
+/// One bet to settle, shared by `bet_and_settle` and `batch_settle`
+#[derive(Clone, Debug)]
+pub struct Settlement {
+    pub owner: Pubkey,
+    pub stake: u64,
+    pub payout: u64,
+    pub bet_id: String,
+    pub game_id: u64,
+    /// `GAME_CATEGORY_*` code the stake counts toward in the house's volume breakdown
+    pub category: u8,
+    pub gem_data: Vec<u8>,
+}
+
+/// A sports bet to lock at fixed odds with `place_pending_bet`
+#[derive(Clone, Debug)]
+pub struct PendingBetOrder {
+    pub owner: Pubkey,
+    pub bet_id: String,
+    pub game_id: u64,
+    pub event_id: u64,
+    /// Decimal odds, 100 = 1x
+    pub odds: u32,
+    pub stake: u64,
+    /// Event start, from which the bet can be settled
+    pub expiry: i64,
+    /// From here settling refunds the stake; at most `PENDING_BET_SETTLE_WINDOW_SECS` after `expiry`
+    pub expires_at: i64,
+    /// Paid into the insurance fund; 0 leaves the bet uninsured
+    pub insurance_premium: u64,
+}
+
+/// Optional per-vault accounts `bet_and_settle` updates when supplied
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SettlementExtras {
+    pub gem_inventory: bool,
+    /// Boost the gems after a losing streak; needs `gem_inventory`
+    pub pity: bool,
+    /// Write a `BetReceipt` PDA, funded from the vault
+    pub receipt: bool,
+    /// Trophy tree from `TrophyConfig`; attaches the accounts to mint a trophy if the win qualifies
+    pub trophy_tree: Option<Pubkey>,
+    /// Open a `PayoutSchedule` PDA, funded by the authority; required exactly when
+    /// `GameLimits::split_payout` streams part of the payout
+    pub stream: bool,
+    /// Pass the staking config and the vault's `TokenStake` to pay its rebate tier
+    pub staking: bool,
+}
+
+/// Optional accounts a withdrawal passes when the vault needs them
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WithdrawExtras {
+    /// Pass the vault's KYC attestation, needed above the compliance threshold
+    pub kyc: bool,
+    /// Device key co-signing for a vault with a withdraw guard
+    pub device: Option<Pubkey>,
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize_vault(user: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeVault {
+            vault: pda::vault(user).0,
+            user: *user,
+            system_program: system_program::ID,
+            global_stats: pda::global_stats().0,
+            blacklist: pda::blacklist().0,
+        },
+        instruction::InitializeVault {},
+    )
+}
+
+pub fn migrate_vault(owner: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateVault {
+            vault: pda::vault(owner).0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateVault {},
+    )
+}
+
+pub fn initialize_house(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeHouse {
+            house_vault: pda::house_vault().0,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeHouse {},
+    )
+}
+
+pub fn initialize_pause_config(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializePauseConfig {
+            pause_config: pda::pause_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializePauseConfig {},
+    )
+}
+
+/// Repack a pre-bitflag PauseConfig; the freed rent goes to `authority`
+pub fn migrate_pause_config(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::MigratePauseConfig {
+            pause_config: pda::pause_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::MigratePauseConfig {},
+    )
+}
+
+/// Bring the HouseVault to the current layout; `authority` pays any extra rent
+pub fn migrate_house_vault(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateHouseVault {
+            house_vault: pda::house_vault().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateHouseVault {},
+    )
+}
+
+/// Bring the GlobalConfig to the current layout; `authority` pays any extra rent
+pub fn migrate_global_config(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateGlobalConfig {
+            global_config: pda::global_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateGlobalConfig {},
+    )
+}
+
+/// Bring the GlobalStats to the current layout; `authority` pays the extra rent
+pub fn migrate_global_stats(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateGlobalStats {
+            global_stats: pda::global_stats().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateGlobalStats {},
+    )
+}
+
+pub fn initialize_global_config(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeGlobalConfig {
+            global_config: pda::global_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeGlobalConfig {},
+    )
+}
+
+/// Set the smallest deposit and non-zero stake the program accepts; 0 disables either check
+pub fn set_minimums(authority: &Pubkey, min_deposit: u64, min_stake: u64) -> Instruction {
+    build(
+        accounts::SetGlobalConfig { global_config: pda::global_config().0, authority: *authority },
+        instruction::SetMinimums { min_deposit, min_stake },
+    )
+}
+
+pub fn initialize_global_stats(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeGlobalStats {
+            global_stats: pda::global_stats().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeGlobalStats {},
+    )
+}
+
+pub fn initialize_blacklist(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeBlacklist {
+            blacklist: pda::blacklist().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeBlacklist {},
+    )
+}
+
+/// Bar `address` from creating a vault, depositing and betting
+pub fn add_to_blacklist(authority: &Pubkey, address: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateBlacklist {
+            blacklist: pda::blacklist().0,
+            authority: *authority,
+        },
+        instruction::AddToBlacklist { address },
+    )
+}
+
+pub fn remove_from_blacklist(authority: &Pubkey, address: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateBlacklist {
+            blacklist: pda::blacklist().0,
+            authority: *authority,
+        },
+        instruction::RemoveFromBlacklist { address },
+    )
+}
+
+/// Create the payout-bound table; multipliers are in hundredths (100 = 1x)
+pub fn initialize_game_limits(
+    authority: &Pubkey,
+    default_max_multiplier: u32,
+    max_unstaked_payout: u64,
+    max_bets_per_second: u32,
+    max_transaction_payout: u64,
+    max_daily_net_win: u64,
+) -> Instruction {
+    build(
+        accounts::InitializeGameLimits {
+            game_limits: pda::game_limits().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeGameLimits {
+            default_max_multiplier,
+            max_unstaked_payout,
+            max_bets_per_second,
+            max_transaction_payout,
+            max_daily_net_win,
+        },
+    )
+}
+
+/// Set or (with 0) remove one game's maximum payout multiplier
+pub fn set_game_limit(authority: &Pubkey, game_id: u64, max_multiplier: u32) -> Instruction {
+    build(
+        accounts::SetGameLimits {
+            game_limits: pda::game_limits().0,
+            authority: *authority,
+        },
+        instruction::SetGameLimit { game_id, max_multiplier },
+    )
+}
+
+/// Multisig: publish the rules of originals game `game_id`
+pub fn initialize_game_config(
+    authority: &Pubkey,
+    game_id: u64,
+    house_edge_bps: u16,
+    max_multiplier: u32,
+    min_stake: u64,
+    max_stake: u64,
+) -> Instruction {
+    build(
+        accounts::InitializeGameConfig {
+            game_config: pda::game_config(game_id).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeGameConfig { game_id, house_edge_bps, max_multiplier, min_stake, max_stake },
+    )
+}
+
+/// Multisig: change the published rules of originals game `game_id`
+pub fn set_game_config(
+    authority: &Pubkey,
+    game_id: u64,
+    house_edge_bps: u16,
+    max_multiplier: u32,
+    min_stake: u64,
+    max_stake: u64,
+) -> Instruction {
+    build(
+        accounts::SetGameConfig { game_config: pda::game_config(game_id).0, authority: *authority },
+        instruction::SetGameConfig { house_edge_bps, max_multiplier, min_stake, max_stake },
+    )
+}
+
+/// Cap settlements per vault per second (0 = unlimited)
+pub fn set_bet_rate_limit(authority: &Pubkey, max_bets_per_second: u32) -> Instruction {
+    build(
+        accounts::SetGameLimits {
+            game_limits: pda::game_limits().0,
+            authority: *authority,
+        },
+        instruction::SetBetRateLimit { max_bets_per_second },
+    )
+}
+
+/// Cap the summed payouts of all settlements in one transaction (0 = unlimited)
+pub fn set_transaction_payout_limit(authority: &Pubkey, max_transaction_payout: u64) -> Instruction {
+    build(
+        accounts::SetGameLimits {
+            game_limits: pda::game_limits().0,
+            authority: *authority,
+        },
+        instruction::SetTransactionPayoutLimit { max_transaction_payout },
+    )
+}
+
+/// Stream the part of payouts above `threshold` in `tranches`, one every `interval_secs` (threshold 0 = off)
+pub fn set_payout_stream(authority: &Pubkey, threshold: u64, tranches: u16, interval_secs: i64) -> Instruction {
+    build(
+        accounts::SetGameLimits {
+            game_limits: pda::game_limits().0,
+            authority: *authority,
+        },
+        instruction::SetPayoutStream { threshold, tranches, interval_secs },
+    )
+}
+
+/// Cap each vault's net winnings per 24h window (0 = unlimited)
+pub fn set_daily_win_cap(authority: &Pubkey, max_daily_net_win: u64) -> Instruction {
+    build(
+        accounts::SetGameLimits {
+            game_limits: pda::game_limits().0,
+            authority: *authority,
+        },
+        instruction::SetDailyWinCap { max_daily_net_win },
+    )
+}
+
+/// Give `owner`'s vault its own daily net-win cap (0 = the global cap)
+pub fn set_vault_win_cap(authority: &Pubkey, owner: &Pubkey, daily_win_cap: u64) -> Instruction {
+    build(
+        accounts::SetVaultWinCap { vault: pda::vault(owner).0, authority: *authority },
+        instruction::SetVaultWinCap { daily_win_cap },
+    )
+}
+
+pub fn set_default_game_limits(authority: &Pubkey, default_max_multiplier: u32, max_unstaked_payout: u64) -> Instruction {
+    build(
+        accounts::SetGameLimits {
+            game_limits: pda::game_limits().0,
+            authority: *authority,
+        },
+        instruction::SetDefaultGameLimits { default_max_multiplier, max_unstaked_payout },
+    )
+}
+
+pub fn initialize_announcement(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeAnnouncement {
+            announcement: pda::announcement().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeAnnouncement {},
+    )
+}
+
+/// Publish a banner; `message_hash` is the sha256 of the text served at `uri`
+pub fn set_announcement(
+    authority: &Pubkey,
+    uri: String,
+    message_hash: [u8; 32],
+    severity: u8,
+    expires_at: i64,
+) -> Instruction {
+    build(
+        accounts::SetAnnouncement {
+            announcement: pda::announcement().0,
+            authority: *authority,
+        },
+        instruction::SetAnnouncement { uri, message_hash, severity, expires_at },
+    )
+}
+
+pub fn clear_announcement(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::SetAnnouncement {
+            announcement: pda::announcement().0,
+            authority: *authority,
+        },
+        instruction::ClearAnnouncement {},
+    )
+}
+
+/// Deposit from `owner`'s wallet into their vault
+pub fn deposit(owner: &Pubkey, amount: u64) -> Instruction {
+    deposit_ix(owner, amount, None, None)
+}
+
+/// Deposit above the screening threshold, passing the screening `program` and its optional `state`
+pub fn deposit_screened(owner: &Pubkey, amount: u64, program: &Pubkey, state: Option<Pubkey>) -> Instruction {
+    deposit_ix(owner, amount, None, Some((*program, state)))
+}
+
+/// Deposit tagged with a Solana Pay `reference`
+///
+/// The reference is recorded in the `Deposited` event and appended as a
+/// read-only account, so the deposit can be found by reference alone.
+pub fn deposit_with_reference(owner: &Pubkey, amount: u64, reference: &Pubkey) -> Instruction {
+    let mut ix = deposit_ix(owner, amount, Some(reference.to_bytes()), None);
+    ix.accounts.push(AccountMeta::new_readonly(*reference, false));
+    ix
+}
+
+/// Deposit tagged with an off-chain order, e.g. an on-ramp order id through [`crate::order_reference`]
+///
+/// Only the `Deposited` event carries the reference; use
+/// [`deposit_with_reference`] when the deposit must also be found by address.
+pub fn deposit_for_order(owner: &Pubkey, amount: u64, reference: [u8; 32]) -> Instruction {
+    deposit_ix(owner, amount, Some(reference), None)
+}
+
+fn deposit_ix(
+    owner: &Pubkey,
+    amount: u64,
+    reference: Option<[u8; 32]>,
+    screening: Option<(Pubkey, Option<Pubkey>)>,
+) -> Instruction {
+    build(
+        accounts::Deposit {
+            vault: pda::vault(owner).0,
+            owner: *owner,
+            pause_config: pda::pause_config().0,
+            system_program: system_program::ID,
+            global_config: pda::global_config().0,
+            global_stats: pda::global_stats().0,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            screening_program: screening.map(|(program, _)| program),
+            screening_state: screening.and_then(|(_, state)| state),
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::Deposit { amount, reference },
+    )
+}
+
+/// Legacy message for `instructions` with `relayer` as fee payer
+///
+/// The vault owner still signs, e.g. for a deposit, but needs no SOL for fees.
+pub fn relayed_message(relayer: &Pubkey, instructions: &[Instruction], recent_blockhash: Hash) -> Message {
+    Message::new_with_blockhash(instructions, Some(relayer), &recent_blockhash)
+}
+
+/// Ed25519 precompile check of `signature` by `signer` over `message`, with all data inline
+pub fn ed25519_verify(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    const KEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = KEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+    let offsets = [
+        SIGNATURE_OFFSET,
+        u16::MAX,
+        KEY_OFFSET,
+        u16::MAX,
+        MESSAGE_OFFSET,
+        message.len() as u16,
+        u16::MAX,
+    ];
+    let mut data = vec![1, 0];
+    data.extend(offsets.iter().flat_map(|offset| offset.to_le_bytes()));
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    Instruction { program_id: ed25519_program::ID, accounts: Vec::new(), data }
+}
+
+/// A withdraw the owner authorized off-chain by signing [`withdraw_intent_message`]
+#[derive(Clone, Copy, Debug)]
+pub struct RelayedWithdraw {
+    pub owner: Pubkey,
+    pub amount: u64,
+    /// Paid from the vault to the relayer, on top of `amount`
+    pub fee: u64,
+    pub nonce: u64,
+    pub expires_at: i64,
+    pub extras: WithdrawExtras,
+}
+
+impl RelayedWithdraw {
+    /// Bytes the owner signs, e.g. with a wallet's `signMessage`
+    pub fn message(&self) -> Vec<u8> {
+        withdraw_intent_message(&pda::vault(&self.owner).0, self.amount, self.fee, self.nonce, self.expires_at)
+    }
+}
+
+/// Relayer-paid withdraw: the signature check followed by `withdraw_with_intent`
+pub fn withdraw_with_intent(relayer: &Pubkey, intent: &RelayedWithdraw, signature: &[u8; 64]) -> Vec<Instruction> {
+    let vault = pda::vault(&intent.owner).0;
+    let withdraw = build(
+        accounts::WithdrawWithIntent {
+            vault,
+            owner: intent.owner,
+            relayer: *relayer,
+            intent: pda::withdraw_intent(&vault, intent.nonce).0,
+            pause_config: pda::pause_config().0,
+            global_config: pda::global_config().0,
+            global_stats: pda::global_stats().0,
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            compliance_config: pda::compliance_config().0,
+            kyc_attestation: intent.extras.kyc.then(|| pda::kyc_attestation(&vault).0),
+            withdraw_guard: pda::withdraw_guard(&vault).0,
+            device: intent.extras.device,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::WithdrawWithIntent {
+            amount: intent.amount,
+            fee: intent.fee,
+            nonce: intent.nonce,
+            expires_at: intent.expires_at,
+        },
+    );
+    vec![ed25519_verify(&intent.owner, signature, &intent.message()), withdraw]
+}
+
+/// Withdraw from `owner`'s vault back to their wallet
+pub fn withdraw(owner: &Pubkey, amount: u64) -> Instruction {
+    withdraw_with_extras(owner, amount, WithdrawExtras::default())
+}
+
+/// [`withdraw`] passing the KYC attestation or device key the vault requires
+pub fn withdraw_with_extras(owner: &Pubkey, amount: u64, extras: WithdrawExtras) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::Withdraw {
+            vault,
+            owner: *owner,
+            pause_config: pda::pause_config().0,
+            global_config: pda::global_config().0,
+            global_stats: pda::global_stats().0,
+            compliance_config: pda::compliance_config().0,
+            kyc_attestation: extras.kyc.then(|| pda::kyc_attestation(&vault).0),
+            withdraw_guard: pda::withdraw_guard(&vault).0,
+            device: extras.device,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::Withdraw { amount },
+    )
+}
+
+pub fn bet_and_settle(authority: &Pubkey, settlement: &Settlement, extras: SettlementExtras) -> Instruction {
+    build(
+        bet_and_settle_accounts(authority, settlement, extras),
+        instruction::BetAndSettle {
+            stake: settlement.stake,
+            payout: settlement.payout,
+            bet_id: settlement.bet_id.clone(),
+            game_id: settlement.game_id,
+            category: settlement.category,
+            gem_data: settlement.gem_data.clone(),
+        },
+    )
+}
+
+/// Settle `settlement` together with up to `MAX_SIDE_BETS` side bets placed with it
+///
+/// Each side bet's game config is appended as a remaining account in order.
+pub fn bet_and_settle_with_side_bets(
+    authority: &Pubkey,
+    settlement: &Settlement,
+    side_bets: &[SideBet],
+    extras: SettlementExtras,
+) -> Instruction {
+    let mut ix = build(
+        bet_and_settle_accounts(authority, settlement, extras),
+        instruction::BetAndSettleWithSideBets {
+            stake: settlement.stake,
+            payout: settlement.payout,
+            bet_id: settlement.bet_id.clone(),
+            game_id: settlement.game_id,
+            category: settlement.category,
+            gem_data: settlement.gem_data.clone(),
+            side_bets: side_bets.to_vec(),
+        },
+    );
+    ix.accounts.extend(
+        side_bets
+            .iter()
+            .map(|side_bet| AccountMeta::new_readonly(pda::game_config(side_bet.game_id).0, false)),
+    );
+    ix
+}
+
+fn bet_and_settle_accounts(
+    authority: &Pubkey,
+    settlement: &Settlement,
+    extras: SettlementExtras,
+) -> accounts::BetAndSettle {
+    let vault = pda::vault(&settlement.owner).0;
+    accounts::BetAndSettle {
+        vault,
+        house_vault: pda::house_vault().0,
+        authority: *authority,
+        pause_config: pda::pause_config().0,
+        bonus: pda::deposit_bonus(&vault).0,
+        gem_inventory: extras.gem_inventory.then(|| pda::gem_inventory(&vault).0),
+        loss_back_promo: pda::loss_back_promo().0,
+        loss_back: pda::loss_back(&vault).0,
+        pity_config: extras.pity.then(|| pda::pity_config().0),
+        bet_receipt: extras
+            .receipt
+            .then(|| pda::bet_receipt(&vault, &bet_id_hash(&settlement.bet_id)).0),
+        global_config: pda::global_config().0,
+        system_program: system_program::ID,
+        global_stats: pda::global_stats().0,
+        game_limits: pda::game_limits().0,
+        instructions: sysvar::instructions::ID,
+        blacklist: pda::blacklist().0,
+        compliance_config: pda::compliance_config().0,
+        game_config: pda::game_config(settlement.game_id).0,
+        jackpot: pda::jackpot(settlement.game_id).0,
+        game_programs: None,
+        trophy_config: extras.trophy_tree.map(|_| pda::trophy_config().0),
+        player: extras.trophy_tree.map(|_| settlement.owner),
+        tree_config: extras.trophy_tree.map(|tree| pda::trophy_tree_config(&tree).0),
+        merkle_tree: extras.trophy_tree,
+        log_wrapper: extras
+            .trophy_tree
+            .map(|_| "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV".parse().unwrap()),
+        compression_program: extras
+            .trophy_tree
+            .map(|_| "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK".parse().unwrap()),
+        bubblegum_program: extras.trophy_tree.map(|_| pda::BUBBLEGUM_PROGRAM_ID.parse().unwrap()),
+        payout_schedule: extras
+            .stream
+            .then(|| pda::payout_schedule(&vault, &bet_id_hash(&settlement.bet_id)).0),
+        staking_config: extras.staking.then(|| pda::staking_config().0),
+        token_stake: extras.staking.then(|| pda::token_stake(&vault).0),
+        event_authority: pda::event_authority().0,
+        program: ID,
+    }
+}
+
+/// Settle several bets at once
+///
+/// User vaults, then each bet's game config, then each vault's deposit bonus,
+/// then each vault's loss-back record are appended as remaining accounts in order.
+pub fn batch_settle(authority: &Pubkey, settlements: &[Settlement]) -> Instruction {
+    let mut ix = build(
+        accounts::BatchSettle {
+            house_vault: pda::house_vault().0,
+            authority: *authority,
+            pause_config: pda::pause_config().0,
+            global_config: pda::global_config().0,
+            global_stats: pda::global_stats().0,
+            game_limits: pda::game_limits().0,
+            instructions: sysvar::instructions::ID,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            loss_back_promo: pda::loss_back_promo().0,
+            game_programs: None,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::BatchSettle {
+            owners: settlements.iter().map(|s| s.owner).collect(),
+            stakes: settlements.iter().map(|s| s.stake).collect(),
+            payouts: settlements.iter().map(|s| s.payout).collect(),
+            bet_ids: settlements.iter().map(|s| s.bet_id.clone()).collect(),
+            game_ids: settlements.iter().map(|s| s.game_id).collect(),
+            categories: settlements.iter().map(|s| s.category).collect(),
+            gem_datas: settlements.iter().map(|s| s.gem_data.clone()).collect(),
+        },
+    );
+    ix.accounts.extend(
+        settlements
+            .iter()
+            .map(|s| AccountMeta::new(pda::vault(&s.owner).0, false)),
+    );
+    ix.accounts.extend(
+        settlements
+            .iter()
+            .map(|s| AccountMeta::new_readonly(pda::game_config(s.game_id).0, false)),
+    );
+    ix.accounts.extend(
+        settlements
+            .iter()
+            .map(|s| AccountMeta::new(pda::deposit_bonus(&pda::vault(&s.owner).0).0, false)),
+    );
+    ix.accounts.extend(
+        settlements
+            .iter()
+            .map(|s| AccountMeta::new(pda::loss_back(&pda::vault(&s.owner).0).0, false)),
+    );
+    ix
+}
+
+/// Largest serialized transaction the network accepts
+pub const MAX_TRANSACTION_BYTES: usize = 1232;
+
+/// Distinct accounts a transaction may lock
+pub const MAX_TRANSACTION_ACCOUNTS: usize = 64;
+
+/// `SetComputeUnitLimit` for the compute-budget program
+pub fn set_compute_unit_limit(units: u32) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction {
+        program_id: "ComputeBudget111111111111111111111111111111".parse().unwrap(),
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Compute units a `batch_settle` of `items` bets needs
+pub fn batch_settle_compute_units(items: usize) -> u32 {
+    BATCH_SETTLE_BASE_CU.saturating_add(BATCH_SETTLE_ITEM_CU.saturating_mul(items as u32))
+}
+
+/// Per-transaction limits [`pack_batch_settle`] fills each batch up to
+#[derive(Clone, Copy, Debug)]
+pub struct BatchBudget {
+    /// Compute units a transaction may request
+    pub compute_units: u32,
+    /// Distinct accounts a transaction may reference
+    pub max_accounts: usize,
+}
+
+impl Default for BatchBudget {
+    fn default() -> Self {
+        Self { compute_units: MAX_COMPUTE_UNIT_LIMIT, max_accounts: MAX_TRANSACTION_ACCOUNTS }
+    }
+}
+
+/// Split `settlements` into as few `batch_settle` transactions as `budget` allows
+///
+/// Each batch is `[set_compute_unit_limit, batch_settle]`, requesting only the
+/// units its size needs. A batch closes when the next bet would exceed the
+/// compute budget, the account limit, [`MAX_BATCH_SETTLE`] or
+/// [`MAX_TRANSACTION_BYTES`] as a v0 transaction signed by `authority` and
+/// compiled against `lookup_tables`, or would repeat a vault the program
+/// rejects as a duplicate. A bet that exceeds the budget on its own still
+/// gets a batch of one.
+pub fn pack_batch_settle(
+    authority: &Pubkey,
+    settlements: &[Settlement],
+    budget: BatchBudget,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Vec<Vec<Instruction>> {
+    let batch = |chunk: &[Settlement]| {
+        vec![set_compute_unit_limit(batch_settle_compute_units(chunk.len())), batch_settle(authority, chunk)]
+    };
+    let fits = |ixs: &[Instruction]| {
+        let Ok(message) = v0::Message::try_compile(authority, ixs, lookup_tables, Hash::default()) else {
+            return false;
+        };
+        let signatures = message.header.num_required_signatures as usize;
+        let accounts = message.account_keys.len()
+            + message
+                .address_table_lookups
+                .iter()
+                .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+                .sum::<usize>();
+        let bytes = 1 + 64 * signatures + VersionedMessage::V0(message).serialize().len();
+        accounts <= budget.max_accounts && bytes <= MAX_TRANSACTION_BYTES
+    };
+
+    let mut batches = Vec::new();
+    let mut start = 0;
+    for end in 1..=settlements.len() {
+        let chunk = &settlements[start..end];
+        let size = chunk.len();
+        let duplicate = chunk[..size - 1].iter().any(|s| s.owner == chunk[size - 1].owner);
+        if size > 1
+            && (duplicate
+                || size > MAX_BATCH_SETTLE
+                || batch_settle_compute_units(size) > budget.compute_units
+                || !fits(&batch(chunk)))
+        {
+            batches.push(batch(&settlements[start..end - 1]));
+            start = end - 1;
+        }
+    }
+    if start < settlements.len() {
+        batches.push(batch(&settlements[start..]));
+    }
+    batches
+}
+
+/// Admin: credit each `(owner, lamports)` vault from the promo pool
+///
+/// Vaults are appended as remaining accounts in the order given.
+pub fn batch_credit(authority: &Pubkey, credits: &[(Pubkey, u64)]) -> Instruction {
+    let mut ix = build(
+        accounts::BatchCredit {
+            promo_pool: pda::promo_pool().0,
+            authority: *authority,
+        },
+        instruction::BatchCredit {
+            users: credits.iter().map(|(owner, _)| *owner).collect(),
+            amounts: credits.iter().map(|(_, amount)| *amount).collect(),
+        },
+    );
+    ix.accounts.extend(credits.iter().map(|(owner, _)| AccountMeta::new(pda::vault(owner).0, false)));
+    ix
+}
+
+/// Admin: create the stats snapshot ring buffer, recording at most every `interval_secs`
+pub fn initialize_stats_snapshots(authority: &Pubkey, interval_secs: u32) -> Instruction {
+    build(
+        accounts::InitializeStatsSnapshots {
+            stats_snapshots: pda::stats_snapshots().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeStatsSnapshots { interval_secs },
+    )
+}
+
+/// Permissionless: run due global upkeep; safe to schedule from an automation thread
+pub fn automation_tick() -> Instruction {
+    build(
+        accounts::AutomationTick {
+            pause_config: pda::pause_config().0,
+            stats_snapshots: pda::stats_snapshots().0,
+            global_stats: pda::global_stats().0,
+            house_vault: pda::house_vault().0,
+        },
+        instruction::AutomationTick {},
+    )
+}
+
+/// Admin: create the empty game program registry
+pub fn initialize_game_programs(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeGamePrograms {
+            game_programs: pda::game_programs().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeGamePrograms {},
+    )
+}
+
+/// Multisig: allow `program` to settle bets via CPI
+pub fn add_game_program(authority: &Pubkey, program: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateGamePrograms {
+            game_programs: pda::game_programs().0,
+            authority: *authority,
+        },
+        instruction::AddGameProgram { program },
+    )
+}
+
+/// Multisig: revoke `program`'s CPI settlement rights
+pub fn remove_game_program(authority: &Pubkey, program: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateGamePrograms {
+            game_programs: pda::game_programs().0,
+            authority: *authority,
+        },
+        instruction::RemoveGameProgram { program },
+    )
+}
+
+/// Admin: create the trophy config; `merkle_tree`'s delegate must be the trophy config PDA
+pub fn initialize_trophy_config(
+    authority: &Pubkey,
+    merkle_tree: Pubkey,
+    min_multiplier: u32,
+    base_uri: String,
+) -> Instruction {
+    build(
+        accounts::InitializeTrophyConfig {
+            trophy_config: pda::trophy_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeTrophyConfig { merkle_tree, min_multiplier, base_uri },
+    )
+}
+
+/// Multisig: change the trophy tree, threshold (100 = 1x, 0 = off) or metadata URI
+pub fn set_trophy_config(authority: &Pubkey, merkle_tree: Pubkey, min_multiplier: u32, base_uri: String) -> Instruction {
+    build(
+        accounts::SetTrophyConfig {
+            trophy_config: pda::trophy_config().0,
+            authority: *authority,
+        },
+        instruction::SetTrophyConfig { merkle_tree, min_multiplier, base_uri },
+    )
+}
+
+/// Admin: create the bridge config, trusting VAAs posted through `core_bridge`
+pub fn initialize_bridge_config(authority: &Pubkey, core_bridge: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeBridgeConfig {
+            bridge_config: pda::bridge_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeBridgeConfig { core_bridge },
+    )
+}
+
+/// Multisig: accept deposit messages from `address` on Wormhole chain `chain`
+pub fn add_bridge_emitter(authority: &Pubkey, chain: u16, address: [u8; 32]) -> Instruction {
+    build(
+        accounts::UpdateBridgeConfig {
+            bridge_config: pda::bridge_config().0,
+            authority: *authority,
+        },
+        instruction::AddBridgeEmitter { chain, address },
+    )
+}
+
+/// Multisig: stop accepting deposit messages from an emitter
+pub fn remove_bridge_emitter(authority: &Pubkey, chain: u16, address: [u8; 32]) -> Instruction {
+    build(
+        accounts::UpdateBridgeConfig {
+            bridge_config: pda::bridge_config().0,
+            authority: *authority,
+        },
+        instruction::RemoveBridgeEmitter { chain, address },
+    )
+}
+
+/// Top up the pool bridged deposits are credited from
+pub fn fund_bridge_pool(funder: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::FundBridgePool {
+            bridge_config: pda::bridge_config().0,
+            funder: *funder,
+            system_program: system_program::ID,
+        },
+        instruction::FundBridgePool { amount },
+    )
+}
+
+/// Permissionless: credit `owner`'s vault for the deposit in `posted_vaa`; `payer` funds the claim PDA
+pub fn credit_bridged_deposit(payer: &Pubkey, owner: &Pubkey, posted_vaa: &Pubkey) -> Instruction {
+    build(
+        accounts::CreditBridgedDeposit {
+            bridge_config: pda::bridge_config().0,
+            posted_vaa: *posted_vaa,
+            bridge_claim: pda::bridge_claim(posted_vaa).0,
+            vault: pda::vault(owner).0,
+            pause_config: pda::pause_config().0,
+            global_config: pda::global_config().0,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            payer: *payer,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::CreditBridgedDeposit {},
+    )
+}
+
+/// Require `device` to co-sign withdrawals from `owner`'s vault; both keys sign
+pub fn enable_withdraw_guard(owner: &Pubkey, device: &Pubkey) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::EnableWithdrawGuard {
+            vault,
+            withdraw_guard: pda::withdraw_guard(&vault).0,
+            owner: *owner,
+            device: *device,
+            system_program: system_program::ID,
+        },
+        instruction::EnableWithdrawGuard {},
+    )
+}
+
+/// Start the delay after which `owner` can drop the withdraw guard without the device key
+pub fn request_withdraw_guard_removal(owner: &Pubkey) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::RequestWithdrawGuardRemoval { vault, withdraw_guard: pda::withdraw_guard(&vault).0, owner: *owner },
+        instruction::RequestWithdrawGuardRemoval {},
+    )
+}
+
+/// Drop the withdraw guard, co-signed by `device` or once a requested removal has matured
+pub fn remove_withdraw_guard(owner: &Pubkey, device: Option<Pubkey>) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::RemoveWithdrawGuard {
+            vault,
+            withdraw_guard: pda::withdraw_guard(&vault).0,
+            owner: *owner,
+            device,
+        },
+        instruction::RemoveWithdrawGuard {},
+    )
+}
+
+/// Admin: create the queue ordering withdrawals requested during pauses
+pub fn initialize_withdrawal_queue(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeWithdrawalQueue {
+            withdrawal_queue: pda::withdrawal_queue().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeWithdrawalQueue {},
+    )
+}
+
+/// Queue a withdrawal of `amount` while operations are paused, passing the KYC attestation or device key as needed
+pub fn request_withdrawal(owner: &Pubkey, amount: u64, extras: WithdrawExtras) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::RequestWithdrawal {
+            vault,
+            owner: *owner,
+            withdrawal_request: pda::withdrawal_request(&vault).0,
+            withdrawal_queue: pda::withdrawal_queue().0,
+            pause_config: pda::pause_config().0,
+            compliance_config: pda::compliance_config().0,
+            kyc_attestation: extras.kyc.then(|| pda::kyc_attestation(&vault).0),
+            withdraw_guard: pda::withdraw_guard(&vault).0,
+            device: extras.device,
+            system_program: system_program::ID,
+        },
+        instruction::RequestWithdrawal { amount },
+    )
+}
+
+/// Permissionless: honor the queued withdrawal of `owner`'s vault, which must hold the head ticket
+pub fn process_withdrawal_request(owner: &Pubkey) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::ProcessWithdrawalRequest {
+            vault,
+            owner: *owner,
+            withdrawal_request: pda::withdrawal_request(&vault).0,
+            withdrawal_queue: pda::withdrawal_queue().0,
+            pause_config: pda::pause_config().0,
+            global_config: pda::global_config().0,
+            global_stats: pda::global_stats().0,
+            compliance_config: pda::compliance_config().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::ProcessWithdrawalRequest {},
+    )
+}
+
+/// Vault owner or multisig: drop the queued withdrawal of `owner`'s vault, which must hold the head ticket
+pub fn drop_withdrawal_request(authority: &Pubkey, owner: &Pubkey) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::DropWithdrawalRequest {
+            vault,
+            owner: *owner,
+            withdrawal_request: pda::withdrawal_request(&vault).0,
+            withdrawal_queue: pda::withdrawal_queue().0,
+            authority: *authority,
+        },
+        instruction::DropWithdrawalRequest {},
+    )
+}
+
+/// Admin: create the platform-token staking config; `escrow` is a token account of `mint` owned by
+/// [`pda::staking_config`]
+pub fn initialize_staking_config(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    escrow: &Pubkey,
+    cooldown_secs: i64,
+) -> Instruction {
+    build(
+        accounts::InitializeStakingConfig {
+            staking_config: pda::staking_config().0,
+            mint: *mint,
+            escrow: *escrow,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeStakingConfig { cooldown_secs },
+    )
+}
+
+/// Multisig: replace the staking rebate tiers and unstaking cooldown
+pub fn set_staking_tiers(authority: &Pubkey, cooldown_secs: i64, tiers: Vec<RebateTier>) -> Instruction {
+    build(
+        accounts::SetStakingTiers { staking_config: pda::staking_config().0, authority: *authority },
+        instruction::SetStakingTiers { cooldown_secs, tiers },
+    )
+}
+
+/// Create the owner's platform-token stake record
+pub fn initialize_token_stake(owner: &Pubkey) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::InitializeTokenStake {
+            vault,
+            token_stake: pda::token_stake(&vault).0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeTokenStake {},
+    )
+}
+
+/// Stake `amount` platform tokens from the owner's token account `source` into `escrow`
+pub fn stake_tokens(owner: &Pubkey, source: &Pubkey, escrow: &Pubkey, amount: u64) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::StakeTokens {
+            vault,
+            owner: *owner,
+            token_stake: pda::token_stake(&vault).0,
+            staking_config: pda::staking_config().0,
+            source: *source,
+            escrow: *escrow,
+            token_program: SPL_TOKEN_PROGRAM_ID.parse().unwrap(),
+        },
+        instruction::StakeTokens { amount },
+    )
+}
+
+/// Start unstaking `amount` tokens, restarting the cooldown
+pub fn request_unstake(owner: &Pubkey, amount: u64) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::RequestUnstake {
+            vault,
+            owner: *owner,
+            token_stake: pda::token_stake(&vault).0,
+            staking_config: pda::staking_config().0,
+        },
+        instruction::RequestUnstake { amount },
+    )
+}
+
+/// Withdraw every unstaked token from `escrow` to `destination` once the cooldown is over
+pub fn withdraw_unstaked(owner: &Pubkey, escrow: &Pubkey, destination: &Pubkey) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::WithdrawUnstaked {
+            vault,
+            owner: *owner,
+            token_stake: pda::token_stake(&vault).0,
+            staking_config: pda::staking_config().0,
+            escrow: *escrow,
+            destination: *destination,
+            token_program: SPL_TOKEN_PROGRAM_ID.parse().unwrap(),
+        },
+        instruction::WithdrawUnstaked {},
+    )
+}
+
+/// Admin: create the responsible-gambling config with its platform floors
+pub fn initialize_rg_config(
+    authority: &Pubkey,
+    min_cool_off_secs: i64,
+    min_limit_raise_interval_secs: i64,
+) -> Instruction {
+    build(
+        accounts::InitializeRgConfig {
+            rg_config: pda::rg_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeRgConfig { min_cool_off_secs, min_limit_raise_interval_secs },
+    )
+}
+
+/// Multisig: change the responsible-gambling floors
+pub fn set_rg_config(authority: &Pubkey, min_cool_off_secs: i64, min_limit_raise_interval_secs: i64) -> Instruction {
+    build(
+        accounts::SetRgConfig { rg_config: pda::rg_config().0, authority: *authority },
+        instruction::SetRgConfig { min_cool_off_secs, min_limit_raise_interval_secs },
+    )
+}
+
+/// Admin: create the compliance config with KYC gating off
+pub fn initialize_compliance_config(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeComplianceConfig {
+            compliance_config: pda::compliance_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeComplianceConfig {},
+    )
+}
+
+/// Multisig: require a KYC attestation for withdrawals above `threshold` lamports (0 = off)
+pub fn set_kyc_threshold(authority: &Pubkey, threshold: u64) -> Instruction {
+    build(
+        accounts::UpdateComplianceConfig { compliance_config: pda::compliance_config().0, authority: *authority },
+        instruction::SetKycThreshold { threshold },
+    )
+}
+
+/// Multisig: emit `LargeTransfer` for transfers above `threshold` lamports (0 = off)
+pub fn set_large_transfer_threshold(authority: &Pubkey, threshold: u64) -> Instruction {
+    build(
+        accounts::UpdateComplianceConfig { compliance_config: pda::compliance_config().0, authority: *authority },
+        instruction::SetLargeTransferThreshold { threshold },
+    )
+}
+
+/// Multisig: screen deposits above `threshold` lamports through `program`, or stop screening with `None`
+pub fn set_deposit_screening(authority: &Pubkey, program: Option<Pubkey>, threshold: u64) -> Instruction {
+    build(
+        accounts::UpdateComplianceConfig { compliance_config: pda::compliance_config().0, authority: *authority },
+        instruction::SetDepositScreening { program, threshold },
+    )
+}
+
+/// Multisig: cap stakes from vaults in `region` for `game_id`, or every game with `None`; 0 bars the game
+pub fn set_region_rule(authority: &Pubkey, region: u8, game_id: Option<u64>, max_stake: u64) -> Instruction {
+    build(
+        accounts::UpdateComplianceConfig { compliance_config: pda::compliance_config().0, authority: *authority },
+        instruction::SetRegionRule { region, game_id, max_stake },
+    )
+}
+
+/// Multisig: drop the rule for `region` and `game_id`
+pub fn remove_region_rule(authority: &Pubkey, region: u8, game_id: Option<u64>) -> Instruction {
+    build(
+        accounts::UpdateComplianceConfig { compliance_config: pda::compliance_config().0, authority: *authority },
+        instruction::RemoveRegionRule { region, game_id },
+    )
+}
+
+/// Admin: assign `owner`'s vault to jurisdiction `region`
+pub fn set_vault_region(authority: &Pubkey, owner: &Pubkey, region: u8) -> Instruction {
+    build(
+        accounts::SetVaultRegion {
+            vault: pda::vault(owner).0,
+            authority: *authority,
+            global_config: pda::global_config().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::SetVaultRegion { region },
+    )
+}
+
+/// Admin: publish terms of service `version_hash`, optionally required before the next staked bet
+pub fn set_terms(authority: &Pubkey, version_hash: [u8; 32], required: bool) -> Instruction {
+    build(
+        accounts::UpdateComplianceConfig { compliance_config: pda::compliance_config().0, authority: *authority },
+        instruction::SetTerms { version_hash, required },
+    )
+}
+
+/// Record `owner`'s acceptance of the terms of service hashed to `version_hash`
+pub fn accept_terms(owner: &Pubkey, version_hash: [u8; 32]) -> Instruction {
+    build(
+        accounts::AcceptTerms {
+            vault: pda::vault(owner).0,
+            owner: *owner,
+            compliance_config: pda::compliance_config().0,
+        },
+        instruction::AcceptTerms { version_hash },
+    )
+}
+
+/// Admin: approve `attestor` to issue KYC attestations
+pub fn add_attestor(authority: &Pubkey, attestor: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateComplianceConfig { compliance_config: pda::compliance_config().0, authority: *authority },
+        instruction::AddAttestor { attestor },
+    )
+}
+
+/// Admin: revoke an attestor's approval
+pub fn remove_attestor(authority: &Pubkey, attestor: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateComplianceConfig { compliance_config: pda::compliance_config().0, authority: *authority },
+        instruction::RemoveAttestor { attestor },
+    )
+}
+
+/// Approved attestor: attest `owner` passed KYC until `expires_at`
+pub fn issue_kyc_attestation(attestor: &Pubkey, owner: &Pubkey, expires_at: i64) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::IssueKycAttestation {
+            compliance_config: pda::compliance_config().0,
+            vault,
+            kyc_attestation: pda::kyc_attestation(&vault).0,
+            attestor: *attestor,
+            system_program: system_program::ID,
+        },
+        instruction::IssueKycAttestation { expires_at },
+    )
+}
+
+/// Issuing attestor or admin: revoke `owner`'s attestation, refunding rent to `attestor`
+pub fn revoke_kyc_attestation(authority: &Pubkey, attestor: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        accounts::RevokeKycAttestation {
+            kyc_attestation: pda::kyc_attestation(&pda::vault(owner).0).0,
+            attestor: *attestor,
+            authority: *authority,
+        },
+        instruction::RevokeKycAttestation {},
+    )
+}
+
+/// Admin: create the empty vault registry
+pub fn initialize_vault_registry(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeVaultRegistry {
+            vault_registry: pda::vault_registry().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeVaultRegistry {},
+    )
+}
+
+/// Permissionless: open registry page `page`, which must equal the registry's page count
+pub fn open_registry_page(payer: &Pubkey, page: u32) -> Instruction {
+    build(
+        accounts::OpenRegistryPage {
+            vault_registry: pda::vault_registry().0,
+            registry_page: pda::registry_page(page).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::OpenRegistryPage {},
+    )
+}
+
+/// Permissionless: list `owner`'s vault on `page`, the registry's current page
+pub fn register_vault(payer: &Pubkey, owner: &Pubkey, page: u32) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::RegisterVault {
+            vault_registry: pda::vault_registry().0,
+            registry_page: pda::registry_page(page).0,
+            vault,
+            registry_entry: pda::registry_entry(&vault).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterVault {},
+    )
+}
+
+pub fn start_maintenance_pause(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::StartMaintenancePause {
+            pause_config: pda::pause_config().0,
+            authority: *authority,
+        },
+        instruction::StartMaintenancePause {},
+    )
+}
+
+pub fn emergency_pause(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::EmergencyPause {
+            pause_config: pda::pause_config().0,
+            authority: *authority,
+        },
+        instruction::EmergencyPause {},
+    )
+}
+
+pub fn unpause(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::Unpause {
+            pause_config: pda::pause_config().0,
+            authority: *authority,
+        },
+        instruction::Unpause {},
+    )
+}
+
+/// Permissionless: clear a maintenance pause whose window has elapsed
+pub fn resume_after_maintenance() -> Instruction {
+    build(
+        accounts::ResumeAfterMaintenance {
+            pause_config: pda::pause_config().0,
+        },
+        instruction::ResumeAfterMaintenance {},
+    )
+}
+
+pub fn get_pause_status() -> Instruction {
+    build(
+        accounts::GetPauseStatus {
+            pause_config: pda::pause_config().0,
+        },
+        instruction::GetPauseStatus {},
+    )
+}
+
+/// View instruction; simulate and decode with [`crate::decode_return_data`]
+pub fn get_vault_info(owner: &Pubkey) -> Instruction {
+    build(
+        accounts::GetVaultInfo {
+            vault: pda::vault(owner).0,
+        },
+        instruction::GetVaultInfo {},
+    )
+}
+
+/// View instruction; simulate and decode with [`crate::decode_return_data`]
+pub fn get_house_info() -> Instruction {
+    build(
+        accounts::GetHouseInfo {
+            house_vault: pda::house_vault().0,
+            pause_config: pda::pause_config().0,
+        },
+        instruction::GetHouseInfo {},
+    )
+}
+
+/// Emit audit page `page` as an `AuditPage` event; needs no signer beyond the fee payer
+pub fn export_audit_page(page: u8) -> Instruction {
+    build(
+        accounts::ExportAuditPage {
+            global_config: pda::global_config().0,
+            house_vault: pda::house_vault().0,
+            pause_config: pda::pause_config().0,
+            global_stats: pda::global_stats().0,
+            game_limits: pda::game_limits().0,
+            game_programs: pda::game_programs().0,
+            promo_pool: pda::promo_pool().0,
+            compliance_config: pda::compliance_config().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::ExportAuditPage { page },
+    )
+}
+
+pub fn change_authority(
+    authority: &Pubkey,
+    new_multisig: Option<Pubkey>,
+    new_admin: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::ChangeAuthority {
+            house_vault: pda::house_vault().0,
+            authority: *authority,
+        },
+        instruction::ChangeAuthority { new_multisig, new_admin },
+    )
+}
+
+/// Permissionless: forfeit an expired bonus (or close a cleared one) on `vault`
+pub fn forfeit_bonus(vault: &Pubkey) -> Instruction {
+    build(
+        accounts::ForfeitBonus {
+            vault: *vault,
+            bonus: pda::deposit_bonus(vault).0,
+            promo_pool: pda::promo_pool().0,
+        },
+        instruction::ForfeitBonus {},
+    )
+}
+
+/// Permissionless: pay out `vault`'s loss-back balance once its window has closed
+pub fn claim_loss_back(vault: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimLossBack {
+            vault: *vault,
+            loss_back: pda::loss_back(vault).0,
+            promo_pool: pda::promo_pool().0,
+        },
+        instruction::ClaimLossBack {},
+    )
+}
+
+/// Pay the due tranches of a streamed win; the last one refunds the schedule's rent to `rent_payer`
+pub fn claim_payout_tranches(vault: &Pubkey, bet_id_hash: &[u8; 16], rent_payer: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimPayoutTranches {
+            vault: *vault,
+            house_vault: pda::house_vault().0,
+            payout_schedule: pda::payout_schedule(vault, bet_id_hash).0,
+            rent_payer: *rent_payer,
+            global_config: pda::global_config().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::ClaimPayoutTranches {},
+    )
+}
+
+/// Close a bet receipt; `closer` is the vault owner, or anyone once the retention period has passed
+pub fn close_bet_receipt(closer: &Pubkey, vault: &Pubkey, bet_id_hash: &[u8; 16]) -> Instruction {
+    build(
+        accounts::CloseBetReceipt {
+            vault: *vault,
+            bet_receipt: pda::bet_receipt(vault, bet_id_hash).0,
+            closer: *closer,
+        },
+        instruction::CloseBetReceipt {},
+    )
+}
+
+/// Close the receipts of `(vault, bet_id_hash)` once past the dispute window, refunding each vault
+pub fn sweep_bet_receipts(receipts: &[(Pubkey, [u8; 16])]) -> Instruction {
+    let mut ix = build(
+        accounts::SweepBetReceipts { dispute_config: pda::dispute_config().0 },
+        instruction::SweepBetReceipts {},
+    );
+    for (vault, bet_id_hash) in receipts {
+        ix.accounts.push(AccountMeta::new(pda::bet_receipt(vault, bet_id_hash).0, false));
+        ix.accounts.push(AccountMeta::new(*vault, false));
+    }
+    ix
+}
+
+/// Admin: create the dispute config; a `window_secs` of 0 keeps disputes closed
+pub fn initialize_dispute_config(authority: &Pubkey, window_secs: i64) -> Instruction {
+    build(
+        accounts::InitializeDisputeConfig {
+            dispute_config: pda::dispute_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeDisputeConfig { window_secs },
+    )
+}
+
+/// Multisig: change how long after settlement a bet can be challenged
+pub fn set_dispute_window(authority: &Pubkey, window_secs: i64) -> Instruction {
+    build(
+        accounts::SetDisputeConfig { dispute_config: pda::dispute_config().0, authority: *authority },
+        instruction::SetDisputeWindow { window_secs },
+    )
+}
+
+/// Admin: create the upgrade governance account, requiring `min_delay_slots` of notice per upgrade
+pub fn initialize_upgrade_governance(authority: &Pubkey, min_delay_slots: u64) -> Instruction {
+    build(
+        accounts::InitializeUpgradeGovernance {
+            upgrade_governance: pda::upgrade_governance().0,
+            program_data: pda::program_data().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeUpgradeGovernance { min_delay_slots },
+    )
+}
+
+/// Multisig: change the notice later upgrade announcements must give
+pub fn set_upgrade_delay(authority: &Pubkey, min_delay_slots: u64) -> Instruction {
+    build(
+        accounts::SetUpgradeGovernance { upgrade_governance: pda::upgrade_governance().0, authority: *authority },
+        instruction::SetUpgradeDelay { min_delay_slots },
+    )
+}
+
+/// Current upgrade authority: hand the program's upgrade authority to `multisig`
+pub fn transfer_upgrade_authority(authority: &Pubkey, multisig: &Pubkey) -> Instruction {
+    build(
+        accounts::TransferUpgradeAuthority {
+            program_data: pda::program_data().0,
+            authority: *authority,
+            new_authority: *multisig,
+            bpf_loader_upgradeable: bpf_loader_upgradeable::ID,
+        },
+        instruction::TransferUpgradeAuthority {},
+    )
+}
+
+/// Multisig: announce the upgrade in `buffer`; `buffer_hash` comes from [`crate::upgrade_buffer_hash`]
+pub fn announce_upgrade(
+    authority: &Pubkey,
+    buffer: &Pubkey,
+    buffer_hash: [u8; 32],
+    earliest_upgrade_slot: u64,
+) -> Instruction {
+    build(
+        accounts::AnnounceUpgrade {
+            upgrade_governance: pda::upgrade_governance().0,
+            buffer: *buffer,
+            authority: *authority,
+            global_config: pda::global_config().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::AnnounceUpgrade { buffer_hash, earliest_upgrade_slot },
+    )
+}
+
+/// Multisig: withdraw the pending upgrade announcement
+pub fn cancel_upgrade(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::SetUpgradeGovernance { upgrade_governance: pda::upgrade_governance().0, authority: *authority },
+        instruction::CancelUpgrade {},
+    )
+}
+
+/// Anyone: record the program's latest deployment against the pending announcement
+pub fn record_upgrade() -> Instruction {
+    build(
+        accounts::RecordUpgrade {
+            upgrade_governance: pda::upgrade_governance().0,
+            program_data: pda::program_data().0,
+            global_config: pda::global_config().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::RecordUpgrade {},
+    )
+}
+
+/// Admin: create the health monitor, expecting a heartbeat from `operator` every `interval_secs`
+pub fn initialize_health_monitor(authority: &Pubkey, operator: &Pubkey, interval_secs: i64) -> Instruction {
+    build(
+        accounts::InitializeHealthMonitor {
+            health_monitor: pda::health_monitor().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeHealthMonitor { operator: *operator, interval_secs },
+    )
+}
+
+/// Multisig: change the heartbeat operator or interval
+pub fn set_heartbeat_config(authority: &Pubkey, operator: &Pubkey, interval_secs: i64) -> Instruction {
+    build(
+        accounts::SetHealthMonitor { health_monitor: pda::health_monitor().0, authority: *authority },
+        instruction::SetHeartbeatConfig { operator: *operator, interval_secs },
+    )
+}
+
+/// Operator heartbeat, also checking the house covers its open exposure
+pub fn operator_heartbeat(operator: &Pubkey) -> Instruction {
+    build(
+        accounts::OperatorHeartbeat {
+            health_monitor: pda::health_monitor().0,
+            house_vault: pda::house_vault().0,
+            operator: *operator,
+        },
+        instruction::OperatorHeartbeat {},
+    )
+}
+
+/// Flag the program degraded after a missed heartbeat or failed solvency check
+pub fn check_health() -> Instruction {
+    build(
+        accounts::CheckHealth { health_monitor: pda::health_monitor().0, house_vault: pda::house_vault().0 },
+        instruction::CheckHealth {},
+    )
+}
+
+/// Challenge `owner`'s bet `bet_id`, which must have been settled with a receipt
+pub fn challenge_bet(owner: &Pubkey, bet_id: &str) -> Instruction {
+    let vault = pda::vault(owner).0;
+    let bet_receipt = pda::bet_receipt(&vault, &bet_id_hash(bet_id)).0;
+    build(
+        accounts::ChallengeBet {
+            vault,
+            bet_receipt,
+            dispute_config: pda::dispute_config().0,
+            dispute: pda::dispute(&bet_receipt).0,
+            house_vault: pda::house_vault().0,
+            owner: *owner,
+            pause_config: pda::pause_config().0,
+            global_config: pda::global_config().0,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::ChallengeBet { bet_id: bet_id.to_string() },
+    )
+}
+
+/// Multisig: rule on `owner`'s challenge of `bet_id_hash`, paying `award` of the frozen stake to their vault
+pub fn resolve_dispute(authority: &Pubkey, owner: &Pubkey, bet_id_hash: &[u8; 16], award: u64) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::ResolveDispute {
+            dispute: pda::dispute(&pda::bet_receipt(&vault, bet_id_hash).0).0,
+            vault,
+            owner: *owner,
+            house_vault: pda::house_vault().0,
+            authority: *authority,
+            global_config: pda::global_config().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::ResolveDispute { award },
+    )
+}
+
+/// Admin: create the insurance fund with its premium and refund rates in basis points
+pub fn initialize_insurance_fund(authority: &Pubkey, premium_bps: u16, refund_bps: u16) -> Instruction {
+    build(
+        accounts::InitializeInsuranceFund {
+            insurance_fund: pda::insurance_fund().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeInsuranceFund { premium_bps, refund_bps },
+    )
+}
+
+/// Admin: create the pity boost on gems after `min_losses` straight losses
+pub fn initialize_pity_config(authority: &Pubkey, min_losses: u16, multiplier: u16) -> Instruction {
+    build(
+        accounts::InitializePityConfig {
+            pity_config: pda::pity_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializePityConfig { min_losses, multiplier },
+    )
+}
+
+/// Admin: change the pity boost parameters
+pub fn set_pity_config(authority: &Pubkey, min_losses: u16, multiplier: u16) -> Instruction {
+    build(
+        accounts::SetPityConfig { pity_config: pda::pity_config().0, authority: *authority },
+        instruction::SetPityConfig { min_losses, multiplier },
+    )
+}
+
+/// Multisig: change the insurance rates for bets placed from now on
+pub fn set_insurance_terms(authority: &Pubkey, premium_bps: u16, refund_bps: u16) -> Instruction {
+    build(
+        accounts::SetInsuranceTerms { insurance_fund: pda::insurance_fund().0, authority: *authority },
+        instruction::SetInsuranceTerms { premium_bps, refund_bps },
+    )
+}
+
+/// Top up the insurance fund from `funder`
+pub fn fund_insurance_fund(funder: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::FundInsuranceFund {
+            insurance_fund: pda::insurance_fund().0,
+            funder: *funder,
+            system_program: system_program::ID,
+        },
+        instruction::FundInsuranceFund { amount },
+    )
+}
+
+/// Lock `order`'s stake at its odds; `authority` pays the bet's rent and must settle it
+pub fn place_pending_bet(authority: &Pubkey, order: &PendingBetOrder) -> Instruction {
+    let vault = pda::vault(&order.owner).0;
+    build(
+        accounts::PlacePendingBet {
+            vault,
+            pending_bet: pda::pending_bet(&vault, &bet_id_hash(&order.bet_id)).0,
+            house_vault: pda::house_vault().0,
+            authority: *authority,
+            game_limits: pda::game_limits().0,
+            pause_config: pda::pause_config().0,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            game_programs: None,
+            insurance_fund: (order.insurance_premium > 0).then(|| pda::insurance_fund().0),
+            global_config: pda::global_config().0,
+            system_program: system_program::ID,
+        },
+        instruction::PlacePendingBet {
+            bet_id: order.bet_id.clone(),
+            terms: PendingBetTerms {
+                game_id: order.game_id,
+                event_id: order.event_id,
+                odds: order.odds,
+                stake: order.stake,
+                expiry: order.expiry,
+                expires_at: order.expires_at,
+                insurance_premium: order.insurance_premium,
+            },
+        },
+    )
+}
+
+/// Settle `owner`'s pending bet `bet_id_hash` for `payout`, refunding the rent to `authority`, which placed it
+///
+/// Set `insured` for bets placed with an insurance premium.
+pub fn settle_pending_bet(
+    authority: &Pubkey,
+    owner: &Pubkey,
+    bet_id_hash: &[u8; 16],
+    payout: u64,
+    insured: bool,
+) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::SettlePendingBet {
+            pending_bet: pda::pending_bet(&vault, bet_id_hash).0,
+            vault,
+            bonus: pda::deposit_bonus(&vault).0,
+            loss_back_promo: pda::loss_back_promo().0,
+            loss_back: pda::loss_back(&vault).0,
+            payer: *authority,
+            house_vault: pda::house_vault().0,
+            global_stats: pda::global_stats().0,
+            authority: *authority,
+            pause_config: pda::pause_config().0,
+            game_programs: None,
+            global_config: pda::global_config().0,
+            insurance_fund: insured.then(|| pda::insurance_fund().0),
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::SettlePendingBet { payout },
+    )
+}
+
+/// Cash out `owner`'s pending bet `bet_id` early for `cashout_value`, refunding the rent to `authority`
+pub fn cashout_bet(authority: &Pubkey, owner: &Pubkey, bet_id: &str, cashout_value: u64, insured: bool) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::CashoutBet {
+            pending_bet: pda::pending_bet(&vault, &bet_id_hash(bet_id)).0,
+            vault,
+            bonus: pda::deposit_bonus(&vault).0,
+            loss_back_promo: pda::loss_back_promo().0,
+            loss_back: pda::loss_back(&vault).0,
+            payer: *authority,
+            house_vault: pda::house_vault().0,
+            global_stats: pda::global_stats().0,
+            authority: *authority,
+            pause_config: pda::pause_config().0,
+            game_programs: None,
+            global_config: pda::global_config().0,
+            insurance_fund: insured.then(|| pda::insurance_fund().0),
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::CashoutBet { bet_id: bet_id.to_string(), cashout_value },
+    )
+}
+
+/// Release the stake of `owner`'s pending bet left unsettled past the settle window; anyone may send it
+pub fn expire_pending_bet(payer: &Pubkey, owner: &Pubkey, bet_id_hash: &[u8; 16], insured: bool) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::ExpirePendingBet {
+            pending_bet: pda::pending_bet(&vault, bet_id_hash).0,
+            vault,
+            payer: *payer,
+            house_vault: pda::house_vault().0,
+            insurance_fund: insured.then(|| pda::insurance_fund().0),
+        },
+        instruction::ExpirePendingBet {},
+    )
+}
+
+/// Admin: open crash round `round_id`, committing to [`crate::crash_commitment`] of its seed
+pub fn open_crash_round(
+    authority: &Pubkey,
+    round_id: u64,
+    game_id: u64,
+    commitment: [u8; 32],
+    betting_closes_at: i64,
+) -> Instruction {
+    build(
+        accounts::OpenCrashRound {
+            crash_round: pda::crash_round(round_id).0,
+            authority: *authority,
+            game_programs: None,
+            system_program: system_program::ID,
+        },
+        instruction::OpenCrashRound { round_id, game_id, commitment, betting_closes_at },
+    )
+}
+
+/// Stake on crash round `round_id` from `owner`'s vault, cashing out at `cashout_at` (100 = 1x)
+pub fn place_crash_bet(owner: &Pubkey, round_id: u64, stake: u64, cashout_at: u32) -> Instruction {
+    let vault = pda::vault(owner).0;
+    let crash_round = pda::crash_round(round_id).0;
+    build(
+        accounts::PlaceCrashBet {
+            vault,
+            owner: *owner,
+            crash_round,
+            crash_bet: pda::crash_bet(&crash_round, &vault).0,
+            house_vault: pda::house_vault().0,
+            game_limits: pda::game_limits().0,
+            pause_config: pda::pause_config().0,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            global_config: pda::global_config().0,
+            system_program: system_program::ID,
+        },
+        instruction::PlaceCrashBet { stake, cashout_at },
+    )
+}
+
+/// Reveal crash round `round_id`'s seed once betting has closed; signed by the authority that opened it
+pub fn reveal_crash_round(authority: &Pubkey, round_id: u64, seed: [u8; 32]) -> Instruction {
+    build(
+        accounts::RevealCrashRound { crash_round: pda::crash_round(round_id).0, authority: *authority },
+        instruction::RevealCrashRound { seed },
+    )
+}
+
+/// Settle `owner`'s bet on crash round `round_id`; any fee payer may send it
+pub fn settle_crash_bet(owner: &Pubkey, round_id: u64) -> Instruction {
+    let vault = pda::vault(owner).0;
+    let crash_round = pda::crash_round(round_id).0;
+    build(
+        accounts::SettleCrashBet {
+            crash_bet: pda::crash_bet(&crash_round, &vault).0,
+            crash_round,
+            vault,
+            bonus: pda::deposit_bonus(&vault).0,
+            loss_back_promo: pda::loss_back_promo().0,
+            loss_back: pda::loss_back(&vault).0,
+            owner: *owner,
+            house_vault: pda::house_vault().0,
+            global_stats: pda::global_stats().0,
+            global_config: pda::global_config().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::SettleCrashBet {},
+    )
+}
+
+/// Close crash round `round_id` once all its bets are settled, refunding the rent to `authority`
+pub fn close_crash_round(authority: &Pubkey, round_id: u64) -> Instruction {
+    build(
+        accounts::CloseCrashRound { crash_round: pda::crash_round(round_id).0, authority: *authority },
+        instruction::CloseCrashRound {},
+    )
+}
+
+/// Admin: create the PvP config with the house rake in basis points
+pub fn initialize_pvp_config(authority: &Pubkey, rake_bps: u16) -> Instruction {
+    build(
+        accounts::InitializePvpConfig {
+            pvp_config: pda::pvp_config().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializePvpConfig { rake_bps },
+    )
+}
+
+/// Multisig: change the rake charged on PvP bets created from now on
+pub fn set_pvp_rake(authority: &Pubkey, rake_bps: u16) -> Instruction {
+    build(
+        accounts::SetPvpConfig { pvp_config: pda::pvp_config().0, authority: *authority },
+        instruction::SetPvpRake { rake_bps },
+    )
+}
+
+/// Admin: create the queue listing open PvP challenges
+pub fn initialize_pvp_queue(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializePvpQueue {
+            pvp_queue: pda::pvp_queue().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializePvpQueue {},
+    )
+}
+
+/// Challenge `opponent`'s vault, or anyone through the PvP queue with `None`, to bet `stake` on `game_id`
+///
+/// The stake is escrowed from `owner`'s vault. Open challenges need an
+/// `expires_at` in the future; direct ones may pass 0 for no expiry.
+pub fn create_pvp_bet(
+    owner: &Pubkey,
+    opponent: Option<&Pubkey>,
+    bet_id: &str,
+    game_id: u64,
+    stake: u64,
+    expires_at: i64,
+) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::CreatePvpBet {
+            vault,
+            owner: *owner,
+            pvp_bet: pda::pvp_bet(&vault, &bet_id_hash(bet_id)).0,
+            pvp_config: pda::pvp_config().0,
+            pvp_queue: opponent.is_none().then(|| pda::pvp_queue().0),
+            pause_config: pda::pause_config().0,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            global_config: pda::global_config().0,
+            system_program: system_program::ID,
+        },
+        instruction::CreatePvpBet {
+            bet_id: bet_id.to_string(),
+            opponent_vault: opponent.map(|opponent| pda::vault(opponent).0),
+            game_id,
+            stake,
+            expires_at,
+        },
+    )
+}
+
+/// Accept `creator`'s PvP bet `bet_id_hash` as `owner`, escrowing the same stake
+pub fn accept_pvp_bet(owner: &Pubkey, creator: &Pubkey, bet_id_hash: &[u8; 16]) -> Instruction {
+    build(
+        accounts::AcceptPvpBet {
+            pvp_bet: pda::pvp_bet(&pda::vault(creator).0, bet_id_hash).0,
+            pvp_queue: Some(pda::pvp_queue().0),
+            vault: pda::vault(owner).0,
+            owner: *owner,
+            pause_config: pda::pause_config().0,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+        },
+        instruction::AcceptPvpBet {},
+    )
+}
+
+/// Cancel `creator`'s unaccepted PvP bet, returning the stake and rent
+pub fn cancel_pvp_bet(creator: &Pubkey, bet_id_hash: &[u8; 16]) -> Instruction {
+    let vault = pda::vault(creator).0;
+    build(
+        accounts::CancelPvpBet {
+            pvp_bet: pda::pvp_bet(&vault, bet_id_hash).0,
+            vault,
+            creator: *creator,
+            pvp_queue: Some(pda::pvp_queue().0),
+        },
+        instruction::CancelPvpBet {},
+    )
+}
+
+/// Refund `creator`'s unaccepted PvP bet after its expiry; any fee payer may send it
+pub fn expire_pvp_bet(creator: &Pubkey, bet_id_hash: &[u8; 16]) -> Instruction {
+    let vault = pda::vault(creator).0;
+    build(
+        accounts::ExpirePvpBet {
+            pvp_bet: pda::pvp_bet(&vault, bet_id_hash).0,
+            vault,
+            creator: *creator,
+            pvp_queue: Some(pda::pvp_queue().0),
+        },
+        instruction::ExpirePvpBet {},
+    )
+}
+
+/// Settle the PvP bet between `creator` and `opponent` for `winner`'s vault, or as a draw with `None`
+pub fn settle_pvp_bet(
+    authority: &Pubkey,
+    creator: &Pubkey,
+    opponent: &Pubkey,
+    bet_id_hash: &[u8; 16],
+    winner: Option<&Pubkey>,
+) -> Instruction {
+    let (creator_vault, opponent_vault) = (pda::vault(creator).0, pda::vault(opponent).0);
+    build(
+        accounts::SettlePvpBet {
+            pvp_bet: pda::pvp_bet(&creator_vault, bet_id_hash).0,
+            creator_vault,
+            opponent_vault,
+            creator_bonus: pda::deposit_bonus(&creator_vault).0,
+            opponent_bonus: pda::deposit_bonus(&opponent_vault).0,
+            loss_back_promo: pda::loss_back_promo().0,
+            creator_loss_back: pda::loss_back(&creator_vault).0,
+            opponent_loss_back: pda::loss_back(&opponent_vault).0,
+            creator: *creator,
+            house_vault: pda::house_vault().0,
+            global_stats: pda::global_stats().0,
+            authority: *authority,
+            pause_config: pda::pause_config().0,
+            game_programs: None,
+            global_config: pda::global_config().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::SettlePvpBet { winner: winner.map(|owner| pda::vault(owner).0) },
+    )
+}
+
+/// Writable seat vaults of `owners`, in order, for the table round instructions
+fn seat_vaults(owners: impl Iterator<Item = Pubkey>) -> impl Iterator<Item = AccountMeta> {
+    owners.map(|owner| AccountMeta::new(pda::vault(&owner).0, false))
+}
+
+/// Open table round `round_id`, escrowing each `(owner, stake)` seat; `authority` pays the rent
+pub fn open_table_round(authority: &Pubkey, round_id: u64, game_id: u64, seats: &[(Pubkey, u64)]) -> Instruction {
+    let mut ix = build(
+        accounts::OpenTableRound {
+            table_round: pda::table_round(round_id).0,
+            authority: *authority,
+            pvp_config: pda::pvp_config().0,
+            pause_config: pda::pause_config().0,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            game_programs: None,
+            system_program: system_program::ID,
+        },
+        instruction::OpenTableRound { round_id, game_id, stakes: seats.iter().map(|(_, stake)| *stake).collect() },
+    );
+    ix.accounts.extend(seat_vaults(seats.iter().map(|(owner, _)| *owner)));
+    ix
+}
+
+/// Escrow each `(owner, raise)` on top of that seat's stake in table round `round_id`
+pub fn raise_table_round(authority: &Pubkey, round_id: u64, raises: &[(Pubkey, u64)]) -> Instruction {
+    let mut ix = build(
+        accounts::RaiseTableRound {
+            table_round: pda::table_round(round_id).0,
+            authority: *authority,
+            pause_config: pda::pause_config().0,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            game_programs: None,
+        },
+        instruction::RaiseTableRound { raises: raises.iter().map(|(_, raise)| *raise).collect() },
+    );
+    ix.accounts.extend(seat_vaults(raises.iter().map(|(owner, _)| *owner)));
+    ix
+}
+
+/// Pay each `(owner, payout)` seat of table round `round_id`, in seat order, refunding the rent to `authority`
+///
+/// The seat vaults, then their deposit bonuses, then their loss-back records, are appended as remaining accounts.
+pub fn settle_table_round(authority: &Pubkey, round_id: u64, payouts: &[(Pubkey, u64)]) -> Instruction {
+    let mut ix = build(
+        accounts::SettleTableRound {
+            table_round: pda::table_round(round_id).0,
+            payer: *authority,
+            house_vault: pda::house_vault().0,
+            global_stats: pda::global_stats().0,
+            authority: *authority,
+            pause_config: pda::pause_config().0,
+            game_programs: None,
+            global_config: pda::global_config().0,
+            loss_back_promo: pda::loss_back_promo().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::SettleTableRound { payouts: payouts.iter().map(|(_, payout)| *payout).collect() },
+    );
+    ix.accounts.extend(seat_vaults(payouts.iter().map(|(owner, _)| *owner)));
+    ix.accounts.extend(
+        payouts
+            .iter()
+            .map(|(owner, _)| AccountMeta::new(pda::deposit_bonus(&pda::vault(owner).0).0, false)),
+    );
+    ix.accounts.extend(
+        payouts
+            .iter()
+            .map(|(owner, _)| AccountMeta::new(pda::loss_back(&pda::vault(owner).0).0, false)),
+    );
+    ix
+}
+
+/// Refund every seat of table round `round_id`; `owners` in seat order, rent to `payer`, which opened it
+pub fn cancel_table_round(authority: &Pubkey, payer: &Pubkey, round_id: u64, owners: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::CancelTableRound {
+            table_round: pda::table_round(round_id).0,
+            payer: *payer,
+            authority: *authority,
+            game_programs: None,
+        },
+        instruction::CancelTableRound {},
+    );
+    ix.accounts.extend(seat_vaults(owners.iter().copied()));
+    ix
+}
+
+/// Credit `spins` free spins worth `spin_value` each to `owner`'s vault
+pub fn credit_free_spins(authority: &Pubkey, owner: &Pubkey, spins: u32, spin_value: u64) -> Instruction {
+    build(
+        accounts::CreditFreeSpins {
+            vault: pda::vault(owner).0,
+            authority: *authority,
+            pause_config: pda::pause_config().0,
+            game_programs: None,
+        },
+        instruction::CreditFreeSpins { spins, spin_value },
+    )
+}
+
+/// Settle one of `owner`'s free spins for `payout`, paid by the house
+pub fn settle_free_spin(authority: &Pubkey, owner: &Pubkey, bet_id: String, game_id: u64, payout: u64) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::SettleFreeSpin {
+            vault,
+            bonus: pda::deposit_bonus(&vault).0,
+            house_vault: pda::house_vault().0,
+            authority: *authority,
+            pause_config: pda::pause_config().0,
+            global_config: pda::global_config().0,
+            global_stats: pda::global_stats().0,
+            game_limits: pda::game_limits().0,
+            instructions: sysvar::instructions::ID,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            game_config: pda::game_config(game_id).0,
+            game_programs: None,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::SettleFreeSpin { bet_id, game_id, payout },
+    )
+}
+
+/// Mirror the bets of `leader`'s vault from `owner`'s, staking at most `max_stake` per bet
+pub fn follow_player(owner: &Pubkey, leader: &Pubkey, max_stake: u64) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::FollowPlayer {
+            vault,
+            leader_vault: pda::vault(leader).0,
+            copy_follow: pda::copy_follow(&vault).0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::FollowPlayer { max_stake },
+    )
+}
+
+/// Stop mirroring from `owner`'s vault, refunding the follow account's rent
+pub fn unfollow_player(owner: &Pubkey) -> Instruction {
+    let vault = pda::vault(owner).0;
+    build(
+        accounts::UnfollowPlayer { vault, copy_follow: pda::copy_follow(&vault).0, owner: *owner },
+        instruction::UnfollowPlayer {},
+    )
+}
+
+/// Mirror the leader's settled `bet` into the vaults of `followers`, owners that follow `bet.owner`
+///
+/// Each follower's vault, follow account, deposit bonus and loss-back record are appended as remaining accounts.
+pub fn settle_mirrored_bets(authority: &Pubkey, bet: &Settlement, followers: &[Pubkey]) -> Instruction {
+    let vaults: Vec<Pubkey> = followers.iter().map(|owner| pda::vault(owner).0).collect();
+    let mut ix = build(
+        accounts::SettleMirroredBets {
+            leader_vault: pda::vault(&bet.owner).0,
+            house_vault: pda::house_vault().0,
+            authority: *authority,
+            pause_config: pda::pause_config().0,
+            global_config: pda::global_config().0,
+            global_stats: pda::global_stats().0,
+            game_limits: pda::game_limits().0,
+            instructions: sysvar::instructions::ID,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            game_config: pda::game_config(bet.game_id).0,
+            loss_back_promo: pda::loss_back_promo().0,
+            game_programs: None,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::SettleMirroredBets {
+            bet_id: bet.bet_id.clone(),
+            game_id: bet.game_id,
+            stake: bet.stake,
+            payout: bet.payout,
+            followers: vaults.clone(),
+        },
+    );
+    for vault in vaults {
+        ix.accounts.push(AccountMeta::new(vault, false));
+        ix.accounts.push(AccountMeta::new_readonly(pda::copy_follow(&vault).0, false));
+        ix.accounts.push(AccountMeta::new(pda::deposit_bonus(&vault).0, false));
+        ix.accounts.push(AccountMeta::new(pda::loss_back(&vault).0, false));
+    }
+    ix
+}
+
+/// Admin: create bracket `tournament_id` seeding the vaults of `owners` in order, paying `prizes` by standing
+pub fn create_tournament(
+    authority: &Pubkey,
+    tournament_id: u64,
+    game_id: u64,
+    owners: &[Pubkey],
+    prizes: Vec<u64>,
+) -> Instruction {
+    let mut ix = build(
+        accounts::CreateTournament {
+            tournament: pda::tournament(tournament_id).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::CreateTournament { tournament_id, game_id, prizes },
+    );
+    ix.accounts.extend(owners.iter().map(|owner| AccountMeta::new_readonly(pda::vault(owner).0, false)));
+    ix
+}
+
+/// Post that the vault of `winner` won match `match_index` of the bracket's current round
+pub fn post_match_result(authority: &Pubkey, tournament_id: u64, match_index: u16, winner: &Pubkey) -> Instruction {
+    build(
+        accounts::PostMatchResult {
+            tournament: pda::tournament(tournament_id).0,
+            authority: *authority,
+            pause_config: pda::pause_config().0,
+            global_config: pda::global_config().0,
+            game_programs: None,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::PostMatchResult { match_index, winner: pda::vault(winner).0 },
+    )
+}
+
+/// Move bracket `tournament_id` to its next round
+pub fn advance_tournament_round(authority: &Pubkey, tournament_id: u64) -> Instruction {
+    build(
+        accounts::AdvanceTournamentRound {
+            tournament: pda::tournament(tournament_id).0,
+            authority: *authority,
+            game_programs: None,
+        },
+        instruction::AdvanceTournamentRound {},
+    )
+}
+
+/// Pay a completed bracket's prizes; `owners` in seeding order, rent to `payer`, the admin that created it
+pub fn distribute_tournament_prizes(payer: &Pubkey, tournament_id: u64, owners: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::DistributeTournamentPrizes {
+            tournament: pda::tournament(tournament_id).0,
+            payer: *payer,
+            promo_pool: pda::promo_pool().0,
+            global_config: pda::global_config().0,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::DistributeTournamentPrizes {},
+    );
+    ix.accounts.extend(owners.iter().map(|owner| AccountMeta::new(pda::vault(owner).0, false)));
+    ix
+}
+
+/// Multisig: create the tiered jackpot of `game_id`, drawn with randomness from `vrf_authority`
+pub fn initialize_jackpot(authority: &Pubkey, game_id: u64, vrf_authority: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeJackpot {
+            jackpot: pda::jackpot(game_id).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeJackpot { game_id, vrf_authority },
+    )
+}
+
+/// Multisig: configure jackpot `tier` (0 mini, 1 major, 2 grand) of `game_id`
+pub fn set_jackpot_tier(
+    authority: &Pubkey,
+    game_id: u64,
+    tier: u8,
+    contribution_bps: u16,
+    seed: u64,
+    odds: u32,
+) -> Instruction {
+    build(
+        accounts::SetJackpotTier {
+            jackpot: pda::jackpot(game_id).0,
+            house_vault: pda::house_vault().0,
+            authority: *authority,
+        },
+        instruction::SetJackpotTier { tier, contribution_bps, seed, odds },
+    )
+}
+
+/// Pay jackpot `tier` of `game_id` to `owner`'s vault for `bet_id`, whose receipt and fulfilled ORAO
+/// randomness must exist; the settler and VRF authority both sign
+pub fn award_jackpot(
+    authority: &Pubkey,
+    vrf_authority: &Pubkey,
+    owner: &Pubkey,
+    game_id: u64,
+    tier: u8,
+    bet_id: String,
+) -> Instruction {
+    let (jackpot, vault) = (pda::jackpot(game_id).0, pda::vault(owner).0);
+    let bet_hash = bet_id_hash(&bet_id);
+    build(
+        accounts::AwardJackpot {
+            jackpot,
+            vault,
+            house_vault: pda::house_vault().0,
+            authority: *authority,
+            vrf_authority: *vrf_authority,
+            bet_receipt: pda::bet_receipt(&vault, &bet_hash).0,
+            randomness: pda::jackpot_randomness(&Jackpot::vrf_seed(&jackpot, &bet_hash, tier)).0,
+            pause_config: pda::pause_config().0,
+            global_config: pda::global_config().0,
+            blacklist: pda::blacklist().0,
+            compliance_config: pda::compliance_config().0,
+            game_programs: None,
+            event_authority: pda::event_authority().0,
+            program: ID,
+        },
+        instruction::AwardJackpot { game_id, tier, bet_id },
+    )
+}
+
+/// Builders for the QA instructions of `devnet` program builds
+#[cfg(feature = "devnet")]
+pub mod devnet {
+    use super::*;
+    use smart_vault_v2::devnet::{accounts, instruction};
+
+    /// Credit `amount` lamports from the house into `owner`'s vault; any signer may call it
+    pub fn faucet_deposit(caller: &Pubkey, owner: &Pubkey, amount: u64) -> Instruction {
+        build(
+            accounts::FaucetDeposit {
+                vault: pda::vault(owner).0,
+                house_vault: pda::house_vault().0,
+                caller: *caller,
+            },
+            instruction::FaucetDeposit { amount },
+        )
+    }
+
+    pub fn force_settle(authority: &Pubkey, owner: &Pubkey, stake: u64, payout: u64) -> Instruction {
+        build(
+            accounts::ForceSettle {
+                vault: pda::vault(owner).0,
+                house_vault: pda::house_vault().0,
+                authority: *authority,
+            },
+            instruction::ForceSettle { stake, payout },
+        )
+    }
+
+    pub fn reset_config(authority: &Pubkey, default_max_multiplier: u32, max_unstaked_payout: u64) -> Instruction {
+        build(
+            accounts::ResetConfig {
+                pause_config: pda::pause_config().0,
+                game_limits: pda::game_limits().0,
+                authority: *authority,
+            },
+            instruction::ResetConfig { default_max_multiplier, max_unstaked_payout },
+        )
+    }
+}
@@ -0,0 +1,106 @@
+//! Client SDK for the Smart Vault V2 program
+//!
+//! * [`pda`] – address derivation for every program PDA
+//! * [`constants`] – PDA seeds, account sizes and client defaults
+//! * [`instructions`] – typed instruction builders
+//! * [`lookup_table`] – address lookup tables that shrink settlement transactions
+//! * [`durable_nonce`] – nonce-backed transactions for slow multisig signing
+//! * [`sns`] – `.sol` name resolution to owners and vaults
+//! * [`events`] – decoding of emitted events
+//! * [`decode_account`] – account deserialization with discriminator checks
+//! * [`decode_return_data`] – decoding of view-instruction results
+//! * [`needs_migration`] – layout check before sending instructions to a versioned account
+//! * [`upgrade_buffer_hash`] – the hash an upgrade announcement commits to
+
+pub mod durable_nonce;
+pub mod events;
+pub mod instructions;
+pub mod lookup_table;
+pub mod pda;
+pub mod sns;
+
+use anchor_lang::solana_program::bpf_loader_upgradeable::UpgradeableLoaderState;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize};
+
+pub use smart_vault_v2::constants;
+
+pub use smart_vault_v2::{
+    crash_point, Announcement, BetReceipt, Blacklist, BridgeClaim, BridgeConfig, BridgeEmitter, ComplianceConfig,
+    CopyFollow, CrashBet, CrashRound, DepositBonus, DisputeConfig, GameConfig, GameLimits, GameProgram, GamePrograms,
+    GemInventory, GlobalConfig, GlobalStats, HealthMonitor, HouseInfo, HouseVault, InsuranceFund, Jackpot, JackpotTier,
+    KycAttestation, LossBack, LossBackPromo, PauseConfig, PayoutSchedule, PendingBet, PityConfig, PromoPool, PvpBet,
+    PvpConfig, PvpQueue, RebateTier, RegionRule, RgConfig, SideBet, StakingConfig, StatsSnapshot, StatsSnapshots,
+    TableRound, TableSeat, TokenStake, Tournament, TrophyConfig, UpgradeGovernance, UserVault, VaultInfo, VaultRegistry,
+    VaultRegistryEntry, VaultRegistryPage, Versioned, WithdrawGuard, WithdrawalQueue, WithdrawalRequest,
+    ID as PROGRAM_ID, AUDIT_PAGE_COUNT, BATCH_SETTLE_BASE_CU, BATCH_SETTLE_ITEM_CU, BRIDGE_DEPOSIT_PAYLOAD_ID,
+    CRASH_INSTANT_ODDS, CRASH_REVEAL_TIMEOUT_SECS, GAME_AUTHORITY_SEED, GAME_CATEGORIES, GAME_CATEGORY_LIVE,
+    GAME_CATEGORY_ORIGINALS, GAME_CATEGORY_SLOTS, GLOBAL_CONFIG_VERSION, GLOBAL_STATS_VERSION, JACKPOT_TIERS,
+    MAX_ATTESTORS, MAX_BATCH_CREDIT, MAX_BATCH_SETTLE, MAX_BET_ID_LEN, MAX_BRACKET_ROUNDS, MAX_BRACKET_SIZE,
+    MAX_BRIDGE_EMITTERS, MAX_COMPUTE_UNIT_LIMIT, MAX_GEMS_PER_SETTLEMENT, MAX_MIRROR_FOLLOWERS, MAX_PAYOUT_TRANCHES,
+    MAX_PITY_MULTIPLIER, MAX_PVP_QUEUE_LEN, MAX_PVP_RAKE_BPS, MAX_REBATE_BPS, MAX_REBATE_TIERS, MAX_REGION_RULES,
+    MAX_SIDE_BETS, MAX_TABLE_SEATS, MAX_TROPHY_URI_LEN, ORAO_VRF_PROGRAM_ID, PAUSE_CONFIG_VERSION,
+    PENDING_BET_SETTLE_WINDOW_SECS, RECEIPT_RETENTION_SECS, SPL_TOKEN_PROGRAM_ID, TABLE_ROUND_TIMEOUT_SECS,
+    TRANSFER_WINDOW_SECS, TROPHY_SYMBOL, VAULT_REGISTRY_PAGE_LEN, VAULT_VERSION, VAULT_VERSION_OFFSET,
+    WITHDRAW_GUARD_REMOVAL_DELAY_SECS,
+};
+
+/// Deserialize raw account data (including the 8-byte discriminator) into a program account
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> anchor_lang::Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Decode the return data of a simulated view instruction (e.g. [`VaultInfo`], [`HouseInfo`])
+pub fn decode_return_data<T: AnchorDeserialize>(data: &[u8]) -> std::io::Result<T> {
+    T::try_from_slice(data)
+}
+
+/// Turn a backend bet identifier into one the program accepts
+///
+/// IDs up to [`MAX_BET_ID_LEN`] bytes (UUIDs included) pass through unchanged;
+/// longer ones are replaced by the hex of their [`events::bet_id_hash`].
+pub fn bet_id_for(backend_id: &str) -> String {
+    if backend_id.len() <= MAX_BET_ID_LEN {
+        return backend_id.to_string();
+    }
+    events::bet_id_hash(backend_id).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Whether raw account data predates `T`'s current layout and must go through its `migrate_*` instruction
+pub fn needs_migration<T: Versioned>(data: &[u8]) -> bool {
+    T::stored_version(data) < T::VERSION
+}
+
+/// Whether raw `UserVault` data must go through `migrate_vault` before the program accepts it
+pub fn vault_needs_migration(data: &[u8]) -> bool {
+    needs_migration::<UserVault>(data)
+}
+
+/// Commitment to a crash round's `seed` for `open_crash_round`: keccak of the seed
+///
+/// Pass the same seed to `reveal_crash_round`; [`crash_point`] gives the
+/// multiplier it will reveal.
+pub fn crash_commitment(seed: &[u8; 32]) -> [u8; 32] {
+    anchor_lang::solana_program::keccak::hash(seed).to_bytes()
+}
+
+/// Deposit reference of an off-chain order id: SHA-256 of the id
+///
+/// Accounting systems recompute it from their own order ids to match
+/// `Deposited` events without keeping a mapping.
+pub fn order_reference(order_id: &str) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hash(order_id.as_bytes()).to_bytes()
+}
+
+/// Hash identifying a terms-of-service document for `accept_terms` and `set_terms`: SHA-256 of its bytes
+pub fn terms_hash(document: &[u8]) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hash(document).to_bytes()
+}
+
+/// Hash to announce for an upgrade buffer: SHA-256 of the program bytes after the buffer header
+///
+/// Recompute it from the buffer account to check an `UpgradeAnnounced` event
+/// before the upgrade lands.
+pub fn upgrade_buffer_hash(buffer_data: &[u8]) -> [u8; 32] {
+    let header = UpgradeableLoaderState::size_of_buffer_metadata().min(buffer_data.len());
+    anchor_lang::solana_program::hash::hash(&buffer_data[header..]).to_bytes()
+}
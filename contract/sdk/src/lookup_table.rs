@@ -0,0 +1,67 @@
+//! Address lookup tables for settlement transactions
+//!
+//! A table holding the shared settlement accounts and the busiest vaults lets
+//! `batch_settle` reference each of them with a one-byte index instead of a
+//! 32-byte key, so more settlements fit in one transaction. The table
+//! authority pays for creation and every extension.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::address_lookup_table::instruction as alt;
+use anchor_lang::solana_program::address_lookup_table::state::{AddressLookupTable, LOOKUP_TABLE_MAX_ADDRESSES};
+use anchor_lang::solana_program::address_lookup_table::AddressLookupTableAccount;
+use anchor_lang::solana_program::instruction::{Instruction, InstructionError};
+use anchor_lang::solana_program::{system_program, sysvar};
+
+use crate::pda;
+
+/// Addresses one `extend` instruction carries while staying under the packet size
+pub const MAX_EXTEND_ADDRESSES: usize = 20;
+
+/// Accounts every settlement references, in the order they are added to a new table
+pub fn shared_addresses() -> Vec<Pubkey> {
+    vec![
+        pda::house_vault().0,
+        pda::pause_config().0,
+        pda::global_config().0,
+        pda::global_stats().0,
+        pda::game_limits().0,
+        pda::blacklist().0,
+        pda::event_authority().0,
+        sysvar::instructions::ID,
+        system_program::ID,
+    ]
+}
+
+/// Create a table owned by `authority`; `recent_slot` must be a recent finalized slot
+///
+/// Returns the instruction and the table address derived from the slot.
+pub fn create(authority: &Pubkey, recent_slot: u64) -> (Instruction, Pubkey) {
+    alt::create_lookup_table(*authority, *authority, recent_slot)
+}
+
+/// Append `addresses` to `table`, one instruction per [`MAX_EXTEND_ADDRESSES`]
+pub fn extend(table: &Pubkey, authority: &Pubkey, addresses: &[Pubkey]) -> Vec<Instruction> {
+    addresses
+        .chunks(MAX_EXTEND_ADDRESSES)
+        .map(|chunk| alt::extend_lookup_table(*table, *authority, Some(*authority), chunk.to_vec()))
+        .collect()
+}
+
+/// Decode raw table account data for use when compiling v0 messages
+pub fn decode(key: Pubkey, data: &[u8]) -> Result<AddressLookupTableAccount, InstructionError> {
+    let table = AddressLookupTable::deserialize(data)?;
+    Ok(AddressLookupTableAccount { key, addresses: table.addresses.to_vec() })
+}
+
+/// Vaults of `owners` the table lacks, capped at the room it has left
+pub fn missing_vaults(table: &AddressLookupTableAccount, owners: &[Pubkey]) -> Vec<Pubkey> {
+    let mut missing: Vec<Pubkey> = Vec::new();
+    for owner in owners {
+        let vault = pda::vault(owner).0;
+        if !table.addresses.contains(&vault) && !missing.contains(&vault) {
+            missing.push(vault);
+        }
+    }
+    missing.truncate(LOOKUP_TABLE_MAX_ADDRESSES.saturating_sub(table.addresses.len()));
+    missing
+}
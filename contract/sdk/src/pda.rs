@@ -0,0 +1,282 @@
+//! PDA derivation helpers matching the program's seeds
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+use smart_vault_v2::constants::*;
+use smart_vault_v2::{ID, ORAO_RANDOMNESS_SEED, ORAO_VRF_PROGRAM_ID};
+
+/// `[b"vault", owner]`
+pub fn vault(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, owner.as_ref()], &ID)
+}
+
+/// `[b"house_vault"]`
+pub fn house_vault() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[HOUSE_VAULT_SEED], &ID)
+}
+
+/// `[b"pause_config"]`
+pub fn pause_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PAUSE_CONFIG_SEED], &ID)
+}
+
+/// `[b"global_config"]`
+pub fn global_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &ID)
+}
+
+/// `[b"global_stats"]`
+pub fn global_stats() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_STATS_SEED], &ID)
+}
+
+/// `[b"blacklist"]`
+pub fn blacklist() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BLACKLIST_SEED], &ID)
+}
+
+/// `[b"game_limits"]`
+pub fn game_limits() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_LIMITS_SEED], &ID)
+}
+
+/// `[b"game_config", game_id (LE)]`, the published rules of an originals game
+pub fn game_config(game_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_CONFIG_SEED, game_id.to_le_bytes().as_ref()], &ID)
+}
+
+/// `[b"announcement"]`
+pub fn announcement() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ANNOUNCEMENT_SEED], &ID)
+}
+
+/// `[b"promo_pool"]`
+pub fn promo_pool() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROMO_POOL_SEED], &ID)
+}
+
+/// `[b"bonus", vault]`
+pub fn deposit_bonus(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BONUS_SEED, vault.as_ref()], &ID)
+}
+
+/// `[b"gems", vault]`
+pub fn gem_inventory(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GEMS_SEED, vault.as_ref()], &ID)
+}
+
+/// `[b"pity_config"]`
+pub fn pity_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PITY_CONFIG_SEED], &ID)
+}
+
+/// `[b"lossback_promo"]`
+pub fn loss_back_promo() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LOSSBACK_PROMO_SEED], &ID)
+}
+
+/// `[b"lossback", vault]`
+pub fn loss_back(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LOSSBACK_SEED, vault.as_ref()], &ID)
+}
+
+/// `[b"receipt", vault, bet_id_hash]`; see [`crate::events::bet_id_hash`]
+pub fn bet_receipt(vault: &Pubkey, bet_id_hash: &[u8; 16]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECEIPT_SEED, vault.as_ref(), bet_id_hash], &ID)
+}
+
+/// `[b"payout_schedule", vault, bet_id_hash]`, open while a streamed win still has tranches to pay
+pub fn payout_schedule(vault: &Pubkey, bet_id_hash: &[u8; 16]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PAYOUT_SCHEDULE_SEED, vault.as_ref(), bet_id_hash], &ID)
+}
+
+/// `[b"stats_snapshots"]`
+pub fn stats_snapshots() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STATS_SNAPSHOTS_SEED], &ID)
+}
+
+/// `[b"game_programs"]`
+pub fn game_programs() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_PROGRAMS_SEED], &ID)
+}
+
+/// `[GAME_AUTHORITY_SEED]` under `program`, the signer a registered game program settles with
+pub fn game_authority(program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_AUTHORITY_SEED], program)
+}
+
+/// `[b"vault_registry"]`
+pub fn vault_registry() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_REGISTRY_SEED], &ID)
+}
+
+/// `[b"registry_page", page as u32 LE]`
+pub fn registry_page(page: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_PAGE_SEED, &page.to_le_bytes()], &ID)
+}
+
+/// `[b"registry_entry", vault]`
+pub fn registry_entry(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_ENTRY_SEED, vault.as_ref()], &ID)
+}
+
+/// `[b"trophy_config"]`, also the Bubblegum tree delegate that signs trophy mints
+pub fn trophy_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TROPHY_CONFIG_SEED], &ID)
+}
+
+/// Metaplex Bubblegum, which mints the trophy cNFTs
+pub const BUBBLEGUM_PROGRAM_ID: &str = "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY";
+
+/// `[merkle_tree]` under Bubblegum, the tree config of a trophy tree
+pub fn trophy_tree_config(merkle_tree: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[merkle_tree.as_ref()], &BUBBLEGUM_PROGRAM_ID.parse().unwrap())
+}
+
+/// `[b"intent", vault, nonce as u64 LE]`, created when a relayed withdraw uses `nonce`
+pub fn withdraw_intent(vault: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INTENT_SEED, vault.as_ref(), &nonce.to_le_bytes()], &ID)
+}
+
+/// `[b"bridge_config"]`, which also holds the bridged deposit pool
+pub fn bridge_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BRIDGE_CONFIG_SEED], &ID)
+}
+
+/// `[b"bridge_claim", posted_vaa]`, created once a bridged deposit is credited
+pub fn bridge_claim(posted_vaa: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BRIDGE_CLAIM_SEED, posted_vaa.as_ref()], &ID)
+}
+
+/// `[b"dispute_config"]`
+pub fn dispute_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DISPUTE_CONFIG_SEED], &ID)
+}
+
+/// `[b"dispute", bet_receipt]`, open while a challenge of that bet awaits a ruling
+pub fn dispute(bet_receipt: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DISPUTE_SEED, bet_receipt.as_ref()], &ID)
+}
+
+/// `[b"pending_bet", vault, bet_id_hash]`, open until the sports bet is settled
+pub fn pending_bet(vault: &Pubkey, bet_id_hash: &[u8; 16]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PENDING_BET_SEED, vault.as_ref(), bet_id_hash.as_ref()], &ID)
+}
+
+/// `[b"insurance_fund"]`, holding premiums and the refunds owed to insured bets
+pub fn insurance_fund() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INSURANCE_FUND_SEED], &ID)
+}
+
+/// `[b"crash_round", round_id (LE)]`
+pub fn crash_round(round_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CRASH_ROUND_SEED, round_id.to_le_bytes().as_ref()], &ID)
+}
+
+/// `[b"crash_bet", crash_round, vault]`, one bet per vault and round
+pub fn crash_bet(crash_round: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CRASH_BET_SEED, crash_round.as_ref(), vault.as_ref()], &ID)
+}
+
+/// `[b"pvp_config"]`
+pub fn pvp_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PVP_CONFIG_SEED], &ID)
+}
+
+/// `[b"pvp_queue"]`, listing open PvP challenges
+pub fn pvp_queue() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PVP_QUEUE_SEED], &ID)
+}
+
+/// `[b"jackpot", game_id (LE)]`, holding the pots of the game's jackpot tiers
+pub fn jackpot(game_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[JACKPOT_SEED, game_id.to_le_bytes().as_ref()], &ID)
+}
+
+/// `[b"orao-vrf-randomness-request", seed]` under ORAO VRF, the request drawing a jackpot tier
+///
+/// `seed` is `Jackpot::vrf_seed` of the jackpot, bet and tier; request it from ORAO before `award_jackpot`.
+pub fn jackpot_randomness(seed: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORAO_RANDOMNESS_SEED, seed], &ORAO_VRF_PROGRAM_ID.parse().unwrap())
+}
+
+/// `[b"tournament", tournament_id (LE)]`, the bracket until its prizes are paid
+pub fn tournament(tournament_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TOURNAMENT_SEED, tournament_id.to_le_bytes().as_ref()], &ID)
+}
+
+/// `[b"table_round", round_id (LE)]`, escrowing every seat's stake until settled or cancelled
+pub fn table_round(round_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TABLE_ROUND_SEED, round_id.to_le_bytes().as_ref()], &ID)
+}
+
+/// `[b"pvp_bet", creator_vault, bet_id_hash]`, holding both stakes until settlement
+pub fn pvp_bet(creator_vault: &Pubkey, bet_id_hash: &[u8; 16]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PVP_BET_SEED, creator_vault.as_ref(), bet_id_hash.as_ref()], &ID)
+}
+
+/// `[b"rg_config"]`
+pub fn rg_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RG_CONFIG_SEED], &ID)
+}
+
+/// `[b"compliance_config"]`
+pub fn compliance_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COMPLIANCE_CONFIG_SEED], &ID)
+}
+
+/// `[b"kyc", vault]`, the attestation that lets `vault` withdraw above the KYC threshold
+pub fn kyc_attestation(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[KYC_SEED, vault.as_ref()], &ID)
+}
+
+/// `[b"copy_follow", vault]`, present while the vault mirrors a leader's bets
+pub fn copy_follow(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COPY_FOLLOW_SEED, vault.as_ref()], &ID)
+}
+
+/// `[b"withdraw_guard", vault]`, present while withdrawals need the device key
+pub fn withdraw_guard(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WITHDRAW_GUARD_SEED, vault.as_ref()], &ID)
+}
+
+/// `[b"upgrade_governance"]`, the pending upgrade announcement
+pub fn upgrade_governance() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[UPGRADE_GOVERNANCE_SEED], &ID)
+}
+
+/// `[b"health_monitor"]`, the operator heartbeat and degraded flag
+pub fn health_monitor() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[HEALTH_MONITOR_SEED], &ID)
+}
+
+/// `[b"withdrawal_queue"]`, the ticket counters of withdrawals requested during pauses
+pub fn withdrawal_queue() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WITHDRAWAL_QUEUE_SEED], &ID)
+}
+
+/// `[b"withdrawal_request", vault]`, present while the vault has a queued withdrawal
+pub fn withdrawal_request(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WITHDRAWAL_REQUEST_SEED, vault.as_ref()], &ID)
+}
+
+/// `[b"staking_config"]`, the platform-token escrow and rebate tiers; also the escrow's token owner
+pub fn staking_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKING_CONFIG_SEED], &ID)
+}
+
+/// `[b"token_stake", vault]`, present once the vault's owner has set up platform-token staking
+pub fn token_stake(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TOKEN_STAKE_SEED, vault.as_ref()], &ID)
+}
+
+/// `[program id]` under the upgradeable BPF loader, the program's executable data
+pub fn program_data() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+/// `[b"__event_authority"]`, signer of the program's self-CPI events
+pub fn event_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority"], &ID)
+}
@@ -0,0 +1,94 @@
+//! Solana Name Service (`.sol`) resolution
+//!
+//! Tipping and deposit-for flows can take a name like `alice.sol` instead of a
+//! raw address: derive the name account with [`domain_key`], fetch it, and
+//! read the owner (or the owner's vault) with [`owner`] / [`vault`].
+//! Subdomains (`pay.alice.sol`) are supported. Tokenized names are rejected,
+//! as their record points at the tokenizer's escrow rather than the holder.
+
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::pda;
+
+/// Prefix hashed with every name before deriving its account
+const HASH_PREFIX: &str = "SPL Name Service";
+
+/// Bytes of the name record header: parent name, owner, class
+pub const NAME_RECORD_HEADER_LEN: usize = 32 * 3;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SnsError {
+    /// Empty labels, too many labels or a non-`.sol` name
+    InvalidName,
+    /// The account data is too short to be a name record
+    NotANameRecord,
+    /// The name is wrapped as an NFT; its record owner is the tokenizer escrow
+    Tokenized,
+}
+
+impl fmt::Display for SnsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnsError::InvalidName => write!(f, "not a name.sol or sub.name.sol domain"),
+            SnsError::NotANameRecord => write!(f, "account is not a name record"),
+            SnsError::Tokenized => write!(f, "domain is tokenized; resolve the holder of its NFT instead"),
+        }
+    }
+}
+
+impl std::error::Error for SnsError {}
+
+fn name_program() -> Pubkey {
+    "namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX".parse().unwrap()
+}
+
+/// Parent of every `.sol` name
+fn sol_tld() -> Pubkey {
+    "58PwtjSDuFHuUkYjH9BYnnQKHfwo9reZhC2zMJv9JPkx".parse().unwrap()
+}
+
+/// Owner the name tokenizer leaves on names it has wrapped
+fn tokenizer_escrow() -> Pubkey {
+    let tokenizer: Pubkey = "nftD3vbNkNqfj2Sd3HZwbpw4BxxKWr4AjGb9X38JeZk".parse().unwrap();
+    Pubkey::find_program_address(&[tokenizer.as_ref()], &tokenizer).0
+}
+
+fn name_account(hashed_name: &str, parent: &Pubkey) -> Pubkey {
+    let hashed = hashv(&[HASH_PREFIX.as_bytes(), hashed_name.as_bytes()]);
+    Pubkey::find_program_address(&[hashed.as_ref(), &[0u8; 32], parent.as_ref()], &name_program()).0
+}
+
+/// Name account of `alice`, `alice.sol` or `pay.alice.sol`
+pub fn domain_key(domain: &str) -> Result<Pubkey, SnsError> {
+    let domain = domain.strip_suffix(".sol").unwrap_or(domain);
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.iter().any(|label| label.is_empty()) {
+        return Err(SnsError::InvalidName);
+    }
+    match labels.as_slice() {
+        [name] => Ok(name_account(name, &sol_tld())),
+        // Subdomain names are hashed with a leading zero byte
+        [sub, name] => Ok(name_account(&format!("\0{sub}"), &name_account(name, &sol_tld()))),
+        _ => Err(SnsError::InvalidName),
+    }
+}
+
+/// Owner stored in a name record's account data
+pub fn owner(record_data: &[u8]) -> Result<Pubkey, SnsError> {
+    if record_data.len() < NAME_RECORD_HEADER_LEN {
+        return Err(SnsError::NotANameRecord);
+    }
+    let owner = Pubkey::try_from(&record_data[32..64]).map_err(|_| SnsError::NotANameRecord)?;
+    if owner == tokenizer_escrow() {
+        return Err(SnsError::Tokenized);
+    }
+    Ok(owner)
+}
+
+/// Vault PDA of the owner in a name record's account data
+pub fn vault(record_data: &[u8]) -> Result<Pubkey, SnsError> {
+    owner(record_data).map(|owner| pda::vault(&owner).0)
+}
@@ -0,0 +1,137 @@
+//! PDA seeds, account sizes and client defaults shared with the SDK, CLI and keeper
+
+use crate::{
+    GAME_CATEGORIES, JACKPOT_TIERS, MAX_ANNOUNCEMENT_URI_LEN, MAX_ATTESTORS, MAX_BLACKLIST_LEN, MAX_BRACKET_SIZE,
+    MAX_BRIDGE_EMITTERS, MAX_GAME_LIMITS, MAX_GAME_PROGRAMS, MAX_PVP_QUEUE_LEN, MAX_REBATE_TIERS, MAX_REGION_RULES,
+    MAX_TABLE_SEATS, MAX_TROPHY_URI_LEN, STATS_SNAPSHOT_SLOTS, VAULT_REGISTRY_PAGE_LEN,
+};
+
+// PDA seeds, first component of each program address
+
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const GAME_CONFIG_SEED: &[u8] = b"game_config";
+pub const TROPHY_CONFIG_SEED: &[u8] = b"trophy_config";
+pub const GLOBAL_STATS_SEED: &[u8] = b"global_stats";
+pub const BLACKLIST_SEED: &[u8] = b"blacklist";
+pub const HOUSE_VAULT_SEED: &[u8] = b"house_vault";
+pub const PAUSE_CONFIG_SEED: &[u8] = b"pause_config";
+pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+pub const ANNOUNCEMENT_SEED: &[u8] = b"announcement";
+pub const GAME_LIMITS_SEED: &[u8] = b"game_limits";
+pub const JACKPOT_SEED: &[u8] = b"jackpot";
+pub const COMPLIANCE_CONFIG_SEED: &[u8] = b"compliance_config";
+pub const GAME_PROGRAMS_SEED: &[u8] = b"game_programs";
+pub const STATS_SNAPSHOTS_SEED: &[u8] = b"stats_snapshots";
+pub const KYC_SEED: &[u8] = b"kyc";
+pub const WITHDRAW_GUARD_SEED: &[u8] = b"withdraw_guard";
+pub const INTENT_SEED: &[u8] = b"intent";
+pub const BONUS_SEED: &[u8] = b"bonus";
+pub const GEMS_SEED: &[u8] = b"gems";
+pub const LOSSBACK_PROMO_SEED: &[u8] = b"lossback_promo";
+pub const LOSSBACK_SEED: &[u8] = b"lossback";
+pub const PITY_CONFIG_SEED: &[u8] = b"pity_config";
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+pub const DISPUTE_CONFIG_SEED: &[u8] = b"dispute_config";
+pub const DISPUTE_SEED: &[u8] = b"dispute";
+pub const PVP_CONFIG_SEED: &[u8] = b"pvp_config";
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+pub const PENDING_BET_SEED: &[u8] = b"pending_bet";
+pub const CRASH_ROUND_SEED: &[u8] = b"crash_round";
+pub const CRASH_BET_SEED: &[u8] = b"crash_bet";
+pub const PVP_QUEUE_SEED: &[u8] = b"pvp_queue";
+pub const PVP_BET_SEED: &[u8] = b"pvp_bet";
+pub const TABLE_ROUND_SEED: &[u8] = b"table_round";
+pub const COPY_FOLLOW_SEED: &[u8] = b"copy_follow";
+pub const PROMO_POOL_SEED: &[u8] = b"promo_pool";
+pub const RAFFLE_SEED: &[u8] = b"raffle";
+pub const RAFFLE_ENTRY_SEED: &[u8] = b"raffle_entry";
+pub const TOURNAMENT_SEED: &[u8] = b"tournament";
+pub const REWARDS_CONFIG_SEED: &[u8] = b"rewards_config";
+pub const VAULT_REGISTRY_SEED: &[u8] = b"vault_registry";
+pub const REGISTRY_PAGE_SEED: &[u8] = b"registry_page";
+pub const REGISTRY_ENTRY_SEED: &[u8] = b"registry_entry";
+pub const RG_CONFIG_SEED: &[u8] = b"rg_config";
+pub const BRIDGE_CONFIG_SEED: &[u8] = b"bridge_config";
+pub const BRIDGE_CLAIM_SEED: &[u8] = b"bridge_claim";
+pub const UPGRADE_GOVERNANCE_SEED: &[u8] = b"upgrade_governance";
+pub const PAYOUT_SCHEDULE_SEED: &[u8] = b"payout_schedule";
+pub const HEALTH_MONITOR_SEED: &[u8] = b"health_monitor";
+pub const WITHDRAWAL_QUEUE_SEED: &[u8] = b"withdrawal_queue";
+pub const WITHDRAWAL_REQUEST_SEED: &[u8] = b"withdrawal_request";
+pub const STAKING_CONFIG_SEED: &[u8] = b"staking_config";
+pub const TOKEN_STAKE_SEED: &[u8] = b"token_stake";
+
+/// Seed of the PDA a registered game program signs settlements with
+pub const GAME_AUTHORITY_SEED: &[u8] = b"sols_bet_authority";
+
+// Account sizes passed to `init`, 8-byte discriminator included
+
+pub const USER_VAULT_SPACE: usize =
+    8 + 32 + 1 + 8 + 4 + 8 + 1 + 8 + 8 + 8 + 4 + 8 + 8 + 1 + 32 + 8 + 4 + 8 + 2 + 8 + 8 + 8;
+pub const HOUSE_VAULT_SPACE: usize = 8 + 1 + 32 + 32 + 8 + 1 + 8 + GAME_CATEGORIES * 8;
+pub const PAUSE_CONFIG_SPACE: usize = 8 + 32 + 32 + 1 + 4 + 1 + 1 + 1;
+pub const GLOBAL_CONFIG_SPACE: usize = 8 + 8 + 1 + 1 + 8 + 8;
+pub const GLOBAL_STATS_SPACE: usize = 8 + 8 + 8 + 8 + 8 + 32 + 1 + 8 + 8 + 8 + 8 + 1;
+pub const ANNOUNCEMENT_SPACE: usize = 8 + 4 + MAX_ANNOUNCEMENT_URI_LEN + 32 + 1 + 8 + 8 + 1;
+pub const GAME_LIMITS_SPACE: usize = 8 + 4 + 8 + 4 + 8 + 8 + 8 + 2 + 8 + 4 + MAX_GAME_LIMITS * (8 + 4) + 1;
+pub const GAME_CONFIG_SPACE: usize = 8 + 8 + 2 + 4 + 8 + 8 + 1;
+pub const JACKPOT_SPACE: usize = 8 + 8 + 32 + JACKPOT_TIERS * (2 + 8 + 4 + 8 + 4) + 1;
+pub const BLACKLIST_SPACE: usize = 8 + 4 + MAX_BLACKLIST_LEN * 32 + 1;
+pub const STATS_SNAPSHOTS_SPACE: usize = 8 + 4 + 8 + 2 + 4 + STATS_SNAPSHOT_SLOTS * (8 + 8 + 8 + 8 + 8) + 1;
+pub const GAME_PROGRAMS_SPACE: usize = 8 + 4 + MAX_GAME_PROGRAMS * (32 + 32) + 1;
+pub const WITHDRAW_INTENT_SPACE: usize = 8 + 32 + 8 + 1;
+pub const BET_RECEIPT_SPACE: usize = 8 + 32 + 16 + 8 + 8 + 8 + 8 + 1 + 1 + 1;
+pub const DISPUTE_CONFIG_SPACE: usize = 8 + 8 + 1;
+pub const DISPUTE_SPACE: usize = 8 + 32 + 32 + 16 + 8 + 8 + 1;
+pub const PVP_CONFIG_SPACE: usize = 8 + 2 + 1;
+pub const INSURANCE_FUND_SPACE: usize = 8 + 2 + 2 + 8 + 8 + 8 + 1;
+pub const PENDING_BET_SPACE: usize = 8 + 32 + 32 + 16 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+pub const CRASH_ROUND_SPACE: usize = 8 + 8 + 8 + 32 + 32 + 8 + 32 + 4 + 8 + 4 + 1;
+pub const CRASH_BET_SPACE: usize = 8 + 32 + 32 + 32 + 8 + 4 + 1;
+pub const PVP_QUEUE_SPACE: usize = 8 + 4 + MAX_PVP_QUEUE_LEN * 32 + 1;
+pub const PVP_BET_SPACE: usize = 8 + 32 + 32 + 32 + 16 + 8 + 8 + 2 + 8 + 8 + 8 + 1;
+pub const TABLE_ROUND_SPACE: usize = 8 + 8 + 8 + 32 + 2 + 8 + 4 + MAX_TABLE_SEATS * (32 + 8) + 1;
+pub const COPY_FOLLOW_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+pub const PROMO_POOL_SPACE: usize = 8 + 1 + 8 + 8;
+pub const DEPOSIT_BONUS_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 1;
+pub const GEM_INVENTORY_SPACE: usize = 8 + 32 + 4 * 7 + 2 + 1;
+pub const RAFFLE_SPACE: usize = 8 + 8 + 1 + 8 + 8 + 32 + 32 + 8 + 8 + 1 + 1 + 1;
+pub const RAFFLE_ENTRY_SPACE: usize = 8 + 32 + 32 + 8 + 4 + 1;
+pub const TOURNAMENT_SPACE: usize = 8 + 8 + 8 + 32 + 1 + 4 + MAX_BRACKET_SIZE * 32 + 4 + MAX_BRACKET_SIZE;
+pub const LOSS_BACK_PROMO_SPACE: usize = 8 + 4 + 8 + 8 + 2 + 1;
+pub const PITY_CONFIG_SPACE: usize = 8 + 2 + 2 + 1;
+pub const LOSS_BACK_SPACE: usize = 8 + 32 + 4 + 8 + 8 + 8 + 1;
+pub const REWARDS_CONFIG_SPACE: usize = 8 + 8 + 2 + 1;
+pub const VAULT_REGISTRY_SPACE: usize = 8 + 8 + 4 + 1;
+pub const VAULT_REGISTRY_PAGE_SPACE: usize = 8 + 4 + 4 + VAULT_REGISTRY_PAGE_LEN * 32 + 1;
+pub const VAULT_REGISTRY_ENTRY_SPACE: usize = 8 + 32 + 8 + 1;
+pub const TROPHY_CONFIG_SPACE: usize = 8 + 32 + 4 + 4 + MAX_TROPHY_URI_LEN + 1;
+pub const RG_CONFIG_SPACE: usize = 8 + 8 + 8 + 1;
+pub const BRIDGE_CONFIG_SPACE: usize = 8 + 32 + 4 + MAX_BRIDGE_EMITTERS * (2 + 32) + 8 + 1;
+pub const BRIDGE_CLAIM_SPACE: usize = 8 + 32 + 8 + 1;
+pub const COMPLIANCE_CONFIG_SPACE: usize =
+    8 + 8 + 8 + 4 + MAX_REGION_RULES * (1 + 9 + 8) + 32 + 1 + 32 + 8 + 4 + MAX_ATTESTORS * 32 + 1;
+pub const WITHDRAW_GUARD_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+pub const KYC_ATTESTATION_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+pub const UPGRADE_GOVERNANCE_SPACE: usize = 8 + 8 + 32 + 32 + 8 + 8 + 8 + 1;
+pub const PAYOUT_SCHEDULE_SPACE: usize = 8 + 32 + 16 + 8 + 8 + 2 + 8 + 8 + 32 + 1;
+pub const HEALTH_MONITOR_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1 + 1;
+pub const WITHDRAWAL_QUEUE_SPACE: usize = 8 + 8 + 8 + 1;
+pub const WITHDRAWAL_REQUEST_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1;
+pub const STAKING_CONFIG_SPACE: usize = 8 + 32 + 32 + 8 + 4 + MAX_REBATE_TIERS * (8 + 2) + 1;
+pub const TOKEN_STAKE_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1;
+
+/// Seconds between stats snapshots the CLI proposes
+pub const DEFAULT_STATS_SNAPSHOT_INTERVAL_SECS: u32 = 3600;
+
+/// Settlements per vault per second the CLI proposes for new game limits
+pub const DEFAULT_MAX_BETS_PER_SECOND: u32 = 20;
+
+/// Hours an announcement stays up unless the CLI is told otherwise
+pub const DEFAULT_ANNOUNCEMENT_HOURS: i64 = 24;
+
+/// Longest gap between operator heartbeats the CLI proposes for the health monitor
+pub const DEFAULT_HEARTBEAT_INTERVAL_SECS: i64 = 600;
+
+/// Wait between requesting an unstake and withdrawing it that the CLI proposes for platform-token staking
+pub const DEFAULT_UNSTAKE_COOLDOWN_SECS: i64 = 7 * 24 * 3600;
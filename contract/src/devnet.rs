@@ -0,0 +1,202 @@
+//! QA instructions compiled only into `--features devnet` builds
+//!
+//! `#[program]` cannot leave instructions out behind a `cfg`, so these are
+//! reached through the program's fallback, which mainnet builds leave at
+//! Anchor's default error. Clients build them with [`accounts`] and
+//! [`instruction`] like any other instruction.
+
+use anchor_lang::prelude::*;
+use anchor_lang::{Bumps, Discriminator, InstructionData};
+use std::collections::BTreeSet;
+
+use crate::*;
+
+/// Most a single faucet deposit moves from the house into a vault (10 SOL)
+pub const DEVNET_FAUCET_MAX: u64 = 10_000_000_000;
+
+/// Credit up to [`DEVNET_FAUCET_MAX`] lamports from the house into a vault (anyone)
+pub fn faucet_deposit(ctx: Context<FaucetDeposit>, amount: u64) -> Result<()> {
+    require!((1..=DEVNET_FAUCET_MAX).contains(&amount), VaultError::InvalidAmount);
+    let house_info = ctx.accounts.house_vault.to_account_info();
+    let vault_info = ctx.accounts.vault.to_account_info();
+    transfer_lamports(&house_info, &vault_info, amount)?;
+    msg!("Devnet faucet: {} lamports to {}", amount, vault_info.key());
+    Ok(())
+}
+
+/// Settle against a vault without pause, limit or compliance checks and release its locks (admin only)
+pub fn force_settle(ctx: Context<ForceSettle>, stake: u64, payout: u64) -> Result<()> {
+    let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+    require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+    let house_info = ctx.accounts.house_vault.to_account_info();
+    let vault_info = ctx.accounts.vault.to_account_info();
+    apply_settlement(&vault_info, &house_info, stake, payout)?;
+
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.locked_amount = 0;
+    vault.active_games = 0;
+    msg!("Devnet force settle: stake {}, payout {}", stake, payout);
+    Ok(())
+}
+
+/// Lift every pause and put the game limits back to a freshly initialized state (admin only)
+pub fn reset_config(ctx: Context<ResetConfig>, default_max_multiplier: u32, max_unstaked_payout: u64) -> Result<()> {
+    let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+    require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+    require!(default_max_multiplier >= 100, VaultError::InvalidAmount);
+
+    let config = &mut ctx.accounts.pause_config;
+    config.flags = 0;
+    config.maintenance_start_time = 0;
+    config.maintenance_duration_hours = 4;
+
+    let limits = &mut ctx.accounts.game_limits;
+    limits.default_max_multiplier = default_max_multiplier;
+    limits.max_unstaked_payout = max_unstaked_payout;
+    limits.max_bets_per_second = 0;
+    limits.max_transaction_payout = 0;
+    limits.max_daily_net_win = 0;
+    limits.payout_stream_threshold = 0;
+    limits.payout_stream_tranches = 0;
+    limits.payout_stream_interval_secs = 0;
+    limits.games = Vec::new();
+    msg!("Devnet config reset");
+    Ok(())
+}
+
+/// Run the devnet instruction `data` names, or fail as an unknown instruction
+pub fn dispatch<'info>(program_id: &Pubkey, accounts: &'info [AccountInfo<'info>], data: &[u8]) -> Result<()> {
+    let Some((tag, args)) = data.split_first_chunk::<8>() else {
+        return err!(anchor_lang::error::ErrorCode::InstructionMissing);
+    };
+    match *tag {
+        instruction::FaucetDeposit::DISCRIMINATOR => {
+            let ix = instruction::FaucetDeposit::deserialize(&mut &args[..])
+                .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotDeserialize)?;
+            run::<FaucetDeposit>(program_id, accounts, args, |ctx| faucet_deposit(ctx, ix.amount))
+        }
+        instruction::ForceSettle::DISCRIMINATOR => {
+            let ix = instruction::ForceSettle::deserialize(&mut &args[..])
+                .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotDeserialize)?;
+            run::<ForceSettle>(program_id, accounts, args, |ctx| force_settle(ctx, ix.stake, ix.payout))
+        }
+        instruction::ResetConfig::DISCRIMINATOR => {
+            let ix = instruction::ResetConfig::deserialize(&mut &args[..])
+                .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotDeserialize)?;
+            run::<ResetConfig>(program_id, accounts, args, |ctx| {
+                reset_config(ctx, ix.default_max_multiplier, ix.max_unstaked_payout)
+            })
+        }
+        _ => err!(anchor_lang::error::ErrorCode::InstructionFallbackNotFound),
+    }
+}
+
+/// Validate the accounts of `T`, run `handler` and persist what it changed, as Anchor's dispatch does
+fn run<'info, T>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    args: &[u8],
+    handler: impl FnOnce(Context<'_, '_, 'info, 'info, T>) -> Result<()>,
+) -> Result<()>
+where
+    T: Accounts<'info, T::Bumps> + AccountsExit<'info> + Bumps,
+    T::Bumps: Default,
+{
+    let mut bumps = T::Bumps::default();
+    let mut remaining = accounts;
+    let mut accounts = T::try_accounts(program_id, &mut remaining, args, &mut bumps, &mut BTreeSet::new())?;
+    handler(Context::new(program_id, &mut accounts, remaining, bumps))?;
+    accounts.exit(program_id)
+}
+
+#[derive(Accounts)]
+pub struct FaucetDeposit<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ForceSettle<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResetConfig<'info> {
+    #[account(mut, seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[GAME_LIMITS_SEED], bump = game_limits.bump)]
+    pub game_limits: Account<'info, GameLimits>,
+    pub authority: Signer<'info>,
+}
+
+/// Account structs for building the devnet instructions
+pub mod accounts {
+    pub use super::__client_accounts_faucet_deposit::FaucetDeposit;
+    pub use super::__client_accounts_force_settle::ForceSettle;
+    pub use super::__client_accounts_reset_config::ResetConfig;
+}
+
+/// Instruction data of the devnet instructions
+pub mod instruction {
+    use super::*;
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct FaucetDeposit {
+        pub amount: u64,
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct ForceSettle {
+        pub stake: u64,
+        pub payout: u64,
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct ResetConfig {
+        pub default_max_multiplier: u32,
+        pub max_unstaked_payout: u64,
+    }
+
+    // Anchor's discriminators for these names: the first 8 bytes of sha256("global:<name>")
+    impl Discriminator for FaucetDeposit {
+        const DISCRIMINATOR: [u8; 8] = [202, 105, 178, 67, 194, 18, 107, 124];
+    }
+
+    impl Discriminator for ForceSettle {
+        const DISCRIMINATOR: [u8; 8] = [201, 10, 239, 245, 47, 198, 225, 204];
+    }
+
+    impl Discriminator for ResetConfig {
+        const DISCRIMINATOR: [u8; 8] = [81, 97, 49, 46, 19, 200, 8, 99];
+    }
+
+    impl InstructionData for FaucetDeposit {}
+    impl InstructionData for ForceSettle {}
+    impl InstructionData for ResetConfig {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::instruction;
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::Discriminator;
+
+    fn sighash(name: &str) -> [u8; 8] {
+        hash(format!("global:{name}").as_bytes()).to_bytes()[..8].try_into().unwrap()
+    }
+
+    #[test]
+    fn discriminators_match_anchor_sighash() {
+        assert_eq!(instruction::FaucetDeposit::DISCRIMINATOR, sighash("faucet_deposit"));
+        assert_eq!(instruction::ForceSettle::DISCRIMINATOR, sighash("force_settle"));
+        assert_eq!(instruction::ResetConfig::DISCRIMINATOR, sighash("reset_config"));
+    }
+}
@@ -1,5 +1,20 @@
+// Settlement instructions take each bet field as its own argument, as do their generated CPI helpers
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as sysvar_instructions, load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::{bpf_loader_upgradeable, ed25519_program, keccak, system_instruction};
+use anchor_lang::Discriminator;
+
+pub mod constants;
+pub use constants::*;
+#[cfg(feature = "devnet")]
+pub mod devnet;
 
 declare_id!("3hYE1Bv7ZtUUJLMjzFjq13j2AKd63TzrdvduzUBRjbCg");
 
@@ -9,24 +24,60 @@ pub mod smart_vault_v2 {
 
     /// Initialize a new UserVault PDA for the user
     pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
-        let vault = &mut ctx.accounts.vault;
+        let mut vault = ctx.accounts.vault.load_init()?;
         vault.owner = ctx.accounts.user.key();
         vault.bump = ctx.bumps.vault;
         vault.locked_amount = 0;
         vault.active_games = 0;
         vault.accum_wager = 0;
-        vault.version = 2;
+        vault.version = VAULT_VERSION;
+        vault.created_at = Clock::get()?.unix_timestamp;
+        vault.lifetime_wager = 0;
+        vault.rate_window_start = 0;
+        vault.rate_window_bets = 0;
+        vault.transfer_window_start = 0;
+        vault.transfer_window_total = 0;
+        vault.region = 0;
+        vault.terms_hash = [0; 32];
+        vault.terms_accepted_at = 0;
+        vault.free_spins = 0;
+        vault.free_spin_value = 0;
+        vault.loss_streak = 0;
+        vault.win_window_start = 0;
+        vault.win_window_net = 0;
+        vault.daily_win_cap = 0;
+
+        let mut stats = ctx.accounts.global_stats.load_mut()?;
+        stats.total_users = stats.total_users.checked_add(1).ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+
+    /// Grow a legacy UserVault to the current layout (owner pays the extra rent)
+    ///
+    /// New fields are zero-filled; a `created_at` of 0 marks a vault that predates age tracking.
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let owner_info = ctx.accounts.owner.to_account_info();
+        let from = migrate_account::<UserVault>(&vault_info, &owner_info, &ctx.accounts.system_program)?;
+
+        let vault = UserVault::try_deserialize(&mut &vault_info.data.borrow()[..])?;
+        require!(vault.owner == owner_info.key(), VaultError::Unauthorized);
+        if from < VAULT_VERSION {
+            msg!("Vault migrated: user={}, version {} -> {}", vault.owner, from, VAULT_VERSION);
+        }
         Ok(())
     }
 
     /// Initialize the global HouseVault PDA
     pub fn initialize_house(ctx: Context<InitializeHouse>) -> Result<()> {
-        let house_vault = &mut ctx.accounts.house_vault;
+        let mut house_vault = ctx.accounts.house_vault.load_init()?;
         house_vault.bump = ctx.bumps.house_vault;
         house_vault.multisig_authority = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
         house_vault.admin_authority = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
         house_vault.total_volume = 0;
-        house_vault.version = 2;
+        house_vault.version = HOUSE_VERSION;
+        house_vault.open_exposure = 0;
+        house_vault.category_volume = [0; GAME_CATEGORIES];
         Ok(())
     }
 
@@ -35,538 +86,8965 @@ pub mod smart_vault_v2 {
         let config = &mut ctx.accounts.pause_config;
         config.multisig_authority = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
         config.admin_authority = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
-        config.maintenance_pause = false;
+        config.flags = 0;
         config.maintenance_start_time = 0;
         config.maintenance_duration_hours = 4;
-        config.emergency_pause = false;
+        config.version = PAUSE_CONFIG_VERSION;
         config.bump = ctx.bumps.pause_config;
         Ok(())
     }
 
-    /// Close pause configuration account and return rent to authority
-    pub fn close_pause_config(ctx: Context<ClosePauseConfig>) -> Result<()> {
-        // Only admin or multisig can close
-        require!(
-            ctx.accounts.authority.key() == ctx.accounts.pause_config.admin_authority ||
-            ctx.accounts.authority.key() == ctx.accounts.pause_config.multisig_authority,
-            VaultError::Unauthorized
-        );
-        
-        // Close the account and return rent to authority
-        ctx.accounts.pause_config.close(ctx.accounts.authority.to_account_info())?;
+    /// Initialize the global configuration PDA (admin only)
+    pub fn initialize_global_config(ctx: Context<InitializeGlobalConfig>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.global_config;
+        config.event_seq = 0;
+        config.bump = ctx.bumps.global_config;
+        config.version = GLOBAL_CONFIG_VERSION;
+        config.min_deposit = 0;
+        config.min_stake = 0;
         Ok(())
     }
 
-    /// Deposit SOL into the user's vault
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        require!(amount > 0, VaultError::InvalidAmount);
-        
-        // Check for any pause (with auto-unpause for maintenance)
-        let mut pause_config = ctx.accounts.pause_config.clone();
-        if pause_config.maintenance_pause {
-            let clock = Clock::get()?;
-            let elapsed_seconds = clock.unix_timestamp - pause_config.maintenance_start_time;
-            let elapsed_hours = (elapsed_seconds / 3600) as u8;
-            if elapsed_hours >= pause_config.maintenance_duration_hours {
-                pause_config.maintenance_pause = false;
-                pause_config.maintenance_start_time = 0;
-            }
-        }
-        require!(!pause_config.emergency_pause, VaultError::EmergencyPaused);
-        require!(!pause_config.maintenance_pause, VaultError::MaintenancePaused);
-        
-        let user = &ctx.accounts.user;
-        let vault_info = ctx.accounts.vault.to_account_info();
+    /// Set the smallest deposit and the smallest non-zero stake accepted; 0 disables either (multisig only)
+    pub fn set_minimums(ctx: Context<SetGlobalConfig>, min_deposit: u64, min_stake: u64) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
 
-        invoke(
-            &system_instruction::transfer(
-                &user.key(),
-                &vault_info.key(),
-                amount,
-            ),
-            &[
-                user.to_account_info().clone(),
-                vault_info.clone(),
-                ctx.accounts.system_program.to_account_info().clone(),
-            ],
-        )?;
+        let config = &mut ctx.accounts.global_config;
+        config.min_deposit = min_deposit;
+        config.min_stake = min_stake;
+
+        msg!("Minimums set: deposit={}, stake={}", min_deposit, min_stake);
         Ok(())
     }
 
-    /// Withdraw SOL from the vault back to the user's wallet
-    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
-        require!(amount > 0, VaultError::InvalidAmount);
-        
-        // Check for any pause (with auto-unpause for maintenance)
-        let mut pause_config = ctx.accounts.pause_config.clone();
-        if pause_config.maintenance_pause {
-            let clock = Clock::get()?;
-            let elapsed_seconds = clock.unix_timestamp - pause_config.maintenance_start_time;
-            let elapsed_hours = (elapsed_seconds / 3600) as u8;
-            if elapsed_hours >= pause_config.maintenance_duration_hours {
-                pause_config.maintenance_pause = false;
-                pause_config.maintenance_start_time = 0;
-            }
-        }
-        require!(!pause_config.emergency_pause, VaultError::EmergencyPaused);
-        require!(!pause_config.maintenance_pause, VaultError::MaintenancePaused);
-        
-        let vault = &mut ctx.accounts.vault;
-        let user_info = ctx.accounts.owner.to_account_info();
-        let vault_info = vault.to_account_info();
+    /// Initialize the platform-wide stats account (admin only)
+    pub fn initialize_global_stats(ctx: Context<InitializeGlobalStats>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
 
-        require!(vault.active_games == 0, VaultError::GamesInProgress);
-        require!(
-            **vault_info.lamports.borrow() >= amount,
-            VaultError::InsufficientFunds
-        );
+        let mut stats = ctx.accounts.global_stats.load_init()?;
+        stats.total_users = 0;
+        stats.total_bets = 0;
+        stats.total_volume = 0;
+        stats.biggest_win = 0;
+        stats.biggest_win_vault = Pubkey::default();
+        stats.bump = ctx.bumps.global_stats;
+        stats.settle_calls = 0;
+        stats.batch_calls = 0;
+        stats.deposit_calls = 0;
+        stats.withdraw_calls = 0;
+        stats.version = GLOBAL_STATS_VERSION;
+        Ok(())
+    }
 
-        **vault_info.try_borrow_mut_lamports()? -= amount;
-        **user_info.try_borrow_mut_lamports()? += amount;
+    /// Create the empty announcement banner account (admin only)
+    pub fn initialize_announcement(ctx: Context<InitializeAnnouncement>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let announcement = &mut ctx.accounts.announcement;
+        announcement.uri = String::new();
+        announcement.message_hash = [0u8; 32];
+        announcement.severity = 0;
+        announcement.expires_at = 0;
+        announcement.updated_at = 0;
+        announcement.bump = ctx.bumps.announcement;
         Ok(())
     }
 
-    /// Atomic bet and settle operation
-    pub fn bet_and_settle(
-        ctx: Context<BetAndSettle>,
-        stake: u64,
-        payout: u64,
-        bet_id: String,
-        game_id: u64,
-        gem_data: Vec<u8>,
+    /// Publish a banner for frontends to display until `expires_at` (admin only)
+    ///
+    /// The message itself lives at `uri`; `message_hash` is its sha256 so clients can verify it.
+    pub fn set_announcement(
+        ctx: Context<SetAnnouncement>,
+        uri: String,
+        message_hash: [u8; 32],
+        severity: u8,
+        expires_at: i64,
     ) -> Result<()> {
-        // Require exactly 7 u8 values
-        require!(gem_data.len() == 7, VaultError::InvalidAmount);
-
-        // stake can be 0 if it was already deducted in a previous transaction
-        
-        // Check for any pause (with auto-unpause for maintenance)
-        let mut pause_config = ctx.accounts.pause_config.clone();
-        if pause_config.maintenance_pause {
-            let clock = Clock::get()?;
-            let elapsed_seconds = clock.unix_timestamp - pause_config.maintenance_start_time;
-            let elapsed_hours = (elapsed_seconds / 3600) as u8;
-            if elapsed_hours >= pause_config.maintenance_duration_hours {
-                pause_config.maintenance_pause = false;
-                pause_config.maintenance_start_time = 0;
-            }
-        }
-        require!(!pause_config.emergency_pause, VaultError::EmergencyPaused);
-        require!(!pause_config.maintenance_pause, VaultError::MaintenancePaused);
-        
-        // Authority check (assume admin for now; adjust if game server)
         let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
         require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!(uri.len() <= MAX_ANNOUNCEMENT_URI_LEN, VaultError::AnnouncementTooLong);
+        require!(severity <= ANNOUNCEMENT_SEVERITY_CRITICAL, VaultError::InvalidSeverity);
 
-        let vault = &mut ctx.accounts.vault;
-        let house_vault = &mut ctx.accounts.house_vault;
-        let vault_info = vault.to_account_info();
-        let house_info = house_vault.to_account_info();
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at > now, VaultError::InvalidExpiry);
 
-        // If stake > 0, ensure vault has enough funds
-        if stake > 0 {
-            require!(**vault_info.lamports.borrow() >= stake, VaultError::InsufficientFunds);
-        }
+        let announcement = &mut ctx.accounts.announcement;
+        announcement.uri = uri;
+        announcement.message_hash = message_hash;
+        announcement.severity = severity;
+        announcement.expires_at = expires_at;
+        announcement.updated_at = now;
 
-        // Update house vault volume (only if there was an actual stake)
-        if stake > 0 {
-            house_vault.total_volume = house_vault.total_volume.checked_add(stake).ok_or(VaultError::Overflow)?;
-        }
+        msg!("Announcement set: severity={}, expiresAt={}, uri={}", severity, expires_at, announcement.uri);
+        Ok(())
+    }
 
-        // Calculate net change based on stake and payout
-        if stake == 0 {
-            // Stake was already deducted - this is a pure payout (win)
-            if payout > 0 {
-                // Player wins - house pays the full payout
-                require!(**house_info.lamports.borrow() >= payout, VaultError::HouseInsufficient);
-                **house_info.try_borrow_mut_lamports()? -= payout;
-                **vault_info.try_borrow_mut_lamports()? += payout;
-            }
-        } else {
-            // Normal bet and settle with stake
-            if payout > stake {
-                // Player wins - house pays the difference
-                let house_payout = payout - stake;
-                require!(**house_info.lamports.borrow() >= house_payout, VaultError::HouseInsufficient);
-                
-                // House pays winnings to vault
-                **house_info.try_borrow_mut_lamports()? -= house_payout;
-                **vault_info.try_borrow_mut_lamports()? += house_payout;
-            } else if payout < stake {
-                // Player loses - deduct loss from vault, add to house
-                let loss = stake - payout;
-                require!(**vault_info.lamports.borrow() >= loss, VaultError::InsufficientFunds);
-                **vault_info.try_borrow_mut_lamports()? -= loss;
-                **house_info.try_borrow_mut_lamports()? += loss;
-            } else {
-                // Draw - no net change
-            }
-        }
+    /// Withdraw the current banner immediately (admin only)
+    pub fn clear_announcement(ctx: Context<SetAnnouncement>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
 
-        msg!("Atomic bet and settle: betId={}, gameId={}, stake={}, payout={}, user={}, outcome={}, gameData={:?}", 
-             bet_id, game_id, stake, payout, ctx.accounts.vault.owner,
-             if payout > stake { "WIN" } else if payout < stake { "LOSS" } else { "DRAW" }, gem_data);
+        let announcement = &mut ctx.accounts.announcement;
+        announcement.expires_at = 0;
+        announcement.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!("Announcement cleared");
         Ok(())
     }
 
-    /// Batch bet and settle multiple games in one transaction (admin only)
-    pub fn batch_settle(
-        ctx: Context<BatchSettle>,
-        stakes: Vec<u64>,
-        payouts: Vec<u64>,
-        bet_ids: Vec<String>,
-        game_ids: Vec<u64>,
-        gem_datas: Vec<Vec<u8>>,
-    ) -> Result<()> {
-        require!(stakes.len() <= 10, VaultError::BatchTooLarge);
-        require!(stakes.len() > 0, VaultError::InvalidAmount);
-        require!(stakes.len() == payouts.len(), VaultError::InvalidAmount);
-        require!(stakes.len() == bet_ids.len(), VaultError::InvalidAmount);
-        require!(stakes.len() == game_ids.len(), VaultError::InvalidAmount);
-        require!(stakes.len() == gem_datas.len(), VaultError::InvalidAmount);
-        
-        // Check each gem_data has exactly 7 u8 values
-        for data in &gem_datas {
-            require!(data.len() == 7, VaultError::InvalidAmount);
-        }
-        
-        // Check for any pause (with auto-unpause for maintenance)
-        let mut pause_config = ctx.accounts.pause_config.clone();
-        if pause_config.maintenance_pause {
-            let clock = Clock::get()?;
-            let elapsed_seconds = clock.unix_timestamp - pause_config.maintenance_start_time;
-            let elapsed_hours = (elapsed_seconds / 3600) as u8;
-            if elapsed_hours >= pause_config.maintenance_duration_hours {
-                pause_config.maintenance_pause = false;
-                pause_config.maintenance_start_time = 0;
-            }
-        }
-        require!(!pause_config.emergency_pause, VaultError::EmergencyPaused);
-        require!(!pause_config.maintenance_pause, VaultError::MaintenancePaused);
-        
-        // Admin only access
+    /// Create the empty blacklist (admin only)
+    pub fn initialize_blacklist(ctx: Context<InitializeBlacklist>) -> Result<()> {
         let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
         require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
-        
-        // Validate remaining accounts match stakes
-        require!(
-            ctx.remaining_accounts.len() == stakes.len(),
-            VaultError::InvalidAmount
-        );
 
-        let house_info = ctx.accounts.house_vault.to_account_info();
-        let house_vault = &mut ctx.accounts.house_vault;
+        let blacklist = &mut ctx.accounts.blacklist;
+        blacklist.addresses = Vec::new();
+        blacklist.bump = ctx.bumps.blacklist;
+        Ok(())
+    }
 
-        // Process each bet and settle operation
-        for (i, ((((stake, payout), bet_id), game_id), gem_data)) in stakes.iter()
-            .zip(payouts.iter())
-            .zip(bet_ids.iter())
-            .zip(game_ids.iter())
-            .zip(gem_datas.iter())
-            .enumerate() {
-            let vault_info = &ctx.remaining_accounts[i];
-            
-            // stake can be 0 if it was already deducted in a previous transaction
-            
-            // If stake > 0, ensure vault has enough funds
-            if *stake > 0 {
-                require!(**vault_info.lamports.borrow() >= *stake, VaultError::InsufficientFunds);
-            }
-        
-            // Update house vault volume (only if there was an actual stake)
-            if *stake > 0 {
-                house_vault.total_volume = house_vault.total_volume.checked_add(*stake).ok_or(VaultError::Overflow)?;
-            }
-        
-            // Calculate net change based on stake and payout
-            if *stake == 0 {
-                // Stake was already deducted - this is a pure payout (win)
-                if *payout > 0 {
-                    // Player wins - house pays the full payout
-                    require!(**house_info.lamports.borrow() >= *payout, VaultError::HouseInsufficient);
-                    **house_info.try_borrow_mut_lamports()? -= *payout;
-                    **vault_info.try_borrow_mut_lamports()? += *payout;
-                }
-            } else {
-                // Normal bet and settle with stake
-                if *payout > *stake {
-                    // Player wins - house pays the difference
-                    let profit = *payout - *stake;
-                    require!(**house_info.lamports.borrow() >= profit, VaultError::HouseInsufficient);
-                    **house_info.try_borrow_mut_lamports()? -= profit;
-                    **vault_info.try_borrow_mut_lamports()? += profit;
-                } else if *payout < *stake {
-                    // Player loses - deduct loss from vault, add to house
-                    let loss = *stake - *payout;
-                    require!(**vault_info.lamports.borrow() >= loss, VaultError::InsufficientFunds);
-                    **vault_info.try_borrow_mut_lamports()? -= loss;
-                    **house_info.try_borrow_mut_lamports()? += loss;
-                } else {
-                    // Draw - no net change
-                }
-            }
+    /// Bar a wallet from creating a vault, depositing and betting (multisig only)
+    ///
+    /// Withdrawals stay open so blocked users can still recover their funds.
+    pub fn add_to_blacklist(ctx: Context<UpdateBlacklist>, address: Pubkey) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
 
-            msg!("Batch item {}: betId={}, gameId={}, stake={}, payout={}, outcome={}, gameData={:?}", 
-                 i, bet_id, game_id, stake, payout,
-                 if *payout > *stake { "WIN" } else if *payout < *stake { "LOSS" } else { "DRAW" }, gem_data);
+        let blacklist = &mut ctx.accounts.blacklist;
+        if !blacklist.contains(&address) {
+            require!(blacklist.addresses.len() < MAX_BLACKLIST_LEN, VaultError::BlacklistFull);
+            blacklist.addresses.push(address);
         }
 
-        msg!("Batch bet and settle completed: {} games, betIds={:?}, gameIds={:?}", stakes.len(), bet_ids, game_ids);
+        msg!("Blacklisted: {}", address);
         Ok(())
     }
 
-    /// Start maintenance pause (admin or multisig)
-    pub fn start_maintenance_pause(ctx: Context<StartMaintenancePause>) -> Result<()> {
-        let config = &mut ctx.accounts.pause_config;
+    /// Lift a blacklist entry (multisig only)
+    pub fn remove_from_blacklist(ctx: Context<UpdateBlacklist>, address: Pubkey) -> Result<()> {
         let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        ctx.accounts.blacklist.addresses.retain(|entry| *entry != address);
+
+        msg!("Removed from blacklist: {}", address);
+        Ok(())
+    }
+
+    /// Create the empty registry of game programs allowed to settle via CPI (admin only)
+    pub fn initialize_game_programs(ctx: Context<InitializeGamePrograms>) -> Result<()> {
         let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
-        
-        require!(
-            ctx.accounts.authority.key() == multisig || ctx.accounts.authority.key() == admin,
-            VaultError::Unauthorized
-        );
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
 
-        config.maintenance_pause = true;
-        config.maintenance_start_time = Clock::get()?.unix_timestamp;
-        
-        msg!("Maintenance pause started at {}", config.maintenance_start_time);
+        let registry = &mut ctx.accounts.game_programs;
+        registry.programs = Vec::new();
+        registry.bump = ctx.bumps.game_programs;
         Ok(())
     }
 
-    /// Emergency pause (multisig only)
-    pub fn emergency_pause(ctx: Context<EmergencyPause>) -> Result<()> {
-        let config = &mut ctx.accounts.pause_config;
+    /// Allow `program` to settle bets via CPI, signing with its `GAME_AUTHORITY_SEED` PDA (multisig only)
+    pub fn add_game_program(ctx: Context<UpdateGamePrograms>, program: Pubkey) -> Result<()> {
         let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
-        
         require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
 
-        config.emergency_pause = true;
-        config.maintenance_pause = false; // Override maintenance pause
-        
-        msg!("Emergency pause activated");
+        let registry = &mut ctx.accounts.game_programs;
+        if !registry.programs.iter().any(|entry| entry.program_id == program) {
+            require!(registry.programs.len() < MAX_GAME_PROGRAMS, VaultError::GameProgramsFull);
+            let (authority, _bump) = Pubkey::find_program_address(&[GAME_AUTHORITY_SEED], &program);
+            registry.programs.push(GameProgram { program_id: program, authority });
+        }
+
+        msg!("Game program allowed: {}", program);
         Ok(())
     }
 
-    /// Unpause (multisig only)
-    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
-        let config = &mut ctx.accounts.pause_config;
+    /// Revoke a game program's CPI settlement rights (multisig only)
+    pub fn remove_game_program(ctx: Context<UpdateGamePrograms>, program: Pubkey) -> Result<()> {
         let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
-        
         require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
 
-        config.emergency_pause = false;
-        config.maintenance_pause = false;
-        config.maintenance_start_time = 0;
-        
-        msg!("All pauses deactivated");
+        ctx.accounts.game_programs.programs.retain(|entry| entry.program_id != program);
+
+        msg!("Game program removed: {}", program);
         Ok(())
     }
 
-    /// Get pause status for UI (readable method)
-    pub fn get_pause_status(ctx: Context<GetPauseStatus>) -> Result<()> {
-        let config = &ctx.accounts.pause_config;
-        let clock = Clock::get()?;
-        let current_time = clock.unix_timestamp;
-        
-        if config.emergency_pause {
-            msg!("EMERGENCY_PAUSE:true");
-            msg!("MAINTENANCE_PAUSE:false");
-            msg!("RESUME_TIME:indefinite");
-            msg!("MESSAGE:Emergency pause active - no operations allowed");
-        } else if config.maintenance_pause {
-            let elapsed_hours = (current_time - config.maintenance_start_time) / 3600;
-            let remaining_hours = config.maintenance_duration_hours.saturating_sub(elapsed_hours as u8);
-            
-            if remaining_hours > 0 {
-                msg!("EMERGENCY_PAUSE:false");
-                msg!("MAINTENANCE_PAUSE:true");
-                msg!("RESUME_TIME:{} hours", remaining_hours);
-                msg!("MESSAGE:Maintenance in progress - will resume in {} hours", remaining_hours);
-            } else {
-                // Auto-resume maintenance pause
-                msg!("EMERGENCY_PAUSE:false");
-                msg!("MAINTENANCE_PAUSE:false");
-                msg!("RESUME_TIME:now");
-                msg!("MESSAGE:Maintenance complete - operations restored");
-            }
-        } else {
-            msg!("EMERGENCY_PAUSE:false");
-            msg!("MAINTENANCE_PAUSE:false");
-            msg!("RESUME_TIME:now");
-            msg!("MESSAGE:All operations active");
-        }
-        
+    /// Create the payout-bound table used to sanity-check every settlement (admin only)
+    ///
+    /// * `default_max_multiplier` – bound for games without their own entry (100 = 1x)
+    /// * `max_unstaked_payout` – bound for payouts settled with `stake == 0`
+    /// * `max_bets_per_second` – settlements allowed per vault per second (0 = unlimited)
+    /// * `max_transaction_payout` – summed payouts allowed per transaction (0 = unlimited)
+    /// * `max_daily_net_win` – net winnings per vault per 24h before bets are refused (0 = unlimited)
+    pub fn initialize_game_limits(
+        ctx: Context<InitializeGameLimits>,
+        default_max_multiplier: u32,
+        max_unstaked_payout: u64,
+        max_bets_per_second: u32,
+        max_transaction_payout: u64,
+        max_daily_net_win: u64,
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!(default_max_multiplier >= 100, VaultError::InvalidAmount);
+
+        let limits = &mut ctx.accounts.game_limits;
+        limits.default_max_multiplier = default_max_multiplier;
+        limits.max_unstaked_payout = max_unstaked_payout;
+        limits.max_bets_per_second = max_bets_per_second;
+        limits.max_transaction_payout = max_transaction_payout;
+        limits.max_daily_net_win = max_daily_net_win;
+        limits.payout_stream_threshold = 0;
+        limits.payout_stream_tranches = 0;
+        limits.payout_stream_interval_secs = 0;
+        limits.games = Vec::new();
+        limits.bump = ctx.bumps.game_limits;
         Ok(())
     }
 
-    /// Change authorities (multisig only)
-    pub fn change_authority(
-        ctx: Context<ChangeAuthority>,
-        new_multisig: Option<Pubkey>,
-        new_admin: Option<Pubkey>,
-    ) -> Result<()> {
-        let house_vault = &mut ctx.accounts.house_vault;
+    /// Cap settlements per vault per second; 0 disables the limit (multisig only)
+    pub fn set_bet_rate_limit(ctx: Context<SetGameLimits>, max_bets_per_second: u32) -> Result<()> {
         let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
-        
         require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
 
-        if let Some(new_multisig_pubkey) = new_multisig {
-            house_vault.multisig_authority = new_multisig_pubkey;
-            msg!("Multisig authority updated to: {}", new_multisig_pubkey);
-        }
+        ctx.accounts.game_limits.max_bets_per_second = max_bets_per_second;
 
-        if let Some(new_admin_pubkey) = new_admin {
-            house_vault.admin_authority = new_admin_pubkey;
-            msg!("Admin authority updated to: {}", new_admin_pubkey);
-        }
+        msg!("Bet rate limit set: maxBetsPerSecond={}", max_bets_per_second);
+        Ok(())
+    }
+
+    /// Cap the summed payouts of all settlements in one transaction; 0 disables the cap (multisig only)
+    pub fn set_transaction_payout_limit(ctx: Context<SetGameLimits>, max_transaction_payout: u64) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
 
+        ctx.accounts.game_limits.max_transaction_payout = max_transaction_payout;
+
+        msg!("Transaction payout limit set: maxTransactionPayout={}", max_transaction_payout);
         Ok(())
     }
-}
 
-// Data structures
-#[account]
-pub struct UserVault {
-    pub owner: Pubkey,           // Vault owner
-    pub bump: u8,                // PDA bump
-    pub locked_amount: u64,      // Amount locked in active games
-    pub active_games: u32,       // Number of active games
-    pub accum_wager: u64,        // Accumulated wager for gem rewards
-    pub version: u8,             // Contract version (2)
-}
+    /// Cap each vault's net winnings per 24h window, refusing bets once reached; 0 disables it (multisig only)
+    ///
+    /// A vault's own `daily_win_cap`, set with `set_vault_win_cap`, takes precedence.
+    pub fn set_daily_win_cap(ctx: Context<SetGameLimits>, max_daily_net_win: u64) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
 
-#[account]
-pub struct HouseVault {
-    pub bump: u8,                // PDA bump
-    pub multisig_authority: Pubkey, // Multisig authority
-    pub admin_authority: Pubkey,    // Admin authority
-    pub total_volume: u64,          // Total betting volume
-    pub version: u8,             // Contract version (2)
-}
+        ctx.accounts.game_limits.max_daily_net_win = max_daily_net_win;
+
+        msg!("Daily win cap set: maxDailyNetWin={}", max_daily_net_win);
+        Ok(())
+    }
+
+    /// Give one vault its own daily net-win cap, or fall back to the global one with 0 (multisig only)
+    pub fn set_vault_win_cap(ctx: Context<SetVaultWinCap>, daily_win_cap: u64) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        vault.daily_win_cap = daily_win_cap;
+
+        msg!("Vault win cap set: user={}, dailyWinCap={}", vault.owner, daily_win_cap);
+        Ok(())
+    }
+
+    /// Stream the part of payouts above `threshold` in `tranches` parts, one every `interval_secs` (multisig only)
+    ///
+    /// Only `bet_and_settle` can open the schedule; other settlement paths
+    /// refuse payouts above the threshold. A threshold of 0 disables streaming.
+    pub fn set_payout_stream(
+        ctx: Context<SetGameLimits>,
+        threshold: u64,
+        tranches: u16,
+        interval_secs: i64,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+        require!(
+            threshold == 0 || ((1..=MAX_PAYOUT_TRANCHES).contains(&tranches) && interval_secs > 0),
+            VaultError::InvalidAmount
+        );
+
+        let limits = &mut ctx.accounts.game_limits;
+        limits.payout_stream_threshold = threshold;
+        limits.payout_stream_tranches = tranches;
+        limits.payout_stream_interval_secs = interval_secs;
+
+        msg!("Payout stream set: threshold={}, tranches={}, interval={}s", threshold, tranches, interval_secs);
+        Ok(())
+    }
+
+    /// Set one game's maximum payout multiplier, or remove it with 0 (multisig only)
+    ///
+    /// Kept off the admin key so a compromised settlement key cannot loosen its own bound.
+    pub fn set_game_limit(ctx: Context<SetGameLimits>, game_id: u64, max_multiplier: u32) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+        require!(max_multiplier == 0 || max_multiplier >= 100, VaultError::InvalidAmount);
+
+        let limits = &mut ctx.accounts.game_limits;
+        let existing = limits.games.iter().position(|limit| limit.game_id == game_id);
+        match (existing, max_multiplier) {
+            (Some(i), 0) => {
+                limits.games.swap_remove(i);
+            }
+            (Some(i), _) => limits.games[i].max_multiplier = max_multiplier,
+            (None, 0) => {}
+            (None, _) => {
+                require!(limits.games.len() < MAX_GAME_LIMITS, VaultError::GameLimitsFull);
+                limits.games.push(GameLimit { game_id, max_multiplier });
+            }
+        }
+
+        msg!("Game limit set: gameId={}, maxMultiplier={}", game_id, max_multiplier);
+        Ok(())
+    }
+
+    /// Publish the rules of an originals game, enforced on each of its settlements (multisig only)
+    ///
+    /// * `house_edge_bps` – edge taken off the fair `max_multiplier` (100 = 1x) to cap a win
+    /// * `min_stake`, `max_stake` – stake bounds; a `max_stake` of 0 leaves stakes uncapped
+    pub fn initialize_game_config(
+        ctx: Context<InitializeGameConfig>,
+        game_id: u64,
+        house_edge_bps: u16,
+        max_multiplier: u32,
+        min_stake: u64,
+        max_stake: u64,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.game_config;
+        config.game_id = game_id;
+        config.bump = ctx.bumps.game_config;
+        config.set_rules(house_edge_bps, max_multiplier, min_stake, max_stake)?;
+
+        msg!("Game config created: gameId={}, edge={}bps, maxMultiplier={}, stake={}..{}",
+             game_id, house_edge_bps, max_multiplier, min_stake, max_stake);
+        Ok(())
+    }
+
+    /// Change the published rules of an originals game (multisig only)
+    pub fn set_game_config(
+        ctx: Context<SetGameConfig>,
+        house_edge_bps: u16,
+        max_multiplier: u32,
+        min_stake: u64,
+        max_stake: u64,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.game_config;
+        config.set_rules(house_edge_bps, max_multiplier, min_stake, max_stake)?;
+
+        msg!("Game config set: gameId={}, edge={}bps, maxMultiplier={}, stake={}..{}",
+             config.game_id, house_edge_bps, max_multiplier, min_stake, max_stake);
+        Ok(())
+    }
+
+    /// Create the mini, major and grand jackpot of `game_id`, all disabled until configured (multisig only)
+    ///
+    /// `vrf_authority` co-signs every draw; the outcomes come from ORAO VRF.
+    pub fn initialize_jackpot(ctx: Context<InitializeJackpot>, game_id: u64, vrf_authority: Pubkey) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let jackpot = &mut ctx.accounts.jackpot;
+        jackpot.game_id = game_id;
+        jackpot.vrf_authority = vrf_authority;
+        jackpot.tiers = [JackpotTier::default(); JACKPOT_TIERS];
+        jackpot.bump = ctx.bumps.jackpot;
+
+        msg!("Jackpot initialized: gameId={}, vrfAuthority={}", game_id, vrf_authority);
+        Ok(())
+    }
+
+    /// Configure one jackpot tier: 0 mini, 1 major, 2 grand (multisig only)
+    ///
+    /// * `contribution_bps` – share of each stake the house adds to the pot
+    /// * `seed`             – pot the tier restarts from; a smaller pot is topped up from the house now
+    /// * `odds`             – a draw hits once in `odds`; 0 disables the tier
+    pub fn set_jackpot_tier(
+        ctx: Context<SetJackpotTier>,
+        tier: u8,
+        contribution_bps: u16,
+        seed: u64,
+        odds: u32,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let jackpot_info = ctx.accounts.jackpot.to_account_info();
+        let jackpot = &mut ctx.accounts.jackpot;
+        let top_up = jackpot.set_tier(tier as usize, contribution_bps, seed, odds)?;
+        if top_up > 0 {
+            transfer_lamports(&ctx.accounts.house_vault.to_account_info(), &jackpot_info, top_up)?;
+        }
+
+        msg!("Jackpot tier set: gameId={}, tier={}, contribution={}bps, seed={}, odds=1/{}, pot={}",
+             jackpot.game_id, tier, contribution_bps, seed, odds, jackpot.tiers[tier as usize].pot);
+        Ok(())
+    }
+
+    /// Pay a jackpot tier's pot into the vault that placed `bet_id`, if the bet's VRF result hits it
+    ///
+    /// The bet must have a receipt on `vault` for this game. The draw reads the
+    /// fulfilled ORAO randomness requested with `Jackpot::vrf_seed`, which is
+    /// fixed by the jackpot, bet and tier, so no signer can pick the outcome.
+    /// A tier pays at most once per bet. It restarts from its seed, paid by the house.
+    pub fn award_jackpot(ctx: Context<AwardJackpot>, game_id: u64, tier: u8, bet_id: String) -> Result<()> {
+        require!(bet_id.len() <= MAX_BET_ID_LEN, VaultError::BetIdTooLong);
+        require!((tier as usize) < JACKPOT_TIERS, VaultError::InvalidJackpotTier);
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+
+        let receipt = &mut ctx.accounts.bet_receipt;
+        require!(receipt.jackpot_tiers_paid & (1 << tier) == 0, VaultError::JackpotAlreadyPaid);
+        let bet_hash = receipt.bet_id_hash;
+        let seed = Jackpot::vrf_seed(&ctx.accounts.jackpot.key(), &bet_hash, tier);
+        let randomness = orao_randomness(&ctx.accounts.randomness, &seed)?;
+        require!(ctx.accounts.jackpot.is_hit(tier as usize, &randomness), VaultError::JackpotNotHit);
+        receipt.jackpot_tiers_paid |= 1 << tier;
+
+        let jackpot_info = ctx.accounts.jackpot.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let jackpot = &mut ctx.accounts.jackpot;
+        let (amount, seed) = jackpot.reset_tier(tier as usize)?;
+        transfer_lamports(&jackpot_info, &vault_info, amount)?;
+        transfer_lamports(&ctx.accounts.house_vault.to_account_info(), &jackpot_info, seed)?;
+
+        let vault_key = ctx.accounts.vault.key();
+        msg!("Jackpot awarded: gameId={}, tier={}, betId={}, amount={}, vault={}",
+             game_id, tier, bet_id, amount, vault_key);
+        emit_cpi!(JackpotAwarded {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault: vault_key,
+            bet_id_hash: bet_hash,
+            game_id,
+            tier,
+            amount,
+        });
+        let threshold = large_transfer_threshold(&ctx.accounts.compliance_config)?;
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let config = &mut ctx.accounts.global_config;
+        if let Some(event) = flag_large_transfer(&mut vault, vault_key, TRANSFER_PAYOUT, amount, threshold, config)? {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
+    /// Update the bounds applied to games without an entry and to unstaked payouts (multisig only)
+    pub fn set_default_game_limits(
+        ctx: Context<SetGameLimits>,
+        default_max_multiplier: u32,
+        max_unstaked_payout: u64,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+        require!(default_max_multiplier >= 100, VaultError::InvalidAmount);
+
+        let limits = &mut ctx.accounts.game_limits;
+        limits.default_max_multiplier = default_max_multiplier;
+        limits.max_unstaked_payout = max_unstaked_payout;
+
+        msg!("Default game limits set: maxMultiplier={}, maxUnstakedPayout={}", default_max_multiplier, max_unstaked_payout);
+        Ok(())
+    }
+
+    /// Close pause configuration account and return rent to authority
+    pub fn close_pause_config(ctx: Context<ClosePauseConfig>) -> Result<()> {
+        // Only admin or multisig can close
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.pause_config.admin_authority ||
+            ctx.accounts.authority.key() == ctx.accounts.pause_config.multisig_authority,
+            VaultError::Unauthorized
+        );
+        
+        // Close the account and return rent to authority
+        ctx.accounts.pause_config.close(ctx.accounts.authority.to_account_info())?;
+        Ok(())
+    }
+
+    /// Rewrite a pre-bitflag PauseConfig in the packed layout (admin or multisig)
+    ///
+    /// The account shrinks to its new size and the freed rent goes to the caller.
+    pub fn migrate_pause_config(ctx: Context<MigratePauseConfig>) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(
+            ctx.accounts.authority.key() == multisig || ctx.accounts.authority.key() == admin,
+            VaultError::Unauthorized
+        );
+
+        let config_info = ctx.accounts.pause_config.to_account_info();
+        let authority_info = ctx.accounts.authority.to_account_info();
+        let from = migrate_account::<PauseConfig>(&config_info, &authority_info, &ctx.accounts.system_program)?;
+        msg!("Pause config migrated: version {} -> {}", from, PAUSE_CONFIG_VERSION);
+        Ok(())
+    }
+
+    /// Bring the HouseVault to the current layout (admin or multisig, who pays any extra rent)
+    pub fn migrate_house_vault(ctx: Context<MigrateHouseVault>) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(
+            ctx.accounts.authority.key() == multisig || ctx.accounts.authority.key() == admin,
+            VaultError::Unauthorized
+        );
+
+        let house_info = ctx.accounts.house_vault.to_account_info();
+        let authority_info = ctx.accounts.authority.to_account_info();
+        let from = migrate_account::<HouseVault>(&house_info, &authority_info, &ctx.accounts.system_program)?;
+        msg!("House vault migrated: version {} -> {}", from, HOUSE_VERSION);
+        Ok(())
+    }
+
+    /// Bring the GlobalConfig to the current layout (admin or multisig, who pays the extra rent)
+    pub fn migrate_global_config(ctx: Context<MigrateGlobalConfig>) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(
+            ctx.accounts.authority.key() == multisig || ctx.accounts.authority.key() == admin,
+            VaultError::Unauthorized
+        );
+
+        let config_info = ctx.accounts.global_config.to_account_info();
+        let authority_info = ctx.accounts.authority.to_account_info();
+        let from = migrate_account::<GlobalConfig>(&config_info, &authority_info, &ctx.accounts.system_program)?;
+        msg!("Global config migrated: version {} -> {}", from, GLOBAL_CONFIG_VERSION);
+        Ok(())
+    }
+
+    /// Bring the GlobalStats to the current layout (admin or multisig, who pays the extra rent)
+    pub fn migrate_global_stats(ctx: Context<MigrateGlobalStats>) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(
+            ctx.accounts.authority.key() == multisig || ctx.accounts.authority.key() == admin,
+            VaultError::Unauthorized
+        );
+
+        let stats_info = ctx.accounts.global_stats.to_account_info();
+        let authority_info = ctx.accounts.authority.to_account_info();
+        let from = migrate_account::<GlobalStats>(&stats_info, &authority_info, &ctx.accounts.system_program)?;
+        msg!("Global stats migrated: version {} -> {}", from, GLOBAL_STATS_VERSION);
+        Ok(())
+    }
+
+    /// Deposit SOL into the user's vault
+    ///
+    /// `reference` is a Solana Pay reference or any 32-byte off-chain order id,
+    /// echoed in the `Deposited` event. It may also be passed as the first
+    /// remaining account, read-only and holding no lamports, so
+    /// `getSignaturesForAddress` finds the deposit.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, reference: Option<[u8; 32]>) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        ctx.accounts.global_config.check_deposit(amount)?;
+        let reference = reference.unwrap_or_default();
+        if let Some(account) = ctx.remaining_accounts.first() {
+            require!(account.key.to_bytes() == reference, VaultError::ReferenceMismatch);
+        }
+        
+        // Check for any pause (with auto-unpause for maintenance)
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        
+        let owner = &ctx.accounts.owner;
+        let vault_info = ctx.accounts.vault.to_account_info();
+        screen_deposit(ctx.accounts, amount)?;
+        #[cfg(feature = "audit")]
+        let audit_before = lamport_total(&[owner.to_account_info(), vault_info.clone()]);
+
+        invoke(
+            &system_instruction::transfer(
+                &owner.key(),
+                &vault_info.key(),
+                amount,
+            ),
+            &[
+                owner.to_account_info().clone(),
+                vault_info.clone(),
+                ctx.accounts.system_program.to_account_info().clone(),
+            ],
+        )?;
+        ctx.accounts.global_stats.load_mut()?.record_call(Invocation::Deposit)?;
+
+        #[cfg(feature = "audit")]
+        {
+            audit_conservation(audit_before, lamport_total(&[owner.to_account_info(), vault_info.clone()]))?;
+            audit_locked(&vault_info, ctx.accounts.vault.load()?.locked_amount)?;
+        }
+
+        emit_cpi!(Deposited {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            user: ctx.accounts.vault.load()?.owner,
+            amount,
+            new_balance: vault_info.lamports(),
+            reference,
+        });
+        let threshold = large_transfer_threshold(&ctx.accounts.compliance_config)?;
+        let vault_key = ctx.accounts.vault.key();
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let config = &mut ctx.accounts.global_config;
+        if let Some(event) = flag_large_transfer(&mut vault, vault_key, TRANSFER_DEPOSIT, amount, threshold, config)? {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
+    /// Withdraw SOL from the vault back to the user's wallet
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        
+        // Check for any pause (with auto-unpause for maintenance)
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let user_info = ctx.accounts.owner.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+
+        require!(vault.active_games == 0, VaultError::GamesInProgress);
+        require_kyc(&ctx.accounts.compliance_config, ctx.accounts.kyc_attestation.as_deref(), amount)?;
+        require_device_signature(&ctx.accounts.withdraw_guard, ctx.accounts.device.as_ref())?;
+        // Locked funds (e.g. an uncleared deposit bonus) cannot be withdrawn
+        let available = (**vault_info.lamports.borrow()).saturating_sub(vault.locked_amount);
+        require!(available >= amount, VaultError::InsufficientFunds);
+
+        #[cfg(feature = "audit")]
+        let audit_before = lamport_total(&[vault_info.clone(), user_info.clone()]);
+
+        transfer_lamports(&vault_info, &user_info, amount)?;
+        ctx.accounts.global_stats.load_mut()?.record_call(Invocation::Withdraw)?;
+
+        #[cfg(feature = "audit")]
+        {
+            audit_conservation(audit_before, lamport_total(&[vault_info.clone(), user_info.clone()]))?;
+            audit_locked(&vault_info, vault.locked_amount)?;
+        }
+
+        emit_cpi!(Withdrawn {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            user: vault.owner,
+            amount,
+            destination: user_info.key(),
+        });
+        let threshold = large_transfer_threshold(&ctx.accounts.compliance_config)?;
+        let vault_key = ctx.accounts.vault.key();
+        let config = &mut ctx.accounts.global_config;
+        if let Some(event) = flag_large_transfer(&mut vault, vault_key, TRANSFER_WITHDRAWAL, amount, threshold, config)? {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
+    /// Withdraw on behalf of an owner who signed an intent off-chain; the relayer pays the fees
+    ///
+    /// The owner's ed25519 signature over [`withdraw_intent_message`] must be
+    /// verified by the instruction right before this one. `fee` comes out of
+    /// the vault on top of `amount` and goes to the relayer. Each nonce can be
+    /// used once per vault.
+    pub fn withdraw_with_intent(
+        ctx: Context<WithdrawWithIntent>,
+        amount: u64,
+        fee: u64,
+        nonce: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require!(Clock::get()?.unix_timestamp <= expires_at, VaultError::IntentExpired);
+
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let message = withdraw_intent_message(&ctx.accounts.vault.key(), amount, fee, nonce, expires_at);
+        require_ed25519_signature(&ctx.accounts.instructions, &vault.owner, &message)?;
+
+        let owner_info = ctx.accounts.owner.to_account_info();
+        let relayer_info = ctx.accounts.relayer.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+
+        require!(vault.active_games == 0, VaultError::GamesInProgress);
+        require_kyc(&ctx.accounts.compliance_config, ctx.accounts.kyc_attestation.as_deref(), amount)?;
+        require_device_signature(&ctx.accounts.withdraw_guard, ctx.accounts.device.as_ref())?;
+        let total = amount.checked_add(fee).ok_or(VaultError::Overflow)?;
+        let available = vault_info.lamports().saturating_sub(vault.locked_amount);
+        require!(available >= total, VaultError::InsufficientFunds);
+
+        transfer_lamports(&vault_info, &owner_info, amount)?;
+        transfer_lamports(&vault_info, &relayer_info, fee)?;
+        ctx.accounts.global_stats.load_mut()?.record_call(Invocation::Withdraw)?;
+
+        let intent = &mut ctx.accounts.intent;
+        intent.vault = ctx.accounts.vault.key();
+        intent.nonce = nonce;
+        intent.bump = ctx.bumps.intent;
+
+        msg!("Relayed withdraw: user={}, amount={}, fee={}, nonce={}", vault.owner, amount, fee, nonce);
+        emit_cpi!(Withdrawn {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            user: vault.owner,
+            amount,
+            destination: owner_info.key(),
+        });
+        let threshold = large_transfer_threshold(&ctx.accounts.compliance_config)?;
+        let vault_key = ctx.accounts.vault.key();
+        let config = &mut ctx.accounts.global_config;
+        if let Some(event) = flag_large_transfer(&mut vault, vault_key, TRANSFER_WITHDRAWAL, total, threshold, config)? {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
+    /// Require `device` to co-sign every withdrawal from the owner's vault
+    ///
+    /// Both keys sign here, so the device key is known to be held.
+    pub fn enable_withdraw_guard(ctx: Context<EnableWithdrawGuard>) -> Result<()> {
+        let guard = &mut ctx.accounts.withdraw_guard;
+        guard.vault = ctx.accounts.vault.key();
+        guard.device = ctx.accounts.device.key();
+        guard.removal_requested_at = 0;
+        guard.bump = ctx.bumps.withdraw_guard;
+
+        msg!("Withdraw guard enabled: vault={}, device={}", guard.vault, guard.device);
+        Ok(())
+    }
+
+    /// Start the delay after which the owner alone may drop the guard, for a lost device key
+    pub fn request_withdraw_guard_removal(ctx: Context<RequestWithdrawGuardRemoval>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.withdraw_guard.removal_requested_at = now;
+
+        msg!("Withdraw guard removal requested: vault={}, at={}", ctx.accounts.vault.key(), now);
+        Ok(())
+    }
+
+    /// Drop the guard, refunding its rent to the owner
+    ///
+    /// Needs the device key to co-sign, or a removal requested at least
+    /// `WITHDRAW_GUARD_REMOVAL_DELAY_SECS` ago.
+    pub fn remove_withdraw_guard(ctx: Context<RemoveWithdrawGuard>) -> Result<()> {
+        let guard = &ctx.accounts.withdraw_guard;
+        let device_signed = ctx.accounts.device.as_ref().is_some_and(|device| device.key() == guard.device);
+        let delay_elapsed = guard.removal_requested_at != 0
+            && Clock::get()?.unix_timestamp - guard.removal_requested_at >= WITHDRAW_GUARD_REMOVAL_DELAY_SECS;
+        require!(device_signed || delay_elapsed, VaultError::DeviceSignatureRequired);
+
+        msg!("Withdraw guard removed: vault={}", guard.vault);
+        Ok(())
+    }
+
+    /// Create the queue that orders withdrawal requests filed during pauses (admin only)
+    pub fn initialize_withdrawal_queue(ctx: Context<InitializeWithdrawalQueue>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let queue = &mut ctx.accounts.withdrawal_queue;
+        queue.head = 0;
+        queue.tail = 0;
+        queue.bump = ctx.bumps.withdrawal_queue;
+        Ok(())
+    }
+
+    /// Queue a withdrawal of `amount` while an emergency or maintenance pause is in force
+    ///
+    /// The request takes the next ticket, and `process_withdrawal_request`
+    /// honors tickets strictly in order once operations resume. KYC and the
+    /// withdraw guard are checked now, as `withdraw` would. One open request per vault.
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.pause_config.is_paused(now), VaultError::NotPaused);
+        require_kyc(&ctx.accounts.compliance_config, ctx.accounts.kyc_attestation.as_deref(), amount)?;
+        require_device_signature(&ctx.accounts.withdraw_guard, ctx.accounts.device.as_ref())?;
+
+        let queue = &mut ctx.accounts.withdrawal_queue;
+        let request = &mut ctx.accounts.withdrawal_request;
+        request.vault = ctx.accounts.vault.key();
+        request.ticket = queue.tail;
+        request.amount = amount;
+        request.requested_at = now;
+        request.bump = ctx.bumps.withdrawal_request;
+        queue.tail = queue.tail.checked_add(1).ok_or(VaultError::Overflow)?;
+
+        msg!("Withdrawal requested: vault={}, amount={}, ticket={}", request.vault, amount, request.ticket);
+        Ok(())
+    }
+
+    /// Drop the withdrawal request at the head of the queue without paying it (vault owner or multisig)
+    ///
+    /// Unblocks the queue when the head request cannot be processed. The
+    /// request closes, refunding its rent to the owner.
+    pub fn drop_withdrawal_request(ctx: Context<DropWithdrawalRequest>) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        let authority = ctx.accounts.authority.key();
+        require!(authority == ctx.accounts.owner.key() || authority == multisig, VaultError::Unauthorized);
+        let request = &ctx.accounts.withdrawal_request;
+        let queue = &mut ctx.accounts.withdrawal_queue;
+        require!(request.ticket == queue.head, VaultError::WithdrawalOutOfOrder);
+        queue.head = queue.head.checked_add(1).ok_or(VaultError::Overflow)?;
+
+        msg!("Withdrawal request dropped: vault={}, ticket={}, amount={}, by={}",
+             request.vault, request.ticket, request.amount, authority);
+        Ok(())
+    }
+
+    /// Honor the withdrawal request at the head of the queue once operations have resumed (anyone)
+    ///
+    /// Pays what the vault can spare up to the requested amount, and nothing
+    /// while games are in progress, so one short vault cannot stall the queue.
+    /// The request closes either way, refunding its rent to the owner.
+    pub fn process_withdrawal_request(ctx: Context<ProcessWithdrawalRequest>) -> Result<()> {
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        let request = &ctx.accounts.withdrawal_request;
+        let queue = &mut ctx.accounts.withdrawal_queue;
+        require!(request.ticket == queue.head, VaultError::WithdrawalOutOfOrder);
+        queue.head = queue.head.checked_add(1).ok_or(VaultError::Overflow)?;
+
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let owner_info = ctx.accounts.owner.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+        // Keep the vault rent exempt, or the transfer fails and the head ticket can never be processed
+        let floor = Rent::get()?.minimum_balance(vault_info.data_len())
+            .checked_add(vault.locked_amount).ok_or(VaultError::Overflow)?;
+        let available = match vault.active_games {
+            0 => vault_info.lamports().saturating_sub(floor),
+            _ => 0,
+        };
+        let paid = request.amount.min(available);
+
+        msg!("Withdrawal request honored: vault={}, ticket={}, requested={}, paid={}",
+             request.vault, request.ticket, request.amount, paid);
+        if paid == 0 {
+            return Ok(());
+        }
+        transfer_lamports(&vault_info, &owner_info, paid)?;
+        ctx.accounts.global_stats.load_mut()?.record_call(Invocation::Withdraw)?;
+
+        emit_cpi!(Withdrawn {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            user: vault.owner,
+            amount: paid,
+            destination: owner_info.key(),
+        });
+        let threshold = large_transfer_threshold(&ctx.accounts.compliance_config)?;
+        let vault_key = ctx.accounts.vault.key();
+        let config = &mut ctx.accounts.global_config;
+        if let Some(event) = flag_large_transfer(&mut vault, vault_key, TRANSFER_WITHDRAWAL, paid, threshold, config)? {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
+    /// Atomic bet and settle operation
+    pub fn bet_and_settle(
+        ctx: Context<BetAndSettle>,
+        stake: u64,
+        payout: u64,
+        bet_id: String,
+        game_id: u64,
+        category: u8,
+        gem_data: Vec<u8>,
+    ) -> Result<()> {
+        bet_and_settle_with_side_bets(ctx, stake, payout, bet_id, game_id, category, gem_data, Vec::new())
+    }
+
+    /// Atomic bet and settle carrying side bets (e.g. blackjack perfect pairs) placed with the parent
+    ///
+    /// Each side bet is bounded by its own game's limits and settled against
+    /// the same vault in this instruction, emitting a `SideBetSettled` after
+    /// the parent's `BetSettled`. Side bet stakes count toward the parent's `category`.
+    pub fn bet_and_settle_with_side_bets(
+        ctx: Context<BetAndSettle>,
+        stake: u64,
+        payout: u64,
+        bet_id: String,
+        game_id: u64,
+        category: u8,
+        gem_data: Vec<u8>,
+        side_bets: Vec<SideBet>,
+    ) -> Result<()> {
+        // Require exactly 7 u8 values
+        check_gem_data(&gem_data)?;
+        require!(bet_id.len() <= MAX_BET_ID_LEN, VaultError::BetIdTooLong);
+        require!(stake > 0 || payout > 0, VaultError::EmptySettlement);
+        require!(side_bets.len() <= MAX_SIDE_BETS, VaultError::TooManySideBets);
+        ctx.accounts.global_config.check_stake(stake)?;
+        ctx.accounts.game_limits.check_payout(game_id, stake, payout)?;
+        if let Some(config) = load_optional::<GameConfig>(&ctx.accounts.game_config)? {
+            config.check_settlement(stake, payout)?;
+        }
+        // Each side bet brings its own game config, in order, as a remaining account
+        require!(ctx.remaining_accounts.len() == side_bets.len(), VaultError::AccountCountMismatch);
+        for (side_bet, config) in side_bets.iter().zip(ctx.remaining_accounts) {
+            require!(side_bet.stake > 0, VaultError::InvalidAmount);
+            ctx.accounts.global_config.check_stake(side_bet.stake)?;
+            ctx.accounts.game_limits.check_payout(side_bet.game_id, side_bet.stake, side_bet.payout)?;
+            // Only the parent payout can stream, so a side bet must fit under the threshold
+            ctx.accounts.game_limits.check_unstreamed(side_bet.payout)?;
+            check_game_config(config, side_bet.game_id, side_bet.stake, side_bet.payout)?;
+        }
+        let total_payout = side_bets.iter()
+            .try_fold(payout, |sum, side_bet| sum.checked_add(side_bet.payout))
+            .ok_or(VaultError::Overflow)?;
+        let total_stake = side_bets.iter()
+            .try_fold(stake, |sum, side_bet| sum.checked_add(side_bet.stake))
+            .ok_or(VaultError::Overflow)?;
+        ctx.accounts.game_limits.check_transaction_payout(&ctx.accounts.instructions, total_payout)?;
+        reject_bundled_withdraw(&ctx.accounts.instructions, &[ctx.accounts.vault.key()])?;
+        {
+            let (now, limits) = (Clock::get()?.unix_timestamp, &ctx.accounts.game_limits);
+            let mut vault = ctx.accounts.vault.load_mut()?;
+            vault.record_settlement(now, limits.max_bets_per_second)?;
+            vault.check_win_cap(now, limits.max_daily_net_win)?;
+            vault.record_net_win(now, total_stake, total_payout)?;
+        }
+        if let Some(config) = load_compliance_config(&ctx.accounts.compliance_config)? {
+            let vault = ctx.accounts.vault.load()?;
+            config.check_region(vault.region, game_id, stake)?;
+            for side_bet in &side_bets {
+                config.check_region(vault.region, side_bet.game_id, side_bet.stake)?;
+            }
+            config.check_terms(&vault, total_stake)?;
+        }
+
+        // stake can be 0 if it was already deducted in a previous transaction
+        
+        // Check for any pause (with auto-unpause for maintenance)
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        
+        // Admin, or a registered game program settling via CPI
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+
+        // The receipt (if any) is funded before the handler runs, so these balances must sum to a constant
+        #[cfg(feature = "audit")]
+        let audit_accounts: Vec<AccountInfo> = [
+            Some(ctx.accounts.vault.to_account_info()),
+            Some(ctx.accounts.house_vault.to_account_info()),
+            Some(ctx.accounts.authority.to_account_info()),
+            Some(ctx.accounts.jackpot.to_account_info()),
+            ctx.accounts.bet_receipt.as_ref().map(|receipt| receipt.to_account_info()),
+            ctx.accounts.payout_schedule.as_ref().map(|schedule| schedule.to_account_info()),
+        ].into_iter().flatten().collect();
+        #[cfg(feature = "audit")]
+        let audit_before = lamport_total(&audit_accounts);
+
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let mut house_vault = ctx.accounts.house_vault.load_mut()?;
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let house_info = ctx.accounts.house_vault.to_account_info();
+
+        // If stake > 0, ensure vault has enough funds
+        if stake > 0 {
+            require!(**vault_info.lamports.borrow() >= stake, VaultError::InsufficientFunds);
+        }
+
+        // Track lifetime wager for reward eligibility
+        vault.lifetime_wager = vault.lifetime_wager.checked_add(stake).ok_or(VaultError::Overflow)?;
+
+        // Spend net losses from an active deposit bonus first, then credit playthrough and unlock it once met
+        settle_bonus(&ctx.accounts.bonus, &mut vault, total_stake, total_payout)?;
+
+        // Update house vault volume, overall and for the game's category
+        house_vault.record_volume(category, stake)?;
+
+        // Move the net result between vault and house, then feed the game's jackpot from the house.
+        // A payout above the streaming threshold is paid up to it; the rest is scheduled below.
+        let (paid_now, streamed) = ctx.accounts.game_limits.split_payout(payout);
+        require!(streamed > 0 || ctx.accounts.payout_schedule.is_none(), VaultError::PayoutScheduleMismatch);
+        apply_settlement(&vault_info, &house_info, stake, paid_now)?;
+        if stake > 0 {
+            contribute_to_jackpot(&ctx.accounts.jackpot.to_account_info(), &house_info, stake)?;
+        }
+
+        // Accrue loss-back on net losses while a promo window is open
+        settle_loss_back(&ctx.accounts.loss_back_promo, &ctx.accounts.loss_back, stake, payout)?;
+
+        // Boost the gems credited after a losing streak, which the boost ends, then count this outcome
+        let loss_streak = vault.loss_streak;
+        let pity = ctx.accounts.pity_config.as_ref()
+            .filter(|_| ctx.accounts.gem_inventory.is_some())
+            .and_then(|config| config.boost(loss_streak, &gem_data));
+        let gem_data = match pity {
+            Some(boosted) => {
+                msg!("Pity boost applied after {} losses: gems={:?}", loss_streak, boosted);
+                vault.loss_streak = 0;
+                boosted
+            }
+            None => gem_data,
+        };
+        vault.record_outcome(stake, payout);
+
+        // Credit awarded gems and consume any pending gem-burn boost
+        if let Some(inventory) = ctx.accounts.gem_inventory.as_mut() {
+            for (count, awarded) in inventory.counts.iter_mut().zip(gem_data.iter()) {
+                *count = count.checked_add(*awarded as u32).ok_or(VaultError::Overflow)?;
+            }
+            let boost_multiplier = inventory.boost_multiplier;
+            if boost_multiplier > 100 {
+                msg!("Gem boost consumed: multiplier={}", boost_multiplier);
+                inventory.boost_multiplier = 100;
+            }
+            emit_cpi!(GemsCredited {
+                version: EVENT_VERSION,
+                seq: ctx.accounts.global_config.next_seq()?,
+                vault: inventory.vault,
+                gems: compact_gems(&gem_data)?,
+                boost_multiplier,
+            });
+        }
+
+        {
+            let mut stats = ctx.accounts.global_stats.load_mut()?;
+            stats.record_call(Invocation::Settle)?;
+            stats.record_bet(ctx.accounts.vault.key(), stake, payout)?;
+        }
+
+        // Mint a trophy when the trophy accounts are attached and the win clears the threshold
+        mint_trophy(ctx.accounts, bet_id_hash(&bet_id), game_id, stake, payout)?;
+
+        // Settle side bets against the same vault, after the parent
+        for side_bet in &side_bets {
+            vault.lifetime_wager = vault.lifetime_wager.checked_add(side_bet.stake).ok_or(VaultError::Overflow)?;
+            house_vault.record_volume(category, side_bet.stake)?;
+            apply_settlement(&vault_info, &house_info, side_bet.stake, side_bet.payout)?;
+            ctx.accounts
+                .global_stats
+                .load_mut()?
+                .record_bet(ctx.accounts.vault.key(), side_bet.stake, side_bet.payout)?;
+        }
+
+        // Rebate part of the wager, side bets included, at the tier the vault's staked tokens reach
+        let rebate = match (ctx.accounts.staking_config.as_ref(), ctx.accounts.token_stake.as_ref()) {
+            (Some(config), Some(token_stake)) => {
+                let rebate_bps = config.rebate_bps(token_stake.staked);
+                Some((rebate_bps, config.rebate(token_stake.staked, total_stake)))
+            }
+            _ => None,
+        };
+        if let Some((rebate_bps, rebate)) = rebate.filter(|&(_, rebate)| rebate > 0) {
+            require!(house_info.lamports() >= rebate, VaultError::HouseInsufficient);
+            transfer_lamports(&house_info, &vault_info, rebate)?;
+
+            msg!("Staking rebate paid: betId={}, wagered={}, rebateBps={}, rebate={}",
+                 bet_id, total_stake, rebate_bps, rebate);
+            emit_cpi!(StakingRebatePaid {
+                version: EVENT_VERSION,
+                seq: ctx.accounts.global_config.next_seq()?,
+                vault: ctx.accounts.vault.key(),
+                bet_id_hash: bet_id_hash(&bet_id),
+                wagered: total_stake,
+                rebate_bps,
+                rebate,
+            });
+        }
+
+        // Write the optional receipt; its rent comes out of the vault so the owner gets it back on close
+        if let Some(receipt) = ctx.accounts.bet_receipt.as_mut() {
+            receipt.vault = ctx.accounts.vault.key();
+            receipt.bet_id_hash = bet_id_hash(&bet_id);
+            receipt.game_id = game_id;
+            receipt.stake = stake;
+            receipt.payout = payout;
+            receipt.settled_at = Clock::get()?.unix_timestamp;
+            receipt.jackpot_tiers_paid = 0;
+            receipt.disputed = false;
+            receipt.bump = ctx.bumps.bet_receipt;
+
+            let vault_info = ctx.accounts.vault.to_account_info();
+            let authority_info = ctx.accounts.authority.to_account_info();
+            let receipt_rent = receipt.to_account_info().lamports();
+            let vault_floor = Rent::get()?.minimum_balance(vault_info.data_len())
+                .checked_add(vault.locked_amount).ok_or(VaultError::Overflow)?;
+            require!(
+                vault_info.lamports().saturating_sub(receipt_rent) >= vault_floor,
+                VaultError::InsufficientFunds
+            );
+            transfer_lamports(&vault_info, &authority_info, receipt_rent)?;
+        }
+
+        // Record the streamed remainder; the house pays it out through `claim_payout_tranches`
+        if streamed > 0 {
+            let limits = &ctx.accounts.game_limits;
+            let schedule = ctx.accounts.payout_schedule.as_mut().ok_or(VaultError::PayoutScheduleMismatch)?;
+            schedule.vault = ctx.accounts.vault.key();
+            schedule.bet_id_hash = bet_id_hash(&bet_id);
+            schedule.total = streamed;
+            schedule.claimed = 0;
+            schedule.tranches = limits.payout_stream_tranches;
+            schedule.start_time = Clock::get()?.unix_timestamp;
+            schedule.interval_secs = limits.payout_stream_interval_secs;
+            schedule.rent_payer = ctx.accounts.authority.key();
+            schedule.bump = ctx.bumps.payout_schedule;
+            house_vault.add_exposure(streamed)?;
+
+            msg!(
+                "Payout streamed: betId={}, paidNow={}, streamed={}, tranches={}",
+                bet_id, paid_now, streamed, schedule.tranches
+            );
+            emit_cpi!(PayoutScheduled {
+                version: EVENT_VERSION,
+                seq: ctx.accounts.global_config.next_seq()?,
+                vault: schedule.vault,
+                bet_id_hash: schedule.bet_id_hash,
+                paid_now,
+                streamed,
+                tranches: schedule.tranches,
+                interval_secs: schedule.interval_secs,
+            });
+        }
+
+        #[cfg(feature = "audit")]
+        {
+            audit_conservation(audit_before, lamport_total(&audit_accounts))?;
+            audit_locked(&vault_info, vault.locked_amount)?;
+        }
+
+        msg!("Atomic bet and settle: betId={}, gameId={}, stake={}, payout={}, user={}, outcome={}, gameData={:?}", 
+             bet_id, game_id, stake, payout, vault.owner,
+             if payout > stake { "WIN" } else if payout < stake { "LOSS" } else { "DRAW" }, gem_data);
+        emit_cpi!(BetSettled {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault: ctx.accounts.vault.key(),
+            bet_id_hash: bet_id_hash(&bet_id),
+            game_id,
+            stake,
+            payout,
+            gem_data: compact_gems(&gem_data)?,
+        });
+        for (index, side_bet) in side_bets.iter().enumerate() {
+            emit_cpi!(SideBetSettled {
+                version: EVENT_VERSION,
+                seq: ctx.accounts.global_config.next_seq()?,
+                vault: ctx.accounts.vault.key(),
+                parent_bet_id_hash: bet_id_hash(&bet_id),
+                index: index as u8,
+                game_id: side_bet.game_id,
+                stake: side_bet.stake,
+                payout: side_bet.payout,
+            });
+        }
+        if total_payout > 0 {
+            let threshold = large_transfer_threshold(&ctx.accounts.compliance_config)?;
+            let vault_key = ctx.accounts.vault.key();
+            let config = &mut ctx.accounts.global_config;
+            let kind = TRANSFER_PAYOUT;
+            if let Some(event) = flag_large_transfer(&mut vault, vault_key, kind, total_payout, threshold, config)? {
+                emit_cpi!(event);
+            }
+        }
+        Ok(())
+    }
+
+    /// Credit `spins` free spins worth `spin_value` each to a vault, from a promo or bonus trigger
+    ///
+    /// Spins of one value are outstanding at a time, so their liability is
+    /// `free_spins * free_spin_value` at the game's multiplier. Signed by the
+    /// admin or a registered game program.
+    pub fn credit_free_spins(ctx: Context<CreditFreeSpins>, spins: u32, spin_value: u64) -> Result<()> {
+        require!(spins > 0 && spin_value > 0, VaultError::InvalidAmount);
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let outstanding = vault.free_spins;
+        require!(outstanding == 0 || vault.free_spin_value == spin_value, VaultError::FreeSpinValueMismatch);
+        vault.free_spins = outstanding.checked_add(spins).ok_or(VaultError::Overflow)?;
+        vault.free_spin_value = spin_value;
+
+        let (owner, outstanding) = (vault.owner, vault.free_spins);
+        msg!("Free spins credited: user={}, spins={}, value={}, outstanding={}", owner, spins, spin_value, outstanding);
+        Ok(())
+    }
+
+    /// Settle one free spin for `payout`, paid entirely by the house
+    ///
+    /// The spin's nominal value stands in for the stake in the game limits and
+    /// game config, so a free spin cannot pay more than a paid one would.
+    pub fn settle_free_spin(ctx: Context<SettleFreeSpin>, bet_id: String, game_id: u64, payout: u64) -> Result<()> {
+        require!(bet_id.len() <= MAX_BET_ID_LEN, VaultError::BetIdTooLong);
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        let spin_value = {
+            let vault = ctx.accounts.vault.load()?;
+            require!(vault.free_spins > 0, VaultError::NoFreeSpins);
+            vault.free_spin_value
+        };
+        ctx.accounts.game_limits.check_payout(game_id, spin_value, payout)?;
+        ctx.accounts.game_limits.check_unstreamed(payout)?;
+        if let Some(config) = load_optional::<GameConfig>(&ctx.accounts.game_config)? {
+            config.check_settlement(spin_value, payout)?;
+        }
+        ctx.accounts.game_limits.check_transaction_payout(&ctx.accounts.instructions, payout)?;
+        reject_bundled_withdraw(&ctx.accounts.instructions, &[ctx.accounts.vault.key()])?;
+
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let now = Clock::get()?.unix_timestamp;
+        vault.record_settlement(now, ctx.accounts.game_limits.max_bets_per_second)?;
+        vault.check_win_cap(now, ctx.accounts.game_limits.max_daily_net_win)?;
+        vault.record_net_win(now, 0, payout)?;
+        let remaining = vault.free_spins - 1;
+        vault.free_spins = remaining;
+        apply_settlement(&vault_info, &ctx.accounts.house_vault.to_account_info(), 0, payout)?;
+        settle_bonus(&ctx.accounts.bonus, &mut vault, 0, payout)?;
+        ctx.accounts.global_stats.load_mut()?.record_bet(ctx.accounts.vault.key(), 0, payout)?;
+
+        msg!("Free spin settled: betId={}, gameId={}, value={}, payout={}, remaining={}",
+             bet_id, game_id, spin_value, payout, remaining);
+        emit_cpi!(BetSettled {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault: ctx.accounts.vault.key(),
+            bet_id_hash: bet_id_hash(&bet_id),
+            game_id,
+            stake: 0,
+            payout,
+            gem_data: [0; 7],
+        });
+        if payout > 0 {
+            let threshold = large_transfer_threshold(&ctx.accounts.compliance_config)?;
+            let vault_key = ctx.accounts.vault.key();
+            let config = &mut ctx.accounts.global_config;
+            let kind = TRANSFER_PAYOUT;
+            if let Some(event) = flag_large_transfer(&mut vault, vault_key, kind, payout, threshold, config)? {
+                emit_cpi!(event);
+            }
+        }
+        Ok(())
+    }
+
+    /// Batch bet and settle multiple games in one transaction (admin only)
+    pub fn batch_settle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchSettle<'info>>,
+        owners: Vec<Pubkey>,
+        stakes: Vec<u64>,
+        payouts: Vec<u64>,
+        bet_ids: Vec<String>,
+        game_ids: Vec<u64>,
+        categories: Vec<u8>,
+        gem_datas: Vec<Vec<u8>>,
+    ) -> Result<()> {
+        require!(stakes.len() <= MAX_BATCH_SETTLE, VaultError::BatchTooLarge);
+        require!(!stakes.is_empty(), VaultError::EmptyBatch);
+        require!(stakes.len() == owners.len(), VaultError::LengthMismatch);
+        require!(stakes.len() == payouts.len(), VaultError::LengthMismatch);
+        require!(stakes.len() == bet_ids.len(), VaultError::LengthMismatch);
+        require!(stakes.len() == game_ids.len(), VaultError::LengthMismatch);
+        require!(stakes.len() == categories.len(), VaultError::LengthMismatch);
+        require!(stakes.len() == gem_datas.len(), VaultError::LengthMismatch);
+        
+        // Check each gem_data has exactly 7 counts, each within its gem type's range
+        for data in &gem_datas {
+            check_gem_data(data)?;
+        }
+        for bet_id in &bet_ids {
+            require!(bet_id.len() <= MAX_BET_ID_LEN, VaultError::BetIdTooLong);
+        }
+        for owner in &owners {
+            require!(!ctx.accounts.blacklist.contains(owner), VaultError::Blacklisted);
+        }
+        for ((stake, payout), game_id) in stakes.iter().zip(payouts.iter()).zip(game_ids.iter()) {
+            require!(*stake > 0 || *payout > 0, VaultError::EmptySettlement);
+            ctx.accounts.global_config.check_stake(*stake)?;
+            ctx.accounts.game_limits.check_payout(*game_id, *stake, *payout)?;
+        }
+        let batch_payout = payouts.iter().try_fold(0u64, |sum, payout| sum.checked_add(*payout)).ok_or(VaultError::Overflow)?;
+        ctx.accounts.game_limits.check_transaction_payout(&ctx.accounts.instructions, batch_payout)?;
+        
+        // Check for any pause (with auto-unpause for maintenance)
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        
+        // Admin, or a registered game program settling via CPI
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        
+        // Remaining accounts are the vaults, then each settlement's game config, then each vault's bonus,
+        // then each vault's loss-back record, all in order
+        require!(
+            ctx.remaining_accounts.len() == 4 * stakes.len(),
+            VaultError::AccountCountMismatch
+        );
+        let (vault_infos, rest) = ctx.remaining_accounts.split_at(stakes.len());
+        let (game_configs, rest) = rest.split_at(stakes.len());
+        let (bonuses, loss_backs) = rest.split_at(stakes.len());
+        for (((stake, payout), game_id), config) in stakes.iter().zip(&payouts).zip(&game_ids).zip(game_configs) {
+            check_game_config(config, *game_id, *stake, *payout)?;
+        }
+
+        // Each vault may appear only once, so per-item balance checks see the true balance
+        for (i, vault_info) in vault_infos.iter().enumerate() {
+            require!(
+                vault_infos[..i].iter().all(|earlier| earlier.key != vault_info.key),
+                VaultError::DuplicateVault
+            );
+        }
+        let vault_keys: Vec<Pubkey> = vault_infos.iter().map(|vault_info| vault_info.key()).collect();
+        reject_bundled_withdraw(&ctx.accounts.instructions, &vault_keys)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let max_bets_per_second = ctx.accounts.game_limits.max_bets_per_second;
+        let max_daily_net_win = ctx.accounts.game_limits.max_daily_net_win;
+        let compliance_config = load_compliance_config(&ctx.accounts.compliance_config)?;
+        let large_transfer_threshold = compliance_config.as_ref().map_or(0, |config| config.large_transfer_threshold);
+        let house_info = ctx.accounts.house_vault.to_account_info();
+        let mut house_vault = ctx.accounts.house_vault.load_mut()?;
+
+        #[cfg(feature = "audit")]
+        let audit_before = house_info.lamports() as u128
+            + vault_infos.iter().map(|vault| vault.lamports() as u128).sum::<u128>();
+
+        // Process each bet and settle operation
+        for (i, ((((stake, payout), bet_id), game_id), gem_data)) in stakes.iter()
+            .zip(payouts.iter())
+            .zip(bet_ids.iter())
+            .zip(game_ids.iter())
+            .zip(gem_datas.iter())
+            .enumerate() {
+            let vault_info = &vault_infos[i];
+
+            // Vault PDA must be [b"vault", owner]
+            let (expected_vault, _bump) =
+                Pubkey::find_program_address(&[VAULT_SEED, owners[i].as_ref()], ctx.program_id);
+            require!(vault_info.key() == expected_vault, VaultError::Unauthorized);
+            require!(vault_info.is_writable, VaultError::Unauthorized);
+            
+            // stake can be 0 if it was already deducted in a previous transaction
+            
+            // If stake > 0, ensure vault has enough funds
+            if *stake > 0 {
+                require!(**vault_info.lamports.borrow() >= *stake, VaultError::InsufficientFunds);
+            }
+        
+ 
+            require!(vault_info.owner == ctx.program_id, VaultError::Unauthorized);
+            require!(vault_version(vault_info) == VAULT_VERSION, VaultError::VersionMismatch);
+            let vault_loader = AccountLoader::<UserVault>::try_from(vault_info)?;
+            let mut vault = vault_loader.load_mut()?;
+            vault.record_settlement(now, max_bets_per_second)?;
+            vault.check_win_cap(now, max_daily_net_win)?;
+            vault.record_net_win(now, *stake, *payout)?;
+            ctx.accounts.game_limits.check_unstreamed(*payout)?;
+            if let Some(config) = compliance_config.as_ref() {
+                config.check_region(vault.region, *game_id, *stake)?;
+                config.check_terms(&vault, *stake)?;
+            }
+
+            // Update house vault volume, overall and for the game's category
+            house_vault.record_volume(categories[i], *stake)?;
+            if *stake > 0 {
+                // Track lifetime wager for reward eligibility
+                vault.lifetime_wager = vault.lifetime_wager.checked_add(*stake).ok_or(VaultError::Overflow)?;
+            }
+        
+            // Move the net result between vault and house, spending an active bonus first
+            apply_settlement(vault_info, &house_info, *stake, *payout)?;
+            require_vault_pda(&bonuses[i], BONUS_SEED, &expected_vault)?;
+            settle_bonus(&bonuses[i], &mut vault, *stake, *payout)?;
+            require_vault_pda(&loss_backs[i], LOSSBACK_SEED, &expected_vault)?;
+            settle_loss_back(&ctx.accounts.loss_back_promo, &loss_backs[i], *stake, *payout)?;
+            vault.record_outcome(*stake, *payout);
+            #[cfg(feature = "audit")]
+            audit_locked(vault_info, vault.locked_amount)?;
+
+            ctx.accounts.global_stats.load_mut()?.record_bet(vault_info.key(), *stake, *payout)?;
+
+            msg!("Batch item {}: betId={}, gameId={}, stake={}, payout={}, outcome={}, gameData={:?}", 
+                 i, bet_id, game_id, stake, payout,
+                 if *payout > *stake { "WIN" } else if *payout < *stake { "LOSS" } else { "DRAW" }, gem_data);
+            emit_cpi!(BetSettled {
+                version: EVENT_VERSION,
+                seq: ctx.accounts.global_config.next_seq()?,
+                vault: vault_info.key(),
+                bet_id_hash: bet_id_hash(bet_id),
+                game_id: *game_id,
+                stake: *stake,
+                payout: *payout,
+                gem_data: compact_gems(gem_data)?,
+            });
+            if *payout > 0 {
+                let flagged = flag_large_transfer(
+                    &mut vault,
+                    vault_info.key(),
+                    TRANSFER_PAYOUT,
+                    *payout,
+                    large_transfer_threshold,
+                    &mut ctx.accounts.global_config,
+                )?;
+                if let Some(event) = flagged {
+                    emit_cpi!(event);
+                }
+            }
+        }
+
+        #[cfg(feature = "audit")]
+        audit_conservation(
+            audit_before,
+            house_info.lamports() as u128
+                + vault_infos.iter().map(|vault| vault.lamports() as u128).sum::<u128>(),
+        )?;
+
+        ctx.accounts.global_stats.load_mut()?.record_call(Invocation::Batch)?;
+        msg!("Batch bet and settle completed: {} games, betIds={:?}, gameIds={:?}", stakes.len(), bet_ids, game_ids);
+        Ok(())
+    }
+
+    /// Start maintenance pause (admin or multisig)
+    pub fn start_maintenance_pause(ctx: Context<StartMaintenancePause>) -> Result<()> {
+        let config = &mut ctx.accounts.pause_config;
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        
+        require!(
+            ctx.accounts.authority.key() == multisig || ctx.accounts.authority.key() == admin,
+            VaultError::Unauthorized
+        );
+
+        config.set_flag(PAUSE_MAINTENANCE, true);
+        config.maintenance_start_time = u32::try_from(Clock::get()?.unix_timestamp).map_err(|_| VaultError::Overflow)?;
+        
+        msg!("Maintenance pause started at {}", config.maintenance_start_time);
+        Ok(())
+    }
+
+    /// Persist the end of an elapsed maintenance window (permissionless crank)
+    pub fn resume_after_maintenance(ctx: Context<ResumeAfterMaintenance>) -> Result<()> {
+        let config = &mut ctx.accounts.pause_config;
+        require!(config.maintenance_pause(), VaultError::MaintenanceInProgress);
+        require!(config.maintenance_elapsed(Clock::get()?.unix_timestamp), VaultError::MaintenanceInProgress);
+
+        config.set_flag(PAUSE_MAINTENANCE, false);
+        config.maintenance_start_time = 0;
+
+        msg!("Maintenance window elapsed, operations resumed");
+        Ok(())
+    }
+
+    /// Create the stats snapshot ring buffer (admin only)
+    ///
+    /// * `interval_secs` – minimum spacing between recorded snapshots
+    pub fn initialize_stats_snapshots(ctx: Context<InitializeStatsSnapshots>, interval_secs: u32) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!(interval_secs > 0, VaultError::InvalidAmount);
+
+        let snapshots = &mut ctx.accounts.stats_snapshots;
+        snapshots.interval_secs = interval_secs;
+        snapshots.last_recorded = 0;
+        snapshots.next = 0;
+        snapshots.entries = Vec::new();
+        snapshots.bump = ctx.bumps.stats_snapshots;
+        Ok(())
+    }
+
+    /// Run the global upkeep that is due, and nothing else (permissionless)
+    ///
+    /// Lifts an elapsed maintenance pause and records a stats snapshot once per
+    /// interval. It succeeds when nothing is due, so an automation thread
+    /// (e.g. a Clockwork cron thread) can call it on a fixed schedule.
+    pub fn automation_tick(ctx: Context<AutomationTick>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let config = &mut ctx.accounts.pause_config;
+        if config.maintenance_pause() && config.maintenance_elapsed(now) {
+            config.set_flag(PAUSE_MAINTENANCE, false);
+            config.maintenance_start_time = 0;
+            msg!("Maintenance window elapsed, operations resumed");
+        }
+
+        let snapshots = &mut ctx.accounts.stats_snapshots;
+        if now >= snapshots.last_recorded.saturating_add(snapshots.interval_secs as i64) {
+            let stats = ctx.accounts.global_stats.load()?;
+            let snapshot = StatsSnapshot {
+                timestamp: now,
+                total_users: stats.total_users,
+                total_bets: stats.total_bets,
+                total_volume: stats.total_volume,
+                house_balance: ctx.accounts.house_vault.to_account_info().lamports(),
+            };
+            snapshots.record(snapshot);
+            msg!("Stats snapshot recorded: bets={}, volume={}, house={}",
+                 snapshot.total_bets, snapshot.total_volume, snapshot.house_balance);
+        }
+        Ok(())
+    }
+
+    /// Close a bet receipt, returning its rent to the vault
+    ///
+    /// The vault owner can close at any time; anyone else (a sweeper) only
+    /// once the receipt is older than `RECEIPT_RETENTION_SECS`.
+    pub fn close_bet_receipt(ctx: Context<CloseBetReceipt>) -> Result<()> {
+        let receipt = &ctx.accounts.bet_receipt;
+        let owner = ctx.accounts.vault.load()?.owner;
+        if ctx.accounts.closer.key() != owner {
+            let age = Clock::get()?.unix_timestamp - receipt.settled_at;
+            require!(age >= RECEIPT_RETENTION_SECS, VaultError::ReceiptRetained);
+        }
+
+        msg!("Bet receipt closed: user={}, gameId={}, closer={}",
+             owner, receipt.game_id, ctx.accounts.closer.key());
+        Ok(())
+    }
+
+    /// Close bet receipts settled before the dispute window, returning each one's rent to its vault (anyone)
+    ///
+    /// Remaining accounts are `(bet_receipt, vault)` pairs. While the dispute
+    /// config is uninitialized receipts are kept for `RECEIPT_RETENTION_SECS`.
+    pub fn sweep_bet_receipts<'info>(ctx: Context<'_, '_, 'info, 'info, SweepBetReceipts<'info>>) -> Result<()> {
+        let pairs = ctx.remaining_accounts;
+        require!(!pairs.is_empty() && pairs.len().is_multiple_of(2), VaultError::AccountCountMismatch);
+        let window_secs = load_optional::<DisputeConfig>(&ctx.accounts.dispute_config)?
+            .map_or(RECEIPT_RETENTION_SECS, |config| config.window_secs);
+
+        let now = Clock::get()?.unix_timestamp;
+        let mut rent = 0u64;
+        for pair in pairs.chunks(2) {
+            let (receipt_info, vault_info) = (&pair[0], &pair[1]);
+            let receipt = Account::<BetReceipt>::try_from(receipt_info)?;
+            require!(vault_info.key() == receipt.vault, VaultError::ReceiptVaultMismatch);
+            require!(now - receipt.settled_at > window_secs, VaultError::ReceiptRetained);
+
+            rent = rent.checked_add(receipt_info.lamports()).ok_or(VaultError::Overflow)?;
+            receipt.close(vault_info.clone())?;
+        }
+
+        msg!("Bet receipts swept: count={}, rent={}", pairs.len() / 2, rent);
+        Ok(())
+    }
+
+    /// Create the dispute config; `window_secs` of 0 keeps disputes closed (admin only)
+    pub fn initialize_dispute_config(ctx: Context<InitializeDisputeConfig>, window_secs: i64) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!((0..=RECEIPT_RETENTION_SECS).contains(&window_secs), VaultError::InvalidAmount);
+
+        let config = &mut ctx.accounts.dispute_config;
+        config.window_secs = window_secs;
+        config.bump = ctx.bumps.dispute_config;
+        Ok(())
+    }
+
+    /// Change how long after settlement a bet can be challenged (multisig only)
+    ///
+    /// Capped at `RECEIPT_RETENTION_SECS`, as a challenge needs the bet receipt.
+    pub fn set_dispute_window(ctx: Context<SetDisputeConfig>, window_secs: i64) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+        require!((0..=RECEIPT_RETENTION_SECS).contains(&window_secs), VaultError::InvalidAmount);
+
+        ctx.accounts.dispute_config.window_secs = window_secs;
+
+        msg!("Dispute window set: {}s", window_secs);
+        Ok(())
+    }
+
+    /// Challenge a settled bet, freezing its stake from the house vault until the multisig rules
+    ///
+    /// Only bets settled with a receipt can be challenged, once each, within
+    /// the dispute window after settlement, and only while the house holds the
+    /// stake on top of its rent and open exposure. The owner pays the dispute
+    /// PDA's rent.
+    pub fn challenge_bet(ctx: Context<ChallengeBet>, bet_id: String) -> Result<()> {
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        let now = Clock::get()?.unix_timestamp;
+        let receipt = &mut ctx.accounts.bet_receipt;
+        receipt.file_dispute(now, ctx.accounts.dispute_config.window_secs)?;
+
+        let amount = receipt.stake;
+        let (house_balance, open_exposure) = house_solvency(&ctx.accounts.house_vault)?;
+        check_house_surplus(house_balance, open_exposure, amount)?;
+        transfer_lamports(
+            &ctx.accounts.house_vault.to_account_info(),
+            &ctx.accounts.dispute.to_account_info(),
+            amount,
+        )?;
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.vault = ctx.accounts.vault.key();
+        dispute.owner = ctx.accounts.owner.key();
+        dispute.bet_id_hash = receipt.bet_id_hash;
+        dispute.amount = amount;
+        dispute.filed_at = now;
+        dispute.bump = ctx.bumps.dispute;
+
+        msg!("Bet challenged: betId={}, user={}, frozen={}", bet_id, dispute.owner, amount);
+        emit_cpi!(DisputeFiled {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault: dispute.vault,
+            bet_id_hash: dispute.bet_id_hash,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Rule on a dispute: `award` of the frozen amount goes to the vault, the rest back to the house (multisig only)
+    ///
+    /// The dispute PDA is closed and its rent returned to the owner who filed it.
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, award: u64) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let dispute_info = ctx.accounts.dispute.to_account_info();
+        let (vault, bet_id_hash, amount) =
+            (ctx.accounts.dispute.vault, ctx.accounts.dispute.bet_id_hash, ctx.accounts.dispute.amount);
+        require!(award <= amount, VaultError::InvalidAmount);
+
+        transfer_lamports(&dispute_info, &ctx.accounts.vault.to_account_info(), award)?;
+        transfer_lamports(&dispute_info, &ctx.accounts.house_vault.to_account_info(), amount - award)?;
+
+        msg!("Dispute resolved: vault={}, frozen={}, award={}", vault, amount, award);
+        emit_cpi!(DisputeResolved {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault,
+            bet_id_hash,
+            amount,
+            award,
+        });
+        Ok(())
+    }
+
+    /// Create the PvP config with the house rake in basis points (admin only)
+    pub fn initialize_pvp_config(ctx: Context<InitializePvpConfig>, rake_bps: u16) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!(rake_bps <= MAX_PVP_RAKE_BPS, VaultError::InvalidAmount);
+
+        let config = &mut ctx.accounts.pvp_config;
+        config.rake_bps = rake_bps;
+        config.bump = ctx.bumps.pvp_config;
+        Ok(())
+    }
+
+    /// Change the rake charged on PvP bets created from now on (multisig only)
+    pub fn set_pvp_rake(ctx: Context<SetPvpConfig>, rake_bps: u16) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+        require!(rake_bps <= MAX_PVP_RAKE_BPS, VaultError::InvalidAmount);
+
+        ctx.accounts.pvp_config.rake_bps = rake_bps;
+
+        msg!("PvP rake set: {}bps", rake_bps);
+        Ok(())
+    }
+
+    /// Create the queue listing open PvP challenges (admin only)
+    pub fn initialize_pvp_queue(ctx: Context<InitializePvpQueue>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let queue = &mut ctx.accounts.pvp_queue;
+        queue.challenges = Vec::new();
+        queue.bump = ctx.bumps.pvp_queue;
+        Ok(())
+    }
+
+    /// Challenge `opponent_vault` to a head-to-head bet, escrowing `stake` from the creator's vault
+    ///
+    /// With no opponent the bet is an open challenge, listed in the PvP queue
+    /// for any vault to accept; it must then expire, at `expires_at`, after
+    /// which anyone may refund it. Direct challenges may pass 0 for no expiry.
+    /// The current rake is fixed on the bet. The creator pays the bet PDA's
+    /// rent, refunded when it is settled, cancelled or expired.
+    pub fn create_pvp_bet(
+        ctx: Context<CreatePvpBet>,
+        bet_id: String,
+        opponent_vault: Option<Pubkey>,
+        game_id: u64,
+        stake: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        require!(bet_id.len() <= MAX_BET_ID_LEN, VaultError::BetIdTooLong);
+        require!(stake > 0, VaultError::InvalidAmount);
+        ctx.accounts.global_config.check_stake(stake)?;
+        require!(opponent_vault != Some(ctx.accounts.vault.key()), VaultError::DuplicateVault);
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at > now || (expires_at == 0 && opponent_vault.is_some()), VaultError::InvalidExpiry);
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        if let Some(config) = load_compliance_config(&ctx.accounts.compliance_config)? {
+            let vault = ctx.accounts.vault.load()?;
+            config.check_region(vault.region, game_id, stake)?;
+            config.check_terms(&vault, stake)?;
+        }
+
+        escrow_pvp_stake(&ctx.accounts.vault, &ctx.accounts.pvp_bet.to_account_info(), stake)?;
+
+        let bet = &mut ctx.accounts.pvp_bet;
+        bet.creator = ctx.accounts.owner.key();
+        bet.creator_vault = ctx.accounts.vault.key();
+        bet.opponent_vault = opponent_vault.unwrap_or_default();
+        bet.bet_id_hash = bet_id_hash(&bet_id);
+        bet.game_id = game_id;
+        bet.stake = stake;
+        bet.rake_bps = ctx.accounts.pvp_config.rake_bps;
+        bet.created_at = now;
+        bet.accepted_at = 0;
+        bet.expires_at = expires_at;
+        bet.bump = ctx.bumps.pvp_bet;
+
+        if bet.is_open() {
+            let queue = ctx.accounts.pvp_queue.as_mut().ok_or(VaultError::PvpQueueMissing)?;
+            require!(queue.challenges.len() < MAX_PVP_QUEUE_LEN, VaultError::PvpQueueFull);
+            queue.challenges.push(bet.key());
+        }
+
+        msg!("PvP bet created: betId={}, creator={}, opponent={:?}, stake={}, expiresAt={}",
+             bet_id, bet.creator_vault, opponent_vault, stake, expires_at);
+        Ok(())
+    }
+
+    /// Match a PvP bet as the challenged vault's owner, or any other vault's for an open challenge,
+    /// escrowing the same stake
+    pub fn accept_pvp_bet(ctx: Context<AcceptPvpBet>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.pvp_bet.accepted_at == 0, VaultError::PvpBetAccepted);
+        require!(!ctx.accounts.pvp_bet.is_expired(now), VaultError::PvpBetExpired);
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        let (game_id, stake) = (ctx.accounts.pvp_bet.game_id, ctx.accounts.pvp_bet.stake);
+        if let Some(config) = load_compliance_config(&ctx.accounts.compliance_config)? {
+            let vault = ctx.accounts.vault.load()?;
+            config.check_region(vault.region, game_id, stake)?;
+            config.check_terms(&vault, stake)?;
+        }
+
+        escrow_pvp_stake(&ctx.accounts.vault, &ctx.accounts.pvp_bet.to_account_info(), stake)?;
+        if ctx.accounts.pvp_bet.is_open() {
+            let queue = ctx.accounts.pvp_queue.as_mut().ok_or(VaultError::PvpQueueMissing)?;
+            queue.remove(&ctx.accounts.pvp_bet.key());
+            ctx.accounts.pvp_bet.opponent_vault = ctx.accounts.vault.key();
+        }
+        ctx.accounts.pvp_bet.accepted_at = now;
+
+        let creator_vault = ctx.accounts.pvp_bet.creator_vault;
+        msg!("PvP bet accepted: creator={}, opponent={}, stake={}", creator_vault, ctx.accounts.vault.key(), stake);
+        Ok(())
+    }
+
+    /// Withdraw an unaccepted PvP bet, returning the stake to the creator's vault
+    pub fn cancel_pvp_bet(ctx: Context<CancelPvpBet>) -> Result<()> {
+        let bet = &ctx.accounts.pvp_bet;
+        require!(bet.accepted_at == 0, VaultError::PvpBetAccepted);
+
+        refund_pvp_bet(bet, &ctx.accounts.vault.to_account_info(), ctx.accounts.pvp_queue.as_mut())?;
+
+        msg!("PvP bet cancelled: creator={}, stake={}", bet.creator_vault, bet.stake);
+        Ok(())
+    }
+
+    /// Refund an unaccepted PvP bet past its expiry to the creator's vault (anyone)
+    ///
+    /// Lets a crank clear unmatched challenges from the queue without the
+    /// creator; the rent still goes back to the creator.
+    pub fn expire_pvp_bet(ctx: Context<ExpirePvpBet>) -> Result<()> {
+        let bet = &ctx.accounts.pvp_bet;
+        require!(bet.accepted_at == 0, VaultError::PvpBetAccepted);
+        require!(bet.is_expired(Clock::get()?.unix_timestamp), VaultError::PvpBetNotExpired);
+
+        refund_pvp_bet(bet, &ctx.accounts.vault.to_account_info(), ctx.accounts.pvp_queue.as_mut())?;
+
+        msg!("PvP bet expired: creator={}, stake={}", bet.creator_vault, bet.stake);
+        Ok(())
+    }
+
+    /// Settle an accepted PvP bet in favour of `winner`, a vault of the bet, or as a draw with `None`
+    ///
+    /// The winner takes both stakes less the bet's rake, which goes to the
+    /// house; a draw refunds both stakes without rake. Signed by the admin or a
+    /// registered game program, like `bet_and_settle`.
+    pub fn settle_pvp_bet(ctx: Context<SettlePvpBet>, winner: Option<Pubkey>) -> Result<()> {
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        let bet = &ctx.accounts.pvp_bet;
+        require!(bet.accepted_at != 0, VaultError::PvpBetOpen);
+        let (creator_payout, opponent_payout, rake) = bet.payouts(winner)?;
+
+        let bet_info = bet.to_account_info();
+        transfer_lamports(&bet_info, &ctx.accounts.creator_vault.to_account_info(), creator_payout)?;
+        transfer_lamports(&bet_info, &ctx.accounts.opponent_vault.to_account_info(), opponent_payout)?;
+        transfer_lamports(&bet_info, &ctx.accounts.house_vault.to_account_info(), rake)?;
+
+        let accounts = &ctx.accounts;
+        settle_bonus(&accounts.creator_bonus, &mut *accounts.creator_vault.load_mut()?, bet.stake, creator_payout)?;
+        settle_bonus(&accounts.opponent_bonus, &mut *accounts.opponent_vault.load_mut()?, bet.stake, opponent_payout)?;
+        settle_loss_back(&accounts.loss_back_promo, &accounts.creator_loss_back, bet.stake, creator_payout)?;
+        settle_loss_back(&accounts.loss_back_promo, &accounts.opponent_loss_back, bet.stake, opponent_payout)?;
+
+        let mut stats = ctx.accounts.global_stats.load_mut()?;
+        stats.record_bet(bet.creator_vault, bet.stake, creator_payout)?;
+        stats.record_bet(bet.opponent_vault, bet.stake, opponent_payout)?;
+
+        msg!("PvP bet settled: creator={}, opponent={}, winner={:?}, rake={}",
+             bet.creator_vault, bet.opponent_vault, winner, rake);
+        emit_cpi!(PvpBetSettled {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            bet_id_hash: bet.bet_id_hash,
+            game_id: bet.game_id,
+            creator_vault: bet.creator_vault,
+            opponent_vault: bet.opponent_vault,
+            winner: winner.unwrap_or_default(),
+            stake: bet.stake,
+            rake,
+        });
+        Ok(())
+    }
+
+    /// Open table round `round_id`, seating the vaults passed as remaining accounts
+    ///
+    /// Each seat escrows its `stakes` entry from its vault into the round; the
+    /// current PvP rake is fixed on the round. Signed by the admin or a
+    /// registered game program, which pays the round's rent.
+    pub fn open_table_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenTableRound<'info>>,
+        round_id: u64,
+        game_id: u64,
+        stakes: Vec<u64>,
+    ) -> Result<()> {
+        require!(!stakes.is_empty() && stakes.len() <= MAX_TABLE_SEATS, VaultError::TableFull);
+        require!(ctx.remaining_accounts.len() == stakes.len(), VaultError::AccountCountMismatch);
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        let compliance_config = load_compliance_config(&ctx.accounts.compliance_config)?;
+
+        let round_info = ctx.accounts.table_round.to_account_info();
+        let mut seats = Vec::with_capacity(stakes.len());
+        for (i, (vault_info, stake)) in ctx.remaining_accounts.iter().zip(&stakes).enumerate() {
+            require!(*stake > 0, VaultError::InvalidAmount);
+            require!(
+                ctx.remaining_accounts[..i].iter().all(|earlier| earlier.key != vault_info.key),
+                VaultError::DuplicateVault
+            );
+            let blacklist = &ctx.accounts.blacklist;
+            let vault = table_seat_vault(vault_info, blacklist, compliance_config.as_ref(), game_id, *stake)?;
+            escrow_pvp_stake(&vault, &round_info, *stake)?;
+            seats.push(TableSeat { vault: vault_info.key(), committed: *stake });
+        }
+
+        let round = &mut ctx.accounts.table_round;
+        round.round_id = round_id;
+        round.game_id = game_id;
+        round.payer = ctx.accounts.authority.key();
+        round.rake_bps = ctx.accounts.pvp_config.rake_bps;
+        round.opened_at = Clock::get()?.unix_timestamp;
+        round.seats = seats;
+        round.bump = ctx.bumps.table_round;
+
+        msg!("Table round opened: roundId={}, gameId={}, seats={}, pot={}",
+             round_id, game_id, stakes.len(), round.pot()?);
+        Ok(())
+    }
+
+    /// Escrow more of each seat's stake, one `raises` entry per seated vault passed as a remaining account
+    pub fn raise_table_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RaiseTableRound<'info>>,
+        raises: Vec<u64>,
+    ) -> Result<()> {
+        require!(!raises.is_empty(), VaultError::InvalidAmount);
+        require!(ctx.remaining_accounts.len() == raises.len(), VaultError::AccountCountMismatch);
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        let compliance_config = load_compliance_config(&ctx.accounts.compliance_config)?;
+
+        let round_info = ctx.accounts.table_round.to_account_info();
+        let game_id = ctx.accounts.table_round.game_id;
+        for (vault_info, raise) in ctx.remaining_accounts.iter().zip(&raises) {
+            require!(*raise > 0, VaultError::InvalidAmount);
+            let blacklist = &ctx.accounts.blacklist;
+            let vault = table_seat_vault(vault_info, blacklist, compliance_config.as_ref(), game_id, *raise)?;
+            let seat = ctx.accounts.table_round.seat_mut(&vault_info.key())?;
+            seat.committed = seat.committed.checked_add(*raise).ok_or(VaultError::Overflow)?;
+            escrow_pvp_stake(&vault, &round_info, *raise)?;
+        }
+
+        let round = &ctx.accounts.table_round;
+        msg!("Table round raised: roundId={}, raises={:?}, pot={}", round.round_id, raises, round.pot()?);
+        Ok(())
+    }
+
+    /// Pay out every seat of a table round and take the rake, closing the round
+    ///
+    /// Seat vaults are passed as remaining accounts in seat order, then each
+    /// seat vault's bonus, then its loss-back record, with one `payouts` entry
+    /// each; the payouts must add up to the pot less the rake.
+    pub fn settle_table_round<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleTableRound<'info>>,
+        payouts: Vec<u64>,
+    ) -> Result<()> {
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        let round = &ctx.accounts.table_round;
+        require!(payouts.len() == round.seats.len(), VaultError::LengthMismatch);
+        require!(ctx.remaining_accounts.len() == 3 * payouts.len(), VaultError::AccountCountMismatch);
+        let (vault_infos, rest) = ctx.remaining_accounts.split_at(payouts.len());
+        let (bonuses, loss_backs) = rest.split_at(payouts.len());
+        require_table_seats(round, vault_infos)?;
+        let rake = round.rake()?;
+        let paid = payouts.iter().try_fold(rake, |sum, payout| sum.checked_add(*payout)).ok_or(VaultError::Overflow)?;
+        require!(paid == round.pot()?, VaultError::TablePayoutMismatch);
+
+        let round_info = round.to_account_info();
+        let mut stats = ctx.accounts.global_stats.load_mut()?;
+        let mut results = Vec::with_capacity(payouts.len());
+        let seats = round.seats.iter().zip(vault_infos).zip(bonuses.iter().zip(loss_backs));
+        for (((seat, vault_info), (bonus, loss_back)), payout) in seats.zip(&payouts) {
+            transfer_lamports(&round_info, vault_info, *payout)?;
+            require_vault_pda(bonus, BONUS_SEED, &seat.vault)?;
+            let vault = AccountLoader::<UserVault>::try_from(vault_info)?;
+            settle_bonus(bonus, &mut *vault.load_mut()?, seat.committed, *payout)?;
+            require_vault_pda(loss_back, LOSSBACK_SEED, &seat.vault)?;
+            settle_loss_back(&ctx.accounts.loss_back_promo, loss_back, seat.committed, *payout)?;
+            stats.record_bet(seat.vault, seat.committed, *payout)?;
+            results.push(TableSeatResult { vault: seat.vault, committed: seat.committed, payout: *payout });
+        }
+        transfer_lamports(&round_info, &ctx.accounts.house_vault.to_account_info(), rake)?;
+
+        msg!("Table round settled: roundId={}, payouts={:?}, rake={}", round.round_id, payouts, rake);
+        emit_cpi!(TableRoundSettled {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            round_id: round.round_id,
+            game_id: round.game_id,
+            rake,
+            seats: results,
+        });
+        Ok(())
+    }
+
+    /// Refund every seat of a table round its escrowed stake, closing the round
+    ///
+    /// The settlement authority may cancel at any time, anyone else once the
+    /// round is `TABLE_ROUND_TIMEOUT_SECS` old. Seat vaults are passed as
+    /// remaining accounts in seat order.
+    pub fn cancel_table_round<'info>(ctx: Context<'_, '_, 'info, 'info, CancelTableRound<'info>>) -> Result<()> {
+        let round = &ctx.accounts.table_round;
+        if Clock::get()?.unix_timestamp < round.opened_at.saturating_add(TABLE_ROUND_TIMEOUT_SECS) {
+            require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        }
+        require_table_seats(round, ctx.remaining_accounts)?;
+
+        let round_info = round.to_account_info();
+        for (seat, vault_info) in round.seats.iter().zip(ctx.remaining_accounts) {
+            transfer_lamports(&round_info, vault_info, seat.committed)?;
+        }
+
+        msg!("Table round cancelled: roundId={}, refunded={}", round.round_id, round.pot()?);
+        Ok(())
+    }
+
+    /// Opt in to mirror the bets of `leader_vault`'s owner, staking at most `max_stake` per bet
+    ///
+    /// A vault follows one leader at a time; unfollow first to switch leader or cap.
+    pub fn follow_player(ctx: Context<FollowPlayer>, max_stake: u64) -> Result<()> {
+        require!(max_stake > 0, VaultError::InvalidAmount);
+        let follow = &mut ctx.accounts.copy_follow;
+        follow.vault = ctx.accounts.vault.key();
+        follow.leader = ctx.accounts.leader_vault.key();
+        follow.max_stake = max_stake;
+        follow.bump = ctx.bumps.copy_follow;
+
+        msg!("Copy-betting started: vault={}, leader={}, maxStake={}", follow.vault, follow.leader, max_stake);
+        Ok(())
+    }
+
+    /// Stop mirroring, refunding the follow account's rent to the owner
+    pub fn unfollow_player(ctx: Context<UnfollowPlayer>) -> Result<()> {
+        let follow = &ctx.accounts.copy_follow;
+        msg!("Copy-betting stopped: vault={}, leader={}", follow.vault, follow.leader);
+        Ok(())
+    }
+
+    /// Mirror a bet the leader settled into each of `followers`' vaults
+    ///
+    /// Remaining accounts are a `(vault, copy_follow, bonus, loss_back)`
+    /// quadruple per follower, in order. Each follower stakes the lead stake up to their cap and is paid
+    /// the lead payout scaled by the same ratio, rounded down. The
+    /// per-transaction payout limit counts the lead payout once per follower.
+    pub fn settle_mirrored_bets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleMirroredBets<'info>>,
+        bet_id: String,
+        game_id: u64,
+        stake: u64,
+        payout: u64,
+        followers: Vec<Pubkey>,
+    ) -> Result<()> {
+        require!(bet_id.len() <= MAX_BET_ID_LEN, VaultError::BetIdTooLong);
+        require!(stake > 0, VaultError::InvalidAmount);
+        require!(!followers.is_empty() && followers.len() <= MAX_MIRROR_FOLLOWERS, VaultError::TooManyFollowers);
+        require!(ctx.remaining_accounts.len() == 4 * followers.len(), VaultError::AccountCountMismatch);
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        ctx.accounts.game_limits.check_payout(game_id, stake, payout)?;
+        reject_bundled_withdraw(&ctx.accounts.instructions, &followers)?;
+        let game_config = load_optional::<GameConfig>(&ctx.accounts.game_config)?;
+        let compliance_config = load_compliance_config(&ctx.accounts.compliance_config)?;
+        let threshold = large_transfer_threshold(&ctx.accounts.compliance_config)?;
+
+        let leader = ctx.accounts.leader_vault.key();
+        let house_info = ctx.accounts.house_vault.to_account_info();
+        let now = Clock::get()?.unix_timestamp;
+        let max_bets_per_second = ctx.accounts.game_limits.max_bets_per_second;
+        let max_daily_net_win = ctx.accounts.game_limits.max_daily_net_win;
+        let (mut total_stake, mut total_payout) = (0u64, 0u64);
+        for (i, (follower, accounts)) in followers.iter().zip(ctx.remaining_accounts.chunks(4)).enumerate() {
+            let (vault_info, follow_info, bonus, loss_back) = (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+            require!(!followers[..i].contains(follower), VaultError::DuplicateVault);
+            let follow = Account::<CopyFollow>::try_from(follow_info)?;
+            require!(vault_info.key() == *follower && follow.vault == *follower, VaultError::NotFollowing);
+            require!(follow.leader == leader, VaultError::NotFollowing);
+
+            let (follower_stake, follower_payout) = follow.mirror(stake, payout)?;
+            ctx.accounts.game_limits.check_payout(game_id, follower_stake, follower_payout)?;
+            ctx.accounts.game_limits.check_unstreamed(follower_payout)?;
+            if let Some(config) = game_config.as_ref() {
+                config.check_settlement(follower_stake, follower_payout)?;
+            }
+            let blacklist = &ctx.accounts.blacklist;
+            let loader = table_seat_vault(vault_info, blacklist, compliance_config.as_ref(), game_id, follower_stake)?;
+            require!(vault_info.lamports() >= follower_stake, VaultError::InsufficientFunds);
+
+            let mut vault = loader.load_mut()?;
+            vault.record_settlement(now, max_bets_per_second)?;
+            vault.check_win_cap(now, max_daily_net_win)?;
+            vault.record_net_win(now, follower_stake, follower_payout)?;
+            vault.lifetime_wager = vault.lifetime_wager.checked_add(follower_stake).ok_or(VaultError::Overflow)?;
+            apply_settlement(vault_info, &house_info, follower_stake, follower_payout)?;
+            require_vault_pda(bonus, BONUS_SEED, follower)?;
+            settle_bonus(bonus, &mut vault, follower_stake, follower_payout)?;
+            require_vault_pda(loss_back, LOSSBACK_SEED, follower)?;
+            settle_loss_back(&ctx.accounts.loss_back_promo, loss_back, follower_stake, follower_payout)?;
+            ctx.accounts.global_stats.load_mut()?.record_bet(*follower, follower_stake, follower_payout)?;
+            total_stake = total_stake.checked_add(follower_stake).ok_or(VaultError::Overflow)?;
+            total_payout = total_payout.checked_add(follower_payout).ok_or(VaultError::Overflow)?;
+
+            emit_cpi!(BetMirrored {
+                version: EVENT_VERSION,
+                seq: ctx.accounts.global_config.next_seq()?,
+                vault: *follower,
+                leader,
+                bet_id_hash: bet_id_hash(&bet_id),
+                game_id,
+                stake: follower_stake,
+                payout: follower_payout,
+            });
+            if follower_payout > 0 {
+                let config = &mut ctx.accounts.global_config;
+                let (kind, amount) = (TRANSFER_PAYOUT, follower_payout);
+                if let Some(event) = flag_large_transfer(&mut vault, *follower, kind, amount, threshold, config)? {
+                    emit_cpi!(event);
+                }
+            }
+        }
+        ctx.accounts.game_limits.check_transaction_payout(&ctx.accounts.instructions, total_payout)?;
+        let mut house_vault = ctx.accounts.house_vault.load_mut()?;
+        house_vault.total_volume = house_vault.total_volume.checked_add(total_stake).ok_or(VaultError::Overflow)?;
+
+        msg!("Bets mirrored: betId={}, gameId={}, leader={}, followers={}, stake={}, payout={}",
+             bet_id, game_id, leader, followers.len(), total_stake, total_payout);
+        Ok(())
+    }
+
+    /// Open crash round `round_id`, committing to `keccak(seed)` before any stake is placed
+    ///
+    /// Bets are taken until `betting_closes_at`; the seed, and with it the crash
+    /// point, is revealed after. Signed by the admin or a registered game program.
+    pub fn open_crash_round(
+        ctx: Context<OpenCrashRound>,
+        round_id: u64,
+        game_id: u64,
+        commitment: [u8; 32],
+        betting_closes_at: i64,
+    ) -> Result<()> {
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        require!(betting_closes_at > Clock::get()?.unix_timestamp, VaultError::InvalidExpiry);
+
+        let round = &mut ctx.accounts.crash_round;
+        round.round_id = round_id;
+        round.game_id = game_id;
+        round.authority = ctx.accounts.authority.key();
+        round.commitment = commitment;
+        round.betting_closes_at = betting_closes_at;
+        round.seed = [0; 32];
+        round.crash_point = 0;
+        round.revealed_at = 0;
+        round.open_bets = 0;
+        round.bump = ctx.bumps.crash_round;
+
+        msg!("Crash round opened: roundId={}, gameId={}, closesAt={}", round_id, game_id, betting_closes_at);
+        Ok(())
+    }
+
+    /// Stake on a crash round, cashing out automatically at `cashout_at` (100 = 1x)
+    ///
+    /// The stake stays in the vault, locked as an active game until the bet is
+    /// settled. The owner pays the bet PDA's rent, refunded on settlement.
+    pub fn place_crash_bet(ctx: Context<PlaceCrashBet>, stake: u64, cashout_at: u32) -> Result<()> {
+        let round = &ctx.accounts.crash_round;
+        require!(Clock::get()?.unix_timestamp < round.betting_closes_at, VaultError::CrashBettingClosed);
+        require!(stake > 0 && cashout_at > 100, VaultError::InvalidAmount);
+        ctx.accounts.global_config.check_stake(stake)?;
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        let payout = crash_payout(stake, cashout_at)?;
+        ctx.accounts.game_limits.check_payout(round.game_id, stake, payout)?;
+
+        if let Some(config) = load_compliance_config(&ctx.accounts.compliance_config)? {
+            let vault = ctx.accounts.vault.load()?;
+            config.check_region(vault.region, round.game_id, stake)?;
+            config.check_terms(&vault, stake)?;
+        }
+        let max_daily_net_win = ctx.accounts.game_limits.max_daily_net_win;
+        ctx.accounts.vault.load_mut()?.check_win_cap(Clock::get()?.unix_timestamp, max_daily_net_win)?;
+        lock_stake(&ctx.accounts.vault, stake)?;
+        ctx.accounts.house_vault.load_mut()?.add_exposure(payout - stake)?;
+
+        let bet = &mut ctx.accounts.crash_bet;
+        bet.round = round.key();
+        bet.vault = ctx.accounts.vault.key();
+        bet.owner = ctx.accounts.owner.key();
+        bet.stake = stake;
+        bet.cashout_at = cashout_at;
+        bet.bump = ctx.bumps.crash_bet;
+        let round = &mut ctx.accounts.crash_round;
+        round.open_bets = round.open_bets.checked_add(1).ok_or(VaultError::Overflow)?;
+
+        msg!("Crash bet placed: roundId={}, user={}, stake={}, cashoutAt={}", round.round_id, bet.owner, stake, cashout_at);
+        Ok(())
+    }
+
+    /// Reveal the round's seed once betting has closed, fixing its crash point
+    ///
+    /// Fails unless `keccak(seed)` matches the commitment made when the round opened.
+    pub fn reveal_crash_round(ctx: Context<RevealCrashRound>, seed: [u8; 32]) -> Result<()> {
+        let round = &mut ctx.accounts.crash_round;
+        let now = Clock::get()?.unix_timestamp;
+        require!(round.revealed_at == 0, VaultError::CrashRoundRevealed);
+        require!(now >= round.betting_closes_at, VaultError::CrashBettingOpen);
+        require!(keccak::hash(&seed).to_bytes() == round.commitment, VaultError::CrashSeedMismatch);
+
+        round.seed = seed;
+        round.crash_point = crash_point(&seed, round.round_id);
+        round.revealed_at = now;
+
+        msg!("Crash round revealed: roundId={}, crashPoint={}", round.round_id, round.crash_point);
+        Ok(())
+    }
+
+    /// Settle a crash bet against its revealed round (anyone)
+    ///
+    /// Pays `stake * cashout_at / 100` if the round crashed at or above the
+    /// cashout, nothing otherwise. A round not revealed within
+    /// `CRASH_REVEAL_TIMEOUT_SECS` after betting closed refunds the stake,
+    /// even if it is revealed later.
+    pub fn settle_crash_bet(ctx: Context<SettleCrashBet>) -> Result<()> {
+        let round = &ctx.accounts.crash_round;
+        let bet = &ctx.accounts.crash_bet;
+        let deadline = round.betting_closes_at.saturating_add(CRASH_REVEAL_TIMEOUT_SECS);
+        let payout = if round.revealed_at != 0 && round.revealed_at < deadline {
+            if bet.cashout_at <= round.crash_point { crash_payout(bet.stake, bet.cashout_at)? } else { 0 }
+        } else {
+            require!(Clock::get()?.unix_timestamp >= deadline, VaultError::CrashRoundNotRevealed);
+            msg!("Crash round {} not revealed in time; refunding stake", round.round_id);
+            bet.stake
+        };
+        let (stake, game_id, round_id) = (bet.stake, round.game_id, round.round_id);
+
+        ctx.accounts.house_vault.load_mut()?.release_exposure(bet.exposure()?);
+        let accounts = &ctx.accounts;
+        let (vault, bonus, house_vault) = (&accounts.vault, &accounts.bonus, &accounts.house_vault);
+        settle_locked_stake(vault, bonus, house_vault, &accounts.global_stats, stake, payout)?;
+        settle_loss_back(&accounts.loss_back_promo, &accounts.loss_back, stake, payout)?;
+        let round = &mut ctx.accounts.crash_round;
+        round.open_bets = round.open_bets.checked_sub(1).ok_or(VaultError::Overflow)?;
+
+        msg!("Crash bet settled: roundId={}, user={}, stake={}, payout={}",
+             round_id, ctx.accounts.owner.key(), stake, payout);
+        emit_cpi!(BetSettled {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault: ctx.accounts.vault.key(),
+            bet_id_hash: bet_id_hash(&format!("crash-{}", round_id)),
+            game_id,
+            stake,
+            payout,
+            gem_data: [0; 7],
+        });
+        Ok(())
+    }
+
+    /// Close a crash round with no unsettled bets, refunding its rent to the authority that opened it
+    pub fn close_crash_round(ctx: Context<CloseCrashRound>) -> Result<()> {
+        let round = &ctx.accounts.crash_round;
+        require!(round.open_bets == 0, VaultError::GamesInProgress);
+
+        msg!("Crash round closed: roundId={}", round.round_id);
+        Ok(())
+    }
+
+    /// Create the bet insurance fund (admin only)
+    ///
+    /// * `premium_bps` – least premium accepted, as a share of the stake
+    /// * `refund_bps`  – share of the stake refunded when an insured bet loses; 0 disables insurance
+    pub fn initialize_insurance_fund(
+        ctx: Context<InitializeInsuranceFund>,
+        premium_bps: u16,
+        refund_bps: u16,
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!(premium_bps <= 10_000 && refund_bps <= 10_000, VaultError::InvalidAmount);
+
+        let fund = &mut ctx.accounts.insurance_fund;
+        fund.premium_bps = premium_bps;
+        fund.refund_bps = refund_bps;
+        fund.reserved = 0;
+        fund.total_premiums = 0;
+        fund.total_refunds = 0;
+        fund.bump = ctx.bumps.insurance_fund;
+        Ok(())
+    }
+
+    /// Change the insurance terms for bets placed from now on (multisig only)
+    pub fn set_insurance_terms(ctx: Context<SetInsuranceTerms>, premium_bps: u16, refund_bps: u16) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+        require!(premium_bps <= 10_000 && refund_bps <= 10_000, VaultError::InvalidAmount);
+
+        let fund = &mut ctx.accounts.insurance_fund;
+        fund.premium_bps = premium_bps;
+        fund.refund_bps = refund_bps;
+
+        msg!("Insurance terms set: premium={}bps, refund={}bps", premium_bps, refund_bps);
+        Ok(())
+    }
+
+    /// Top up the insurance fund with SOL from any funder
+    pub fn fund_insurance_fund(ctx: Context<FundInsuranceFund>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        let funder = &ctx.accounts.funder;
+        let fund_info = ctx.accounts.insurance_fund.to_account_info();
+        invoke(
+            &system_instruction::transfer(&funder.key(), &fund_info.key(), amount),
+            &[
+                funder.to_account_info(),
+                fund_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+
+        msg!("Insurance fund funded: {} lamports", amount);
+        Ok(())
+    }
+
+    /// Lock `terms.stake` in the vault on the outcome of a sports event at fixed odds
+    ///
+    /// The odds are recorded on the bet and bound its payout. It can be
+    /// settled from `expiry`, the event's start, until `expires_at`, at most
+    /// `PENDING_BET_SETTLE_WINDOW_SECS` later; from then on settling refunds
+    /// the stake and anyone may release it. Signed by the admin or a
+    /// registered game program, which pays the bet PDA's rent.
+    ///
+    /// A non-zero `insurance_premium` insures the bet: the premium moves from
+    /// the vault to the insurance fund, which reserves the refund the bet is
+    /// owed if it loses. The premium is kept whatever the outcome.
+    pub fn place_pending_bet(ctx: Context<PlacePendingBet>, bet_id: String, terms: PendingBetTerms) -> Result<()> {
+        let PendingBetTerms { game_id, event_id, odds, stake, expiry, expires_at, insurance_premium } = terms;
+        require!(bet_id.len() <= MAX_BET_ID_LEN, VaultError::BetIdTooLong);
+        require!(stake > 0 && odds > 100, VaultError::InvalidAmount);
+        ctx.accounts.global_config.check_stake(stake)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(expiry > now, VaultError::InvalidExpiry);
+        require!(
+            expires_at > expiry && expires_at - expiry <= PENDING_BET_SETTLE_WINDOW_SECS,
+            VaultError::InvalidExpiry
+        );
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        let max_payout = crash_payout(stake, odds)?;
+        ctx.accounts.game_limits.check_payout(game_id, stake, max_payout)?;
+        if let Some(config) = load_compliance_config(&ctx.accounts.compliance_config)? {
+            let vault = ctx.accounts.vault.load()?;
+            config.check_region(vault.region, game_id, stake)?;
+            config.check_terms(&vault, stake)?;
+        }
+        ctx.accounts.vault.load_mut()?.check_win_cap(now, ctx.accounts.game_limits.max_daily_net_win)?;
+        lock_stake(&ctx.accounts.vault, stake)?;
+        ctx.accounts.house_vault.load_mut()?.add_exposure(max_payout - stake)?;
+        let insurance_refund = if insurance_premium > 0 {
+            let fund = ctx.accounts.insurance_fund.as_mut().ok_or(VaultError::InsuranceFundMissing)?;
+            insure_bet(&ctx.accounts.vault, fund, stake, insurance_premium)?
+        } else {
+            0
+        };
+
+        let bet = &mut ctx.accounts.pending_bet;
+        bet.vault = ctx.accounts.vault.key();
+        bet.payer = ctx.accounts.authority.key();
+        bet.bet_id_hash = bet_id_hash(&bet_id);
+        bet.game_id = game_id;
+        bet.event_id = event_id;
+        bet.odds = odds;
+        bet.stake = stake;
+        bet.placed_at = now;
+        bet.expiry = expiry;
+        bet.expires_at = expires_at;
+        bet.insurance_premium = insurance_premium;
+        bet.insurance_refund = insurance_refund;
+        bet.bump = ctx.bumps.pending_bet;
+
+        msg!("Pending bet placed: betId={}, eventId={}, odds={}, stake={}, premium={}, user={}",
+             bet_id, event_id, odds, stake, insurance_premium, ctx.accounts.vault.load()?.owner);
+        Ok(())
+    }
+
+    /// Settle a pending bet once its event has started, paying at most the stake at its locked odds
+    ///
+    /// Pass 0 for a loss, the stake for a void and the full payout for a win.
+    /// From the bet's `expires_at` on, the result is ignored and the stake refunded.
+    pub fn settle_pending_bet(ctx: Context<SettlePendingBet>, payout: u64) -> Result<()> {
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        let bet = &ctx.accounts.pending_bet;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= bet.expiry, VaultError::EventNotStarted);
+        require!(payout <= bet.max_payout()?, VaultError::PayoutExceedsLimit);
+        let payout = if now >= bet.expires_at {
+            msg!("Pending bet expired at {}; refunding stake", bet.expires_at);
+            bet.stake
+        } else {
+            payout
+        };
+
+        let (stake, game_id, bet_id_hash) = (bet.stake, bet.game_id, bet.bet_id_hash);
+        ctx.accounts.house_vault.load_mut()?.release_exposure(bet.exposure()?);
+        let accounts = &ctx.accounts;
+        let (vault, bonus, house_vault) = (&accounts.vault, &accounts.bonus, &accounts.house_vault);
+        settle_locked_stake(vault, bonus, house_vault, &accounts.global_stats, stake, payout)?;
+        settle_loss_back(&accounts.loss_back_promo, &accounts.loss_back, stake, payout)?;
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let refund = release_insurance(ctx.accounts.insurance_fund.as_mut(), &vault_info, bet, payout == 0)?;
+
+        msg!("Pending bet settled: eventId={}, stake={}, payout={}, refund={}", bet.event_id, stake, payout, refund);
+        emit_cpi!(BetSettled {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault: ctx.accounts.vault.key(),
+            bet_id_hash,
+            game_id,
+            stake,
+            payout,
+            gem_data: [0; 7],
+        });
+        if refund > 0 {
+            emit_cpi!(InsuranceRefunded {
+                version: EVENT_VERSION,
+                seq: ctx.accounts.global_config.next_seq()?,
+                vault: ctx.accounts.vault.key(),
+                bet_id_hash,
+                stake,
+                premium: ctx.accounts.pending_bet.insurance_premium,
+                refund,
+            });
+        }
+        Ok(())
+    }
+
+    /// Settle pending bet `bet_id` early for `cashout_value`, locking in part of its winnings for the player
+    ///
+    /// Allowed before the event starts, bounded by the payout at the bet's
+    /// locked odds. Signed by the admin or a registered game program.
+    pub fn cashout_bet(ctx: Context<CashoutBet>, bet_id: String, cashout_value: u64) -> Result<()> {
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+        let bet = &ctx.accounts.pending_bet;
+        bet.check_cashout_open(Clock::get()?.unix_timestamp)?;
+        require!(cashout_value > 0, VaultError::InvalidAmount);
+        require!(cashout_value <= bet.max_payout()?, VaultError::PayoutExceedsLimit);
+
+        let (stake, game_id, bet_id_hash) = (bet.stake, bet.game_id, bet.bet_id_hash);
+        ctx.accounts.house_vault.load_mut()?.release_exposure(bet.exposure()?);
+        let accounts = &ctx.accounts;
+        let (vault, bonus, house_vault) = (&accounts.vault, &accounts.bonus, &accounts.house_vault);
+        settle_locked_stake(vault, bonus, house_vault, &accounts.global_stats, stake, cashout_value)?;
+        settle_loss_back(&accounts.loss_back_promo, &accounts.loss_back, stake, cashout_value)?;
+        let vault_info = ctx.accounts.vault.to_account_info();
+        release_insurance(ctx.accounts.insurance_fund.as_mut(), &vault_info, bet, false)?;
+
+        msg!("Bet cashed out: betId={}, eventId={}, stake={}, cashout={}", bet_id, bet.event_id, stake, cashout_value);
+        emit_cpi!(BetSettled {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault: ctx.accounts.vault.key(),
+            bet_id_hash,
+            game_id,
+            stake,
+            payout: cashout_value,
+            gem_data: [0; 7],
+        });
+        Ok(())
+    }
+
+    /// Release the stake of a pending bet left unsettled at its `expires_at` (anyone)
+    pub fn expire_pending_bet(ctx: Context<ExpirePendingBet>) -> Result<()> {
+        let bet = &ctx.accounts.pending_bet;
+        require!(Clock::get()?.unix_timestamp >= bet.expires_at, VaultError::PendingBetNotExpired);
+
+        unlock_stake(&mut *ctx.accounts.vault.load_mut()?, bet.stake)?;
+        ctx.accounts.house_vault.load_mut()?.release_exposure(bet.exposure()?);
+        let vault_info = ctx.accounts.vault.to_account_info();
+        release_insurance(ctx.accounts.insurance_fund.as_mut(), &vault_info, bet, false)?;
+
+        msg!("Pending bet expired: eventId={}, stake={} released", bet.event_id, bet.stake);
+        Ok(())
+    }
+
+    /// Emergency pause (multisig only)
+    pub fn emergency_pause(ctx: Context<EmergencyPause>) -> Result<()> {
+        let config = &mut ctx.accounts.pause_config;
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        config.set_flag(PAUSE_EMERGENCY, true);
+        config.set_flag(PAUSE_MAINTENANCE, false); // Override maintenance pause
+        
+        msg!("Emergency pause activated");
+        Ok(())
+    }
+
+    /// Unpause (multisig only)
+    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
+        let config = &mut ctx.accounts.pause_config;
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        config.flags = 0;
+        config.maintenance_start_time = 0;
+        
+        msg!("All pauses deactivated");
+        Ok(())
+    }
+
+    /// Get pause status for UI (readable method)
+    pub fn get_pause_status(ctx: Context<GetPauseStatus>) -> Result<()> {
+        let config = &ctx.accounts.pause_config;
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+        
+        if config.emergency_pause() {
+            msg!("EMERGENCY_PAUSE:true");
+            msg!("MAINTENANCE_PAUSE:false");
+            msg!("RESUME_TIME:indefinite");
+            msg!("MESSAGE:Emergency pause active - no operations allowed");
+        } else if config.maintenance_pause() {
+            let elapsed_hours = (current_time - config.maintenance_start_time as i64) / 3600;
+            let remaining_hours = config.maintenance_duration_hours.saturating_sub(elapsed_hours as u8);
+            
+            if remaining_hours > 0 {
+                msg!("EMERGENCY_PAUSE:false");
+                msg!("MAINTENANCE_PAUSE:true");
+                msg!("RESUME_TIME:{} hours", remaining_hours);
+                msg!("MESSAGE:Maintenance in progress - will resume in {} hours", remaining_hours);
+            } else {
+                // Auto-resume maintenance pause
+                msg!("EMERGENCY_PAUSE:false");
+                msg!("MAINTENANCE_PAUSE:false");
+                msg!("RESUME_TIME:now");
+                msg!("MESSAGE:Maintenance complete - operations restored");
+            }
+        } else {
+            msg!("EMERGENCY_PAUSE:false");
+            msg!("MAINTENANCE_PAUSE:false");
+            msg!("RESUME_TIME:now");
+            msg!("MESSAGE:All operations active");
+        }
+        
+        Ok(())
+    }
+
+    /// Read-only vault snapshot returned via return data (simulate to query)
+    pub fn get_vault_info(ctx: Context<GetVaultInfo>) -> Result<VaultInfo> {
+        let vault = ctx.accounts.vault.load()?;
+        let balance = ctx.accounts.vault.to_account_info().lamports();
+
+        Ok(VaultInfo {
+            owner: vault.owner,
+            balance,
+            available: balance.saturating_sub(vault.locked_amount),
+            locked_amount: vault.locked_amount,
+            active_games: vault.active_games,
+            accum_wager: vault.accum_wager,
+            lifetime_wager: vault.lifetime_wager,
+            created_at: vault.created_at,
+            version: vault.version,
+        })
+    }
+
+    /// Read-only house snapshot, including pause flags, returned via return data
+    pub fn get_house_info(ctx: Context<GetHouseInfo>) -> Result<HouseInfo> {
+        let house = ctx.accounts.house_vault.load()?;
+        let pause = &ctx.accounts.pause_config;
+
+        Ok(HouseInfo {
+            balance: ctx.accounts.house_vault.to_account_info().lamports(),
+            total_volume: house.total_volume,
+            category_volume: house.category_volume,
+            multisig_authority: house.multisig_authority,
+            admin_authority: house.admin_authority,
+            emergency_pause: pause.emergency_pause(),
+            maintenance_pause: pause.maintenance_pause(),
+            maintenance_start_time: pause.maintenance_start_time as i64,
+            maintenance_duration_hours: pause.maintenance_duration_hours,
+        })
+    }
+
+    /// Emit page `page` of the audit snapshot as an `AuditPage` event (anyone)
+    ///
+    /// Pages run from 0 to `AUDIT_PAGE_COUNT - 1` and list their entries in a
+    /// fixed order, so exporting every page at one slot snapshots the program.
+    pub fn export_audit_page(ctx: Context<ExportAuditPage>, page: u8) -> Result<()> {
+        let accounts = &ctx.accounts;
+        let mut entries = Vec::new();
+        let mut add = |label: String, address: Pubkey, amount: u64| entries.push(AuditEntry { label, address, amount });
+
+        match page {
+            AUDIT_PAGE_AUTHORITIES => {
+                let house = accounts.house_vault.load()?;
+                let pause = &accounts.pause_config;
+                add("multisig_authority".into(), house.multisig_authority, 0);
+                add("admin_authority".into(), house.admin_authority, 0);
+                add("pause_multisig_authority".into(), pause.multisig_authority, 0);
+                add("pause_admin_authority".into(), pause.admin_authority, 0);
+                add("emergency_pause".into(), Pubkey::default(), pause.emergency_pause() as u64);
+                add("maintenance_pause".into(), Pubkey::default(), pause.maintenance_pause() as u64);
+                add("maintenance_start_time".into(), Pubkey::default(), pause.maintenance_start_time as u64);
+                add("maintenance_duration_hours".into(), Pubkey::default(), pause.maintenance_duration_hours as u64);
+                add("event_seq".into(), Pubkey::default(), accounts.global_config.event_seq);
+            }
+            AUDIT_PAGE_BALANCES => {
+                let house_info = accounts.house_vault.to_account_info();
+                add("house_balance".into(), house_info.key(), house_info.lamports());
+                let house = accounts.house_vault.load()?;
+                add("house_total_volume".into(), house_info.key(), house.total_volume);
+                for (category, volume) in { house.category_volume }.into_iter().enumerate() {
+                    add(format!("house_category_volume_{category}"), house_info.key(), volume);
+                }
+                let promo_info = accounts.promo_pool.to_account_info();
+                if let Some(promo) = load_optional::<PromoPool>(&promo_info)? {
+                    add("promo_pool_balance".into(), promo_info.key(), promo_info.lamports());
+                    add("promo_pool_total_granted".into(), promo_info.key(), promo.total_granted);
+                    add("promo_pool_total_forfeited".into(), promo_info.key(), promo.total_forfeited);
+                }
+                let stats = accounts.global_stats.load()?;
+                add("total_users".into(), Pubkey::default(), stats.total_users);
+                add("total_bets".into(), Pubkey::default(), stats.total_bets);
+                add("total_volume".into(), Pubkey::default(), stats.total_volume);
+                add("biggest_win".into(), stats.biggest_win_vault, stats.biggest_win);
+            }
+            AUDIT_PAGE_GAME_LIMITS => {
+                let limits = &accounts.game_limits;
+                add("default_max_multiplier".into(), Pubkey::default(), limits.default_max_multiplier as u64);
+                add("max_unstaked_payout".into(), Pubkey::default(), limits.max_unstaked_payout);
+                add("max_bets_per_second".into(), Pubkey::default(), limits.max_bets_per_second as u64);
+                add("max_transaction_payout".into(), Pubkey::default(), limits.max_transaction_payout);
+                add("max_daily_net_win".into(), Pubkey::default(), limits.max_daily_net_win);
+                for game in &limits.games {
+                    add(format!("game:{}:max_multiplier", game.game_id), Pubkey::default(), game.max_multiplier as u64);
+                }
+            }
+            AUDIT_PAGE_GAME_PROGRAMS => {
+                if let Some(registry) = load_optional::<GamePrograms>(&accounts.game_programs)? {
+                    for game in &registry.programs {
+                        add("game_program".into(), game.program_id, 0);
+                        add("game_program_authority".into(), game.authority, 0);
+                    }
+                }
+            }
+            AUDIT_PAGE_COMPLIANCE => {
+                if let Some(config) = load_compliance_config(&accounts.compliance_config)? {
+                    add("kyc_withdraw_threshold".into(), Pubkey::default(), config.kyc_withdraw_threshold);
+                    add("large_transfer_threshold".into(), Pubkey::default(), config.large_transfer_threshold);
+                    add("screening_threshold".into(), config.screening_program, config.screening_threshold);
+                    let terms_hash = Pubkey::new_from_array(config.terms_hash);
+                    add("terms_required".into(), terms_hash, config.terms_required as u64);
+                    for rule in &config.region_rules {
+                        let label = match rule.game_id {
+                            Some(game_id) => format!("region:{}:game:{}:max_stake", rule.region, game_id),
+                            None => format!("region:{}:max_stake", rule.region),
+                        };
+                        add(label, Pubkey::default(), rule.max_stake);
+                    }
+                    for attestor in &config.attestors {
+                        add("kyc_attestor".into(), *attestor, 0);
+                    }
+                }
+            }
+            _ => return err!(VaultError::InvalidAuditPage),
+        }
+
+        emit_cpi!(AuditPage {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            slot: Clock::get()?.slot,
+            page,
+            page_count: AUDIT_PAGE_COUNT,
+            entries,
+        });
+        Ok(())
+    }
+
+    /// Change authorities (multisig only)
+    pub fn change_authority(
+        ctx: Context<ChangeAuthority>,
+        new_multisig: Option<Pubkey>,
+        new_admin: Option<Pubkey>,
+    ) -> Result<()> {
+        let mut house_vault = ctx.accounts.house_vault.load_mut()?;
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        if let Some(new_multisig_pubkey) = new_multisig {
+            house_vault.multisig_authority = new_multisig_pubkey;
+            msg!("Multisig authority updated to: {}", new_multisig_pubkey);
+        }
+
+        if let Some(new_admin_pubkey) = new_admin {
+            house_vault.admin_authority = new_admin_pubkey;
+            msg!("Admin authority updated to: {}", new_admin_pubkey);
+        }
+
+        Ok(())
+    }
+
+    /// Initialize the promo pool PDA that funds bonuses (admin only)
+    pub fn initialize_promo_pool(ctx: Context<InitializePromoPool>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let promo_pool = &mut ctx.accounts.promo_pool;
+        promo_pool.bump = ctx.bumps.promo_pool;
+        promo_pool.total_granted = 0;
+        promo_pool.total_forfeited = 0;
+        Ok(())
+    }
+
+    /// Top up the promo pool with SOL from any funder
+    pub fn fund_promo_pool(ctx: Context<FundPromoPool>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        let funder = &ctx.accounts.funder;
+        let pool_info = ctx.accounts.promo_pool.to_account_info();
+
+        invoke(
+            &system_instruction::transfer(
+                &funder.key(),
+                &pool_info.key(),
+                amount,
+            ),
+            &[
+                funder.to_account_info().clone(),
+                pool_info.clone(),
+                ctx.accounts.system_program.to_account_info().clone(),
+            ],
+        )?;
+
+        msg!("Promo pool funded: {} lamports", amount);
+        Ok(())
+    }
+
+    /// Grant a deposit match bonus (admin only)
+    ///
+    /// * `deposit_amount` – the qualifying deposit being matched
+    /// * `match_bps`      – match rate in basis points (10000 = 100%)
+    /// * `playthrough`    – wager requirement as a multiple of the bonus
+    /// * `expires_at`     – unix timestamp after which an uncleared bonus can be forfeited
+    pub fn grant_deposit_bonus(
+        ctx: Context<GrantDepositBonus>,
+        deposit_amount: u64,
+        match_bps: u16,
+        playthrough: u8,
+        expires_at: i64,
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!(match_bps > 0 && match_bps <= 10_000, VaultError::InvalidAmount);
+        require!(expires_at > Clock::get()?.unix_timestamp, VaultError::InvalidExpiry);
+
+        let bonus_amount = (deposit_amount as u128)
+            .checked_mul(match_bps as u128)
+            .ok_or(VaultError::Overflow)?
+            / 10_000;
+        let bonus_amount = u64::try_from(bonus_amount).map_err(|_| VaultError::Overflow)?;
+        require!(bonus_amount > 0, VaultError::InvalidAmount);
+
+        let pool_info = ctx.accounts.promo_pool.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+
+        // Keep the pool itself rent exempt
+        let rent_floor = Rent::get()?.minimum_balance(pool_info.data_len());
+        require!(
+            **pool_info.lamports.borrow() >= bonus_amount.checked_add(rent_floor).ok_or(VaultError::Overflow)?,
+            VaultError::PromoPoolInsufficient
+        );
+
+        transfer_lamports(&pool_info, &vault_info, bonus_amount)?;
+
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        vault.locked_amount = vault.locked_amount.checked_add(bonus_amount).ok_or(VaultError::Overflow)?;
+
+        let promo_pool = &mut ctx.accounts.promo_pool;
+        promo_pool.total_granted = promo_pool.total_granted.checked_add(bonus_amount).ok_or(VaultError::Overflow)?;
+
+        let bonus = &mut ctx.accounts.bonus;
+        bonus.vault = ctx.accounts.vault.key();
+        bonus.deposit_amount = deposit_amount;
+        bonus.bonus_amount = bonus_amount;
+        bonus.wager_requirement = bonus_amount.checked_mul(playthrough as u64).ok_or(VaultError::Overflow)?;
+        bonus.wagered = 0;
+        bonus.expires_at = expires_at;
+        bonus.cleared = bonus.wager_requirement == 0;
+        bonus.remaining = bonus_amount;
+        bonus.bump = ctx.bumps.bonus;
+
+        if bonus.cleared {
+            vault.locked_amount = vault.locked_amount.checked_sub(bonus_amount).ok_or(VaultError::Overflow)?;
+        }
+
+        msg!("Deposit bonus granted: user={}, deposit={}, bonus={}, wagerRequirement={}, expiresAt={}",
+             vault.owner, deposit_amount, bonus_amount, bonus.wager_requirement, expires_at);
+        Ok(())
+    }
+
+    /// Forfeit an expired, uncleared deposit bonus back to the promo pool (permissionless)
+    ///
+    /// Cleared bonuses can also be closed here to reclaim the record's rent.
+    pub fn forfeit_bonus(ctx: Context<ForfeitBonus>) -> Result<()> {
+        let bonus = &ctx.accounts.bonus;
+        let mut vault = ctx.accounts.vault.load_mut()?;
+
+        if !bonus.cleared {
+            require!(
+                Clock::get()?.unix_timestamp >= bonus.expires_at,
+                VaultError::BonusNotExpired
+            );
+
+            let vault_info = ctx.accounts.vault.to_account_info();
+            let pool_info = ctx.accounts.promo_pool.to_account_info();
+
+            // Only the bonus not yet lost at the tables goes back; the vault keeps its rent and deposits
+            let spendable = vault_info.lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(vault_info.data_len()));
+            let reclaim = bonus.reclaimable(spendable);
+            transfer_lamports(&vault_info, &pool_info, reclaim)?;
+
+            vault.locked_amount = vault.locked_amount.checked_sub(bonus.remaining).ok_or(VaultError::Overflow)?;
+
+            let promo_pool = &mut ctx.accounts.promo_pool;
+            promo_pool.total_forfeited = promo_pool.total_forfeited.checked_add(reclaim).ok_or(VaultError::Overflow)?;
+
+            msg!("Deposit bonus forfeited: user={}, bonus={}, wagered={}/{}, reclaimed={}",
+                 vault.owner, bonus.bonus_amount, bonus.wagered, bonus.wager_requirement, reclaim);
+        } else {
+            msg!("Cleared deposit bonus closed: user={}", vault.owner);
+        }
+
+        // Record rent goes to the promo pool via `close`
+        Ok(())
+    }
+
+    /// Claw back what is left of an abused deposit bonus (multisig only)
+    ///
+    /// Only the bonus still held is reclaimed; the vault's own deposits are never touched.
+    pub fn clawback_bonus(ctx: Context<ClawbackBonus>, reason_code: u8) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let bonus = &ctx.accounts.bonus;
+        require!(!bonus.cleared, VaultError::BonusCleared);
+
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let pool_info = ctx.accounts.promo_pool.to_account_info();
+
+        let spendable = vault_info.lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(vault_info.data_len()));
+        let amount = bonus.reclaimable(spendable);
+        transfer_lamports(&vault_info, &pool_info, amount)?;
+
+        vault.locked_amount = vault.locked_amount.checked_sub(bonus.remaining).ok_or(VaultError::Overflow)?;
+
+        let promo_pool = &mut ctx.accounts.promo_pool;
+        promo_pool.total_forfeited = promo_pool.total_forfeited.checked_add(amount).ok_or(VaultError::Overflow)?;
+
+        emit_cpi!(PromoClawback {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            user: vault.owner,
+            bonus_amount: bonus.bonus_amount,
+            amount,
+            reason_code,
+        });
+        msg!("Bonus clawed back: user={}, amount={}, reason={}", vault.owner, amount, reason_code);
+        Ok(())
+    }
+
+    /// Initialize the on-chain gem inventory for the caller's vault
+    pub fn initialize_gem_inventory(ctx: Context<InitializeGemInventory>) -> Result<()> {
+        let inventory = &mut ctx.accounts.gem_inventory;
+        inventory.vault = ctx.accounts.vault.key();
+        inventory.counts = [0; 7];
+        inventory.boost_multiplier = 100;
+        inventory.bump = ctx.bumps.gem_inventory;
+        Ok(())
+    }
+
+    /// Burn one gem for a one-shot reward multiplier on the next settlement
+    ///
+    /// * `gem_type` – index into the 7 gem types (0 = Garnet ... 6 = Diamond)
+    pub fn burn_gems_for_boost(ctx: Context<BurnGemsForBoost>, gem_type: u8) -> Result<()> {
+        require!((gem_type as usize) < GEM_BOOST_MULTIPLIERS.len(), VaultError::InvalidGemType);
+
+        let inventory = &mut ctx.accounts.gem_inventory;
+        require!(inventory.boost_multiplier <= 100, VaultError::BoostAlreadyActive);
+
+        let count = &mut inventory.counts[gem_type as usize];
+        *count = count.checked_sub(1).ok_or(VaultError::InsufficientGems)?;
+        inventory.boost_multiplier = GEM_BOOST_MULTIPLIERS[gem_type as usize];
+
+        msg!("Gem burned for boost: user={}, gemType={}, multiplier={}",
+             ctx.accounts.owner.key(), gem_type, inventory.boost_multiplier);
+        emit_cpi!(GemBurned {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault: inventory.vault,
+            gem_type,
+            boost_multiplier: inventory.boost_multiplier,
+        });
+        Ok(())
+    }
+
+
+    /// Create a gem raffle paid out from the promo pool (admin only)
+    ///
+    /// * `entry_gem_type` – gem type accepted as tickets (one gem = one ticket)
+    /// * `prize`          – lamports credited to the winner's vault
+    /// * `closes_at`      – entries close and the draw opens at this timestamp
+    /// * `draw_authority` – signer that reveals the draw seed
+    /// * `commitment`     – keccak of the draw seed, fixed before any entry
+    pub fn create_raffle(
+        ctx: Context<CreateRaffle>,
+        raffle_id: u64,
+        entry_gem_type: u8,
+        prize: u64,
+        closes_at: i64,
+        draw_authority: Pubkey,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!((entry_gem_type as usize) < GEM_BOOST_MULTIPLIERS.len(), VaultError::InvalidGemType);
+        require!(prize > 0, VaultError::InvalidAmount);
+        require!(closes_at > Clock::get()?.unix_timestamp, VaultError::InvalidExpiry);
+
+        let raffle = &mut ctx.accounts.raffle;
+        raffle.raffle_id = raffle_id;
+        raffle.entry_gem_type = entry_gem_type;
+        raffle.prize = prize;
+        raffle.closes_at = closes_at;
+        raffle.draw_authority = draw_authority;
+        raffle.commitment = commitment;
+        raffle.total_tickets = 0;
+        raffle.winning_ticket = 0;
+        raffle.drawn = false;
+        raffle.paid = false;
+        raffle.bump = ctx.bumps.raffle;
+
+        msg!("Raffle created: id={}, gemType={}, prize={}, closesAt={}", raffle_id, entry_gem_type, prize, closes_at);
+        Ok(())
+    }
+
+    /// Burn gems from the caller's inventory as raffle tickets (one entry per vault)
+    pub fn enter_raffle(ctx: Context<EnterRaffle>, tickets: u32) -> Result<()> {
+        require!(tickets > 0, VaultError::InvalidAmount);
+
+        let raffle = &mut ctx.accounts.raffle;
+        require!(Clock::get()?.unix_timestamp < raffle.closes_at, VaultError::RaffleClosed);
+
+        let inventory = &mut ctx.accounts.gem_inventory;
+        let count = &mut inventory.counts[raffle.entry_gem_type as usize];
+        *count = count.checked_sub(tickets).ok_or(VaultError::InsufficientGems)?;
+
+        let entry = &mut ctx.accounts.entry;
+        entry.raffle = raffle.key();
+        entry.vault = ctx.accounts.vault.key();
+        entry.first_ticket = raffle.total_tickets;
+        entry.tickets = tickets;
+        entry.bump = ctx.bumps.entry;
+
+        raffle.total_tickets = raffle.total_tickets.checked_add(tickets as u64).ok_or(VaultError::Overflow)?;
+
+        msg!("Raffle entry: id={}, user={}, tickets={}, firstTicket={}",
+             raffle.raffle_id, ctx.accounts.owner.key(), tickets, entry.first_ticket);
+        Ok(())
+    }
+
+    /// Draw the winning ticket by revealing the seed committed at creation (draw authority only)
+    pub fn draw_winner(ctx: Context<DrawWinner>, seed: [u8; 32]) -> Result<()> {
+        let raffle_key = ctx.accounts.raffle.key();
+        let raffle = &mut ctx.accounts.raffle;
+        require!(ctx.accounts.draw_authority.key() == raffle.draw_authority, VaultError::Unauthorized);
+        require!(Clock::get()?.unix_timestamp >= raffle.closes_at, VaultError::RaffleOpen);
+        raffle.draw(&raffle_key, &seed)?;
+
+        msg!("Raffle drawn: id={}, winningTicket={}, totalTickets={}",
+             raffle.raffle_id, raffle.winning_ticket, raffle.total_tickets);
+        Ok(())
+    }
+
+    /// Pay the raffle prize from the promo pool into the winning entry's vault (permissionless)
+    pub fn claim_raffle_prize(ctx: Context<ClaimRafflePrize>) -> Result<()> {
+        let raffle = &mut ctx.accounts.raffle;
+        let entry = &ctx.accounts.entry;
+        require!(raffle.drawn, VaultError::RaffleOpen);
+        require!(!raffle.paid, VaultError::RaffleAlreadyDrawn);
+        require!(
+            raffle.winning_ticket >= entry.first_ticket &&
+            raffle.winning_ticket < entry.first_ticket + entry.tickets as u64,
+            VaultError::NotRaffleWinner
+        );
+
+        let pool_info = ctx.accounts.promo_pool.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(pool_info.data_len());
+        require!(
+            **pool_info.lamports.borrow() >= raffle.prize.checked_add(rent_floor).ok_or(VaultError::Overflow)?,
+            VaultError::PromoPoolInsufficient
+        );
+
+        transfer_lamports(&pool_info, &vault_info, raffle.prize)?;
+        raffle.paid = true;
+
+        msg!("Raffle prize paid: id={}, user={}, prize={}", raffle.raffle_id, ctx.accounts.vault.load()?.owner, raffle.prize);
+        Ok(())
+    }
+
+    /// Create a single-elimination bracket paid out from the promo pool (admin only)
+    ///
+    /// Entrant vaults are passed as remaining accounts in seeding order; round
+    /// one pairs the first with the second, the third with the fourth, and so
+    /// on. `prizes` is paid per entrant by standing: champion, runner-up, each
+    /// semifinal loser, each quarterfinal loser, ...
+    pub fn create_tournament<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateTournament<'info>>,
+        tournament_id: u64,
+        game_id: u64,
+        prizes: Vec<u64>,
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        let size = ctx.remaining_accounts.len();
+        require!((2..=MAX_BRACKET_SIZE).contains(&size) && size.is_power_of_two(), VaultError::BracketSizeInvalid);
+        require!(prizes.len() <= size.trailing_zeros() as usize + 1, VaultError::BracketSizeInvalid);
+
+        let mut entrants = Vec::with_capacity(size);
+        for (i, vault_info) in ctx.remaining_accounts.iter().enumerate() {
+            require!(
+                ctx.remaining_accounts[..i].iter().all(|earlier| earlier.key != vault_info.key),
+                VaultError::DuplicateVault
+            );
+            AccountLoader::<UserVault>::try_from(vault_info)?;
+            entrants.push(vault_info.key());
+        }
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.tournament_id = tournament_id;
+        tournament.game_id = game_id;
+        tournament.payer = ctx.accounts.authority.key();
+        tournament.round = 1;
+        tournament.entrants = entrants;
+        tournament.eliminated_in = vec![0; size];
+        tournament.prizes = prizes;
+        tournament.bump = ctx.bumps.tournament;
+
+        msg!("Tournament created: id={}, gameId={}, entrants={}, rounds={}, prizes={}",
+             tournament_id, game_id, size, tournament.rounds(), tournament.total_prizes()?);
+        Ok(())
+    }
+
+    /// Post the result of match `match_index` in the current round (admin or game program)
+    pub fn post_match_result(ctx: Context<PostMatchResult>, match_index: u16, winner: Pubkey) -> Result<()> {
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+
+        let tournament = &mut ctx.accounts.tournament;
+        let loser = tournament.record_result(match_index as usize, &winner)?;
+
+        msg!("Tournament match settled: id={}, round={}, match={}, winner={}, loser={}",
+             tournament.tournament_id, tournament.round, match_index, winner, loser);
+        emit_cpi!(TournamentMatchSettled {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            tournament_id: tournament.tournament_id,
+            round: tournament.round,
+            match_index,
+            winner,
+            loser,
+        });
+        Ok(())
+    }
+
+    /// Move the bracket to its next round once every match of the current one is posted
+    pub fn advance_tournament_round(ctx: Context<AdvanceTournamentRound>) -> Result<()> {
+        require_settlement_authority(&ctx.accounts.authority.key(), ctx.accounts.game_programs.as_deref())?;
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.advance()?;
+
+        if tournament.is_complete() {
+            msg!("Tournament complete: id={}", tournament.tournament_id);
+        } else {
+            msg!("Tournament advanced: id={}, round={}", tournament.tournament_id, tournament.round);
+        }
+        Ok(())
+    }
+
+    /// Pay every entrant's prize by final standing from the promo pool (permissionless)
+    ///
+    /// Entrant vaults are passed as remaining accounts in seeding order. The
+    /// bracket is closed, refunding its rent to the admin that created it.
+    pub fn distribute_tournament_prizes<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeTournamentPrizes<'info>>,
+    ) -> Result<()> {
+        let tournament = &ctx.accounts.tournament;
+        require!(tournament.is_complete(), VaultError::TournamentInProgress);
+        require!(ctx.remaining_accounts.len() == tournament.entrants.len(), VaultError::AccountCountMismatch);
+
+        let total = tournament.total_prizes()?;
+        let pool_info = ctx.accounts.promo_pool.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(pool_info.data_len());
+        require!(
+            pool_info.lamports() >= total.checked_add(rent_floor).ok_or(VaultError::Overflow)?,
+            VaultError::PromoPoolInsufficient
+        );
+
+        let mut standings = Vec::with_capacity(tournament.entrants.len());
+        for (i, (entrant, vault_info)) in tournament.entrants.iter().zip(ctx.remaining_accounts).enumerate() {
+            require!(vault_info.key() == *entrant && vault_info.is_writable, VaultError::AccountCountMismatch);
+            let (standing, prize) = (tournament.standing(i), tournament.prize(i));
+            if prize > 0 {
+                transfer_lamports(&pool_info, vault_info, prize)?;
+            }
+            standings.push(TournamentStanding { vault: *entrant, standing, prize });
+        }
+
+        msg!("Tournament prizes paid: id={}, entrants={}, total={}",
+             tournament.tournament_id, standings.len(), total);
+        emit_cpi!(TournamentPrizesPaid {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            tournament_id: tournament.tournament_id,
+            game_id: tournament.game_id,
+            standings,
+        });
+        Ok(())
+    }
+
+    /// Initialize the loss-back promo config with no open window (admin only)
+    pub fn initialize_loss_back_promo(ctx: Context<InitializeLossBackPromo>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let promo = &mut ctx.accounts.loss_back_promo;
+        promo.promo_id = 0;
+        promo.start_time = 0;
+        promo.end_time = 0;
+        promo.rate_bps = 0;
+        promo.bump = ctx.bumps.loss_back_promo;
+        Ok(())
+    }
+
+    /// Open a loss-back promo window (admin only)
+    ///
+    /// * `rate_bps` – share of each net loss accrued back, in basis points
+    pub fn configure_loss_back(
+        ctx: Context<ConfigureLossBack>,
+        start_time: i64,
+        end_time: i64,
+        rate_bps: u16,
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!(rate_bps > 0 && rate_bps <= 10_000, VaultError::InvalidAmount);
+        require!(end_time > start_time, VaultError::InvalidExpiry);
+
+        let now = Clock::get()?.unix_timestamp;
+        let promo = &mut ctx.accounts.loss_back_promo;
+        // Windows never overlap, so each accrual maps to exactly one promo
+        require!(promo.end_time <= now, VaultError::PromoActive);
+
+        promo.promo_id = promo.promo_id.checked_add(1).ok_or(VaultError::Overflow)?;
+        promo.start_time = start_time;
+        promo.end_time = end_time;
+        promo.rate_bps = rate_bps;
+
+        msg!("Loss-back promo configured: promoId={}, start={}, end={}, rateBps={}",
+             promo.promo_id, start_time, end_time, rate_bps);
+        Ok(())
+    }
+
+    /// Create the pity boost config (admin only)
+    ///
+    /// * `min_losses` – consecutive staked losses after which the next settlement's gems are boosted; 0 disables it
+    /// * `multiplier` – applied to those gems (100 = 1x), up to `MAX_PITY_MULTIPLIER`
+    pub fn initialize_pity_config(ctx: Context<InitializePityConfig>, min_losses: u16, multiplier: u16) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.pity_config;
+        config.set(min_losses, multiplier)?;
+        config.bump = ctx.bumps.pity_config;
+        Ok(())
+    }
+
+    /// Change the pity boost parameters (admin only)
+    pub fn set_pity_config(ctx: Context<SetPityConfig>, min_losses: u16, multiplier: u16) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        ctx.accounts.pity_config.set(min_losses, multiplier)?;
+
+        msg!("Pity config set: minLosses={}, multiplier={}", min_losses, multiplier);
+        Ok(())
+    }
+
+    /// Create the caller's loss-back balance record
+    pub fn initialize_loss_back(ctx: Context<InitializeLossBack>) -> Result<()> {
+        let loss_back = &mut ctx.accounts.loss_back;
+        loss_back.vault = ctx.accounts.vault.key();
+        loss_back.promo_id = 0;
+        loss_back.window_end = 0;
+        loss_back.accrued = 0;
+        loss_back.carried = 0;
+        loss_back.bump = ctx.bumps.loss_back;
+        Ok(())
+    }
+
+    /// Pay an accrued loss-back balance into the vault once its window has closed (permissionless)
+    pub fn claim_loss_back(ctx: Context<ClaimLossBack>) -> Result<()> {
+        let loss_back = &mut ctx.accounts.loss_back;
+        require!(loss_back.accrued > 0 || loss_back.carried > 0, VaultError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        let amount = loss_back.claimable(now);
+        require!(amount > 0, VaultError::PromoActive);
+
+        let pool_info = ctx.accounts.promo_pool.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(pool_info.data_len());
+        require!(
+            **pool_info.lamports.borrow() >= amount.checked_add(rent_floor).ok_or(VaultError::Overflow)?,
+            VaultError::PromoPoolInsufficient
+        );
+
+        transfer_lamports(&pool_info, &vault_info, amount)?;
+        loss_back.carried = 0;
+        if now >= loss_back.window_end {
+            loss_back.accrued = 0;
+        }
+
+        let promo_pool = &mut ctx.accounts.promo_pool;
+        promo_pool.total_granted = promo_pool.total_granted.checked_add(amount).ok_or(VaultError::Overflow)?;
+
+        msg!("Loss-back claimed: user={}, promoId={}, amount={}", ctx.accounts.vault.load()?.owner, loss_back.promo_id, amount);
+        Ok(())
+    }
+
+    /// Pay the tranches of a streamed win that have fallen due from the house into the vault (anyone)
+    ///
+    /// The last claim closes the schedule, refunding its rent to the settlement authority that opened it.
+    pub fn claim_payout_tranches(ctx: Context<ClaimPayoutTranches>) -> Result<()> {
+        let schedule = &mut ctx.accounts.payout_schedule;
+        let amount = schedule.claimable(Clock::get()?.unix_timestamp);
+        require!(amount > 0, VaultError::NoTrancheDue);
+
+        let house_info = ctx.accounts.house_vault.to_account_info();
+        require!(house_info.lamports() >= amount, VaultError::HouseInsufficient);
+        transfer_lamports(&house_info, &ctx.accounts.vault.to_account_info(), amount)?;
+        ctx.accounts.house_vault.load_mut()?.release_exposure(amount);
+        schedule.claimed = schedule.claimed.checked_add(amount).ok_or(VaultError::Overflow)?;
+
+        msg!(
+            "Payout tranche claimed: vault={}, amount={}, claimed={}/{}",
+            schedule.vault, amount, schedule.claimed, schedule.total
+        );
+        emit_cpi!(PayoutTrancheClaimed {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault: schedule.vault,
+            bet_id_hash: schedule.bet_id_hash,
+            amount,
+            claimed: schedule.claimed,
+            total: schedule.total,
+        });
+        if schedule.claimed == schedule.total {
+            let rent_payer = ctx.accounts.rent_payer.to_account_info();
+            ctx.accounts.payout_schedule.close(rent_payer)?;
+        }
+        Ok(())
+    }
+
+    /// Initialize reward eligibility thresholds (admin only)
+    pub fn initialize_rewards_config(ctx: Context<InitializeRewardsConfig>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.rewards_config;
+        config.min_lifetime_wager = 500_000_000; // 0.5 SOL
+        config.min_vault_age_days = 3;
+        config.bump = ctx.bumps.rewards_config;
+        Ok(())
+    }
+
+    /// Update reward eligibility thresholds (multisig only)
+    pub fn update_reward_eligibility(
+        ctx: Context<UpdateRewardEligibility>,
+        min_lifetime_wager: u64,
+        min_vault_age_days: u16,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.rewards_config;
+        config.min_lifetime_wager = min_lifetime_wager;
+        config.min_vault_age_days = min_vault_age_days;
+
+        msg!("Reward eligibility updated: minLifetimeWager={}, minVaultAgeDays={}", min_lifetime_wager, min_vault_age_days);
+        Ok(())
+    }
+
+    /// Credit a referral commission, airdrop or daily bonus from the promo pool (admin only)
+    ///
+    /// The vault must meet the on-chain activity thresholds in `RewardsConfig`.
+    pub fn credit_reward(ctx: Context<CreditReward>, kind: RewardKind, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let config = &ctx.accounts.rewards_config;
+        let vault = ctx.accounts.vault.load()?;
+        require!(vault.lifetime_wager >= config.min_lifetime_wager, VaultError::RewardIneligible);
+
+        // Legacy vaults (created_at == 0) predate age tracking and are grandfathered in
+        if vault.created_at != 0 {
+            let age_seconds = Clock::get()?.unix_timestamp.saturating_sub(vault.created_at);
+            require!(
+                age_seconds >= config.min_vault_age_days as i64 * 86_400,
+                VaultError::RewardIneligible
+            );
+        }
+
+        let pool_info = ctx.accounts.promo_pool.to_account_info();
+        let vault_info = ctx.accounts.vault.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(pool_info.data_len());
+        require!(
+            **pool_info.lamports.borrow() >= amount.checked_add(rent_floor).ok_or(VaultError::Overflow)?,
+            VaultError::PromoPoolInsufficient
+        );
+
+        transfer_lamports(&pool_info, &vault_info, amount)?;
+
+        let promo_pool = &mut ctx.accounts.promo_pool;
+        promo_pool.total_granted = promo_pool.total_granted.checked_add(amount).ok_or(VaultError::Overflow)?;
+
+        msg!("Reward credited: user={}, kind={:?}, amount={}", vault.owner, kind, amount);
+        Ok(())
+    }
+
+    /// Credit many vaults from the promo pool in one transaction (admin only)
+    ///
+    /// * `users`   – vault owners, one per remaining account
+    /// * `amounts` – lamports credited to each vault
+    ///
+    /// Vaults are passed as remaining accounts in the same order as `users`.
+    pub fn batch_credit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchCredit<'info>>,
+        users: Vec<Pubkey>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!(!users.is_empty(), VaultError::EmptyBatch);
+        require!(users.len() <= MAX_BATCH_CREDIT, VaultError::BatchTooLarge);
+        require!(users.len() == amounts.len(), VaultError::LengthMismatch);
+        require!(ctx.remaining_accounts.len() == users.len(), VaultError::AccountCountMismatch);
+
+        let mut total: u64 = 0;
+        for amount in &amounts {
+            require!(*amount > 0, VaultError::InvalidAmount);
+            total = total.checked_add(*amount).ok_or(VaultError::Overflow)?;
+        }
+
+        // Keep the pool itself rent exempt
+        let pool_info = ctx.accounts.promo_pool.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(pool_info.data_len());
+        require!(
+            **pool_info.lamports.borrow() >= total.checked_add(rent_floor).ok_or(VaultError::Overflow)?,
+            VaultError::PromoPoolInsufficient
+        );
+
+        for (i, ((user, amount), vault_info)) in users.iter().zip(amounts.iter()).zip(ctx.remaining_accounts.iter()).enumerate() {
+            require!(
+                ctx.remaining_accounts[..i].iter().all(|earlier| earlier.key != vault_info.key),
+                VaultError::DuplicateVault
+            );
+            require!(vault_info.is_writable, VaultError::Unauthorized);
+            require!(vault_version(vault_info) == VAULT_VERSION, VaultError::VersionMismatch);
+
+            // Vault PDA must be [b"vault", user], checked against its stored bump
+            let bump = AccountLoader::<UserVault>::try_from(vault_info)?.load()?.bump;
+            let expected_vault = Pubkey::create_program_address(&[VAULT_SEED, user.as_ref(), &[bump]], ctx.program_id)
+                .map_err(|_| VaultError::Unauthorized)?;
+            require!(vault_info.key() == expected_vault, VaultError::Unauthorized);
+
+            transfer_lamports(&pool_info, vault_info, *amount)?;
+        }
+
+        let promo_pool = &mut ctx.accounts.promo_pool;
+        promo_pool.total_granted = promo_pool.total_granted.checked_add(total).ok_or(VaultError::Overflow)?;
+
+        msg!("Batch credit: {} vaults, total={}", users.len(), total);
+        Ok(())
+    }
+
+    /// Initialize the empty vault registry (admin only)
+    pub fn initialize_vault_registry(ctx: Context<InitializeVaultRegistry>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let registry = &mut ctx.accounts.vault_registry;
+        registry.count = 0;
+        registry.pages = 0;
+        registry.bump = ctx.bumps.vault_registry;
+        Ok(())
+    }
+
+    /// Open the next registry page once every existing page is full (permissionless, payer funds rent)
+    pub fn open_registry_page(ctx: Context<OpenRegistryPage>) -> Result<()> {
+        let registry = &mut ctx.accounts.vault_registry;
+        require!(
+            registry.count == registry.pages as u64 * VAULT_REGISTRY_PAGE_LEN as u64,
+            VaultError::RegistryPageNotFull
+        );
+
+        let page = &mut ctx.accounts.registry_page;
+        page.page = registry.pages;
+        page.owners = Vec::new();
+        page.bump = ctx.bumps.registry_page;
+        registry.pages = registry.pages.checked_add(1).ok_or(VaultError::Overflow)?;
+
+        msg!("Registry page opened: page={}", page.page);
+        Ok(())
+    }
+
+    /// Append a vault to the registry (permissionless, payer funds the entry's rent)
+    ///
+    /// The entry PDA can only be created once, so each vault is listed exactly once.
+    pub fn register_vault(ctx: Context<RegisterVault>) -> Result<()> {
+        let owner = ctx.accounts.vault.load()?.owner;
+        let registry = &mut ctx.accounts.vault_registry;
+
+        let entry = &mut ctx.accounts.registry_entry;
+        entry.vault = ctx.accounts.vault.key();
+        entry.index = registry.count;
+        entry.bump = ctx.bumps.registry_entry;
+
+        ctx.accounts.registry_page.owners.push(owner);
+        registry.count = registry.count.checked_add(1).ok_or(VaultError::Overflow)?;
+
+        msg!("Vault registered: user={}, index={}", owner, entry.index);
+        Ok(())
+    }
+
+    /// Create the trophy config for cNFTs minted on big wins (admin only)
+    ///
+    /// * `merkle_tree` – Bubblegum tree whose delegate has been set to the trophy config PDA
+    /// * `min_multiplier` – smallest payout/stake that earns a trophy (100 = 1x, 0 = off)
+    /// * `base_uri` – metadata URI the bet details are appended to as a query string
+    pub fn initialize_trophy_config(
+        ctx: Context<InitializeTrophyConfig>,
+        merkle_tree: Pubkey,
+        min_multiplier: u32,
+        base_uri: String,
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.trophy_config;
+        config.set(merkle_tree, min_multiplier, base_uri)?;
+        config.bump = ctx.bumps.trophy_config;
+
+        msg!("Trophy config initialized: tree={}, minMultiplier={}", merkle_tree, min_multiplier);
+        Ok(())
+    }
+
+    /// Change the trophy tree, threshold or metadata URI (multisig only)
+    pub fn set_trophy_config(
+        ctx: Context<SetTrophyConfig>,
+        merkle_tree: Pubkey,
+        min_multiplier: u32,
+        base_uri: String,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        ctx.accounts.trophy_config.set(merkle_tree, min_multiplier, base_uri)?;
+
+        msg!("Trophy config set: tree={}, minMultiplier={}", merkle_tree, min_multiplier);
+        Ok(())
+    }
+
+    /// Create the responsible-gambling config with the given platform floors (admin only)
+    pub fn initialize_rg_config(
+        ctx: Context<InitializeRgConfig>,
+        min_cool_off_secs: i64,
+        min_limit_raise_interval_secs: i64,
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.rg_config;
+        config.set(min_cool_off_secs, min_limit_raise_interval_secs)?;
+        config.bump = ctx.bumps.rg_config;
+
+        msg!(
+            "RG config initialized: minCoolOff={}s, minLimitRaiseInterval={}s",
+            min_cool_off_secs, min_limit_raise_interval_secs
+        );
+        Ok(())
+    }
+
+    /// Change the responsible-gambling floors (multisig only)
+    pub fn set_rg_config(
+        ctx: Context<SetRgConfig>,
+        min_cool_off_secs: i64,
+        min_limit_raise_interval_secs: i64,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        ctx.accounts.rg_config.set(min_cool_off_secs, min_limit_raise_interval_secs)?;
+
+        msg!(
+            "RG config set: minCoolOff={}s, minLimitRaiseInterval={}s",
+            min_cool_off_secs, min_limit_raise_interval_secs
+        );
+        Ok(())
+    }
+
+    /// Create the bridge config, trusting VAAs posted by `core_bridge` (admin only)
+    ///
+    /// The config also holds the SOL that bridged deposits are paid from.
+    pub fn initialize_bridge_config(ctx: Context<InitializeBridgeConfig>, core_bridge: Pubkey) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.bridge_config;
+        config.core_bridge = core_bridge;
+        config.emitters = Vec::new();
+        config.total_credited = 0;
+        config.bump = ctx.bumps.bridge_config;
+
+        msg!("Bridge config initialized: coreBridge={}", core_bridge);
+        Ok(())
+    }
+
+    /// Accept deposit messages from the deposit contract at `address` on Wormhole chain `chain` (multisig only)
+    pub fn add_bridge_emitter(ctx: Context<UpdateBridgeConfig>, chain: u16, address: [u8; 32]) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.bridge_config;
+        let emitter = BridgeEmitter { chain, address };
+        if !config.emitters.contains(&emitter) {
+            require!(config.emitters.len() < MAX_BRIDGE_EMITTERS, VaultError::BridgeEmittersFull);
+            config.emitters.push(emitter);
+        }
+
+        msg!("Bridge emitter added: chain={}", chain);
+        Ok(())
+    }
+
+    /// Stop accepting deposit messages from an emitter (multisig only)
+    pub fn remove_bridge_emitter(ctx: Context<UpdateBridgeConfig>, chain: u16, address: [u8; 32]) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        ctx.accounts.bridge_config.emitters.retain(|emitter| *emitter != BridgeEmitter { chain, address });
+
+        msg!("Bridge emitter removed: chain={}", chain);
+        Ok(())
+    }
+
+    /// Top up the SOL bridged deposits are credited from
+    pub fn fund_bridge_pool(ctx: Context<FundBridgePool>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        let funder = &ctx.accounts.funder;
+        let pool_info = ctx.accounts.bridge_config.to_account_info();
+
+        invoke(
+            &system_instruction::transfer(
+                &funder.key(),
+                &pool_info.key(),
+                amount,
+            ),
+            &[
+                funder.to_account_info().clone(),
+                pool_info.clone(),
+                ctx.accounts.system_program.to_account_info().clone(),
+            ],
+        )?;
+
+        msg!("Bridge pool funded: {} lamports", amount);
+        Ok(())
+    }
+
+    /// Credit a vault for a deposit attested by a guardian-verified Wormhole VAA (permissionless)
+    ///
+    /// The VAA must already be posted through the core bridge, which checks the
+    /// guardian signatures. The claim PDA is keyed by the posted VAA, so each
+    /// message credits once.
+    pub fn credit_bridged_deposit(ctx: Context<CreditBridgedDeposit>) -> Result<()> {
+        require_not_paused(&mut ctx.accounts.pause_config)?;
+
+        let vaa = PostedVaa::parse(&ctx.accounts.posted_vaa.try_borrow_data()?)?;
+        let emitter = BridgeEmitter { chain: vaa.emitter_chain, address: vaa.emitter_address };
+        require!(ctx.accounts.bridge_config.emitters.contains(&emitter), VaultError::UnknownBridgeEmitter);
+        let (recipient, amount) = parse_bridge_deposit(&vaa.payload)?;
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        let owner = ctx.accounts.vault.load()?.owner;
+        require!(owner == recipient, VaultError::Unauthorized);
+        require!(!ctx.accounts.blacklist.contains(&owner), VaultError::Blacklisted);
+
+        // Keep the pool itself rent exempt
+        let pool_info = ctx.accounts.bridge_config.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(pool_info.data_len());
+        require!(
+            pool_info.lamports() >= amount.checked_add(rent_floor).ok_or(VaultError::Overflow)?,
+            VaultError::BridgePoolInsufficient
+        );
+        let vault_info = ctx.accounts.vault.to_account_info();
+        transfer_lamports(&pool_info, &vault_info, amount)?;
+
+        let config = &mut ctx.accounts.bridge_config;
+        config.total_credited = config.total_credited.checked_add(amount).ok_or(VaultError::Overflow)?;
+
+        let claim = &mut ctx.accounts.bridge_claim;
+        claim.vault = ctx.accounts.vault.key();
+        claim.amount = amount;
+        claim.bump = ctx.bumps.bridge_claim;
+
+        msg!(
+            "Bridged deposit: chain={}, sequence={}, user={}, amount={}",
+            vaa.emitter_chain, vaa.sequence, owner, amount
+        );
+        emit_cpi!(Deposited {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            user: owner,
+            amount,
+            new_balance: vault_info.lamports(),
+            reference: ctx.accounts.posted_vaa.key().to_bytes(),
+        });
+        let threshold = large_transfer_threshold(&ctx.accounts.compliance_config)?;
+        let vault_key = ctx.accounts.vault.key();
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let config = &mut ctx.accounts.global_config;
+        if let Some(event) = flag_large_transfer(&mut vault, vault_key, TRANSFER_DEPOSIT, amount, threshold, config)? {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
+    /// Create the compliance config with KYC gating off (admin only)
+    ///
+    /// Until this account exists, withdrawals are never gated.
+    pub fn initialize_compliance_config(ctx: Context<InitializeComplianceConfig>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.compliance_config;
+        config.kyc_withdraw_threshold = 0;
+        config.large_transfer_threshold = 0;
+        config.region_rules = Vec::new();
+        config.terms_hash = [0; 32];
+        config.terms_required = false;
+        config.screening_program = Pubkey::default();
+        config.screening_threshold = 0;
+        config.attestors = Vec::new();
+        config.bump = ctx.bumps.compliance_config;
+        Ok(())
+    }
+
+    /// Require a valid KYC attestation for withdrawals above `threshold` lamports (multisig only, 0 = off)
+    pub fn set_kyc_threshold(ctx: Context<UpdateComplianceConfig>, threshold: u64) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        ctx.accounts.compliance_config.kyc_withdraw_threshold = threshold;
+
+        msg!("KYC withdraw threshold set: {}", threshold);
+        Ok(())
+    }
+
+    /// Emit `LargeTransfer` for deposits, withdrawals and payouts above `threshold` lamports (multisig only, 0 = off)
+    pub fn set_large_transfer_threshold(ctx: Context<UpdateComplianceConfig>, threshold: u64) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        ctx.accounts.compliance_config.large_transfer_threshold = threshold;
+
+        msg!("Large transfer threshold set: {}", threshold);
+        Ok(())
+    }
+
+    /// Cap stakes from vaults in `region`, for one game or all of them, barring it with 0 (multisig only)
+    pub fn set_region_rule(
+        ctx: Context<UpdateComplianceConfig>,
+        region: u8,
+        game_id: Option<u64>,
+        max_stake: u64,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.compliance_config;
+        match config.region_rules.iter().position(|rule| rule.region == region && rule.game_id == game_id) {
+            Some(i) => config.region_rules[i].max_stake = max_stake,
+            None => {
+                require!(config.region_rules.len() < MAX_REGION_RULES, VaultError::RegionRulesFull);
+                config.region_rules.push(RegionRule { region, game_id, max_stake });
+            }
+        }
+
+        msg!("Region rule set: region={}, gameId={:?}, maxStake={}", region, game_id, max_stake);
+        Ok(())
+    }
+
+    /// Drop the rule for `region` and `game_id` (multisig only)
+    pub fn remove_region_rule(ctx: Context<UpdateComplianceConfig>, region: u8, game_id: Option<u64>) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        ctx.accounts.compliance_config.region_rules.retain(|rule| rule.region != region || rule.game_id != game_id);
+
+        msg!("Region rule removed: region={}, gameId={:?}", region, game_id);
+        Ok(())
+    }
+
+    /// Assign a vault to a jurisdiction whose region rules apply at settlement (admin only)
+    pub fn set_vault_region(ctx: Context<SetVaultRegion>, region: u8) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        let previous_region = vault.region;
+        vault.region = region;
+
+        msg!("Vault region set: user={}, region {} -> {}", vault.owner, previous_region, region);
+        emit_cpi!(VaultRegionSet {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            vault: ctx.accounts.vault.key(),
+            previous_region,
+            region,
+        });
+        Ok(())
+    }
+
+    /// Publish a new terms-of-service version, optionally requiring acceptance before the next staked bet (admin only)
+    pub fn set_terms(ctx: Context<UpdateComplianceConfig>, version_hash: [u8; 32], required: bool) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.compliance_config;
+        config.terms_hash = version_hash;
+        config.terms_required = required;
+
+        msg!("Terms set: hash={:?}, required={}", version_hash, required);
+        Ok(())
+    }
+
+    /// Record that the vault owner accepted the terms of service hashed to `version_hash`
+    ///
+    /// Once terms are published on the compliance config, only the current version can be accepted.
+    pub fn accept_terms(ctx: Context<AcceptTerms>, version_hash: [u8; 32]) -> Result<()> {
+        require!(version_hash != [0; 32], VaultError::InvalidTerms);
+        if let Some(config) = load_compliance_config(&ctx.accounts.compliance_config)? {
+            require!(
+                config.terms_hash == [0; 32] || config.terms_hash == version_hash,
+                VaultError::InvalidTerms
+            );
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let mut vault = ctx.accounts.vault.load_mut()?;
+        vault.terms_hash = version_hash;
+        vault.terms_accepted_at = now;
+
+        msg!("Terms accepted: user={}, hash={:?}, at={}", vault.owner, version_hash, now);
+        Ok(())
+    }
+
+    /// Route deposits above `threshold` through `program` for screening, or stop with `None` (multisig only)
+    pub fn set_deposit_screening(
+        ctx: Context<UpdateComplianceConfig>,
+        program: Option<Pubkey>,
+        threshold: u64,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.compliance_config;
+        config.screening_program = program.unwrap_or_default();
+        config.screening_threshold = threshold;
+
+        msg!("Deposit screening set: program={:?}, threshold={}", program, threshold);
+        Ok(())
+    }
+
+    /// Approve `attestor` to issue KYC attestations (admin only)
+    pub fn add_attestor(ctx: Context<UpdateComplianceConfig>, attestor: Pubkey) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let config = &mut ctx.accounts.compliance_config;
+        if !config.attestors.contains(&attestor) {
+            require!(config.attestors.len() < MAX_ATTESTORS, VaultError::AttestorsFull);
+            config.attestors.push(attestor);
+        }
+
+        msg!("Attestor approved: {}", attestor);
+        Ok(())
+    }
+
+    /// Withdraw an attestor's approval; attestations it issued stop counting (admin only)
+    pub fn remove_attestor(ctx: Context<UpdateComplianceConfig>, attestor: Pubkey) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        ctx.accounts.compliance_config.attestors.retain(|approved| *approved != attestor);
+
+        msg!("Attestor removed: {}", attestor);
+        Ok(())
+    }
+
+    /// Attest that `vault`'s owner passed KYC, valid until `expires_at` (approved attestors only)
+    pub fn issue_kyc_attestation(ctx: Context<IssueKycAttestation>, expires_at: i64) -> Result<()> {
+        let attestor = ctx.accounts.attestor.key();
+        require!(ctx.accounts.compliance_config.attestors.contains(&attestor), VaultError::Unauthorized);
+        require!(expires_at > Clock::get()?.unix_timestamp, VaultError::InvalidExpiry);
+
+        let attestation = &mut ctx.accounts.kyc_attestation;
+        attestation.vault = ctx.accounts.vault.key();
+        attestation.attestor = attestor;
+        attestation.expires_at = expires_at;
+        attestation.bump = ctx.bumps.kyc_attestation;
+
+        msg!("KYC attestation issued: vault={}, attestor={}, expiresAt={}", attestation.vault, attestor, expires_at);
+        Ok(())
+    }
+
+    /// Revoke an attestation, refunding its rent to the issuing attestor (issuer or admin)
+    pub fn revoke_kyc_attestation(ctx: Context<RevokeKycAttestation>) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == admin || authority == ctx.accounts.kyc_attestation.attestor,
+            VaultError::Unauthorized
+        );
+
+        msg!("KYC attestation revoked: vault={}", ctx.accounts.kyc_attestation.vault);
+        Ok(())
+    }
+
+    /// Create the upgrade governance account with the notice announced upgrades must give (admin only)
+    pub fn initialize_upgrade_governance(
+        ctx: Context<InitializeUpgradeGovernance>,
+        min_delay_slots: u64,
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+
+        let governance = &mut ctx.accounts.upgrade_governance;
+        governance.min_delay_slots = min_delay_slots;
+        governance.buffer = Pubkey::default();
+        governance.buffer_hash = [0u8; 32];
+        governance.earliest_upgrade_slot = 0;
+        governance.announced_slot = 0;
+        governance.last_upgrade_slot = load_program_data(&ctx.accounts.program_data)?.slot;
+        governance.bump = ctx.bumps.upgrade_governance;
+
+        msg!("Upgrade governance initialized: minDelaySlots={}", min_delay_slots);
+        Ok(())
+    }
+
+    /// Change the notice later announcements must give; a pending announcement keeps its slot (multisig only)
+    pub fn set_upgrade_delay(ctx: Context<SetUpgradeGovernance>, min_delay_slots: u64) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        ctx.accounts.upgrade_governance.min_delay_slots = min_delay_slots;
+
+        msg!("Upgrade delay set: {} slots", min_delay_slots);
+        Ok(())
+    }
+
+    /// Hand the program's BPF upgrade authority to the multisig (current upgrade authority only)
+    ///
+    /// One-way: from then on only the multisig can deploy, so every upgrade
+    /// goes through the same signers that announce it.
+    pub fn transfer_upgrade_authority(ctx: Context<TransferUpgradeAuthority>) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.new_authority.key() == multisig, VaultError::Unauthorized);
+
+        invoke(
+            &bpf_loader_upgradeable::set_upgrade_authority(&crate::ID, &ctx.accounts.authority.key(), Some(&multisig)),
+            &[
+                ctx.accounts.program_data.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.new_authority.to_account_info(),
+            ],
+        )?;
+
+        msg!("Upgrade authority transferred: from={}, to={}", ctx.accounts.authority.key(), multisig);
+        Ok(())
+    }
+
+    /// Publish the next upgrade: its buffer, the buffer's hash and the first slot it may be deployed in (multisig only)
+    ///
+    /// `buffer_hash` is the sha256 of the buffer's program bytes; the program
+    /// cannot hash a whole buffer within its compute budget, so anyone can
+    /// recompute it from the buffer account before the upgrade lands.
+    pub fn announce_upgrade(
+        ctx: Context<AnnounceUpgrade>,
+        buffer_hash: [u8; 32],
+        earliest_upgrade_slot: u64,
+    ) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let slot = Clock::get()?.slot;
+        let buffer = ctx.accounts.buffer.key();
+        let governance = &mut ctx.accounts.upgrade_governance;
+        require!(governance.buffer == Pubkey::default(), VaultError::UpgradeAlreadyAnnounced);
+        require!(
+            earliest_upgrade_slot >= slot.saturating_add(governance.min_delay_slots),
+            VaultError::UpgradeNoticeTooShort
+        );
+
+        governance.buffer = buffer;
+        governance.buffer_hash = buffer_hash;
+        governance.earliest_upgrade_slot = earliest_upgrade_slot;
+        governance.announced_slot = slot;
+
+        msg!("Upgrade announced: buffer={}, earliestSlot={}", buffer, earliest_upgrade_slot);
+        emit_cpi!(UpgradeAnnounced {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            buffer,
+            buffer_hash,
+            earliest_upgrade_slot,
+        });
+        Ok(())
+    }
+
+    /// Withdraw the pending upgrade announcement (multisig only)
+    pub fn cancel_upgrade(ctx: Context<SetUpgradeGovernance>) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        let governance = &mut ctx.accounts.upgrade_governance;
+        require!(governance.buffer != Pubkey::default(), VaultError::NoUpgradeAnnounced);
+        msg!("Upgrade cancelled: buffer={}", governance.buffer);
+        governance.buffer = Pubkey::default();
+        governance.buffer_hash = [0u8; 32];
+        governance.earliest_upgrade_slot = 0;
+        governance.announced_slot = 0;
+        Ok(())
+    }
+
+    /// Record a deployment the program data shows, matched against the pending announcement (anyone)
+    ///
+    /// `on_schedule` in the event is false when nothing was announced or the
+    /// deployment landed before the announced slot.
+    pub fn record_upgrade(ctx: Context<RecordUpgrade>) -> Result<()> {
+        let deployed_slot = load_program_data(&ctx.accounts.program_data)?.slot;
+        let governance = &mut ctx.accounts.upgrade_governance;
+        require!(deployed_slot > governance.last_upgrade_slot, VaultError::UpgradeAlreadyRecorded);
+
+        let announced = governance.buffer != Pubkey::default() && deployed_slot > governance.announced_slot;
+        let on_schedule = announced && deployed_slot >= governance.earliest_upgrade_slot;
+        let (buffer_hash, earliest_upgrade_slot) = if announced {
+            (governance.buffer_hash, governance.earliest_upgrade_slot)
+        } else {
+            ([0u8; 32], 0)
+        };
+        governance.last_upgrade_slot = deployed_slot;
+        if announced {
+            governance.buffer = Pubkey::default();
+            governance.buffer_hash = [0u8; 32];
+            governance.earliest_upgrade_slot = 0;
+            governance.announced_slot = 0;
+        }
+
+        msg!("Upgrade recorded: deployedSlot={}, onSchedule={}", deployed_slot, on_schedule);
+        emit_cpi!(UpgradeRecorded {
+            version: EVENT_VERSION,
+            seq: ctx.accounts.global_config.next_seq()?,
+            buffer_hash,
+            earliest_upgrade_slot,
+            deployed_slot,
+            on_schedule,
+        });
+        Ok(())
+    }
+
+    /// Create the health monitor expecting a heartbeat from `operator` every `interval_secs` (admin only)
+    pub fn initialize_health_monitor(
+        ctx: Context<InitializeHealthMonitor>,
+        operator: Pubkey,
+        interval_secs: i64,
+    ) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        require!(interval_secs > 0, VaultError::InvalidAmount);
+
+        let monitor = &mut ctx.accounts.health_monitor;
+        monitor.operator = operator;
+        monitor.interval_secs = interval_secs;
+        monitor.last_heartbeat = Clock::get()?.unix_timestamp;
+        monitor.house_balance = 0;
+        monitor.open_exposure = 0;
+        monitor.degraded = false;
+        monitor.bump = ctx.bumps.health_monitor;
+        Ok(())
+    }
+
+    /// Change the heartbeat operator or interval (multisig only)
+    pub fn set_heartbeat_config(ctx: Context<SetHealthMonitor>, operator: Pubkey, interval_secs: i64) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+        require!(interval_secs > 0, VaultError::InvalidAmount);
+
+        let monitor = &mut ctx.accounts.health_monitor;
+        monitor.operator = operator;
+        monitor.interval_secs = interval_secs;
+
+        msg!("Heartbeat config set: operator={}, interval={}s", operator, interval_secs);
+        Ok(())
+    }
+
+    /// Record the operator's heartbeat and check the house can cover its open exposure
+    ///
+    /// A passing check clears `degraded`; a failing one sets it. Either way the
+    /// instruction succeeds so the result is written.
+    pub fn operator_heartbeat(ctx: Context<OperatorHeartbeat>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let (house_balance, open_exposure) = house_solvency(&ctx.accounts.house_vault)?;
+        let monitor = &mut ctx.accounts.health_monitor;
+        monitor.last_heartbeat = now;
+        monitor.record_check(house_balance, open_exposure);
+        monitor.degraded = open_exposure > house_balance;
+
+        msg!("Heartbeat: house={}, exposure={}, degraded={}", house_balance, open_exposure, monitor.degraded);
+        Ok(())
+    }
+
+    /// Set `degraded` when the heartbeat is overdue or the house no longer covers its exposure (anyone)
+    ///
+    /// Only an operator heartbeat clears the flag again.
+    pub fn check_health(ctx: Context<CheckHealth>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let (house_balance, open_exposure) = house_solvency(&ctx.accounts.house_vault)?;
+        let monitor = &mut ctx.accounts.health_monitor;
+        let overdue = monitor.heartbeat_overdue(now);
+        let insolvent = open_exposure > house_balance;
+        require!(overdue || insolvent, VaultError::HealthCheckPassed);
+        monitor.record_check(house_balance, open_exposure);
+        monitor.degraded = true;
+
+        msg!("Health degraded: heartbeatOverdue={}, house={}, exposure={}", overdue, house_balance, open_exposure);
+        Ok(())
+    }
+
+    /// Create the platform-token staking config (admin only)
+    ///
+    /// `escrow` must be a token account of `mint` owned by the config PDA; it
+    /// holds every stake. Starts without rebate tiers.
+    pub fn initialize_staking_config(ctx: Context<InitializeStakingConfig>, cooldown_secs: i64) -> Result<()> {
+        let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+        require!(ctx.accounts.authority.key() == admin, VaultError::Unauthorized);
+        let (escrow_mint, escrow_owner) = token_account_parts(&ctx.accounts.escrow)?;
+        require!(escrow_mint == ctx.accounts.mint.key(), VaultError::WrongStakingToken);
+        require!(escrow_owner == ctx.accounts.staking_config.key(), VaultError::WrongStakingToken);
+
+        let config = &mut ctx.accounts.staking_config;
+        config.mint = ctx.accounts.mint.key();
+        config.escrow = ctx.accounts.escrow.key();
+        config.set(cooldown_secs, Vec::new())?;
+        config.bump = ctx.bumps.staking_config;
+
+        msg!("Staking config initialized: mint={}, escrow={}, cooldown={}s", config.mint, config.escrow, cooldown_secs);
+        Ok(())
+    }
+
+    /// Replace the rebate tiers and unstaking cooldown (multisig only)
+    ///
+    /// Tiers rise strictly in both `min_staked` and `rebate_bps`, up to
+    /// `MAX_REBATE_BPS`. A longer cooldown only applies to unstakes requested after it.
+    pub fn set_staking_tiers(ctx: Context<SetStakingTiers>, cooldown_secs: i64, tiers: Vec<RebateTier>) -> Result<()> {
+        let multisig: Pubkey = "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap();
+        require!(ctx.accounts.authority.key() == multisig, VaultError::Unauthorized);
+
+        ctx.accounts.staking_config.set(cooldown_secs, tiers)?;
+
+        msg!("Staking tiers set: tiers={:?}, cooldown={}s", ctx.accounts.staking_config.tiers, cooldown_secs);
+        Ok(())
+    }
+
+    /// Create the caller's platform-token stake record
+    pub fn initialize_token_stake(ctx: Context<InitializeTokenStake>) -> Result<()> {
+        let stake = &mut ctx.accounts.token_stake;
+        stake.vault = ctx.accounts.vault.key();
+        stake.staked = 0;
+        stake.unstaking = 0;
+        stake.unlock_at = 0;
+        stake.bump = ctx.bumps.token_stake;
+        Ok(())
+    }
+
+    /// Lock `amount` platform tokens from the owner's token account; they count toward the rebate tier at once
+    pub fn stake_tokens(ctx: Context<StakeTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        token_transfer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.source,
+            &ctx.accounts.escrow,
+            &ctx.accounts.owner.to_account_info(),
+            amount,
+            &[],
+        )?;
+        let stake = &mut ctx.accounts.token_stake;
+        stake.staked = stake.staked.checked_add(amount).ok_or(VaultError::Overflow)?;
+
+        let tier = ctx.accounts.staking_config.rebate_bps(stake.staked);
+        msg!("Tokens staked: vault={}, amount={}, staked={}, rebateBps={}", stake.vault, amount, stake.staked, tier);
+        Ok(())
+    }
+
+    /// Start unstaking `amount` tokens: they stop counting toward the tier now and unlock after the cooldown
+    ///
+    /// Every request restarts the cooldown for everything still unstaking, so
+    /// staking just for one bet ties the tokens up for the full cooldown.
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        let now = Clock::get()?.unix_timestamp;
+        let cooldown_secs = ctx.accounts.staking_config.cooldown_secs;
+        let stake = &mut ctx.accounts.token_stake;
+        stake.staked = stake.staked.checked_sub(amount).ok_or(VaultError::InsufficientFunds)?;
+        stake.unstaking = stake.unstaking.checked_add(amount).ok_or(VaultError::Overflow)?;
+        stake.unlock_at = now.checked_add(cooldown_secs).ok_or(VaultError::Overflow)?;
+
+        msg!("Unstake requested: vault={}, amount={}, unstaking={}, unlockAt={}",
+             stake.vault, amount, stake.unstaking, stake.unlock_at);
+        Ok(())
+    }
+
+    /// Return every unstaked token to the owner's token account once the cooldown is over
+    pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let stake = &mut ctx.accounts.token_stake;
+        require!(stake.unstaking > 0, VaultError::InvalidAmount);
+        require!(now >= stake.unlock_at, VaultError::StakeCoolingDown);
+        let amount = stake.unstaking;
+        stake.unstaking = 0;
+
+        let config = &ctx.accounts.staking_config;
+        token_transfer(
+            &ctx.accounts.token_program,
+            &ctx.accounts.escrow,
+            &ctx.accounts.destination,
+            &config.to_account_info(),
+            amount,
+            &[&[STAKING_CONFIG_SEED, &[config.bump]]],
+        )?;
+
+        msg!("Unstaked tokens withdrawn: vault={}, amount={}", stake.vault, amount);
+        Ok(())
+    }
+
+    /// Unknown discriminators land here; devnet builds route their QA instructions through it
+    pub fn fallback<'info>(program_id: &Pubkey, accounts: &'info [AccountInfo<'info>], data: &[u8]) -> Result<()> {
+        #[cfg(feature = "devnet")]
+        return crate::devnet::dispatch(program_id, accounts, data);
+        #[cfg(not(feature = "devnet"))]
+        {
+            let _ = (program_id, accounts, data);
+            err!(anchor_lang::error::ErrorCode::InstructionFallbackNotFound)
+        }
+    }
+}
+
+/// Balances of (`from`, `to`) after moving `amount` lamports
+///
+/// Fails with `InsufficientFunds` if `from` cannot cover the amount and with
+/// `Overflow` if `to` would exceed `u64::MAX`.
+pub fn checked_transfer(from_balance: u64, to_balance: u64, amount: u64) -> Result<(u64, u64)> {
+    let from_balance = from_balance.checked_sub(amount).ok_or(VaultError::InsufficientFunds)?;
+    let to_balance = to_balance.checked_add(amount).ok_or(VaultError::Overflow)?;
+    Ok((from_balance, to_balance))
+}
+
+/// Accept the admin, or a registered game program invoking through CPI
+///
+/// A game program proves its identity by signing with its `GAME_AUTHORITY_SEED`
+/// PDA, which only that program can do; the PDA is resolved when it is registered.
+fn require_settlement_authority(authority: &Pubkey, game_programs: Option<&GamePrograms>) -> Result<()> {
+    let admin: Pubkey = "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap();
+    if *authority == admin {
+        return Ok(());
+    }
+    let registry = game_programs.ok_or(VaultError::Unauthorized)?;
+    require!(get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT, VaultError::Unauthorized);
+    require!(registry.programs.iter().any(|entry| entry.authority == *authority), VaultError::Unauthorized);
+    Ok(())
+}
+
+/// Fail while operations are paused, lifting a maintenance pause whose window has run out
+///
+/// The lift is written back only if the caller passed the config writable, so
+/// routine bets never write-lock it; otherwise `resume_after_maintenance` persists it.
+fn require_not_paused(config: &mut Account<PauseConfig>) -> Result<()> {
+    if config.maintenance_pause() && config.maintenance_elapsed(Clock::get()?.unix_timestamp) {
+        config.set_flag(PAUSE_MAINTENANCE, false);
+        config.maintenance_start_time = 0;
+        if config.to_account_info().is_writable {
+            config.exit(&crate::ID)?;
+        }
+    }
+    require!(!config.emergency_pause(), VaultError::EmergencyPaused);
+    require!(!config.maintenance_pause(), VaultError::MaintenancePaused);
+    Ok(())
+}
+
+/// Fail a withdrawal of `amount` that needs a KYC attestation the vault lacks
+///
+/// Gating is off while the compliance config is uninitialized or its threshold
+/// is 0. An attestation counts while unexpired and its attestor is approved.
+fn require_kyc(compliance_config: &AccountInfo, attestation: Option<&KycAttestation>, amount: u64) -> Result<()> {
+    let Some(config) = load_compliance_config(compliance_config)? else {
+        return Ok(());
+    };
+    if config.kyc_withdraw_threshold == 0 || amount <= config.kyc_withdraw_threshold {
+        return Ok(());
+    }
+    let attestation = attestation.ok_or(VaultError::KycRequired)?;
+    require!(config.attestors.contains(&attestation.attestor), VaultError::KycRequired);
+    require!(attestation.expires_at > Clock::get()?.unix_timestamp, VaultError::KycRequired);
+    Ok(())
+}
+
+/// Move `stake` from a player's vault into a PvP bet escrow, keeping the vault's rent and locked funds
+fn escrow_pvp_stake(vault: &AccountLoader<UserVault>, escrow: &AccountInfo, stake: u64) -> Result<()> {
+    let vault_info = vault.to_account_info();
+    let floor = Rent::get()?.minimum_balance(vault_info.data_len())
+        .checked_add(vault.load()?.locked_amount).ok_or(VaultError::Overflow)?;
+    require!(vault_info.lamports().saturating_sub(stake) >= floor, VaultError::InsufficientFunds);
+    transfer_lamports(&vault_info, escrow, stake)
+}
+
+/// The user vault at a table seat's remaining account, cleared to stake `amount` on `game_id`
+fn table_seat_vault<'info>(
+    vault_info: &'info AccountInfo<'info>,
+    blacklist: &Blacklist,
+    compliance_config: Option<&ComplianceConfig>,
+    game_id: u64,
+    amount: u64,
+) -> Result<AccountLoader<'info, UserVault>> {
+    require!(vault_info.is_writable, VaultError::Unauthorized);
+    require!(vault_version(vault_info) == VAULT_VERSION, VaultError::VersionMismatch);
+    let vault = AccountLoader::<UserVault>::try_from(vault_info)?;
+    {
+        let vault = vault.load()?;
+        require!(!blacklist.contains(&vault.owner), VaultError::Blacklisted);
+        if let Some(config) = compliance_config {
+            config.check_region(vault.region, game_id, amount)?;
+            config.check_terms(&vault, amount)?;
+        }
+    }
+    Ok(vault)
+}
+
+/// Require `vaults` to be the round's seat vaults, in seat order
+fn require_table_seats(round: &TableRound, vaults: &[AccountInfo]) -> Result<()> {
+    require!(vaults.len() == round.seats.len(), VaultError::AccountCountMismatch);
+    for (seat, vault_info) in round.seats.iter().zip(vaults) {
+        require!(vault_info.key() == seat.vault && vault_info.is_writable, VaultError::NotSeated);
+    }
+    Ok(())
+}
+
+/// Return an unaccepted PvP bet's stake to the creator's vault, dropping an open challenge from the queue
+fn refund_pvp_bet(bet: &Account<PvpBet>, vault: &AccountInfo, queue: Option<&mut Account<PvpQueue>>) -> Result<()> {
+    if bet.is_open() {
+        queue.ok_or(VaultError::PvpQueueMissing)?.remove(&bet.key());
+    }
+    transfer_lamports(&bet.to_account_info(), vault, bet.stake)
+}
+
+/// Lock `stake` in the vault as an active game, keeping its rent and already locked funds
+fn lock_stake(vault: &AccountLoader<UserVault>, stake: u64) -> Result<()> {
+    let vault_info = vault.to_account_info();
+    let mut vault = vault.load_mut()?;
+    let locked = vault.locked_amount.checked_add(stake).ok_or(VaultError::Overflow)?;
+    let floor = Rent::get()?.minimum_balance(vault_info.data_len()).checked_add(locked).ok_or(VaultError::Overflow)?;
+    require!(vault_info.lamports() >= floor, VaultError::InsufficientFunds);
+    vault.locked_amount = locked;
+    vault.active_games = vault.active_games.checked_add(1).ok_or(VaultError::Overflow)?;
+    Ok(())
+}
+
+/// Release a stake locked by [`lock_stake`] without settling it
+fn unlock_stake(vault: &mut UserVault, stake: u64) -> Result<()> {
+    vault.locked_amount = vault.locked_amount.checked_sub(stake).ok_or(VaultError::Overflow)?;
+    vault.active_games = vault.active_games.checked_sub(1).ok_or(VaultError::Overflow)?;
+    Ok(())
+}
+
+/// Release a locked stake and settle it for `payout` against the house, counting it as a bet
+///
+/// The win cap was checked when the stake was locked; the result still counts towards it.
+fn settle_locked_stake(
+    vault: &AccountLoader<UserVault>,
+    bonus: &AccountInfo,
+    house_vault: &AccountLoader<HouseVault>,
+    global_stats: &AccountLoader<GlobalStats>,
+    stake: u64,
+    payout: u64,
+) -> Result<()> {
+    {
+        let mut vault = vault.load_mut()?;
+        unlock_stake(&mut vault, stake)?;
+        vault.lifetime_wager = vault.lifetime_wager.checked_add(stake).ok_or(VaultError::Overflow)?;
+        vault.record_net_win(Clock::get()?.unix_timestamp, stake, payout)?;
+        settle_bonus(bonus, &mut vault, stake, payout)?;
+    }
+    apply_settlement(&vault.to_account_info(), &house_vault.to_account_info(), stake, payout)?;
+    let mut house = house_vault.load_mut()?;
+    house.total_volume = house.total_volume.checked_add(stake).ok_or(VaultError::Overflow)?;
+    global_stats.load_mut()?.record_bet(vault.key(), stake, payout)
+}
+
+/// Spend a settlement's net loss from the vault's deposit bonus first and credit its playthrough, if it has one
+///
+/// `bonus` must be the vault's `[b"bonus", vault]` address whether or not it
+/// exists, so no settlement path can leave an active bonus out.
+fn settle_bonus(bonus: &AccountInfo, vault: &mut UserVault, stake: u64, payout: u64) -> Result<()> {
+    let Some(mut state) = load_optional::<DepositBonus>(bonus)? else {
+        return Ok(());
+    };
+    if state.cleared {
+        return Ok(());
+    }
+    let consumed = state.record_loss(stake, payout);
+    vault.locked_amount = vault.locked_amount.checked_sub(consumed).ok_or(VaultError::Overflow)?;
+    if stake > 0 {
+        state.wagered = state.wagered.checked_add(stake).ok_or(VaultError::Overflow)?;
+        if state.wagered >= state.wager_requirement {
+            state.cleared = true;
+            vault.locked_amount = vault.locked_amount.checked_sub(state.remaining).ok_or(VaultError::Overflow)?;
+            msg!("Deposit bonus cleared: {} lamports unlocked", state.remaining);
+        }
+    }
+    require!(bonus.is_writable, VaultError::WrongPromoAccount);
+    state.try_serialize(&mut &mut bonus.try_borrow_mut_data()?[..])
+}
+
+/// Accrue loss-back on a settlement's net loss while the promo window is open, if the vault has a balance record
+///
+/// Like the bonus in [`settle_bonus`], `loss_back` must be the vault's
+/// `[b"lossback", vault]` address whether or not it exists.
+fn settle_loss_back(promo: &AccountInfo, loss_back: &AccountInfo, stake: u64, payout: u64) -> Result<()> {
+    if payout >= stake {
+        return Ok(());
+    }
+    let (Some(promo), Some(mut state)) =
+        (load_optional::<LossBackPromo>(promo)?, load_optional::<LossBack>(loss_back)?)
+    else {
+        return Ok(());
+    };
+    let now = Clock::get()?.unix_timestamp;
+    if now < promo.start_time || now >= promo.end_time {
+        return Ok(());
+    }
+    let accrual = (stake - payout) as u128 * promo.rate_bps as u128 / 10_000;
+    let accrual = u64::try_from(accrual).map_err(|_| VaultError::Overflow)?;
+    state.accrue(&promo, accrual)?;
+    msg!("Loss-back accrued: promoId={}, amount={}, total={}", promo.promo_id, accrual, state.accrued);
+    require!(loss_back.is_writable, VaultError::WrongPromoAccount);
+    state.try_serialize(&mut &mut loss_back.try_borrow_mut_data()?[..])
+}
+
+/// Fail unless `info` is this program's `[seed, vault]` address, for per-vault accounts passed as remaining accounts
+fn require_vault_pda(info: &AccountInfo, seed: &[u8], vault: &Pubkey) -> Result<()> {
+    let (expected, _bump) = Pubkey::find_program_address(&[seed, vault.as_ref()], &crate::ID);
+    require!(info.key() == expected, VaultError::WrongPromoAccount);
+    Ok(())
+}
+
+/// Move an insurance premium from the vault to the fund, reserving the refund it buys
+///
+/// Returns the refund, which the fund must hold on top of its rent and the
+/// refunds already reserved for other bets.
+fn insure_bet(
+    vault: &AccountLoader<UserVault>,
+    fund: &mut Account<InsuranceFund>,
+    stake: u64,
+    premium: u64,
+) -> Result<u64> {
+    let refund = fund.refund_for(stake, premium)?;
+    let vault_info = vault.to_account_info();
+    let fund_info = fund.to_account_info();
+    let rent = Rent::get()?;
+
+    let vault_floor = rent
+        .minimum_balance(vault_info.data_len())
+        .checked_add(vault.load()?.locked_amount)
+        .and_then(|floor| floor.checked_add(premium))
+        .ok_or(VaultError::Overflow)?;
+    require!(vault_info.lamports() >= vault_floor, VaultError::InsufficientFunds);
+    transfer_lamports(&vault_info, &fund_info, premium)?;
+
+    let reserved = fund.reserved.checked_add(refund).ok_or(VaultError::Overflow)?;
+    let fund_floor = rent.minimum_balance(fund_info.data_len()).checked_add(reserved).ok_or(VaultError::Overflow)?;
+    require!(fund_info.lamports() >= fund_floor, VaultError::InsuranceFundInsufficient);
+    fund.reserved = reserved;
+    fund.total_premiums = fund.total_premiums.checked_add(premium).ok_or(VaultError::Overflow)?;
+    Ok(refund)
+}
+
+/// Release the refund reserved for an insured bet, paying it into the vault if the bet `lost`
+///
+/// Returns the lamports refunded; uninsured bets need no fund and refund nothing.
+fn release_insurance(
+    fund: Option<&mut Account<InsuranceFund>>,
+    vault: &AccountInfo,
+    bet: &PendingBet,
+    lost: bool,
+) -> Result<u64> {
+    if bet.insurance_refund == 0 {
+        return Ok(0);
+    }
+    let fund = fund.ok_or(VaultError::InsuranceFundMissing)?;
+    fund.reserved = fund.reserved.checked_sub(bet.insurance_refund).ok_or(VaultError::Overflow)?;
+    if !lost {
+        return Ok(0);
+    }
+    transfer_lamports(&fund.to_account_info(), vault, bet.insurance_refund)?;
+    fund.total_refunds = fund.total_refunds.checked_add(bet.insurance_refund).ok_or(VaultError::Overflow)?;
+    Ok(bet.insurance_refund)
+}
+
+/// Payout of a crash bet that cashed out at `cashout_at` (100 = 1x)
+fn crash_payout(stake: u64, cashout_at: u32) -> Result<u64> {
+    let payout = stake as u128 * cashout_at as u128 / 100;
+    u64::try_from(payout).map_err(|_| VaultError::Overflow.into())
+}
+
+/// Crash point of a round (100 = 1x), derived from its revealed seed
+///
+/// One round in `CRASH_INSTANT_ODDS` crashes at 1x; otherwise the point
+/// follows `(100 * 2^52 - h) / (2^52 - h)` for 52 bits `h` of `keccak(seed || round_id)`.
+pub fn crash_point(seed: &[u8; 32], round_id: u64) -> u32 {
+    let hash = keccak::hashv(&[seed, &round_id.to_le_bytes()]).to_bytes();
+    let h = u64::from_be_bytes(hash[..8].try_into().unwrap()) >> 12;
+    if h % CRASH_INSTANT_ODDS == 0 {
+        return 100;
+    }
+    let e = 1u64 << 52;
+    u32::try_from((100 * e - h) / (e - h)).unwrap_or(u32::MAX)
+}
+
+/// Let the configured screening program veto a deposit above the screening threshold
+///
+/// The program receives `SCREEN_DEPOSIT_DISCRIMINATOR`, the owner and the
+/// amount, with the owner, the vault and the optional screening state as
+/// read-only accounts. An error from it fails the deposit.
+fn screen_deposit(accounts: &Deposit, amount: u64) -> Result<()> {
+    let Some(config) = load_compliance_config(&accounts.compliance_config)? else {
+        return Ok(());
+    };
+    if config.screening_program == Pubkey::default() || amount <= config.screening_threshold {
+        return Ok(());
+    }
+    let program = accounts.screening_program.as_ref()
+        .filter(|program| program.key() == config.screening_program)
+        .ok_or(VaultError::ScreeningProgramMissing)?;
+
+    let mut metas = vec![
+        AccountMeta::new_readonly(accounts.owner.key(), false),
+        AccountMeta::new_readonly(accounts.vault.key(), false),
+    ];
+    let mut infos = vec![accounts.owner.to_account_info(), accounts.vault.to_account_info()];
+    if let Some(state) = accounts.screening_state.as_ref() {
+        metas.push(AccountMeta::new_readonly(state.key(), false));
+        infos.push(state.to_account_info());
+    }
+    infos.push(program.to_account_info());
+
+    let mut data = SCREEN_DEPOSIT_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(accounts.owner.key().as_ref());
+    data.extend_from_slice(&amount.to_le_bytes());
+    invoke(&Instruction { program_id: program.key(), accounts: metas, data }, &infos)?;
+    Ok(())
+}
+
+/// Fail a withdrawal from a vault whose withdraw guard is enabled unless its device key signed
+fn require_device_signature(withdraw_guard: &AccountInfo, device: Option<&Signer>) -> Result<()> {
+    if withdraw_guard.owner != &crate::ID || withdraw_guard.data_is_empty() {
+        return Ok(());
+    }
+    let guard = WithdrawGuard::try_deserialize(&mut &withdraw_guard.try_borrow_data()?[..])?;
+    require!(device.is_some_and(|device| device.key() == guard.device), VaultError::DeviceSignatureRequired);
+    Ok(())
+}
+
+/// The program account at `info`, or `None` while it is uninitialized
+fn load_optional<T: AccountDeserialize>(info: &AccountInfo) -> Result<Option<T>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(T::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+}
+
+/// This program's loader `ProgramData`; contexts take it unchecked because `Account<ProgramData>` breaks `idl-build`
+///
+/// The caller's `seeds::program` constraint pins the address, which only the loader can create.
+fn load_program_data(info: &AccountInfo) -> Result<ProgramData> {
+    ProgramData::try_deserialize(&mut &info.try_borrow_data()?[..])
+}
+
+/// The compliance config at `[b"compliance_config"]`, or `None` while it is uninitialized
+fn load_compliance_config(compliance_config: &AccountInfo) -> Result<Option<ComplianceConfig>> {
+    load_optional(compliance_config)
+}
+
+/// Enforce the rules of `game_id`, if it has a game config, on one settlement
+///
+/// `config` must be the game's `[b"game_config", game_id]` address whether or
+/// not it exists, so a configured game cannot be settled without its rules.
+fn check_game_config(config: &AccountInfo, game_id: u64, stake: u64, payout: u64) -> Result<()> {
+    let (expected, _bump) =
+        Pubkey::find_program_address(&[GAME_CONFIG_SEED, game_id.to_le_bytes().as_ref()], &crate::ID);
+    require!(config.key() == expected, VaultError::WrongGameConfig);
+    if let Some(config) = load_optional::<GameConfig>(config)? {
+        config.check_settlement(stake, payout)?;
+    }
+    Ok(())
+}
+
+/// `ComplianceConfig::large_transfer_threshold`, or 0 (off) while the config is uninitialized
+fn large_transfer_threshold(compliance_config: &AccountInfo) -> Result<u64> {
+    Ok(load_compliance_config(compliance_config)?.map_or(0, |config| config.large_transfer_threshold))
+}
+
+/// Count a transfer towards the vault's 24h total, returning the event to emit if it exceeds `threshold`
+fn flag_large_transfer(
+    vault: &mut UserVault,
+    vault_key: Pubkey,
+    kind: u8,
+    amount: u64,
+    threshold: u64,
+    global_config: &mut GlobalConfig,
+) -> Result<Option<LargeTransfer>> {
+    vault.record_transfer(Clock::get()?.unix_timestamp, amount)?;
+    if threshold == 0 || amount <= threshold {
+        return Ok(None);
+    }
+    Ok(Some(LargeTransfer {
+        version: EVENT_VERSION,
+        seq: global_config.next_seq()?,
+        vault: vault_key,
+        kind,
+        amount,
+        window_start: vault.transfer_window_start,
+        window_total: vault.transfer_window_total,
+    }))
+}
+
+/// Move lamports out of a program-owned account with checked arithmetic on both sides
+fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let (from_balance, to_balance) = checked_transfer(from.lamports(), to.lamports(), amount)?;
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+    Ok(())
+}
+
+/// Vault and house balances after settling one bet
+///
+/// A zero stake means it was deducted in an earlier transaction, so the house
+/// pays the full payout; otherwise only the difference between stake and
+/// payout moves, in whichever direction it points.
+pub fn settle_balances(vault_balance: u64, house_balance: u64, stake: u64, payout: u64) -> Result<(u64, u64)> {
+    require!(vault_balance >= stake, VaultError::InsufficientFunds);
+    let house_pays = if stake == 0 { payout } else { payout.saturating_sub(stake) };
+    if house_pays > 0 {
+        require!(house_balance >= house_pays, VaultError::HouseInsufficient);
+        let (house_balance, vault_balance) = checked_transfer(house_balance, vault_balance, house_pays)?;
+        return Ok((vault_balance, house_balance));
+    }
+    checked_transfer(vault_balance, house_balance, stake - payout)
+}
+
+/// Apply [`settle_balances`] to the vault and house accounts
+fn apply_settlement(vault: &AccountInfo, house: &AccountInfo, stake: u64, payout: u64) -> Result<()> {
+    let (vault_balance, house_balance) = settle_balances(vault.lamports(), house.lamports(), stake, payout)?;
+    **vault.try_borrow_mut_lamports()? = vault_balance;
+    **house.try_borrow_mut_lamports()? = house_balance;
+    Ok(())
+}
+
+/// Mint a trophy cNFT to the player through Bubblegum's `mint_v1`, if the win qualifies
+///
+/// Without a trophy config this is a no-op; with one, the other trophy accounts
+/// must be present. The bet is identified in the URI query string, not the name.
+fn mint_trophy(accounts: &BetAndSettle, bet_id_hash: [u8; 16], game_id: u64, stake: u64, payout: u64) -> Result<()> {
+    let Some(config) = accounts.trophy_config.as_ref() else {
+        return Ok(());
+    };
+    if !config.qualifies(stake, payout) {
+        return Ok(());
+    }
+    let (Some(player), Some(tree_config), Some(merkle_tree), Some(log_wrapper), Some(compression), Some(bubblegum)) = (
+        accounts.player.as_ref(),
+        accounts.tree_config.as_ref(),
+        accounts.merkle_tree.as_ref(),
+        accounts.log_wrapper.as_ref(),
+        accounts.compression_program.as_ref(),
+        accounts.bubblegum_program.as_ref(),
+    ) else {
+        return err!(VaultError::TrophyAccountsMissing);
+    };
+    require!(merkle_tree.key() == config.merkle_tree, VaultError::WrongTrophyTree);
+    // Bubblegum checks the noop and compression programs itself
+    let bubblegum_id: Pubkey = "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY".parse().unwrap();
+    require!(bubblegum.key() == bubblegum_id, VaultError::WrongTrophyTree);
+
+    let multiplier = payout as u128 * 100 / stake as u128;
+    let bet_hex: String = bet_id_hash.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let metadata = TrophyMetadata {
+        name: format!("{}x Trophy", multiplier / 100),
+        symbol: TROPHY_SYMBOL.to_string(),
+        uri: format!("{}?bet={}&game={}&stake={}&payout={}", config.base_uri, bet_hex, game_id, stake, payout),
+        seller_fee_basis_points: 0,
+        primary_sale_happened: false,
+        is_mutable: false,
+        edition_nonce: None,
+        token_standard: Some(0),
+        collection: None,
+        uses: None,
+        token_program_version: 0,
+        creators: Vec::new(),
+    };
+    let mut data = BUBBLEGUM_MINT_V1_DISCRIMINATOR.to_vec();
+    metadata.serialize(&mut data)?;
+
+    let instruction = Instruction {
+        program_id: bubblegum_id,
+        accounts: vec![
+            AccountMeta::new(tree_config.key(), false),
+            AccountMeta::new_readonly(player.key(), false), // leaf owner
+            AccountMeta::new_readonly(player.key(), false), // leaf delegate
+            AccountMeta::new(merkle_tree.key(), false),
+            AccountMeta::new(accounts.authority.key(), true), // payer
+            AccountMeta::new_readonly(config.key(), true),    // tree delegate
+            AccountMeta::new_readonly(log_wrapper.key(), false),
+            AccountMeta::new_readonly(compression.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &instruction,
+        &[
+            tree_config.to_account_info(),
+            player.to_account_info(),
+            merkle_tree.to_account_info(),
+            accounts.authority.to_account_info(),
+            config.to_account_info(),
+            log_wrapper.to_account_info(),
+            compression.to_account_info(),
+            accounts.system_program.to_account_info(),
+            bubblegum.to_account_info(),
+        ],
+        &[&[TROPHY_CONFIG_SEED, &[config.bump]]],
+    )?;
+
+    msg!("Trophy minted: player={}, multiplier={}, gameId={}", player.key(), multiplier, game_id);
+    Ok(())
+}
+
+/// The fulfilled randomness of the ORAO VRF request made with `seed`
+///
+/// Checks the account is ORAO's request PDA for `seed`; ORAO only writes the
+/// randomness once its oracle's VRF proof verifies.
+fn orao_randomness(account: &AccountInfo, seed: &[u8; 32]) -> Result<[u8; 64]> {
+    let orao_program: Pubkey = ORAO_VRF_PROGRAM_ID.parse().unwrap();
+    let (expected, _) = Pubkey::find_program_address(&[ORAO_RANDOMNESS_SEED, seed], &orao_program);
+    require!(account.key() == expected && account.owner == &orao_program, VaultError::WrongRandomnessAccount);
+    // Legacy `Randomness` layout: discriminator, seed, randomness (zero until fulfilled), responses
+    let data = account.try_borrow_data()?;
+    require!(data.len() >= 104 && data[..8] == ORAO_RANDOMNESS_DISCRIMINATOR, VaultError::WrongRandomnessAccount);
+    let randomness: [u8; 64] = data[40..104].try_into().unwrap();
+    require!(randomness.iter().any(|&byte| byte != 0), VaultError::RandomnessPending);
+    Ok(randomness)
+}
+
+/// Mint and token-owner of an SPL token account, the first two fields of its data
+fn token_account_parts(account: &AccountInfo) -> Result<(Pubkey, Pubkey)> {
+    let token_program: Pubkey = SPL_TOKEN_PROGRAM_ID.parse().unwrap();
+    require!(account.owner == &token_program, VaultError::WrongStakingToken);
+    let data = account.try_borrow_data()?;
+    require!(data.len() >= 64, VaultError::WrongStakingToken);
+    Ok((Pubkey::try_from(&data[..32]).unwrap(), Pubkey::try_from(&data[32..64]).unwrap()))
+}
+
+/// Move `amount` tokens with the SPL Token `Transfer` instruction, signed by `authority`
+///
+/// `signer_seeds` is empty when the authority signed the transaction itself.
+fn token_transfer<'info>(
+    token_program: &AccountInfo<'info>,
+    source: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let token_program_id: Pubkey = SPL_TOKEN_PROGRAM_ID.parse().unwrap();
+    require!(token_program.key() == token_program_id, VaultError::WrongStakingToken);
+    let mut data = vec![SPL_TOKEN_TRANSFER_TAG];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    let instruction = Instruction {
+        program_id: token_program_id,
+        accounts: vec![
+            AccountMeta::new(source.key(), false),
+            AccountMeta::new(destination.key(), false),
+            AccountMeta::new_readonly(authority.key(), true),
+        ],
+        data,
+    };
+    invoke_signed(
+        &instruction,
+        &[source.clone(), destination.clone(), authority.clone(), token_program.clone()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Total lamports held by `accounts`
+#[cfg(feature = "audit")]
+fn lamport_total(accounts: &[AccountInfo]) -> u128 {
+    accounts.iter().map(|account| account.lamports() as u128).sum()
+}
+
+/// Audit builds: fail if lamports appeared or vanished between two totals of the same accounts
+#[cfg(feature = "audit")]
+fn audit_conservation(before: u128, after: u128) -> Result<()> {
+    if after != before {
+        msg!("Audit: lamports not conserved, before={}, after={}", before, after);
+        return err!(VaultError::InvariantViolated);
+    }
+    Ok(())
+}
+
+/// Audit builds: fail if a vault holds less than its locked amount
+#[cfg(feature = "audit")]
+fn audit_locked(vault: &AccountInfo, locked_amount: u64) -> Result<()> {
+    if vault.lamports() < locked_amount {
+        msg!("Audit: vault {} holds {} lamports, {} locked", vault.key(), vault.lamports(), locked_amount);
+        return err!(VaultError::InvariantViolated);
+    }
+    Ok(())
+}
+
+/// An account whose layout carries a version byte and is brought up to date by a `migrate_*` instruction
+///
+/// Each layout change bumps `VERSION` and `SPACE`; [`migrate_account`] resizes
+/// the account and runs [`Versioned::upgrade`] on the old bytes, so a new field
+/// only needs a new arm in `upgrade` when zero is not a valid starting value.
+pub trait Versioned: Discriminator {
+    /// Current layout version
+    const VERSION: u8;
+    /// Current account size, discriminator included
+    const SPACE: usize;
+    /// Byte offset of the version field within the account data
+    const VERSION_OFFSET: usize;
+
+    /// Version recorded in raw account data; accounts too short to hold it report 0
+    fn stored_version(data: &[u8]) -> u8 {
+        data.get(Self::VERSION_OFFSET).copied().unwrap_or(0)
+    }
+
+    /// Rewrite `data`, stored at version `from`, in the current layout
+    ///
+    /// The default zero-fills the fields added since `from` and stamps the version.
+    fn upgrade(data: &[u8], from: u8) -> Result<Vec<u8>> {
+        let _ = from;
+        let mut upgraded = data.to_vec();
+        upgraded.resize(Self::SPACE, 0);
+        upgraded[Self::VERSION_OFFSET] = Self::VERSION;
+        Ok(upgraded)
+    }
+}
+
+impl Versioned for UserVault {
+    const VERSION: u8 = VAULT_VERSION;
+    const SPACE: usize = USER_VAULT_SPACE;
+    const VERSION_OFFSET: usize = VAULT_VERSION_OFFSET;
+}
+
+impl Versioned for HouseVault {
+    const VERSION: u8 = HOUSE_VERSION;
+    const SPACE: usize = HOUSE_VAULT_SPACE;
+    const VERSION_OFFSET: usize = 8 + 1 + 32 + 32 + 8;
+}
+
+impl Versioned for GlobalConfig {
+    const VERSION: u8 = GLOBAL_CONFIG_VERSION;
+    const SPACE: usize = GLOBAL_CONFIG_SPACE;
+    const VERSION_OFFSET: usize = 8 + 8 + 1;
+}
+
+impl Versioned for GlobalStats {
+    const VERSION: u8 = GLOBAL_STATS_VERSION;
+    const SPACE: usize = GLOBAL_STATS_SPACE;
+    const VERSION_OFFSET: usize = 8 + 8 + 8 + 8 + 8 + 32 + 1 + 8 + 8 + 8 + 8;
+}
+
+impl Versioned for PauseConfig {
+    const VERSION: u8 = PAUSE_CONFIG_VERSION;
+    const SPACE: usize = PAUSE_CONFIG_SPACE;
+    const VERSION_OFFSET: usize = 8 + 32 + 32 + 1 + 4 + 1;
+
+    /// Version 0 is the pre-bitflag layout, repacked field by field
+    fn upgrade(data: &[u8], from: u8) -> Result<Vec<u8>> {
+        require!(from == 0 && data.len() == LEGACY_PAUSE_CONFIG_LEN, VaultError::VersionMismatch);
+        let legacy = LegacyPauseConfig::try_from_slice(&data[8..]).map_err(|_| VaultError::VersionMismatch)?;
+        let mut config = PauseConfig {
+            multisig_authority: legacy.multisig_authority,
+            admin_authority: legacy.admin_authority,
+            flags: 0,
+            maintenance_start_time: u32::try_from(legacy.maintenance_start_time).map_err(|_| VaultError::Overflow)?,
+            maintenance_duration_hours: legacy.maintenance_duration_hours,
+            version: PAUSE_CONFIG_VERSION,
+            bump: legacy.bump,
+        };
+        config.set_flag(PAUSE_EMERGENCY, legacy.emergency_pause);
+        config.set_flag(PAUSE_MAINTENANCE, legacy.maintenance_pause);
+
+        let mut upgraded = Vec::with_capacity(Self::SPACE);
+        config.try_serialize(&mut upgraded)?;
+        upgraded.resize(Self::SPACE, 0);
+        Ok(upgraded)
+    }
+}
+
+/// Bring `info` to `T`'s current layout and return the version it was stored at
+///
+/// Accounts already current are left alone. A growing account takes the rent
+/// difference from `payer`; a shrinking one refunds it to `payer`.
+fn migrate_account<'info, T: Versioned>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u8> {
+    let (from, upgraded) = {
+        let data = info.data.borrow();
+        require!(data.len() >= 8 && data[..8] == T::DISCRIMINATOR, VaultError::VersionMismatch);
+        let from = T::stored_version(&data);
+        require!(from <= T::VERSION, VaultError::VersionMismatch);
+        if from == T::VERSION && data.len() == T::SPACE {
+            return Ok(from);
+        }
+        (from, T::upgrade(&data, from)?)
+    };
+    require!(upgraded.len() == T::SPACE, VaultError::VersionMismatch);
+
+    let rent = Rent::get()?;
+    let old_rent = rent.minimum_balance(info.data_len());
+    let new_rent = rent.minimum_balance(T::SPACE);
+    if new_rent > old_rent {
+        invoke(
+            &system_instruction::transfer(&payer.key(), &info.key(), new_rent - old_rent),
+            &[payer.clone(), info.clone(), system_program.clone()],
+        )?;
+    }
+    info.realloc(T::SPACE, false)?;
+    info.try_borrow_mut_data()?.copy_from_slice(&upgraded);
+    if old_rent > new_rent {
+        transfer_lamports(info, payer, old_rent - new_rent)?;
+    }
+    Ok(from)
+}
+
+/// The house's lamports above its rent-exempt minimum, and the exposure they must cover
+fn house_solvency(house_vault: &AccountLoader<HouseVault>) -> Result<(u64, u64)> {
+    let house_info = house_vault.to_account_info();
+    let rent = Rent::get()?.minimum_balance(house_info.data_len());
+    Ok((house_info.lamports().saturating_sub(rent), house_vault.load()?.open_exposure))
+}
+
+/// Refuse to move `amount` out of a house whose balance above rent would no longer cover its open exposure
+fn check_house_surplus(house_balance: u64, open_exposure: u64, amount: u64) -> Result<()> {
+    let needed = open_exposure.checked_add(amount).ok_or(VaultError::Overflow)?;
+    require!(house_balance >= needed, VaultError::HouseInsufficient);
+    Ok(())
+}
+
+/// Layout version of a raw `UserVault` account, read without deserializing
+///
+/// The byte sits after discriminator, owner, bump, locked_amount, active_games
+/// and accum_wager; vaults too short to hold it predate versioning and report 0.
+pub fn vault_version(vault_info: &AccountInfo) -> u8 {
+    UserVault::stored_version(&vault_info.data.borrow())
+}
+
+/// Byte offset of `UserVault::version` within the account data
+pub const VAULT_VERSION_OFFSET: usize = 8 + 32 + 1 + 8 + 4 + 8;
+
+/// Reward multiplier (100 = 1x) granted by burning one gem of each type, common to legendary
+pub const GEM_BOOST_MULTIPLIERS: [u16; 7] = [110, 120, 130, 150, 175, 200, 300];
+
+/// Most gems of each type a single settlement may award, common to legendary
+pub const MAX_GEMS_PER_SETTLEMENT: [u8; 7] = [100, 50, 25, 10, 5, 3, 1];
+
+/// Current `UserVault` layout version; older vaults must call `migrate_vault`
+pub const VAULT_VERSION: u8 = 10;
+
+/// Current `HouseVault` layout version; version 3 predates `category_volume`
+pub const HOUSE_VERSION: u8 = 4;
+
+/// Game category codes settlements report their volume under
+pub const GAME_CATEGORY_SLOTS: u8 = 0;
+pub const GAME_CATEGORY_LIVE: u8 = 1;
+pub const GAME_CATEGORY_ORIGINALS: u8 = 2;
+/// Number of game categories `HouseVault::category_volume` tracks
+pub const GAME_CATEGORIES: usize = 3;
+
+/// Current `PauseConfig` layout version; the pre-bitflag layout reads as 0
+pub const PAUSE_CONFIG_VERSION: u8 = 2;
+
+/// Current `GlobalConfig` layout version; the layout before the deposit and stake minimums reads as 0
+pub const GLOBAL_CONFIG_VERSION: u8 = 1;
+
+/// Current `GlobalStats` layout version; the layout before the invocation counters reads as 0
+pub const GLOBAL_STATS_VERSION: u8 = 1;
+
+/// `PauseConfig::flags` bit set while an emergency pause halts everything
+pub const PAUSE_EMERGENCY: u8 = 1 << 0;
+
+/// `PauseConfig::flags` bit set while a maintenance window is open
+pub const PAUSE_MAINTENANCE: u8 = 1 << 1;
+
+/// Size of a `PauseConfig` written before the bitflag layout
+const LEGACY_PAUSE_CONFIG_LEN: usize = 8 + 32 + 32 + 1 + 8 + 1 + 1 + 1;
+
+/// Published rules of one originals game, at `[b"game_config", game_id]`
+#[account]
+pub struct GameConfig {
+    pub game_id: u64,
+    pub house_edge_bps: u16,     // Edge taken off the fair max multiplier
+    pub max_multiplier: u32,     // Fair top multiplier, before the edge (100 = 1x)
+    pub min_stake: u64,
+    pub max_stake: u64,          // 0 for no cap
+    pub bump: u8,
+}
+
+impl GameConfig {
+    /// Validate and store new rules
+    pub fn set_rules(
+        &mut self,
+        house_edge_bps: u16,
+        max_multiplier: u32,
+        min_stake: u64,
+        max_stake: u64,
+    ) -> Result<()> {
+        require!(house_edge_bps < 10_000 && max_multiplier >= 100, VaultError::InvalidAmount);
+        require!(max_stake == 0 || max_stake >= min_stake, VaultError::InvalidAmount);
+        self.house_edge_bps = house_edge_bps;
+        self.max_multiplier = max_multiplier;
+        self.min_stake = min_stake;
+        self.max_stake = max_stake;
+        Ok(())
+    }
+
+    /// Reject a settlement outside the stake bounds or paying more than the top multiplier less the edge
+    ///
+    /// Unstaked settlements, whose stake was taken earlier, are bounded by the game limits alone.
+    pub fn check_settlement(&self, stake: u64, payout: u64) -> Result<()> {
+        if stake == 0 {
+            return Ok(());
+        }
+        require!(
+            stake >= self.min_stake && (self.max_stake == 0 || stake <= self.max_stake),
+            VaultError::StakeOutOfRange
+        );
+        let max_payout = stake as u128 * self.max_multiplier as u128 * (10_000 - self.house_edge_bps) as u128;
+        require!(payout as u128 * 100 * 10_000 <= max_payout, VaultError::PayoutExceedsLimit);
+        Ok(())
+    }
+}
+
+/// Tiers of each game's jackpot: mini, major and grand
+pub const JACKPOT_TIERS: usize = 3;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Debug)]
+pub struct JackpotTier {
+    pub contribution_bps: u16,   // Share of each stake the house adds to the pot
+    pub seed: u64,               // Pot the tier restarts from after a hit
+    pub odds: u32,               // A draw hits once in `odds`; 0 disables the tier
+    pub pot: u64,                // Lamports a hit pays, held in the jackpot account
+    pub hits: u32,               // Times the tier has paid out
+}
+
+/// Tiered jackpot of one game, at `[b"jackpot", game_id]`
+///
+/// Stakes settled through `bet_and_settle` feed every tier's pot.
+#[account]
+pub struct Jackpot {
+    pub game_id: u64,
+    pub vrf_authority: Pubkey,   // Service key that co-signs draws; the outcome comes from ORAO VRF
+    pub tiers: [JackpotTier; JACKPOT_TIERS],
+    pub bump: u8,
+}
+
+impl Jackpot {
+    /// Store a tier's terms, returning the lamports needed to lift its pot to the seed
+    pub fn set_tier(&mut self, tier: usize, contribution_bps: u16, seed: u64, odds: u32) -> Result<u64> {
+        require!(tier < JACKPOT_TIERS, VaultError::InvalidJackpotTier);
+        let others: u32 = self.tiers.iter().enumerate()
+            .filter(|(index, _)| *index != tier)
+            .map(|(_, other)| other.contribution_bps as u32)
+            .sum();
+        require!(others + contribution_bps as u32 <= 10_000, VaultError::InvalidAmount);
+        let entry = &mut self.tiers[tier];
+        entry.contribution_bps = contribution_bps;
+        entry.seed = seed;
+        entry.odds = odds;
+        let top_up = seed.saturating_sub(entry.pot);
+        entry.pot = entry.pot.max(seed);
+        Ok(top_up)
+    }
+
+    /// Add each tier's share of `stake` to its pot, returning the lamports owed by the house
+    pub fn contribute(&mut self, stake: u64) -> Result<u64> {
+        let mut total: u64 = 0;
+        for tier in self.tiers.iter_mut() {
+            let share = (stake as u128 * tier.contribution_bps as u128 / 10_000) as u64;
+            tier.pot = tier.pot.checked_add(share).ok_or(VaultError::Overflow)?;
+            total = total.checked_add(share).ok_or(VaultError::Overflow)?;
+        }
+        Ok(total)
+    }
+
+    /// Seed of the ORAO randomness request that draws `tier` for the bet hashed as `bet_id_hash`
+    ///
+    /// Fixed by the jackpot, bet and tier, so each bet gets exactly one draw per tier.
+    pub fn vrf_seed(jackpot: &Pubkey, bet_id_hash: &[u8; 16], tier: u8) -> [u8; 32] {
+        keccak::hashv(&[jackpot.as_ref(), &bet_id_hash[..], &[tier]]).to_bytes()
+    }
+
+    /// Whether a fulfilled VRF result hits `tier`
+    pub fn is_hit(&self, tier: usize, randomness: &[u8; 64]) -> bool {
+        let entry = &self.tiers[tier];
+        entry.odds != 0 && u64::from_le_bytes(randomness[0..8].try_into().unwrap()) % entry.odds as u64 == 0
+    }
+
+    /// Empty a tier after a hit and restart it from its seed, returning the amount won and the seed
+    pub fn reset_tier(&mut self, tier: usize) -> Result<(u64, u64)> {
+        let entry = &mut self.tiers[tier];
+        let amount = entry.pot;
+        entry.pot = entry.seed;
+        entry.hits = entry.hits.checked_add(1).ok_or(VaultError::Overflow)?;
+        Ok((amount, entry.seed))
+    }
+}
+
+/// Feed the stake of one settlement into the game's jackpot, if it has one
+///
+/// `jackpot` must be the game's `[b"jackpot", game_id]` address whether or
+/// not it exists, so a settlement cannot skip its contribution.
+fn contribute_to_jackpot(jackpot: &AccountInfo, house: &AccountInfo, stake: u64) -> Result<()> {
+    let Some(mut state) = load_optional::<Jackpot>(jackpot)? else {
+        return Ok(());
+    };
+    let contribution = state.contribute(stake)?;
+    if contribution > 0 {
+        transfer_lamports(house, jackpot, contribution)?;
+        state.try_serialize(&mut &mut jackpot.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+/// Compute units a `batch_settle` spends regardless of batch size
+///
+/// Kept above the `batch_settle_*` cases of the compute-unit bench; raise it
+/// and [`BATCH_SETTLE_ITEM_CU`] whenever the baseline grows past them.
+pub const BATCH_SETTLE_BASE_CU: u32 = 30_000;
+
+/// Compute units each settled bet adds to a `batch_settle`
+pub const BATCH_SETTLE_ITEM_CU: u32 = 25_000;
+
+/// Most compute units a transaction can request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Largest batch that fits the compute ceiling at the per-item cost above
+pub const MAX_BATCH_SETTLE: usize = ((MAX_COMPUTE_UNIT_LIMIT - BATCH_SETTLE_BASE_CU) / BATCH_SETTLE_ITEM_CU) as usize;
+
+/// Vaults one `batch_credit` may credit
+pub const MAX_BATCH_CREDIT: usize = 50;
+
+/// Vault owners listed per `VaultRegistryPage`
+pub const VAULT_REGISTRY_PAGE_LEN: usize = 128;
+
+/// Game programs the registry has room for
+pub const MAX_GAME_PROGRAMS: usize = 32;
+
+/// Snapshots `StatsSnapshots` keeps before overwriting the oldest
+pub const STATS_SNAPSHOT_SLOTS: usize = 48;
+
+/// Longest accepted bet_id in bytes; longer backend IDs should be hashed first
+pub const MAX_BET_ID_LEN: usize = 64;
+
+/// Wallets the blacklist account has room for
+pub const MAX_BLACKLIST_LEN: usize = 256;
+
+/// Games that can carry their own payout multiplier in `GameLimits`
+pub const MAX_GAME_LIMITS: usize = 32;
+
+/// Age after which anyone may close a bet receipt (30 days)
+pub const RECEIPT_RETENTION_SECS: i64 = 30 * 24 * 3600;
+
+/// Longest announcement URI the account has space for
+pub const MAX_ANNOUNCEMENT_URI_LEN: usize = 200;
+
+/// Announcement severities: 0 = info, 1 = warning, 2 = critical
+pub const ANNOUNCEMENT_SEVERITY_CRITICAL: u8 = 2;
+
+/// Longest trophy base URI; the bet query string appended to it fits Bubblegum's 200-byte cap
+pub const MAX_TROPHY_URI_LEN: usize = 80;
+
+/// Symbol every trophy cNFT carries
+pub const TROPHY_SYMBOL: &str = "SOLSWIN";
+
+/// Length of the window `UserVault::transfer_window_total` accumulates over
+pub const TRANSFER_WINDOW_SECS: i64 = 24 * 60 * 60;
+
+/// Length of the window `UserVault::win_window_net` accumulates over
+pub const WIN_WINDOW_SECS: i64 = 24 * 60 * 60;
+
+/// `LargeTransfer::kind` of a deposit, including bridged deposits
+pub const TRANSFER_DEPOSIT: u8 = 0;
+
+/// `LargeTransfer::kind` of a withdrawal
+pub const TRANSFER_WITHDRAWAL: u8 = 1;
+
+/// `LargeTransfer::kind` of a settlement payout
+pub const TRANSFER_PAYOUT: u8 = 2;
+
+/// Wait before an owner can drop a withdraw guard without the device key
+pub const WITHDRAW_GUARD_REMOVAL_DELAY_SECS: i64 = 72 * 60 * 60;
+
+/// Region rules the compliance config has room for
+pub const MAX_REGION_RULES: usize = 16;
+
+/// Attestors the compliance config has room for
+pub const MAX_ATTESTORS: usize = 8;
+
+/// Emitters the bridge config has room for
+pub const MAX_BRIDGE_EMITTERS: usize = 16;
+
+/// First byte of a bridged deposit payload
+pub const BRIDGE_DEPOSIT_PAYLOAD_ID: u8 = 1;
+
+/// `export_audit_page` pages, in export order
+pub const AUDIT_PAGE_AUTHORITIES: u8 = 0;
+pub const AUDIT_PAGE_BALANCES: u8 = 1;
+pub const AUDIT_PAGE_GAME_LIMITS: u8 = 2;
+pub const AUDIT_PAGE_GAME_PROGRAMS: u8 = 3;
+pub const AUDIT_PAGE_COMPLIANCE: u8 = 4;
+pub const AUDIT_PAGE_COUNT: u8 = 5;
+
+/// Instruction discriminator a screening program must accept, Anchor's for `screen_deposit`
+pub const SCREEN_DEPOSIT_DISCRIMINATOR: [u8; 8] = [106, 181, 63, 212, 205, 245, 91, 18];
+
+/// Anchor discriminator of Bubblegum's `mint_v1`
+const BUBBLEGUM_MINT_V1_DISCRIMINATOR: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+
+/// ORAO VRF, whose fulfilled requests draw the jackpots
+pub const ORAO_VRF_PROGRAM_ID: &str = "VRFzZoJdhFWL8rkvu87LpKM3RbcVezpMEc6X5GVDr7y";
+
+/// Seed prefix of an ORAO randomness request PDA, followed by the request seed
+pub const ORAO_RANDOMNESS_SEED: &[u8] = b"orao-vrf-randomness-request";
+
+/// Anchor discriminator of ORAO's `Randomness` account
+const ORAO_RANDOMNESS_DISCRIMINATOR: [u8; 8] = [188, 96, 216, 248, 93, 94, 49, 112];
+
+/// SPL Token, which holds the staked platform tokens
+pub const SPL_TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// Instruction tag of SPL Token's `Transfer`
+const SPL_TOKEN_TRANSFER_TAG: u8 = 3;
+
+/// Most rebate tiers a `StakingConfig` holds
+pub const MAX_REBATE_TIERS: usize = 4;
+
+/// Highest settlement rebate a staking tier may grant, in basis points of the wager
+pub const MAX_REBATE_BPS: u16 = 100;
+
+// Data structures
+//
+// Hot accounts are zero-copy. The packed layout is byte-for-byte the Borsh
+// layout they used before, so existing accounts load without migration.
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+pub struct UserVault {
+    pub owner: Pubkey,           // Vault owner
+    pub bump: u8,                // PDA bump
+    pub locked_amount: u64,      // Amount locked in active games
+    pub active_games: u32,       // Number of active games
+    pub accum_wager: u64,        // Accumulated wager for gem rewards
+    pub version: u8,             // Layout version (VAULT_VERSION)
+    pub created_at: i64,         // Vault creation time (0 = legacy vault)
+    pub lifetime_wager: u64,     // Total stake ever settled
+    pub rate_window_start: i64,  // Second the settlement counter belongs to
+    pub rate_window_bets: u32,   // Settlements within that second
+    pub transfer_window_start: i64, // Start of the 24h window the transfer total belongs to
+    pub transfer_window_total: u64, // Deposits, withdrawals and payouts within that window
+    pub region: u8,              // Jurisdiction set by the admin (0 = unassigned)
+    pub terms_hash: [u8; 32],    // Hash of the last terms of service the owner accepted
+    pub terms_accepted_at: i64,  // When they were accepted (0 = never)
+    pub free_spins: u32,         // Free spins credited and not yet played
+    pub free_spin_value: u64,    // Nominal stake of each of those spins
+    pub loss_streak: u16,        // Consecutive staked losses, for the pity boost
+    pub win_window_start: i64,   // Start of the 24h window the net-win total belongs to
+    pub win_window_net: i64,     // Payouts minus stakes within that window
+    pub daily_win_cap: u64,      // Net-win cap set by the multisig (0 = GameLimits::max_daily_net_win)
+}
+
+impl UserVault {
+    /// Count a settlement against the per-second limit (0 = unlimited)
+    pub fn record_settlement(&mut self, now: i64, max_bets_per_second: u32) -> Result<()> {
+        if now != self.rate_window_start {
+            self.rate_window_start = now;
+            self.rate_window_bets = 0;
+        }
+        self.rate_window_bets = self.rate_window_bets.checked_add(1).ok_or(VaultError::Overflow)?;
+        require!(
+            max_bets_per_second == 0 || self.rate_window_bets <= max_bets_per_second,
+            VaultError::BetRateLimited
+        );
+        Ok(())
+    }
+
+    /// Add `amount` to the 24h transfer total, starting a new window once the current one has run out
+    pub fn record_transfer(&mut self, now: i64, amount: u64) -> Result<()> {
+        if now.saturating_sub(self.transfer_window_start) >= TRANSFER_WINDOW_SECS {
+            self.transfer_window_start = now;
+            self.transfer_window_total = 0;
+        }
+        self.transfer_window_total = self.transfer_window_total.checked_add(amount).ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+
+    /// Refuse a bet once the window's net winnings have reached the vault's cap, or `default_cap` if it has none
+    ///
+    /// A cap of 0 is unlimited. The bet that crosses the cap still settles.
+    pub fn check_win_cap(&mut self, now: i64, default_cap: u64) -> Result<()> {
+        self.roll_win_window(now);
+        let cap = if self.daily_win_cap > 0 { self.daily_win_cap } else { default_cap };
+        require!(cap == 0 || (self.win_window_net as i128) < cap as i128, VaultError::DailyWinCapReached);
+        Ok(())
+    }
+
+    /// Add a settlement's net result to the 24h net-win total
+    pub fn record_net_win(&mut self, now: i64, stake: u64, payout: u64) -> Result<()> {
+        self.roll_win_window(now);
+        let net = self.win_window_net as i128 + payout as i128 - stake as i128;
+        self.win_window_net = i64::try_from(net).map_err(|_| VaultError::Overflow)?;
+        Ok(())
+    }
+
+    fn roll_win_window(&mut self, now: i64) {
+        if now.saturating_sub(self.win_window_start) >= WIN_WINDOW_SECS {
+            self.win_window_start = now;
+            self.win_window_net = 0;
+        }
+    }
+
+    /// Extend the losing streak on a loss and end it otherwise; unstaked settlements leave it alone
+    pub fn record_outcome(&mut self, stake: u64, payout: u64) {
+        if stake == 0 {
+            return;
+        }
+        self.loss_streak = if payout < stake { self.loss_streak.saturating_add(1) } else { 0 };
+    }
+}
+
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+pub struct HouseVault {
+    pub bump: u8,                // PDA bump
+    pub multisig_authority: Pubkey, // Multisig authority
+    pub admin_authority: Pubkey,    // Admin authority
+    pub total_volume: u64,          // Total betting volume
+    pub version: u8,             // Layout version (HOUSE_VERSION)
+    pub open_exposure: u64,      // Most the house can still owe on open bets and streamed payouts
+    pub category_volume: [u64; GAME_CATEGORIES], // Volume settled by `bet_and_settle` and `batch_settle`, by category
+}
+
+impl HouseVault {
+    /// Count what the house may owe on a newly opened bet or payout schedule
+    pub fn add_exposure(&mut self, amount: u64) -> Result<()> {
+        self.open_exposure = self.open_exposure.checked_add(amount).ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+
+    /// Release exposure once it is paid or can no longer be owed
+    ///
+    /// Saturates, as bets opened before the house was migrated were never counted.
+    pub fn release_exposure(&mut self, amount: u64) {
+        self.open_exposure = self.open_exposure.saturating_sub(amount);
+    }
+
+    /// Add a settled stake to the total volume and to its game category's
+    pub fn record_volume(&mut self, category: u8, stake: u64) -> Result<()> {
+        let mut volumes = self.category_volume;
+        let slot = volumes.get_mut(category as usize).ok_or(VaultError::InvalidGameCategory)?;
+        *slot = slot.checked_add(stake).ok_or(VaultError::Overflow)?;
+        self.category_volume = volumes;
+        self.total_volume = self.total_volume.checked_add(stake).ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct PauseConfig {
+    pub multisig_authority: Pubkey,  // Multisig authority
+    pub admin_authority: Pubkey,     // Admin authority  
+    pub flags: u8,                   // PAUSE_EMERGENCY | PAUSE_MAINTENANCE
+    pub maintenance_start_time: u32, // When maintenance started (unix seconds)
+    pub maintenance_duration_hours: u8, // How long maintenance lasts
+    pub version: u8,                 // Layout version (PAUSE_CONFIG_VERSION)
+    pub bump: u8,
+}
+
+impl PauseConfig {
+    pub fn emergency_pause(&self) -> bool {
+        self.flags & PAUSE_EMERGENCY != 0
+    }
+
+    pub fn maintenance_pause(&self) -> bool {
+        self.flags & PAUSE_MAINTENANCE != 0
+    }
+
+    /// Whether the maintenance window that started at `maintenance_start_time` is over
+    pub fn maintenance_elapsed(&self, now: i64) -> bool {
+        (now - self.maintenance_start_time as i64) / 3600 >= self.maintenance_duration_hours as i64
+    }
+
+    /// Whether operations are halted at `now`, counting an elapsed maintenance window as lifted
+    pub fn is_paused(&self, now: i64) -> bool {
+        self.emergency_pause() || (self.maintenance_pause() && !self.maintenance_elapsed(now))
+    }
+
+    pub fn set_flag(&mut self, flag: u8, on: bool) {
+        if on {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+}
+
+/// `PauseConfig` as laid out before the bitflag packing; only `PauseConfig::upgrade` reads it
+///
+/// Its `maintenance_start_time` bytes overlap the new `version` field with a
+/// high timestamp byte, which stays 0, so unmigrated accounts fail the version check.
+#[derive(AnchorDeserialize)]
+struct LegacyPauseConfig {
+    multisig_authority: Pubkey,
+    admin_authority: Pubkey,
+    maintenance_pause: bool,
+    maintenance_start_time: i64,
+    maintenance_duration_hours: u8,
+    emergency_pause: bool,
+    bump: u8,
+}
+
+#[account]
+pub struct PromoPool {
+    pub bump: u8,                // PDA bump
+    pub total_granted: u64,      // Lifetime bonus lamports granted
+    pub total_forfeited: u64,    // Lifetime bonus lamports reclaimed
+}
+
+#[account]
+pub struct DepositBonus {
+    pub vault: Pubkey,           // User vault the bonus belongs to
+    pub deposit_amount: u64,     // Qualifying deposit that was matched
+    pub bonus_amount: u64,       // Bonus lamports locked in the vault
+    pub wager_requirement: u64,  // Total stake needed to clear the bonus
+    pub wagered: u64,            // Stake wagered so far
+    pub expires_at: i64,         // Forfeitable after this timestamp
+    pub cleared: bool,           // Playthrough met, funds unlocked
+    pub remaining: u64,          // Bonus lamports still in the vault and locked; net losses spend it first
+    pub bump: u8,
+}
+
+impl DepositBonus {
+    /// Spend a settlement's net loss from the bonus first, returning the bonus lamports it consumed
+    pub fn record_loss(&mut self, stake: u64, payout: u64) -> u64 {
+        let consumed = stake.saturating_sub(payout).min(self.remaining);
+        self.remaining -= consumed;
+        consumed
+    }
+
+    /// Lamports a forfeit or clawback returns to the pool, given the vault's balance above rent
+    ///
+    /// Bounded by the bonus still held, so deposits made after it was lost stay with the vault.
+    pub fn reclaimable(&self, spendable: u64) -> u64 {
+        self.remaining.min(spendable)
+    }
+}
+
+#[account]
+pub struct GemInventory {
+    pub vault: Pubkey,           // User vault the gems belong to
+    pub counts: [u32; 7],        // Gems held per type, common to legendary
+    pub boost_multiplier: u16,   // Pending reward multiplier (100 = none)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Raffle {
+    pub raffle_id: u64,          // Admin-chosen raffle identifier
+    pub entry_gem_type: u8,      // Gem type accepted as tickets
+    pub prize: u64,              // Lamports paid from the promo pool
+    pub closes_at: i64,          // Entries close / draw opens
+    pub draw_authority: Pubkey,  // Reveals the draw seed
+    pub commitment: [u8; 32],    // keccak of the draw seed, set before entries open
+    pub total_tickets: u64,      // Tickets sold so far
+    pub winning_ticket: u64,     // Winning ticket index once drawn
+    pub drawn: bool,
+    pub paid: bool,
+    pub bump: u8,
+}
+
+impl Raffle {
+    /// Pick the winning ticket from the revealed `seed`, which must match the commitment
+    pub fn draw(&mut self, raffle: &Pubkey, seed: &[u8; 32]) -> Result<()> {
+        require!(!self.drawn, VaultError::RaffleAlreadyDrawn);
+        require!(self.total_tickets > 0, VaultError::RaffleEmpty);
+        require!(keccak::hash(seed).to_bytes() == self.commitment, VaultError::RaffleSeedMismatch);
+        let hash = keccak::hashv(&[&seed[..], raffle.as_ref()]).to_bytes();
+        self.winning_ticket = u64::from_le_bytes(hash[0..8].try_into().unwrap()) % self.total_tickets;
+        self.drawn = true;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct RaffleEntry {
+    pub raffle: Pubkey,          // Raffle entered
+    pub vault: Pubkey,           // Entrant's user vault
+    pub first_ticket: u64,       // First ticket index owned
+    pub tickets: u32,            // Number of consecutive tickets owned
+    pub bump: u8,
+}
+
+/// Most entrants in one tournament bracket; brackets hold a power of two
+pub const MAX_BRACKET_SIZE: usize = 16;
+
+/// Rounds in the largest bracket
+pub const MAX_BRACKET_ROUNDS: usize = MAX_BRACKET_SIZE.trailing_zeros() as usize;
+
+#[account]
+pub struct Tournament {
+    pub tournament_id: u64,
+    pub game_id: u64,
+    pub payer: Pubkey,           // Admin that paid the rent, refunded on close
+    pub round: u8,               // Round being played, from 1; past the final once complete
+    pub entrants: Vec<Pubkey>,   // Vaults in seeding order (max MAX_BRACKET_SIZE)
+    pub eliminated_in: Vec<u8>,  // Round each entrant lost, by seed; 0 while still in
+    pub prizes: Vec<u64>,        // Prize per entrant by standing (max MAX_BRACKET_ROUNDS + 1)
+    pub bump: u8,
+}
+
+impl Tournament {
+    pub fn rounds(&self) -> u8 {
+        self.entrants.len().trailing_zeros() as u8
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.round > self.rounds()
+    }
+
+    /// Seeds of the two entrants meeting in match `match_index` of the current round
+    fn match_seeds(&self, match_index: usize) -> Result<(usize, usize)> {
+        let round = self.round;
+        let mut contenders = (0..self.entrants.len())
+            .filter(|&seed| self.eliminated_in[seed] == 0 || self.eliminated_in[seed] >= round)
+            .skip(2 * match_index);
+        match (contenders.next(), contenders.next()) {
+            (Some(first), Some(second)) => Ok((first, second)),
+            _ => err!(VaultError::NoSuchMatch),
+        }
+    }
+
+    /// Eliminate the entrant `winner` beat in match `match_index`, returning the loser
+    pub fn record_result(&mut self, match_index: usize, winner: &Pubkey) -> Result<Pubkey> {
+        require!(!self.is_complete(), VaultError::TournamentOver);
+        let (first, second) = self.match_seeds(match_index)?;
+        require!(self.eliminated_in[first] == 0 && self.eliminated_in[second] == 0, VaultError::MatchAlreadyPosted);
+        let loser = if self.entrants[first] == *winner {
+            second
+        } else if self.entrants[second] == *winner {
+            first
+        } else {
+            return err!(VaultError::NotInMatch);
+        };
+        self.eliminated_in[loser] = self.round;
+        Ok(self.entrants[loser])
+    }
+
+    /// Start the next round, or complete the bracket after the final
+    pub fn advance(&mut self) -> Result<()> {
+        require!(!self.is_complete(), VaultError::TournamentOver);
+        let remaining = self.eliminated_in.iter().filter(|&&round| round == 0).count();
+        require!(remaining << self.round == self.entrants.len(), VaultError::RoundIncomplete);
+        self.round += 1;
+        Ok(())
+    }
+
+    /// Final standing of the entrant at `seed`: 0 for the champion, 1 for the runner-up, 2 for semifinalists, ...
+    pub fn standing(&self, seed: usize) -> u8 {
+        match self.eliminated_in[seed] {
+            0 => 0,
+            round => self.rounds() - round + 1,
+        }
+    }
+
+    /// Prize owed to the entrant at `seed` by final standing
+    pub fn prize(&self, seed: usize) -> u64 {
+        self.prizes.get(self.standing(seed) as usize).copied().unwrap_or(0)
+    }
+
+    /// Lamports the whole bracket pays out
+    pub fn total_prizes(&self) -> Result<u64> {
+        let mut total: u64 = 0;
+        for (standing, prize) in self.prizes.iter().enumerate() {
+            let winners = if standing == 0 { 1 } else { 1u64 << (standing - 1) };
+            total = winners.checked_mul(*prize).and_then(|paid| total.checked_add(paid)).ok_or(VaultError::Overflow)?;
+        }
+        Ok(total)
+    }
+}
+
+#[account]
+pub struct RewardsConfig {
+    pub min_lifetime_wager: u64, // Minimum lifetime wager to receive rewards
+    pub min_vault_age_days: u16, // Minimum vault age to receive rewards
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum RewardKind {
+    ReferralCommission,
+    Airdrop,
+    DailyBonus,
+}
+
+/// Return data of `get_vault_info`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VaultInfo {
+    pub owner: Pubkey,
+    pub balance: u64,            // Vault lamports
+    pub available: u64,          // Withdrawable lamports (balance - locked)
+    pub locked_amount: u64,
+    pub active_games: u32,
+    pub accum_wager: u64,
+    pub lifetime_wager: u64,
+    pub created_at: i64,
+    pub version: u8,
+}
+
+/// Return data of `get_house_info`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct HouseInfo {
+    pub balance: u64,            // House vault lamports
+    pub total_volume: u64,
+    pub category_volume: [u64; GAME_CATEGORIES], // Indexed by `GAME_CATEGORY_*`
+    pub multisig_authority: Pubkey,
+    pub admin_authority: Pubkey,
+    pub emergency_pause: bool,
+    pub maintenance_pause: bool,
+    pub maintenance_start_time: i64,
+    pub maintenance_duration_hours: u8,
+}
+
+/// Highest pity multiplier on gem rolls (100 = 1x)
+pub const MAX_PITY_MULTIPLIER: u16 = 1_000;
+
+/// Boost on the gems of the first settlement after a losing streak, at `[b"pity_config"]`
+#[account]
+pub struct PityConfig {
+    pub min_losses: u16,         // Consecutive losses that trigger the boost; 0 disables it
+    pub multiplier: u16,         // Applied to the awarded gems (100 = 1x)
+    pub bump: u8,
+}
+
+impl PityConfig {
+    /// Validate and store new parameters
+    pub fn set(&mut self, min_losses: u16, multiplier: u16) -> Result<()> {
+        require!((100..=MAX_PITY_MULTIPLIER).contains(&multiplier), VaultError::InvalidAmount);
+        self.min_losses = min_losses;
+        self.multiplier = multiplier;
+        Ok(())
+    }
+
+    /// Gems to credit after `loss_streak` straight losses, or `None` while the streak is too short
+    pub fn boost(&self, loss_streak: u16, gems: &[u8]) -> Option<Vec<u8>> {
+        if self.min_losses == 0 || loss_streak < self.min_losses {
+            return None;
+        }
+        Some(gems.iter().map(|&gem| (gem as u32 * self.multiplier as u32 / 100).min(u8::MAX as u32) as u8).collect())
+    }
+}
+
+#[account]
+pub struct LossBackPromo {
+    pub promo_id: u32,           // Incremented for every new window
+    pub start_time: i64,         // Accrual window start
+    pub end_time: i64,           // Accrual window end, balances claimable after
+    pub rate_bps: u16,           // Share of net losses accrued back
+    pub bump: u8,
+}
+
+#[account]
+pub struct LossBack {
+    pub vault: Pubkey,           // User vault the balance belongs to
+    pub promo_id: u32,           // Latest promo window accrued under
+    pub window_end: i64,         // Claimable from this timestamp
+    pub accrued: u64,            // Loss-back lamports of the `promo_id` window
+    pub carried: u64,            // Unclaimed balance of earlier, ended windows, claimable any time
+    pub bump: u8,
+}
+
+impl LossBack {
+    /// Accrue `amount` under `promo`, carrying over what an earlier window left unclaimed
+    pub fn accrue(&mut self, promo: &LossBackPromo, amount: u64) -> Result<()> {
+        if self.promo_id != promo.promo_id {
+            // Windows never overlap, so the one being replaced has ended and its balance is owed as is
+            self.carried = self.carried.checked_add(self.accrued).ok_or(VaultError::Overflow)?;
+            self.accrued = 0;
+            self.promo_id = promo.promo_id;
+            self.window_end = promo.end_time;
+        }
+        self.accrued = self.accrued.checked_add(amount).ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+
+    /// Balance payable at `now`: everything carried, plus the current window's once it has closed
+    pub fn claimable(&self, now: i64) -> u64 {
+        let current = if now >= self.window_end { self.accrued } else { 0 };
+        self.carried.saturating_add(current)
+    }
+}
+
+#[account]
+pub struct GlobalConfig {
+    pub event_seq: u64,          // Sequence number of the last emitted event
+    pub bump: u8,
+    pub version: u8,             // Layout version (GLOBAL_CONFIG_VERSION)
+    pub min_deposit: u64,        // Smallest deposit accepted (0 = any)
+    pub min_stake: u64,          // Smallest non-zero stake accepted (0 = any)
+}
+
+#[account]
+pub struct Announcement {
+    pub uri: String,             // Where the message text lives (max 200 bytes)
+    pub message_hash: [u8; 32],  // sha256 of the message text
+    pub severity: u8,            // 0 = info, 1 = warning, 2 = critical
+    pub expires_at: i64,         // Hidden after this timestamp (0 = none shown)
+    pub updated_at: i64,         // Last set/clear
+    pub bump: u8,
+}
+
+#[account]
+pub struct PayoutSchedule {
+    pub vault: Pubkey,           // Vault the tranches are paid into
+    pub bet_id_hash: [u8; 16],   // First 16 bytes of keccak(bet_id) of the streamed win
+    pub total: u64,              // Part of the payout held back at settlement
+    pub claimed: u64,            // Paid out of `total` so far
+    pub tranches: u16,
+    pub start_time: i64,         // Settlement time; tranche k is due `k * interval_secs` later
+    pub interval_secs: i64,
+    pub rent_payer: Pubkey,      // Settlement authority that funded the account
+    pub bump: u8,
+}
+
+impl PayoutSchedule {
+    /// Unpaid lamports of the tranches due at `now`; the last tranche carries the rounding
+    pub fn claimable(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start_time).max(0);
+        let due = (elapsed / self.interval_secs).min(self.tranches as i64);
+        let vested = self.total as u128 * due as u128 / self.tranches as u128;
+        (vested as u64).saturating_sub(self.claimed)
+    }
+}
+
+#[account]
+pub struct BetReceipt {
+    pub vault: Pubkey,           // User vault the bet was settled against
+    pub bet_id_hash: [u8; 16],   // First 16 bytes of keccak(bet_id)
+    pub game_id: u64,
+    pub stake: u64,
+    pub payout: u64,
+    pub settled_at: i64,         // Settlement timestamp
+    pub jackpot_tiers_paid: u8,  // Bit per jackpot tier this bet has won
+    pub disputed: bool,          // Challenged once; never again
+    pub bump: u8,
+}
+
+impl BetReceipt {
+    /// Mark the bet challenged, within `window_secs` of settlement and only once
+    pub fn file_dispute(&mut self, now: i64, window_secs: i64) -> Result<()> {
+        require!(window_secs > 0 && now - self.settled_at <= window_secs, VaultError::DisputeWindowClosed);
+        require!(!self.disputed, VaultError::AlreadyDisputed);
+        self.disputed = true;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct DisputeConfig {
+    pub window_secs: i64,        // How long after settlement a bet can be challenged (0 = off)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Dispute {
+    pub vault: Pubkey,           // Vault the challenged bet was settled against
+    pub owner: Pubkey,           // Filer, refunded the rent on resolution
+    pub bet_id_hash: [u8; 16],   // Challenged bet, as in its receipt
+    pub amount: u64,             // Lamports frozen from the house vault
+    pub filed_at: i64,
+    pub bump: u8,
+}
+
+/// Upper bound on the PvP rake (10%)
+pub const MAX_PVP_RAKE_BPS: u16 = 1_000;
+
+#[account]
+pub struct PvpConfig {
+    pub rake_bps: u16,           // House cut of a decided PvP pot, in basis points
+    pub bump: u8,
+}
+
+/// Most seats at one table round
+pub const MAX_TABLE_SEATS: usize = 10;
+
+/// Age after which anyone may cancel a table round, refunding every seat
+pub const TABLE_ROUND_TIMEOUT_SECS: i64 = 24 * 60 * 60;
+
+/// One seat at a table round and the stake it has escrowed so far
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct TableSeat {
+    pub vault: Pubkey,
+    pub committed: u64,
+}
+
+#[account]
+pub struct TableRound {
+    pub round_id: u64,
+    pub game_id: u64,
+    pub payer: Pubkey,           // Settlement authority that paid the rent, refunded on close
+    pub rake_bps: u16,           // PvP rake when the round opened
+    pub opened_at: i64,
+    pub seats: Vec<TableSeat>,   // At most MAX_TABLE_SEATS, in seating order
+    pub bump: u8,
+}
+
+impl TableRound {
+    /// Lamports escrowed by all seats
+    pub fn pot(&self) -> Result<u64> {
+        self.seats.iter()
+            .try_fold(0u64, |pot, seat| pot.checked_add(seat.committed))
+            .ok_or(VaultError::Overflow.into())
+    }
+
+    /// House cut of the pot
+    pub fn rake(&self) -> Result<u64> {
+        Ok((self.pot()? as u128 * self.rake_bps as u128 / 10_000) as u64)
+    }
+
+    fn seat_mut(&mut self, vault: &Pubkey) -> Result<&mut TableSeat> {
+        self.seats.iter_mut().find(|seat| seat.vault == *vault).ok_or(VaultError::NotSeated.into())
+    }
+}
+
+/// Most followers one `settle_mirrored_bets` may mirror a bet into
+pub const MAX_MIRROR_FOLLOWERS: usize = 10;
+
+#[account]
+pub struct CopyFollow {
+    pub vault: Pubkey,           // Follower's vault
+    pub leader: Pubkey,          // Vault whose bets are mirrored
+    pub max_stake: u64,          // Cap on each mirrored stake
+    pub bump: u8,
+}
+
+impl CopyFollow {
+    /// Follower's stake and payout for a lead bet of `stake` paying `payout`
+    pub fn mirror(&self, stake: u64, payout: u64) -> Result<(u64, u64)> {
+        let mirrored = stake.min(self.max_stake);
+        let scaled = (payout as u128 * mirrored as u128).checked_div(stake as u128).ok_or(VaultError::InvalidAmount)?;
+        Ok((mirrored, u64::try_from(scaled).map_err(|_| VaultError::Overflow)?))
+    }
+}
+
+/// Longest a pending bet's `expires_at` may fall after its event start
+pub const PENDING_BET_SETTLE_WINDOW_SECS: i64 = 7 * 24 * 60 * 60;
+
+#[account]
+pub struct PendingBet {
+    pub vault: Pubkey,
+    pub payer: Pubkey,           // Settlement authority that paid the rent, refunded on close
+    pub bet_id_hash: [u8; 16],
+    pub game_id: u64,
+    pub event_id: u64,           // Sports or esports event the bet is on
+    pub odds: u32,               // Locked decimal odds (100 = 1x)
+    pub stake: u64,              // Locked in the vault until settlement
+    pub placed_at: i64,
+    pub expiry: i64,             // Event start; settlement allowed from here
+    pub expires_at: i64,         // Settling from here refunds the stake instead of applying the result
+    pub insurance_premium: u64,  // Paid into the insurance fund at placement; 0 if uninsured
+    pub insurance_refund: u64,   // Reserved in the insurance fund, paid to the vault on a loss
+    pub bump: u8,
+}
+
+impl PendingBet {
+    /// Largest payout the locked odds allow
+    pub fn max_payout(&self) -> Result<u64> {
+        crash_payout(self.stake, self.odds)
+    }
+
+    /// What the house owes on top of the stake if the bet wins at its locked odds
+    pub fn exposure(&self) -> Result<u64> {
+        Ok(self.max_payout()? - self.stake)
+    }
+
+    /// Cash-outs close when the event starts
+    pub fn check_cashout_open(&self, now: i64) -> Result<()> {
+        require!(now < self.expiry, VaultError::EventStarted);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct InsuranceFund {
+    pub premium_bps: u16,        // Least premium accepted, as a share of the stake
+    pub refund_bps: u16,         // Share of the stake refunded on an insured loss; 0 disables insurance
+    pub reserved: u64,           // Refunds owed to open insured bets, kept on top of rent
+    pub total_premiums: u64,     // Lifetime premiums collected
+    pub total_refunds: u64,      // Lifetime refunds paid
+    pub bump: u8,
+}
+
+impl InsuranceFund {
+    /// Refund insuring `stake` for `premium` buys under the current terms
+    pub fn refund_for(&self, stake: u64, premium: u64) -> Result<u64> {
+        require!(self.refund_bps > 0, VaultError::InsuranceUnavailable);
+        let min_premium = stake as u128 * self.premium_bps as u128 / 10_000;
+        require!(premium as u128 >= min_premium, VaultError::InsurancePremiumTooLow);
+        Ok((stake as u128 * self.refund_bps as u128 / 10_000) as u64)
+    }
+}
+
+/// What a pending bet is placed on, passed to `place_pending_bet`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct PendingBetTerms {
+    pub game_id: u64,
+    pub event_id: u64,           // Sports or esports event the bet is on
+    pub odds: u32,               // Decimal odds (100 = 1x)
+    pub stake: u64,
+    pub expiry: i64,             // Event start
+    pub expires_at: i64,         // Results are ignored from here; within `PENDING_BET_SETTLE_WINDOW_SECS` of `expiry`
+    pub insurance_premium: u64,  // 0 leaves the bet uninsured
+}
+
+/// Side bets one settlement may carry
+pub const MAX_SIDE_BETS: usize = 4;
+
+/// Most tranches a streamed payout may be split into
+pub const MAX_PAYOUT_TRANCHES: u16 = 365;
+
+/// A side bet settled with its parent by `bet_and_settle_with_side_bets`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct SideBet {
+    pub game_id: u64,            // Side-bet game, bounded by its own game limits
+    pub stake: u64,
+    pub payout: u64,
+}
+
+/// One crash round in this many crashes instantly at 1x
+pub const CRASH_INSTANT_ODDS: u64 = 33;
+
+/// Time after betting closes by which a crash round must be revealed, or its bets are refunded
+pub const CRASH_REVEAL_TIMEOUT_SECS: i64 = 60 * 60;
+
+#[account]
+pub struct CrashRound {
+    pub round_id: u64,
+    pub game_id: u64,
+    pub authority: Pubkey,       // Opener, refunded the rent on close
+    pub commitment: [u8; 32],    // keccak(seed), fixed before betting
+    pub betting_closes_at: i64,
+    pub seed: [u8; 32],          // Zero until revealed
+    pub crash_point: u32,        // 100 = 1x, set on reveal
+    pub revealed_at: i64,        // 0 until revealed
+    pub open_bets: u32,          // Placed but not yet settled
+    pub bump: u8,
+}
+
+#[account]
+pub struct CrashBet {
+    pub round: Pubkey,
+    pub vault: Pubkey,
+    pub owner: Pubkey,           // Refunded the rent on settlement
+    pub stake: u64,              // Locked in the vault until settlement
+    pub cashout_at: u32,         // Automatic cashout multiplier (100 = 1x)
+    pub bump: u8,
+}
+
+impl CrashBet {
+    /// What the house owes on top of the stake if the round reaches the cashout
+    pub fn exposure(&self) -> Result<u64> {
+        Ok(crash_payout(self.stake, self.cashout_at)? - self.stake)
+    }
+}
+
+/// Open challenges the PvP queue lists at once
+pub const MAX_PVP_QUEUE_LEN: usize = 64;
+
+#[account]
+pub struct PvpQueue {
+    pub challenges: Vec<Pubkey>, // Open `PvpBet`s in posting order (max MAX_PVP_QUEUE_LEN)
+    pub bump: u8,
+}
+
+impl PvpQueue {
+    fn remove(&mut self, bet: &Pubkey) {
+        self.challenges.retain(|challenge| challenge != bet);
+    }
+}
+
+#[account]
+pub struct PvpBet {
+    pub creator: Pubkey,         // Creator's wallet, refunded the rent on close
+    pub creator_vault: Pubkey,
+    pub opponent_vault: Pubkey,  // The only vault that may accept (default = open challenge)
+    pub bet_id_hash: [u8; 16],
+    pub game_id: u64,
+    pub stake: u64,              // Escrowed by each side
+    pub rake_bps: u16,           // Rake fixed at creation
+    pub created_at: i64,
+    pub accepted_at: i64,        // 0 until the opponent matches the stake
+    pub expires_at: i64,         // Unaccepted bets refundable from then (0 = never)
+    pub bump: u8,
+}
+
+impl PvpBet {
+    /// Whether any vault may accept, i.e. the bet was posted without an opponent and is still unmatched
+    pub fn is_open(&self) -> bool {
+        self.opponent_vault == Pubkey::default()
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// Lamports paid to the creator's vault, the opponent's vault and the house for `winner`
+    pub fn payouts(&self, winner: Option<Pubkey>) -> Result<(u64, u64, u64)> {
+        let Some(winner) = winner else {
+            return Ok((self.stake, self.stake, 0));
+        };
+        let pot = self.stake.checked_mul(2).ok_or(VaultError::Overflow)?;
+        let rake = (pot as u128 * self.rake_bps as u128 / 10_000) as u64;
+        if winner == self.creator_vault {
+            Ok((pot - rake, 0, rake))
+        } else if winner == self.opponent_vault {
+            Ok((0, pot - rake, rake))
+        } else {
+            err!(VaultError::InvalidPvpWinner)
+        }
+    }
+}
+
+#[account]
+pub struct WithdrawIntent {
+    pub vault: Pubkey,           // Vault the relayed withdraw drew from
+    pub nonce: u64,              // Owner-chosen nonce, usable once per vault
+    pub bump: u8,
+}
+
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+pub struct GlobalStats {
+    pub total_users: u64,        // Vaults ever initialized
+    pub total_bets: u64,         // Settlements processed
+    pub total_volume: u64,       // Total stake settled
+    pub biggest_win: u64,        // Largest single net win (payout - stake)
+    pub biggest_win_vault: Pubkey, // Vault that won it
+    pub bump: u8,
+    pub settle_calls: u64,       // bet_and_settle invocations, side-bet variant included
+    pub batch_calls: u64,        // batch_settle invocations
+    pub deposit_calls: u64,      // deposit invocations
+    pub withdraw_calls: u64,     // withdraw and withdraw_with_intent invocations
+    pub version: u8,             // Layout version (GLOBAL_STATS_VERSION)
+}
+
+/// Instruction families `GlobalStats` counts invocations of
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Invocation {
+    Settle,
+    Batch,
+    Deposit,
+    Withdraw,
+}
+
+impl GlobalStats {
+    /// Count one settlement and track the biggest net win
+    pub fn record_bet(&mut self, vault: Pubkey, stake: u64, payout: u64) -> Result<()> {
+        self.total_bets = self.total_bets.checked_add(1).ok_or(VaultError::Overflow)?;
+        self.total_volume = self.total_volume.checked_add(stake).ok_or(VaultError::Overflow)?;
+        let net_win = payout.saturating_sub(stake);
+        if net_win > self.biggest_win {
+            self.biggest_win = net_win;
+            self.biggest_win_vault = vault;
+        }
+        Ok(())
+    }
+
+    /// Count one invocation of an instruction in `kind`
+    pub fn record_call(&mut self, kind: Invocation) -> Result<()> {
+        let bump = |count: u64| count.checked_add(1).ok_or(VaultError::Overflow);
+        match kind {
+            Invocation::Settle => self.settle_calls = bump(self.settle_calls)?,
+            Invocation::Batch => self.batch_calls = bump(self.batch_calls)?,
+            Invocation::Deposit => self.deposit_calls = bump(self.deposit_calls)?,
+            Invocation::Withdraw => self.withdraw_calls = bump(self.withdraw_calls)?,
+        }
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct GameLimit {
+    pub game_id: u64,
+    pub max_multiplier: u32,     // Highest payout / stake (100 = 1x)
+}
+
+#[account]
+pub struct GameLimits {
+    pub default_max_multiplier: u32, // Bound for games without an entry (100 = 1x)
+    pub max_unstaked_payout: u64,    // Bound for payouts whose stake was deducted earlier
+    pub max_bets_per_second: u32,    // Settlements per vault per second (0 = unlimited)
+    pub max_transaction_payout: u64, // Summed payouts per transaction (0 = unlimited)
+    pub max_daily_net_win: u64,      // Net winnings per vault per 24h (0 = unlimited)
+    pub payout_stream_threshold: u64, // Largest payout settled at once; the rest streams (0 = no streaming)
+    pub payout_stream_tranches: u16,  // Equal tranches the streamed part is split into
+    pub payout_stream_interval_secs: i64, // Seconds between tranches
+    pub games: Vec<GameLimit>,       // Per-game bounds (max MAX_GAME_LIMITS)
+    pub bump: u8,
+}
+
+impl GameLimits {
+    /// Reject a settlement paying more than the game's multiplier allows
+    pub fn check_payout(&self, game_id: u64, stake: u64, payout: u64) -> Result<()> {
+        if stake == 0 {
+            require!(payout <= self.max_unstaked_payout, VaultError::PayoutExceedsLimit);
+            return Ok(());
+        }
+        let max_multiplier = self.games.iter()
+            .find(|limit| limit.game_id == game_id)
+            .map_or(self.default_max_multiplier, |limit| limit.max_multiplier);
+        require!(
+            payout as u128 * 100 <= stake as u128 * max_multiplier as u128,
+            VaultError::PayoutExceedsLimit
+        );
+        Ok(())
+    }
+
+    /// Split `payout` into what settles now and what streams through a `PayoutSchedule`
+    pub fn split_payout(&self, payout: u64) -> (u64, u64) {
+        let threshold = self.payout_stream_threshold;
+        if threshold == 0 || payout <= threshold {
+            (payout, 0)
+        } else {
+            (threshold, payout - threshold)
+        }
+    }
+
+    /// Reject a payout that would have to stream, for settlement paths that cannot open a schedule
+    pub fn check_unstreamed(&self, payout: u64) -> Result<()> {
+        require!(self.split_payout(payout).1 == 0, VaultError::PayoutMustStream);
+        Ok(())
+    }
+
+    /// Reject the transaction if its settlements together pay more than `max_transaction_payout`
+    ///
+    /// Every settlement instruction runs this over the whole transaction, so the
+    /// cap holds no matter how bets are split across `bet_and_settle` and `batch_settle`.
+    /// A settlement reached through CPI is missing from the instructions sysvar,
+    /// so its own `payout` is added; each CPI settlement is checked on its own.
+    pub fn check_transaction_payout(&self, instructions: &AccountInfo, payout: u64) -> Result<()> {
+        if self.max_transaction_payout == 0 {
+            return Ok(());
+        }
+        let mut total = transaction_payout_total(instructions)?;
+        if get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT {
+            total = total.checked_add(payout).ok_or(VaultError::Overflow)?;
+        }
+        require!(total <= self.max_transaction_payout, VaultError::TransactionPayoutExceeded);
+        Ok(())
+    }
+}
+
+/// Fail if the transaction also withdraws from any of `vaults`
+///
+/// Stops a compromised key from settling a win and siphoning it with a
+/// co-signed `withdraw` before anyone can react.
+fn reject_bundled_withdraw(instructions: &AccountInfo, vaults: &[Pubkey]) -> Result<()> {
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        index += 1;
+        let withdraws = ix.data.starts_with(&instruction::Withdraw::DISCRIMINATOR)
+            || ix.data.starts_with(&instruction::WithdrawWithIntent::DISCRIMINATOR);
+        if ix.program_id != crate::ID || !withdraws {
+            continue;
+        }
+        // `vault` is the first account of both withdraw contexts
+        if let Some(vault) = ix.accounts.first() {
+            require!(!vaults.contains(&vault.pubkey), VaultError::BundledWithdraw);
+        }
+    }
+    Ok(())
+}
+
+/// Summed `payout` of every top-level settlement instruction of this program in the transaction
+/// Bytes an owner signs to authorize a `withdraw_with_intent`
+///
+/// Binding the program and vault keeps the signature from being replayed
+/// against another deployment or vault.
+pub fn withdraw_intent_message(vault: &Pubkey, amount: u64, fee: u64, nonce: u64, expires_at: i64) -> Vec<u8> {
+    let mut message = b"sols.bet withdraw intent".to_vec();
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(vault.as_ref());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&fee.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message.extend_from_slice(&expires_at.to_le_bytes());
+    message
+}
+
+/// Require the previous instruction to be an ed25519 check of `signer`'s signature over `message`
+///
+/// The precompile fails the transaction on a bad signature, so only which key
+/// and message it checked need confirming. Offsets must point into its own data.
+fn require_ed25519_signature(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, VaultError::InvalidIntentSignature);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions)?;
+    require!(ix.program_id == ed25519_program::ID, VaultError::InvalidIntentSignature);
+
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, VaultError::InvalidIntentSignature);
+    let offset = |i: usize| u16::from_le_bytes([data[2 + 2 * i], data[3 + 2 * i]]);
+    let (signature_ix, key_offset, key_ix) = (offset(1), offset(2) as usize, offset(3));
+    let (message_offset, message_len, message_ix) = (offset(4) as usize, offset(5) as usize, offset(6));
+    require!(
+        signature_ix == u16::MAX && key_ix == u16::MAX && message_ix == u16::MAX,
+        VaultError::InvalidIntentSignature
+    );
+    require!(data.get(key_offset..key_offset + 32) == Some(signer.as_ref()), VaultError::InvalidIntentSignature);
+    require!(
+        data.get(message_offset..message_offset + message_len) == Some(message),
+        VaultError::InvalidIntentSignature
+    );
+    Ok(())
+}
+
+fn transaction_payout_total(instructions: &AccountInfo) -> Result<u64> {
+    let mut total: u64 = 0;
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        index += 1;
+        if ix.program_id != crate::ID || ix.data.len() < 8 {
+            continue;
+        }
+        let (tag, mut args) = ix.data.split_at(8);
+        let payout = if tag == instruction::BetAndSettle::DISCRIMINATOR {
+            instruction::BetAndSettle::deserialize(&mut args)?.payout
+        } else if tag == instruction::BetAndSettleWithSideBets::DISCRIMINATOR {
+            let settlement = instruction::BetAndSettleWithSideBets::deserialize(&mut args)?;
+            settlement.side_bets.iter()
+                .try_fold(settlement.payout, |sum, side_bet| sum.checked_add(side_bet.payout))
+                .ok_or(VaultError::Overflow)?
+        } else if tag == instruction::SettleFreeSpin::DISCRIMINATOR {
+            instruction::SettleFreeSpin::deserialize(&mut args)?.payout
+        } else if tag == instruction::SettleMirroredBets::DISCRIMINATOR {
+            let mirror = instruction::SettleMirroredBets::deserialize(&mut args)?;
+            mirror.payout.checked_mul(mirror.followers.len() as u64).ok_or(VaultError::Overflow)?
+        } else if tag == instruction::BatchSettle::DISCRIMINATOR {
+            instruction::BatchSettle::deserialize(&mut args)?.payouts.iter()
+                .try_fold(0u64, |sum, payout| sum.checked_add(*payout))
+                .ok_or(VaultError::Overflow)?
+        } else {
+            continue;
+        };
+        total = total.checked_add(payout).ok_or(VaultError::Overflow)?;
+    }
+    Ok(total)
+}
+
+#[account]
+pub struct StatsSnapshots {
+    pub interval_secs: u32,      // Minimum spacing between snapshots
+    pub last_recorded: i64,      // Timestamp of the newest snapshot
+    pub next: u16,               // Slot the next snapshot overwrites once full
+    pub entries: Vec<StatsSnapshot>, // Ring buffer (max STATS_SNAPSHOT_SLOTS)
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct StatsSnapshot {
+    pub timestamp: i64,
+    pub total_users: u64,
+    pub total_bets: u64,
+    pub total_volume: u64,
+    pub house_balance: u64,      // House vault lamports
+}
+
+impl StatsSnapshots {
+    /// Append `snapshot`, overwriting the oldest once the buffer is full
+    pub fn record(&mut self, snapshot: StatsSnapshot) {
+        if self.entries.len() < STATS_SNAPSHOT_SLOTS {
+            self.entries.push(snapshot);
+        } else {
+            self.entries[self.next as usize] = snapshot;
+        }
+        self.next = ((self.next as usize + 1) % STATS_SNAPSHOT_SLOTS) as u16;
+        self.last_recorded = snapshot.timestamp;
+    }
+}
+
+#[account]
+pub struct GamePrograms {
+    pub programs: Vec<GameProgram>,  // Programs allowed to settle via CPI (max MAX_GAME_PROGRAMS)
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct GameProgram {
+    pub program_id: Pubkey,      // Registered game program
+    pub authority: Pubkey,       // Its GAME_AUTHORITY_SEED PDA, the settlement signer
+}
+
+#[account]
+pub struct Blacklist {
+    pub addresses: Vec<Pubkey>,  // Wallets barred from vaults, deposits and bets (max MAX_BLACKLIST_LEN)
+    pub bump: u8,
+}
+
+impl Blacklist {
+    pub fn contains(&self, address: &Pubkey) -> bool {
+        self.addresses.contains(address)
+    }
+}
+
+#[account]
+pub struct VaultRegistry {
+    pub count: u64,              // Vaults registered so far
+    pub pages: u32,              // Pages opened so far
+    pub bump: u8,
+}
+
+impl VaultRegistry {
+    /// Page the next registered vault lands on
+    pub fn current_page(&self) -> u32 {
+        (self.count / VAULT_REGISTRY_PAGE_LEN as u64) as u32
+    }
+}
+
+#[account]
+pub struct VaultRegistryPage {
+    pub page: u32,               // Position of this page, from 0
+    pub owners: Vec<Pubkey>,     // Owners of registered vaults in registration order (max VAULT_REGISTRY_PAGE_LEN)
+    pub bump: u8,
+}
 
 #[account]
-pub struct PauseConfig {
-    pub multisig_authority: Pubkey,  // Multisig authority
-    pub admin_authority: Pubkey,     // Admin authority  
-    pub maintenance_pause: bool,     // Maintenance pause switch
-    pub maintenance_start_time: i64, // When maintenance started
-    pub maintenance_duration_hours: u8, // How long maintenance lasts
-    pub emergency_pause: bool,       // Emergency stop
+pub struct VaultRegistryEntry {
+    pub vault: Pubkey,           // Registered vault
+    pub index: u64,              // Position across all pages
+    pub bump: u8,
+}
+
+#[account]
+pub struct TrophyConfig {
+    pub merkle_tree: Pubkey,     // Bubblegum tree trophies are minted into
+    pub min_multiplier: u32,     // Smallest payout/stake that earns a trophy (100 = 1x, 0 = off)
+    pub base_uri: String,        // Metadata URI the bet query string is appended to (max MAX_TROPHY_URI_LEN)
+    pub bump: u8,
+}
+
+impl TrophyConfig {
+    fn set(&mut self, merkle_tree: Pubkey, min_multiplier: u32, base_uri: String) -> Result<()> {
+        require!(min_multiplier == 0 || min_multiplier >= 100, VaultError::InvalidAmount);
+        require!(base_uri.len() <= MAX_TROPHY_URI_LEN, VaultError::TrophyUriTooLong);
+        self.merkle_tree = merkle_tree;
+        self.min_multiplier = min_multiplier;
+        self.base_uri = base_uri;
+        Ok(())
+    }
+
+    /// Whether a win of `payout` on `stake` earns a trophy
+    pub fn qualifies(&self, stake: u64, payout: u64) -> bool {
+        self.min_multiplier > 0 && stake > 0 && payout as u128 * 100 >= stake as u128 * self.min_multiplier as u128
+    }
+}
+
+#[account]
+pub struct RgConfig {
+    pub min_cool_off_secs: i64,  // Shortest cool-off a player may choose
+    pub min_limit_raise_interval_secs: i64, // Shortest gap between raises of a self-set limit
+    pub bump: u8,
+}
+
+impl RgConfig {
+    fn set(&mut self, min_cool_off_secs: i64, min_limit_raise_interval_secs: i64) -> Result<()> {
+        require!(min_cool_off_secs >= 0 && min_limit_raise_interval_secs >= 0, VaultError::InvalidAmount);
+        self.min_cool_off_secs = min_cool_off_secs;
+        self.min_limit_raise_interval_secs = min_limit_raise_interval_secs;
+        Ok(())
+    }
+
+    /// Reject a self-chosen cool-off shorter than the platform floor
+    pub fn check_cool_off(&self, duration_secs: i64) -> Result<()> {
+        require!(duration_secs >= self.min_cool_off_secs, VaultError::CoolOffTooShort);
+        Ok(())
+    }
+
+    /// Reject raising a self-set limit sooner than the floor allows after the previous raise
+    ///
+    /// Lowering a limit is always allowed and should not go through this check.
+    pub fn check_limit_raise(&self, last_raised_at: i64, now: i64) -> Result<()> {
+        require!(
+            now.saturating_sub(last_raised_at) >= self.min_limit_raise_interval_secs,
+            VaultError::LimitRaiseTooSoon
+        );
+        Ok(())
+    }
+}
+
+#[account]
+pub struct BridgeConfig {
+    pub core_bridge: Pubkey,     // Wormhole core bridge that owns posted VAAs
+    pub emitters: Vec<BridgeEmitter>, // Deposit contracts trusted on other chains (max MAX_BRIDGE_EMITTERS)
+    pub total_credited: u64,     // Lifetime lamports credited from bridged deposits
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct BridgeEmitter {
+    pub chain: u16,              // Wormhole chain id
+    pub address: [u8; 32],       // Emitter address, left-padded for 20-byte EVM addresses
+}
+
+#[account]
+pub struct BridgeClaim {
+    pub vault: Pubkey,           // Vault the deposit was credited to
+    pub amount: u64,             // Lamports credited
+    pub bump: u8,
+}
+
+#[account]
+pub struct ComplianceConfig {
+    pub kyc_withdraw_threshold: u64, // Withdrawals above this need a KYC attestation (0 = off)
+    pub large_transfer_threshold: u64, // Transfers above this emit `LargeTransfer` (0 = off)
+    pub region_rules: Vec<RegionRule>, // Per-region stake caps (max MAX_REGION_RULES)
+    pub terms_hash: [u8; 32],    // Hash of the current terms of service (zero = none published)
+    pub terms_required: bool,    // Whether staked bets need the current terms accepted
+    pub screening_program: Pubkey, // Program that may veto deposits (default = off)
+    pub screening_threshold: u64, // Deposits above this are screened
+    pub attestors: Vec<Pubkey>,  // Admin-approved KYC attestors (max MAX_ATTESTORS)
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegionRule {
+    pub region: u8,
+    pub game_id: Option<u64>,    // Game the rule covers (None = every game)
+    pub max_stake: u64,          // Largest stake allowed (0 = game barred)
+}
+
+impl ComplianceConfig {
+    /// Reject a stake the region rules of the vault's jurisdiction forbid
+    ///
+    /// Unstaked settlements always pass, so bets placed before a rule change still pay out.
+    pub fn check_region(&self, region: u8, game_id: u64, stake: u64) -> Result<()> {
+        if stake == 0 {
+            return Ok(());
+        }
+        let allowed = self.region_rules.iter()
+            .filter(|rule| rule.region == region && rule.game_id.is_none_or(|id| id == game_id))
+            .all(|rule| stake <= rule.max_stake);
+        require!(allowed, VaultError::RegionRestricted);
+        Ok(())
+    }
+
+    /// Reject a staked bet from a vault that has not accepted the current terms, when required
+    pub fn check_terms(&self, vault: &UserVault, stake: u64) -> Result<()> {
+        if stake == 0 || !self.terms_required {
+            return Ok(());
+        }
+        let accepted = vault.terms_hash;
+        require!(accepted == self.terms_hash, VaultError::TermsNotAccepted);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct WithdrawGuard {
+    pub vault: Pubkey,           // Vault whose withdrawals need the device key
+    pub device: Pubkey,          // Secondary key that must co-sign withdrawals
+    pub removal_requested_at: i64, // When the owner asked to drop the guard alone (0 = not requested)
+    pub bump: u8,
+}
+
+#[account]
+pub struct WithdrawalQueue {
+    pub head: u64,               // Ticket of the next request to honor
+    pub tail: u64,               // Ticket the next request will take
+    pub bump: u8,
+}
+
+#[account]
+pub struct WithdrawalRequest {
+    pub vault: Pubkey,           // Vault to withdraw from; paid to its owner
+    pub ticket: u64,             // Position in `WithdrawalQueue`, honored in ascending order
+    pub amount: u64,             // Lamports requested
+    pub requested_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct KycAttestation {
+    pub vault: Pubkey,           // Vault whose owner passed KYC
+    pub attestor: Pubkey,        // Issuer; must still be approved for the attestation to count
+    pub expires_at: i64,         // Invalid from this timestamp on
+    pub bump: u8,
+}
+
+#[account]
+pub struct HealthMonitor {
+    pub operator: Pubkey,        // Backend key expected to send heartbeats
+    pub interval_secs: i64,      // Longest gap between heartbeats before the program counts as degraded
+    pub last_heartbeat: i64,
+    pub house_balance: u64,      // House lamports above rent at the last check
+    pub open_exposure: u64,      // `HouseVault::open_exposure` at the last check
+    pub degraded: bool,          // Set by a missed heartbeat or failed solvency check, cleared by a heartbeat
+    pub bump: u8,
+}
+
+impl HealthMonitor {
+    /// Whether more than `interval_secs` have passed since the last heartbeat
+    pub fn heartbeat_overdue(&self, now: i64) -> bool {
+        now.saturating_sub(self.last_heartbeat) > self.interval_secs
+    }
+
+    /// Degraded as recorded, or because the heartbeat has gone overdue since; what a frontend should show
+    pub fn is_degraded(&self, now: i64) -> bool {
+        self.degraded || self.heartbeat_overdue(now)
+    }
+
+    fn record_check(&mut self, house_balance: u64, open_exposure: u64) {
+        self.house_balance = house_balance;
+        self.open_exposure = open_exposure;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Debug)]
+pub struct RebateTier {
+    pub min_staked: u64,         // Staked platform tokens (base units) the tier needs
+    pub rebate_bps: u16,         // Share of each settlement's wager the house pays back
+}
+
+/// Platform-token staking and its settlement rebate tiers, at `[b"staking_config"]`
+#[account]
+pub struct StakingConfig {
+    pub mint: Pubkey,            // Platform token
+    pub escrow: Pubkey,          // Token account owned by this PDA that holds every stake
+    pub cooldown_secs: i64,      // Wait between requesting an unstake and withdrawing it
+    pub tiers: Vec<RebateTier>,  // Ascending (max MAX_REBATE_TIERS); none means no rebates
+    pub bump: u8,
+}
+
+impl StakingConfig {
+    /// Validate and store new tiers and cooldown
+    pub fn set(&mut self, cooldown_secs: i64, tiers: Vec<RebateTier>) -> Result<()> {
+        require!(cooldown_secs >= 0, VaultError::InvalidAmount);
+        require!(tiers.len() <= MAX_REBATE_TIERS, VaultError::InvalidRebateTiers);
+        require!(
+            tiers.iter().all(|tier| tier.min_staked > 0 && tier.rebate_bps <= MAX_REBATE_BPS),
+            VaultError::InvalidRebateTiers
+        );
+        let ascending = |pair: &[RebateTier]| {
+            pair[0].min_staked < pair[1].min_staked && pair[0].rebate_bps < pair[1].rebate_bps
+        };
+        require!(tiers.windows(2).all(ascending), VaultError::InvalidRebateTiers);
+        self.cooldown_secs = cooldown_secs;
+        self.tiers = tiers;
+        Ok(())
+    }
+
+    /// Rebate of the highest tier `staked` reaches, in basis points; 0 below the first
+    pub fn rebate_bps(&self, staked: u64) -> u16 {
+        self.tiers.iter().rev().find(|tier| staked >= tier.min_staked).map_or(0, |tier| tier.rebate_bps)
+    }
+
+    /// Lamports paid back on `wagered` at the tier `staked` reaches
+    pub fn rebate(&self, staked: u64, wagered: u64) -> u64 {
+        (wagered as u128 * self.rebate_bps(staked) as u128 / 10_000) as u64
+    }
+}
+
+#[account]
+pub struct TokenStake {
+    pub vault: Pubkey,           // User vault the stake belongs to
+    pub staked: u64,             // Tokens counting toward the rebate tier
+    pub unstaking: u64,          // Tokens on their way out; they no longer count
+    pub unlock_at: i64,          // `unstaking` is withdrawable from this timestamp
+    pub bump: u8,
+}
+
+#[account]
+pub struct UpgradeGovernance {
+    pub min_delay_slots: u64,        // Shortest notice an announced upgrade must give
+    pub buffer: Pubkey,              // Buffer of the announced upgrade (default = none pending)
+    pub buffer_hash: [u8; 32],       // sha256 of the buffer's program bytes
+    pub earliest_upgrade_slot: u64,  // First slot the announced upgrade may be deployed in
+    pub announced_slot: u64,         // Slot the pending announcement was made in
+    pub last_upgrade_slot: u64,      // Deployment slot of the program data last recorded
     pub bump: u8,
 }
 
-// Context structs
+/// The fields of a core bridge `PostedVAA` account this program reads
+///
+/// Layout: `b"vaa"`, version, consistency level, VAA time, signature set,
+/// submission time, nonce, sequence, emitter chain, emitter address, payload.
+/// Messages posted from Solana use the same account type with a `msg` or `msu`
+/// magic and carry no guardian signatures, so the magic is checked first.
+#[derive(Debug, PartialEq)]
+pub struct PostedVaa {
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+impl PostedVaa {
+    const SEQUENCE_OFFSET: usize = 3 + 1 + 1 + 4 + 32 + 4 + 4;
+
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= Self::SEQUENCE_OFFSET + 8 + 2 + 32 + 4, VaultError::InvalidVaa);
+        require!(&data[..3] == b"vaa", VaultError::InvalidVaa);
+        let mut body = &data[Self::SEQUENCE_OFFSET..];
+        let sequence = u64::deserialize(&mut body)?;
+        let emitter_chain = u16::deserialize(&mut body)?;
+        let emitter_address = <[u8; 32]>::deserialize(&mut body)?;
+        let payload = Vec::<u8>::deserialize(&mut body).map_err(|_| VaultError::InvalidVaa)?;
+        Ok(Self { sequence, emitter_chain, emitter_address, payload })
+    }
+}
+
+/// Recipient wallet and lamports of a bridged deposit payload
+///
+/// Layout: payload id ([`BRIDGE_DEPOSIT_PAYLOAD_ID`]), recipient, u64 big-endian amount.
+pub fn parse_bridge_deposit(payload: &[u8]) -> Result<(Pubkey, u64)> {
+    require!(payload.len() == 1 + 32 + 8 && payload[0] == BRIDGE_DEPOSIT_PAYLOAD_ID, VaultError::InvalidVaa);
+    let recipient = Pubkey::try_from(&payload[1..33]).map_err(|_| VaultError::InvalidVaa)?;
+    let amount = u64::from_be_bytes(payload[33..41].try_into().map_err(|_| VaultError::InvalidVaa)?);
+    Ok((recipient, amount))
+}
+
+/// Bubblegum's `MetadataArgs`, with its enums written as their Borsh variant index
+#[derive(AnchorSerialize)]
+struct TrophyMetadata {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    primary_sale_happened: bool,
+    is_mutable: bool,
+    edition_nonce: Option<u8>,
+    token_standard: Option<u8>,             // 0 = NonFungible
+    collection: Option<(bool, Pubkey)>,     // (verified, key)
+    uses: Option<(u8, u64, u64)>,           // (use method, remaining, total)
+    token_program_version: u8,              // 0 = Original
+    creators: Vec<(Pubkey, bool, u8)>,      // (address, verified, share)
+}
+
+impl GlobalConfig {
+    /// Advance and return the sequence number to stamp on the next event
+    pub fn next_seq(&mut self) -> Result<u64> {
+        self.event_seq = self.event_seq.checked_add(1).ok_or(VaultError::Overflow)?;
+        Ok(self.event_seq)
+    }
+
+    /// Refuse dust deposits below `min_deposit`
+    pub fn check_deposit(&self, amount: u64) -> Result<()> {
+        require!(amount >= self.min_deposit, VaultError::DepositBelowMinimum);
+        Ok(())
+    }
+
+    /// Refuse dust stakes below `min_stake`; stake-free settlements (free bets) pass
+    pub fn check_stake(&self, stake: u64) -> Result<()> {
+        require!(stake == 0 || stake >= self.min_stake, VaultError::StakeBelowMinimum);
+        Ok(())
+    }
+}
+
+// Context structs
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(
+        init,
+        seeds=[VAULT_SEED, user.key().as_ref()],
+        bump,
+        payer=user,
+        space=USER_VAULT_SPACE
+    )]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump, constraint = !blacklist.contains(&user.key()) @ VaultError::Blacklisted)]
+    pub blacklist: Account<'info, Blacklist>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    /// CHECK: may still use a legacy layout; checked and upgraded by `migrate_account`
+    #[account(mut, seeds=[VAULT_SEED, owner.key().as_ref()], bump, owner = crate::ID)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeHouse<'info> {
+    #[account(init, seeds=[HOUSE_VAULT_SEED], bump, payer=admin, space=HOUSE_VAULT_SPACE)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePauseConfig<'info> {
+    #[account(init, seeds=[PAUSE_CONFIG_SEED], bump, payer=authority, space=PAUSE_CONFIG_SPACE)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalConfig<'info> {
+    #[account(init, seeds=[GLOBAL_CONFIG_SEED], bump, payer=authority, space=GLOBAL_CONFIG_SPACE)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGlobalConfig<'info> {
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalStats<'info> {
+    #[account(init, seeds=[GLOBAL_STATS_SEED], bump, payer=authority, space=GLOBAL_STATS_SPACE)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAnnouncement<'info> {
+    #[account(init, seeds=[ANNOUNCEMENT_SEED], bump, payer=authority, space=ANNOUNCEMENT_SPACE)]
+    pub announcement: Account<'info, Announcement>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAnnouncement<'info> {
+    #[account(mut, seeds=[ANNOUNCEMENT_SEED], bump = announcement.bump)]
+    pub announcement: Account<'info, Announcement>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGameLimits<'info> {
+    #[account(init, seeds=[GAME_LIMITS_SEED], bump, payer=authority, space=GAME_LIMITS_SPACE)]
+    pub game_limits: Account<'info, GameLimits>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGameLimits<'info> {
+    #[account(mut, seeds=[GAME_LIMITS_SEED], bump = game_limits.bump)]
+    pub game_limits: Account<'info, GameLimits>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct InitializeGameConfig<'info> {
+    #[account(
+        init,
+        seeds=[GAME_CONFIG_SEED, game_id.to_le_bytes().as_ref()],
+        bump,
+        payer=authority,
+        space=GAME_CONFIG_SPACE
+    )]
+    pub game_config: Account<'info, GameConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct InitializeJackpot<'info> {
+    #[account(
+        init,
+        seeds=[JACKPOT_SEED, game_id.to_le_bytes().as_ref()],
+        bump,
+        payer=authority,
+        space=JACKPOT_SPACE
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetJackpotTier<'info> {
+    #[account(mut, seeds=[JACKPOT_SEED, jackpot.game_id.to_le_bytes().as_ref()], bump = jackpot.bump)]
+    pub jackpot: Account<'info, Jackpot>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(game_id: u64, tier: u8, bet_id: String)]
+pub struct AwardJackpot<'info> {
+    #[account(mut, seeds=[JACKPOT_SEED, game_id.to_le_bytes().as_ref()], bump = jackpot.bump, has_one = vrf_authority)]
+    pub jackpot: Account<'info, Jackpot>,
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    pub authority: Signer<'info>,
+    pub vrf_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds=[RECEIPT_SEED, vault.key().as_ref(), bet_id_hash(&bet_id).as_ref()],
+        bump = bet_receipt.bump,
+        has_one = vault,
+        constraint = bet_receipt.game_id == game_id @ VaultError::ReceiptGameMismatch
+    )]
+    pub bet_receipt: Account<'info, BetReceipt>,
+    /// CHECK: the ORAO randomness request for `Jackpot::vrf_seed`, verified by `orao_randomness`
+    pub randomness: UncheckedAccount<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump, constraint = !blacklist.contains(&vault.load()?.owner) @ VaultError::Blacklisted)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so flagging cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+}
+
+#[derive(Accounts)]
+pub struct SetGameConfig<'info> {
+    #[account(mut, seeds=[GAME_CONFIG_SEED, game_config.game_id.to_le_bytes().as_ref()], bump = game_config.bump)]
+    pub game_config: Account<'info, GameConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBlacklist<'info> {
+    #[account(init, seeds=[BLACKLIST_SEED], bump, payer=authority, space=BLACKLIST_SPACE)]
+    pub blacklist: Account<'info, Blacklist>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBlacklist<'info> {
+    #[account(mut, seeds=[BLACKLIST_SEED], bump = blacklist.bump)]
+    pub blacklist: Account<'info, Blacklist>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStatsSnapshots<'info> {
+    #[account(
+        init,
+        seeds=[STATS_SNAPSHOTS_SEED],
+        bump,
+        payer=authority,
+        space=STATS_SNAPSHOTS_SPACE
+    )]
+    pub stats_snapshots: Account<'info, StatsSnapshots>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AutomationTick<'info> {
+    #[account(mut, seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[STATS_SNAPSHOTS_SEED], bump = stats_snapshots.bump)]
+    pub stats_snapshots: Account<'info, StatsSnapshots>,
+    #[account(seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    #[account(seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGamePrograms<'info> {
+    #[account(init, seeds=[GAME_PROGRAMS_SEED], bump, payer=authority, space=GAME_PROGRAMS_SPACE)]
+    pub game_programs: Account<'info, GamePrograms>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGamePrograms<'info> {
+    #[account(mut, seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Account<'info, GamePrograms>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePauseConfig<'info> {
+    /// CHECK: may still use the pre-bitflag layout; checked and upgraded by `migrate_account`
+    #[account(mut, seeds=[PAUSE_CONFIG_SEED], bump, owner = crate::ID)]
+    pub pause_config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateHouseVault<'info> {
+    /// CHECK: may still use an older layout; checked and upgraded by `migrate_account`
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump, owner = crate::ID)]
+    pub house_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGlobalConfig<'info> {
+    /// CHECK: may still use an older layout; checked and upgraded by `migrate_account`
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump, owner = crate::ID)]
+    pub global_config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGlobalStats<'info> {
+    /// CHECK: may still use an older layout; checked and upgraded by `migrate_account`
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump, owner = crate::ID)]
+    pub global_stats: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePauseConfig<'info> {
+    #[account(mut, seeds=[PAUSE_CONFIG_SEED], bump, close=authority)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    pub system_program: Program<'info, System>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump, constraint = !blacklist.contains(&owner.key()) @ VaultError::Blacklisted)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so flagging cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    /// CHECK: compared against `compliance_config.screening_program`; needed only for screened deposits
+    pub screening_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: passed through to the screening program, which validates it
+    pub screening_state: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so gating cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    // Needed only for withdrawals above the KYC threshold
+    #[account(seeds=[KYC_SEED, vault.key().as_ref()], bump = kyc_attestation.bump, has_one = vault)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    /// CHECK: `[b"withdraw_guard", vault]`, read only once the owner has enabled two-key withdrawals
+    #[account(seeds=[WITHDRAW_GUARD_SEED, vault.key().as_ref()], bump)]
+    pub withdraw_guard: UncheckedAccount<'info>,
+    // Needed only while a withdraw guard is enabled
+    pub device: Option<Signer<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(amount: u64, fee: u64, nonce: u64)]
+pub struct WithdrawWithIntent<'info> {
+    #[account(mut, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    /// CHECK: the vault owner's wallet, which receives the withdrawal; it does not sign
+    #[account(mut, address = vault.load()?.owner)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    #[account(
+        init,
+        seeds=[INTENT_SEED, vault.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump,
+        payer=relayer,
+        space=WITHDRAW_INTENT_SPACE
+    )]
+    pub intent: Account<'info, WithdrawIntent>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    /// CHECK: the instructions sysvar, read to find the ed25519 signature check
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so gating cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    // Needed only for withdrawals above the KYC threshold
+    #[account(seeds=[KYC_SEED, vault.key().as_ref()], bump = kyc_attestation.bump, has_one = vault)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    /// CHECK: `[b"withdraw_guard", vault]`, read only once the owner has enabled two-key withdrawals
+    #[account(seeds=[WITHDRAW_GUARD_SEED, vault.key().as_ref()], bump)]
+    pub withdraw_guard: UncheckedAccount<'info>,
+    // Needed only while a withdraw guard is enabled
+    pub device: Option<Signer<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(stake: u64, payout: u64, bet_id: String, game_id: u64)]
+pub struct BetAndSettle<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    /// CHECK: `[b"bonus", vault]`, updated only once initialized; required so an active bonus cannot be skipped
+    #[account(mut, seeds=[BONUS_SEED, vault.key().as_ref()], bump)]
+    pub bonus: UncheckedAccount<'info>,
+    #[account(mut, seeds=[GEMS_SEED, vault.key().as_ref()], bump = gem_inventory.bump, has_one = vault)]
+    pub gem_inventory: Option<Account<'info, GemInventory>>,
+    /// CHECK: `[b"lossback_promo"]`, read only once initialized
+    #[account(seeds=[LOSSBACK_PROMO_SEED], bump)]
+    pub loss_back_promo: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback", vault]`, updated only once initialized; required so a loss cannot skip its accrual
+    #[account(mut, seeds=[LOSSBACK_SEED, vault.key().as_ref()], bump)]
+    pub loss_back: UncheckedAccount<'info>,
+    #[account(seeds=[PITY_CONFIG_SEED], bump = pity_config.bump)]
+    pub pity_config: Option<Account<'info, PityConfig>>,
+    #[account(init, seeds=[RECEIPT_SEED, vault.key().as_ref(), bet_id_hash(&bet_id).as_ref()], bump, payer=authority, space=BET_RECEIPT_SPACE)]
+    pub bet_receipt: Option<Account<'info, BetReceipt>>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub system_program: Program<'info, System>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    #[account(seeds=[GAME_LIMITS_SEED], bump = game_limits.bump)]
+    pub game_limits: Account<'info, GameLimits>,
+    /// CHECK: the instructions sysvar, read to total this transaction's payouts and spot bundled withdraws
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump, constraint = !blacklist.contains(&vault.load()?.owner) @ VaultError::Blacklisted)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so flagging cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    /// CHECK: `[b"game_config", game_id]`, read only once initialized; required so the game's rules cannot be skipped
+    #[account(seeds=[GAME_CONFIG_SEED, game_id.to_le_bytes().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
+    /// CHECK: `[b"jackpot", game_id]`, fed only once initialized; required so contributions cannot be skipped
+    #[account(mut, seeds=[JACKPOT_SEED, game_id.to_le_bytes().as_ref()], bump)]
+    pub jackpot: UncheckedAccount<'info>,
+    // Needed only when a game program, not the admin, is the authority
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+    // Trophy accounts, needed only to mint a trophy for a qualifying win
+    #[account(seeds=[TROPHY_CONFIG_SEED], bump = trophy_config.bump)]
+    pub trophy_config: Option<Account<'info, TrophyConfig>>,
+    /// CHECK: the vault owner's wallet, which receives the trophy
+    #[account(address = vault.load()?.owner)]
+    pub player: Option<UncheckedAccount<'info>>,
+    /// CHECK: Bubblegum's tree config for `merkle_tree`, verified by Bubblegum
+    #[account(mut)]
+    pub tree_config: Option<UncheckedAccount<'info>>,
+    /// CHECK: compared against `trophy_config.merkle_tree` before minting
+    #[account(mut)]
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+    /// CHECK: SPL noop program, verified by Bubblegum
+    pub log_wrapper: Option<UncheckedAccount<'info>>,
+    /// CHECK: SPL account compression program, verified by Bubblegum
+    pub compression_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: compared against the Bubblegum program id before minting
+    pub bubblegum_program: Option<UncheckedAccount<'info>>,
+    // Needed only when the payout is above `GameLimits::payout_stream_threshold`
+    #[account(
+        init,
+        seeds=[PAYOUT_SCHEDULE_SEED, vault.key().as_ref(), bet_id_hash(&bet_id).as_ref()],
+        bump,
+        payer=authority,
+        space=PAYOUT_SCHEDULE_SPACE
+    )]
+    pub payout_schedule: Option<Account<'info, PayoutSchedule>>,
+    // Both needed for a staking rebate
+    #[account(seeds=[STAKING_CONFIG_SEED], bump = staking_config.bump)]
+    pub staking_config: Option<Account<'info, StakingConfig>>,
+    #[account(seeds=[TOKEN_STAKE_SEED, vault.key().as_ref()], bump = token_stake.bump, has_one = vault)]
+    pub token_stake: Option<Account<'info, TokenStake>>,
+}
+
+#[derive(Accounts)]
+pub struct CreditFreeSpins<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(bet_id: String, game_id: u64)]
+pub struct SettleFreeSpin<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    /// CHECK: `[b"bonus", vault]`, updated only once initialized; required so an active bonus cannot be skipped
+    #[account(mut, seeds=[BONUS_SEED, vault.key().as_ref()], bump)]
+    pub bonus: UncheckedAccount<'info>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    #[account(seeds=[GAME_LIMITS_SEED], bump = game_limits.bump)]
+    pub game_limits: Account<'info, GameLimits>,
+    /// CHECK: the instructions sysvar, read to total this transaction's payouts and spot bundled withdraws
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump, constraint = !blacklist.contains(&vault.load()?.owner) @ VaultError::Blacklisted)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so flagging cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    /// CHECK: `[b"game_config", game_id]`, read only once initialized; required so the game's rules cannot be skipped
+    #[account(seeds=[GAME_CONFIG_SEED, game_id.to_le_bytes().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBetReceipt<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[RECEIPT_SEED, vault.key().as_ref(), bet_receipt.bet_id_hash.as_ref()], bump = bet_receipt.bump, has_one = vault, close = vault)]
+    pub bet_receipt: Account<'info, BetReceipt>,
+    pub closer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepBetReceipts<'info> {
+    /// CHECK: `[b"dispute_config"]`, read only once initialized; its window sets how long receipts are kept
+    #[account(seeds=[DISPUTE_CONFIG_SEED], bump)]
+    pub dispute_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeDisputeConfig<'info> {
+    #[account(init, seeds=[DISPUTE_CONFIG_SEED], bump, payer=authority, space=DISPUTE_CONFIG_SPACE)]
+    pub dispute_config: Account<'info, DisputeConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetDisputeConfig<'info> {
+    #[account(mut, seeds=[DISPUTE_CONFIG_SEED], bump = dispute_config.bump)]
+    pub dispute_config: Account<'info, DisputeConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(bet_id: String)]
+pub struct ChallengeBet<'info> {
+    #[account(has_one = owner)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(
+        mut,
+        seeds=[RECEIPT_SEED, vault.key().as_ref(), bet_id_hash(&bet_id).as_ref()],
+        bump = bet_receipt.bump,
+        has_one = vault
+    )]
+    pub bet_receipt: Account<'info, BetReceipt>,
+    #[account(seeds=[DISPUTE_CONFIG_SEED], bump = dispute_config.bump)]
+    pub dispute_config: Account<'info, DisputeConfig>,
+    #[account(
+        init,
+        seeds=[DISPUTE_SEED, bet_receipt.key().as_ref()],
+        bump,
+        payer=owner,
+        space=DISPUTE_SPACE
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut, seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut, has_one = vault, has_one = owner, close = owner)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut)]
+    pub vault: AccountLoader<'info, UserVault>,
+    /// CHECK: the filer, receiving the dispute's rent
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    pub authority: Signer<'info>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePvpConfig<'info> {
+    #[account(init, seeds=[PVP_CONFIG_SEED], bump, payer=authority, space=PVP_CONFIG_SPACE)]
+    pub pvp_config: Account<'info, PvpConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPvpConfig<'info> {
+    #[account(mut, seeds=[PVP_CONFIG_SEED], bump = pvp_config.bump)]
+    pub pvp_config: Account<'info, PvpConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(init, seeds=[INSURANCE_FUND_SEED], bump, payer=authority, space=INSURANCE_FUND_SPACE)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetInsuranceTerms<'info> {
+    #[account(mut, seeds=[INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundInsuranceFund<'info> {
+    #[account(mut, seeds=[INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(bet_id: String)]
+pub struct PlacePendingBet<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(
+        init,
+        seeds=[PENDING_BET_SEED, vault.key().as_ref(), bet_id_hash(&bet_id).as_ref()],
+        bump,
+        payer=authority,
+        space=PENDING_BET_SPACE
+    )]
+    pub pending_bet: Account<'info, PendingBet>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds=[GAME_LIMITS_SEED], bump = game_limits.bump)]
+    pub game_limits: Account<'info, GameLimits>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump, constraint = !blacklist.contains(&vault.load()?.owner) @ VaultError::Blacklisted)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so region checks cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+    #[account(mut, seeds=[INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+    #[account(seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettlePendingBet<'info> {
+    #[account(mut, has_one = vault, has_one = payer, close = payer)]
+    pub pending_bet: Account<'info, PendingBet>,
+    #[account(mut)]
+    pub vault: AccountLoader<'info, UserVault>,
+    /// CHECK: `[b"bonus", vault]`, updated only once initialized; required so an active bonus cannot be skipped
+    #[account(mut, seeds=[BONUS_SEED, vault.key().as_ref()], bump)]
+    pub bonus: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback_promo"]`, read only once initialized
+    #[account(seeds=[LOSSBACK_PROMO_SEED], bump)]
+    pub loss_back_promo: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback", vault]`, updated only once initialized; required so a loss cannot skip its accrual
+    #[account(mut, seeds=[LOSSBACK_SEED, vault.key().as_ref()], bump)]
+    pub loss_back: UncheckedAccount<'info>,
+    /// CHECK: the authority that placed the bet, receiving its rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds=[INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(bet_id: String)]
+pub struct CashoutBet<'info> {
+    #[account(
+        mut,
+        seeds=[PENDING_BET_SEED, vault.key().as_ref(), bet_id_hash(&bet_id).as_ref()],
+        bump = pending_bet.bump,
+        has_one = vault,
+        has_one = payer,
+        close = payer
+    )]
+    pub pending_bet: Account<'info, PendingBet>,
+    #[account(mut)]
+    pub vault: AccountLoader<'info, UserVault>,
+    /// CHECK: `[b"bonus", vault]`, updated only once initialized; required so an active bonus cannot be skipped
+    #[account(mut, seeds=[BONUS_SEED, vault.key().as_ref()], bump)]
+    pub bonus: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback_promo"]`, read only once initialized
+    #[account(seeds=[LOSSBACK_PROMO_SEED], bump)]
+    pub loss_back_promo: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback", vault]`, updated only once initialized; required so a loss cannot skip its accrual
+    #[account(mut, seeds=[LOSSBACK_SEED, vault.key().as_ref()], bump)]
+    pub loss_back: UncheckedAccount<'info>,
+    /// CHECK: the authority that placed the bet, receiving its rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds=[INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+}
+
+#[derive(Accounts)]
+pub struct ExpirePendingBet<'info> {
+    #[account(mut, has_one = vault, has_one = payer, close = payer)]
+    pub pending_bet: Account<'info, PendingBet>,
+    #[account(mut)]
+    pub vault: AccountLoader<'info, UserVault>,
+    /// CHECK: the authority that placed the bet, receiving its rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(mut, seeds=[INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct OpenCrashRound<'info> {
+    #[account(
+        init,
+        seeds=[CRASH_ROUND_SEED, round_id.to_le_bytes().as_ref()],
+        bump,
+        payer=authority,
+        space=CRASH_ROUND_SPACE
+    )]
+    pub crash_round: Account<'info, CrashRound>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceCrashBet<'info> {
+    #[account(mut, has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub crash_round: Account<'info, CrashRound>,
+    #[account(
+        init,
+        seeds=[CRASH_BET_SEED, crash_round.key().as_ref(), vault.key().as_ref()],
+        bump,
+        payer=owner,
+        space=CRASH_BET_SPACE
+    )]
+    pub crash_bet: Account<'info, CrashBet>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(seeds=[GAME_LIMITS_SEED], bump = game_limits.bump)]
+    pub game_limits: Account<'info, GameLimits>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump, constraint = !blacklist.contains(&owner.key()) @ VaultError::Blacklisted)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so region checks cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    #[account(seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealCrashRound<'info> {
+    #[account(mut, has_one = authority)]
+    pub crash_round: Account<'info, CrashRound>,
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleCrashBet<'info> {
+    #[account(
+        mut,
+        has_one = vault,
+        has_one = owner,
+        constraint = crash_bet.round == crash_round.key() @ VaultError::Unauthorized,
+        close = owner
+    )]
+    pub crash_bet: Account<'info, CrashBet>,
+    #[account(mut)]
+    pub crash_round: Account<'info, CrashRound>,
+    #[account(mut)]
+    pub vault: AccountLoader<'info, UserVault>,
+    /// CHECK: `[b"bonus", vault]`, updated only once initialized; required so an active bonus cannot be skipped
+    #[account(mut, seeds=[BONUS_SEED, vault.key().as_ref()], bump)]
+    pub bonus: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback_promo"]`, read only once initialized
+    #[account(seeds=[LOSSBACK_PROMO_SEED], bump)]
+    pub loss_back_promo: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback", vault]`, updated only once initialized; required so a loss cannot skip its accrual
+    #[account(mut, seeds=[LOSSBACK_SEED, vault.key().as_ref()], bump)]
+    pub loss_back: UncheckedAccount<'info>,
+    /// CHECK: the bettor, receiving the bet's rent
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CloseCrashRound<'info> {
+    #[account(mut, has_one = authority, close = authority)]
+    pub crash_round: Account<'info, CrashRound>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePvpQueue<'info> {
+    #[account(init, seeds=[PVP_QUEUE_SEED], bump, payer=authority, space=PVP_QUEUE_SPACE)]
+    pub pvp_queue: Account<'info, PvpQueue>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(bet_id: String)]
+pub struct CreatePvpBet<'info> {
+    #[account(mut, has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        seeds=[PVP_BET_SEED, vault.key().as_ref(), bet_id_hash(&bet_id).as_ref()],
+        bump,
+        payer=owner,
+        space=PVP_BET_SPACE
+    )]
+    pub pvp_bet: Account<'info, PvpBet>,
+    #[account(seeds=[PVP_CONFIG_SEED], bump = pvp_config.bump)]
+    pub pvp_config: Account<'info, PvpConfig>,
+    /// Required for open challenges
+    #[account(mut, seeds=[PVP_QUEUE_SEED], bump = pvp_queue.bump)]
+    pub pvp_queue: Option<Account<'info, PvpQueue>>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump, constraint = !blacklist.contains(&owner.key()) @ VaultError::Blacklisted)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so region checks cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    #[account(seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptPvpBet<'info> {
+    #[account(
+        mut,
+        constraint = pvp_bet.opponent_vault == vault.key() || pvp_bet.is_open() @ VaultError::Unauthorized,
+        constraint = pvp_bet.creator_vault != vault.key() @ VaultError::DuplicateVault
+    )]
+    pub pvp_bet: Account<'info, PvpBet>,
+    /// Required for open challenges
+    #[account(mut, seeds=[PVP_QUEUE_SEED], bump = pvp_queue.bump)]
+    pub pvp_queue: Option<Account<'info, PvpQueue>>,
+    #[account(mut, has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    pub owner: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump, constraint = !blacklist.contains(&owner.key()) @ VaultError::Blacklisted)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so region checks cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelPvpBet<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = pvp_bet.creator_vault == vault.key() @ VaultError::Unauthorized,
+        close = creator
+    )]
+    pub pvp_bet: Account<'info, PvpBet>,
+    #[account(mut)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    /// Required for open challenges
+    #[account(mut, seeds=[PVP_QUEUE_SEED], bump = pvp_queue.bump)]
+    pub pvp_queue: Option<Account<'info, PvpQueue>>,
+}
+
+#[derive(Accounts)]
+pub struct ExpirePvpBet<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        constraint = pvp_bet.creator_vault == vault.key() @ VaultError::Unauthorized,
+        close = creator
+    )]
+    pub pvp_bet: Account<'info, PvpBet>,
+    #[account(mut)]
+    pub vault: AccountLoader<'info, UserVault>,
+    /// CHECK: the bet's creator, receiving its rent
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    /// Required for open challenges
+    #[account(mut, seeds=[PVP_QUEUE_SEED], bump = pvp_queue.bump)]
+    pub pvp_queue: Option<Account<'info, PvpQueue>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettlePvpBet<'info> {
+    #[account(mut, has_one = creator, has_one = creator_vault, has_one = opponent_vault, close = creator)]
+    pub pvp_bet: Account<'info, PvpBet>,
+    #[account(mut)]
+    pub creator_vault: AccountLoader<'info, UserVault>,
+    #[account(mut)]
+    pub opponent_vault: AccountLoader<'info, UserVault>,
+    /// CHECK: `[b"bonus", creator_vault]`, updated only once initialized; required so an active bonus cannot be skipped
+    #[account(mut, seeds=[BONUS_SEED, creator_vault.key().as_ref()], bump)]
+    pub creator_bonus: UncheckedAccount<'info>,
+    /// CHECK: `[b"bonus", opponent_vault]`, as `creator_bonus`
+    #[account(mut, seeds=[BONUS_SEED, opponent_vault.key().as_ref()], bump)]
+    pub opponent_bonus: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback_promo"]`, read only once initialized
+    #[account(seeds=[LOSSBACK_PROMO_SEED], bump)]
+    pub loss_back_promo: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback", creator_vault]`, updated only once initialized; required so a loss cannot skip its accrual
+    #[account(mut, seeds=[LOSSBACK_SEED, creator_vault.key().as_ref()], bump)]
+    pub creator_loss_back: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback", opponent_vault]`, as `creator_loss_back`
+    #[account(mut, seeds=[LOSSBACK_SEED, opponent_vault.key().as_ref()], bump)]
+    pub opponent_loss_back: UncheckedAccount<'info>,
+    /// CHECK: the bet's creator, receiving its rent
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct OpenTableRound<'info> {
+    #[account(
+        init,
+        seeds=[TABLE_ROUND_SEED, round_id.to_le_bytes().as_ref()],
+        bump,
+        payer=authority,
+        space=TABLE_ROUND_SPACE
+    )]
+    pub table_round: Account<'info, TableRound>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds=[PVP_CONFIG_SEED], bump = pvp_config.bump)]
+    pub pvp_config: Account<'info, PvpConfig>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so region checks cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RaiseTableRound<'info> {
+    #[account(mut)]
+    pub table_round: Account<'info, TableRound>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so region checks cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleTableRound<'info> {
+    #[account(mut, has_one = payer, close = payer)]
+    pub table_round: Account<'info, TableRound>,
+    /// CHECK: the authority that opened the round, receiving its rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    /// CHECK: `[b"lossback_promo"]`, read only once initialized
+    #[account(seeds=[LOSSBACK_PROMO_SEED], bump)]
+    pub loss_back_promo: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelTableRound<'info> {
+    #[account(mut, has_one = payer, close = payer)]
+    pub table_round: Account<'info, TableRound>,
+    /// CHECK: the authority that opened the round, receiving its rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+}
+
+#[derive(Accounts)]
+pub struct FollowPlayer<'info> {
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(constraint = leader_vault.key() != vault.key() @ VaultError::CannotFollowSelf)]
+    pub leader_vault: AccountLoader<'info, UserVault>,
+    #[account(
+        init,
+        seeds=[COPY_FOLLOW_SEED, vault.key().as_ref()],
+        bump,
+        payer=owner,
+        space=COPY_FOLLOW_SPACE
+    )]
+    pub copy_follow: Account<'info, CopyFollow>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnfollowPlayer<'info> {
+    #[account(has_one = owner)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[COPY_FOLLOW_SEED, vault.key().as_ref()], bump = copy_follow.bump, close = owner)]
+    pub copy_follow: Account<'info, CopyFollow>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(bet_id: String, game_id: u64)]
+pub struct SettleMirroredBets<'info> {
+    #[account(constraint = leader_vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub leader_vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    #[account(seeds=[GAME_LIMITS_SEED], bump = game_limits.bump)]
+    pub game_limits: Account<'info, GameLimits>,
+    /// CHECK: the instructions sysvar, read to total this transaction's payouts and spot bundled withdraws
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump)]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so region checks cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    /// CHECK: `[b"game_config", game_id]`, read only once initialized; required so the game's rules cannot be skipped
+    #[account(seeds=[GAME_CONFIG_SEED, game_id.to_le_bytes().as_ref()], bump)]
+    pub game_config: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback_promo"]`, read only once initialized
+    #[account(seeds=[LOSSBACK_PROMO_SEED], bump)]
+    pub loss_back_promo: UncheckedAccount<'info>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BatchSettle<'info> {
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    // User vaults will be passed as remaining_accounts
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    #[account(seeds=[GAME_LIMITS_SEED], bump = game_limits.bump)]
+    pub game_limits: Account<'info, GameLimits>,
+    /// CHECK: the instructions sysvar, read to total this transaction's payouts and spot bundled withdraws
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump)]
+    pub blacklist: Account<'info, Blacklist>,
+    // Needed only when a game program, not the admin, is the authority
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so flagging cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    /// CHECK: `[b"lossback_promo"]`, read only once initialized
+    #[account(seeds=[LOSSBACK_PROMO_SEED], bump)]
+    pub loss_back_promo: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartMaintenancePause<'info> {
+    #[account(mut, seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResumeAfterMaintenance<'info> {
+    #[account(mut, seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyPause<'info> {
+    #[account(mut, seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    #[account(mut, seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetPauseStatus<'info> {
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+}
+
+#[derive(Accounts)]
+pub struct GetVaultInfo<'info> {
+    #[account(seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+}
+
+#[derive(Accounts)]
+pub struct GetHouseInfo<'info> {
+    #[account(seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExportAuditPage<'info> {
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    #[account(seeds=[GAME_LIMITS_SEED], bump = game_limits.bump)]
+    pub game_limits: Account<'info, GameLimits>,
+    /// CHECK: `[b"game_programs"]`, read only once initialized
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump)]
+    pub game_programs: UncheckedAccount<'info>,
+    /// CHECK: `[b"promo_pool"]`, read only once initialized
+    #[account(seeds=[PROMO_POOL_SEED], bump)]
+    pub promo_pool: UncheckedAccount<'info>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeAuthority<'info> {
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePromoPool<'info> {
+    #[account(init, seeds=[PROMO_POOL_SEED], bump, payer=authority, space=PROMO_POOL_SPACE)]
+    pub promo_pool: Account<'info, PromoPool>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundPromoPool<'info> {
+    #[account(mut, seeds=[PROMO_POOL_SEED], bump = promo_pool.bump)]
+    pub promo_pool: Account<'info, PromoPool>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GrantDepositBonus<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(init, seeds=[BONUS_SEED, vault.key().as_ref()], bump, payer=authority, space=DEPOSIT_BONUS_SPACE)]
+    pub bonus: Account<'info, DepositBonus>,
+    #[account(mut, seeds=[PROMO_POOL_SEED], bump = promo_pool.bump)]
+    pub promo_pool: Account<'info, PromoPool>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ForfeitBonus<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[BONUS_SEED, vault.key().as_ref()], bump = bonus.bump, close = promo_pool)]
+    pub bonus: Account<'info, DepositBonus>,
+    #[account(mut, seeds=[PROMO_POOL_SEED], bump = promo_pool.bump)]
+    pub promo_pool: Account<'info, PromoPool>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGemInventory<'info> {
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(init, seeds=[GEMS_SEED, vault.key().as_ref()], bump, payer=owner, space=GEM_INVENTORY_SPACE)]
+    pub gem_inventory: Account<'info, GemInventory>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BurnGemsForBoost<'info> {
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[GEMS_SEED, vault.key().as_ref()], bump = gem_inventory.bump)]
+    pub gem_inventory: Account<'info, GemInventory>,
+    pub owner: Signer<'info>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(raffle_id: u64)]
+pub struct CreateRaffle<'info> {
+    #[account(init, seeds=[RAFFLE_SEED, raffle_id.to_le_bytes().as_ref()], bump, payer=authority, space=RAFFLE_SPACE)]
+    pub raffle: Account<'info, Raffle>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnterRaffle<'info> {
+    #[account(mut, seeds=[RAFFLE_SEED, raffle.raffle_id.to_le_bytes().as_ref()], bump = raffle.bump)]
+    pub raffle: Account<'info, Raffle>,
+    #[account(init, seeds=[RAFFLE_ENTRY_SEED, raffle.key().as_ref(), vault.key().as_ref()], bump, payer=owner, space=RAFFLE_ENTRY_SPACE)]
+    pub entry: Account<'info, RaffleEntry>,
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[GEMS_SEED, vault.key().as_ref()], bump = gem_inventory.bump)]
+    pub gem_inventory: Account<'info, GemInventory>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DrawWinner<'info> {
+    #[account(mut, seeds=[RAFFLE_SEED, raffle.raffle_id.to_le_bytes().as_ref()], bump = raffle.bump)]
+    pub raffle: Account<'info, Raffle>,
+    pub draw_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRafflePrize<'info> {
+    #[account(mut, seeds=[RAFFLE_SEED, raffle.raffle_id.to_le_bytes().as_ref()], bump = raffle.bump)]
+    pub raffle: Account<'info, Raffle>,
+    #[account(seeds=[RAFFLE_ENTRY_SEED, raffle.key().as_ref(), vault.key().as_ref()], bump = entry.bump, has_one = raffle, has_one = vault)]
+    pub entry: Account<'info, RaffleEntry>,
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[PROMO_POOL_SEED], bump = promo_pool.bump)]
+    pub promo_pool: Account<'info, PromoPool>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct CreateTournament<'info> {
+    #[account(
+        init,
+        seeds=[TOURNAMENT_SEED, tournament_id.to_le_bytes().as_ref()],
+        bump,
+        payer=authority,
+        space=TOURNAMENT_SPACE
+            + 4 + (MAX_BRACKET_ROUNDS + 1) * 8 + 1
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PostMatchResult<'info> {
+    #[account(mut, seeds=[TOURNAMENT_SEED, tournament.tournament_id.to_le_bytes().as_ref()], bump = tournament.bump)]
+    pub tournament: Account<'info, Tournament>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceTournamentRound<'info> {
+    #[account(mut, seeds=[TOURNAMENT_SEED, tournament.tournament_id.to_le_bytes().as_ref()], bump = tournament.bump)]
+    pub tournament: Account<'info, Tournament>,
+    pub authority: Signer<'info>,
+    #[account(seeds=[GAME_PROGRAMS_SEED], bump = game_programs.bump)]
+    pub game_programs: Option<Account<'info, GamePrograms>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DistributeTournamentPrizes<'info> {
+    #[account(
+        mut,
+        seeds=[TOURNAMENT_SEED, tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+        has_one = payer,
+        close = payer
+    )]
+    pub tournament: Account<'info, Tournament>,
+    /// CHECK: the admin that created the bracket, receiving its rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    #[account(mut, seeds=[PROMO_POOL_SEED], bump = promo_pool.bump)]
+    pub promo_pool: Account<'info, PromoPool>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeLossBackPromo<'info> {
+    #[account(init, seeds=[LOSSBACK_PROMO_SEED], bump, payer=authority, space=LOSS_BACK_PROMO_SPACE)]
+    pub loss_back_promo: Account<'info, LossBackPromo>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureLossBack<'info> {
+    #[account(mut, seeds=[LOSSBACK_PROMO_SEED], bump = loss_back_promo.bump)]
+    pub loss_back_promo: Account<'info, LossBackPromo>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePityConfig<'info> {
+    #[account(init, seeds=[PITY_CONFIG_SEED], bump, payer=authority, space=PITY_CONFIG_SPACE)]
+    pub pity_config: Account<'info, PityConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPityConfig<'info> {
+    #[account(mut, seeds=[PITY_CONFIG_SEED], bump = pity_config.bump)]
+    pub pity_config: Account<'info, PityConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeLossBack<'info> {
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(init, seeds=[LOSSBACK_SEED, vault.key().as_ref()], bump, payer=owner, space=LOSS_BACK_SPACE)]
+    pub loss_back: Account<'info, LossBack>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimPayoutTranches<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    #[account(
+        mut,
+        seeds=[PAYOUT_SCHEDULE_SEED, vault.key().as_ref(), payout_schedule.bet_id_hash.as_ref()],
+        bump = payout_schedule.bump,
+        has_one = vault,
+        has_one = rent_payer
+    )]
+    pub payout_schedule: Account<'info, PayoutSchedule>,
+    /// CHECK: receives the schedule's rent once the last tranche is paid; checked by `has_one`
+    #[account(mut)]
+    pub rent_payer: UncheckedAccount<'info>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimLossBack<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[LOSSBACK_SEED, vault.key().as_ref()], bump = loss_back.bump, has_one = vault)]
+    pub loss_back: Account<'info, LossBack>,
+    #[account(mut, seeds=[PROMO_POOL_SEED], bump = promo_pool.bump)]
+    pub promo_pool: Account<'info, PromoPool>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRewardsConfig<'info> {
+    #[account(init, seeds=[REWARDS_CONFIG_SEED], bump, payer=authority, space=REWARDS_CONFIG_SPACE)]
+    pub rewards_config: Account<'info, RewardsConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRewardEligibility<'info> {
+    #[account(mut, seeds=[REWARDS_CONFIG_SEED], bump = rewards_config.bump)]
+    pub rewards_config: Account<'info, RewardsConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreditReward<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(seeds=[REWARDS_CONFIG_SEED], bump = rewards_config.bump)]
+    pub rewards_config: Account<'info, RewardsConfig>,
+    #[account(mut, seeds=[PROMO_POOL_SEED], bump = promo_pool.bump)]
+    pub promo_pool: Account<'info, PromoPool>,
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClawbackBonus<'info> {
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[BONUS_SEED, vault.key().as_ref()], bump = bonus.bump, has_one = vault, close = promo_pool)]
+    pub bonus: Account<'info, DepositBonus>,
+    #[account(mut, seeds=[PROMO_POOL_SEED], bump = promo_pool.bump)]
+    pub promo_pool: Account<'info, PromoPool>,
+    pub authority: Signer<'info>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVaultRegistry<'info> {
+    #[account(init, seeds=[VAULT_REGISTRY_SEED], bump, payer=authority, space=VAULT_REGISTRY_SPACE)]
+    pub vault_registry: Account<'info, VaultRegistry>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenRegistryPage<'info> {
+    #[account(mut, seeds=[VAULT_REGISTRY_SEED], bump = vault_registry.bump)]
+    pub vault_registry: Account<'info, VaultRegistry>,
+    #[account(
+        init,
+        seeds=[REGISTRY_PAGE_SEED, vault_registry.pages.to_le_bytes().as_ref()],
+        bump,
+        payer=payer,
+        space=VAULT_REGISTRY_PAGE_SPACE
+    )]
+    pub registry_page: Account<'info, VaultRegistryPage>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterVault<'info> {
+    #[account(mut, seeds=[VAULT_REGISTRY_SEED], bump = vault_registry.bump)]
+    pub vault_registry: Account<'info, VaultRegistry>,
+    #[account(
+        mut,
+        seeds=[REGISTRY_PAGE_SEED, registry_page.page.to_le_bytes().as_ref()],
+        bump = registry_page.bump,
+        constraint = registry_page.page == vault_registry.current_page() @ VaultError::WrongRegistryPage
+    )]
+    pub registry_page: Account<'info, VaultRegistryPage>,
+    #[account(seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(init, seeds=[REGISTRY_ENTRY_SEED, vault.key().as_ref()], bump, payer=payer, space=VAULT_REGISTRY_ENTRY_SPACE)]
+    pub registry_entry: Account<'info, VaultRegistryEntry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTrophyConfig<'info> {
+    #[account(init, seeds=[TROPHY_CONFIG_SEED], bump, payer=authority, space=TROPHY_CONFIG_SPACE)]
+    pub trophy_config: Account<'info, TrophyConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTrophyConfig<'info> {
+    #[account(mut, seeds=[TROPHY_CONFIG_SEED], bump = trophy_config.bump)]
+    pub trophy_config: Account<'info, TrophyConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRgConfig<'info> {
+    #[account(init, seeds=[RG_CONFIG_SEED], bump, payer=authority, space=RG_CONFIG_SPACE)]
+    pub rg_config: Account<'info, RgConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRgConfig<'info> {
+    #[account(mut, seeds=[RG_CONFIG_SEED], bump = rg_config.bump)]
+    pub rg_config: Account<'info, RgConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeBridgeConfig<'info> {
+    #[account(
+        init,
+        seeds=[BRIDGE_CONFIG_SEED],
+        bump,
+        payer=authority,
+        space=BRIDGE_CONFIG_SPACE
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBridgeConfig<'info> {
+    #[account(mut, seeds=[BRIDGE_CONFIG_SEED], bump = bridge_config.bump)]
+    pub bridge_config: Account<'info, BridgeConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundBridgePool<'info> {
+    #[account(mut, seeds=[BRIDGE_CONFIG_SEED], bump = bridge_config.bump)]
+    pub bridge_config: Account<'info, BridgeConfig>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreditBridgedDeposit<'info> {
+    #[account(mut, seeds=[BRIDGE_CONFIG_SEED], bump = bridge_config.bump)]
+    pub bridge_config: Account<'info, BridgeConfig>,
+    /// CHECK: a `PostedVAA` owned by the configured core bridge, parsed in the handler
+    #[account(owner = bridge_config.core_bridge @ VaultError::InvalidVaa)]
+    pub posted_vaa: UncheckedAccount<'info>,
+    #[account(init, seeds=[BRIDGE_CLAIM_SEED, posted_vaa.key().as_ref()], bump, payer=payer, space=BRIDGE_CLAIM_SPACE)]
+    pub bridge_claim: Account<'info, BridgeClaim>,
+    #[account(mut, seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(seeds=[BLACKLIST_SEED], bump = blacklist.bump)]
+    pub blacklist: Account<'info, Blacklist>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so flagging cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeComplianceConfig<'info> {
+    #[account(
+        init,
+        seeds=[COMPLIANCE_CONFIG_SEED],
+        bump,
+        payer=authority,
+        space=COMPLIANCE_CONFIG_SPACE
+    )]
+    pub compliance_config: Account<'info, ComplianceConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateComplianceConfig<'info> {
+    #[account(mut, seeds=[COMPLIANCE_CONFIG_SEED], bump = compliance_config.bump)]
+    pub compliance_config: Account<'info, ComplianceConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnableWithdrawGuard<'info> {
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(
+        init,
+        seeds=[WITHDRAW_GUARD_SEED, vault.key().as_ref()],
+        bump,
+        payer=owner,
+        space=WITHDRAW_GUARD_SPACE
+    )]
+    pub withdraw_guard: Account<'info, WithdrawGuard>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub device: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestWithdrawGuardRemoval<'info> {
+    #[account(has_one = owner)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[WITHDRAW_GUARD_SEED, vault.key().as_ref()], bump = withdraw_guard.bump)]
+    pub withdraw_guard: Account<'info, WithdrawGuard>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveWithdrawGuard<'info> {
+    #[account(has_one = owner)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut, seeds=[WITHDRAW_GUARD_SEED, vault.key().as_ref()], bump = withdraw_guard.bump, close = owner)]
+    pub withdraw_guard: Account<'info, WithdrawGuard>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub device: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeWithdrawalQueue<'info> {
+    #[account(init, seeds=[WITHDRAWAL_QUEUE_SEED], bump, payer=authority, space=WITHDRAWAL_QUEUE_SPACE)]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        seeds=[WITHDRAWAL_REQUEST_SEED, vault.key().as_ref()],
+        bump,
+        payer=owner,
+        space=WITHDRAWAL_REQUEST_SPACE
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+    #[account(mut, seeds=[WITHDRAWAL_QUEUE_SEED], bump = withdrawal_queue.bump)]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized; required so gating cannot be skipped
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+    // Needed only for withdrawals above the KYC threshold
+    #[account(seeds=[KYC_SEED, vault.key().as_ref()], bump = kyc_attestation.bump, has_one = vault)]
+    pub kyc_attestation: Option<Account<'info, KycAttestation>>,
+    /// CHECK: `[b"withdraw_guard", vault]`, read only once the owner has enabled two-key withdrawals
+    #[account(seeds=[WITHDRAW_GUARD_SEED, vault.key().as_ref()], bump)]
+    pub withdraw_guard: UncheckedAccount<'info>,
+    // Needed only while a withdraw guard is enabled
+    pub device: Option<Signer<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
 #[derive(Accounts)]
-pub struct InitializeVault<'info> {
-    #[account(init, seeds=[b"vault", user.key().as_ref()], bump, payer=user, space=8 + 32 + 1 + 8 + 4 + 8 + 1)]
-    pub vault: Account<'info, UserVault>,
+pub struct ProcessWithdrawalRequest<'info> {
+    #[account(mut, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    /// CHECK: the vault owner's wallet, which receives the withdrawal and the request's rent; it does not sign
+    #[account(mut, address = vault.load()?.owner)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds=[WITHDRAWAL_REQUEST_SEED, vault.key().as_ref()],
+        bump = withdrawal_request.bump,
+        has_one = vault,
+        close = owner
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+    #[account(mut, seeds=[WITHDRAWAL_QUEUE_SEED], bump = withdrawal_queue.bump)]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+    #[account(seeds=[PAUSE_CONFIG_SEED], bump, constraint = pause_config.version == PAUSE_CONFIG_VERSION @ VaultError::VersionMismatch)]
+    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, seeds=[GLOBAL_STATS_SEED], bump = global_stats.load()?.bump)]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized for the large-transfer threshold
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DropWithdrawalRequest<'info> {
+    #[account(constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    /// CHECK: the vault owner's wallet, which receives the request's rent
+    #[account(mut, address = vault.load()?.owner)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds=[WITHDRAWAL_REQUEST_SEED, vault.key().as_ref()],
+        bump = withdrawal_request.bump,
+        has_one = vault,
+        close = owner
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+    #[account(mut, seeds=[WITHDRAWAL_QUEUE_SEED], bump = withdrawal_queue.bump)]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptTerms<'info> {
+    #[account(mut, has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    pub owner: Signer<'info>,
+    /// CHECK: `[b"compliance_config"]`, read only once initialized for the current terms hash
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump)]
+    pub compliance_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetVaultWinCap<'info> {
+    #[account(
+        mut,
+        seeds=[VAULT_SEED, vault.load()?.owner.as_ref()],
+        bump = vault.load()?.bump,
+        constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch
+    )]
+    pub vault: AccountLoader<'info, UserVault>,
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetVaultRegion<'info> {
+    #[account(
+        mut,
+        seeds=[VAULT_SEED, vault.load()?.owner.as_ref()],
+        bump = vault.load()?.bump,
+        constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch
+    )]
+    pub vault: AccountLoader<'info, UserVault>,
+    pub authority: Signer<'info>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct IssueKycAttestation<'info> {
+    #[account(seeds=[COMPLIANCE_CONFIG_SEED], bump = compliance_config.bump)]
+    pub compliance_config: Account<'info, ComplianceConfig>,
+    #[account(seeds=[VAULT_SEED, vault.load()?.owner.as_ref()], bump = vault.load()?.bump)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(init, seeds=[KYC_SEED, vault.key().as_ref()], bump, payer=attestor, space=KYC_ATTESTATION_SPACE)]
+    pub kyc_attestation: Account<'info, KycAttestation>,
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub attestor: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeHouse<'info> {
-    #[account(init, seeds=[b"house_vault"], bump, payer=admin, space=8 + 1 + 32 + 32 + 8 + 1)]
-    pub house_vault: Account<'info, HouseVault>,
+pub struct RevokeKycAttestation<'info> {
+    #[account(mut, seeds=[KYC_SEED, kyc_attestation.vault.as_ref()], bump = kyc_attestation.bump, close = attestor)]
+    pub kyc_attestation: Account<'info, KycAttestation>,
+    /// CHECK: receives the rent; must be the issuing attestor
+    #[account(mut, address = kyc_attestation.attestor)]
+    pub attestor: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BatchCredit<'info> {
+    #[account(mut, seeds=[PROMO_POOL_SEED], bump = promo_pool.bump)]
+    pub promo_pool: Account<'info, PromoPool>,
+    pub authority: Signer<'info>,
+    // User vaults are passed as remaining_accounts
+}
+
+#[derive(Accounts)]
+pub struct InitializeHealthMonitor<'info> {
+    #[account(init, seeds=[HEALTH_MONITOR_SEED], bump, payer=authority, space=HEALTH_MONITOR_SPACE)]
+    pub health_monitor: Account<'info, HealthMonitor>,
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct InitializePauseConfig<'info> {
-    #[account(init, seeds=[b"pause_config"], bump, payer=authority, space=8 + 32 + 32 + 1 + 8 + 1 + 1 + 1)]
-    pub pause_config: Account<'info, PauseConfig>,
+pub struct SetHealthMonitor<'info> {
+    #[account(mut, seeds=[HEALTH_MONITOR_SEED], bump = health_monitor.bump)]
+    pub health_monitor: Account<'info, HealthMonitor>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OperatorHeartbeat<'info> {
+    #[account(mut, seeds=[HEALTH_MONITOR_SEED], bump = health_monitor.bump, has_one = operator)]
+    pub health_monitor: Account<'info, HealthMonitor>,
+    #[account(seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CheckHealth<'info> {
+    #[account(mut, seeds=[HEALTH_MONITOR_SEED], bump = health_monitor.bump)]
+    pub health_monitor: Account<'info, HealthMonitor>,
+    #[account(seeds=[HOUSE_VAULT_SEED], bump = house_vault.load()?.bump, constraint = house_vault.load()?.version == HOUSE_VERSION @ VaultError::VersionMismatch)]
+    pub house_vault: AccountLoader<'info, HouseVault>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStakingConfig<'info> {
+    #[account(init, seeds=[STAKING_CONFIG_SEED], bump, payer=authority, space=STAKING_CONFIG_SPACE)]
+    pub staking_config: Account<'info, StakingConfig>,
+    /// CHECK: the platform token's mint, recorded as is and matched against the escrow's
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: parsed by `token_account_parts`, which checks its mint and that the config PDA owns it
+    pub escrow: UncheckedAccount<'info>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ClosePauseConfig<'info> {
-    #[account(mut, seeds=[b"pause_config"], bump, close=authority)]
-    pub pause_config: Account<'info, PauseConfig>,
-    #[account(mut)]
+pub struct SetStakingTiers<'info> {
+    #[account(mut, seeds=[STAKING_CONFIG_SEED], bump = staking_config.bump)]
+    pub staking_config: Account<'info, StakingConfig>,
     pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct Deposit<'info> {
-    #[account(mut, has_one = owner)]
-    pub vault: Account<'info, UserVault>,
-    #[account(signer)]
-    pub owner: AccountInfo<'info>,
+pub struct InitializeTokenStake<'info> {
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    #[account(init, seeds=[TOKEN_STAKE_SEED, vault.key().as_ref()], bump, payer=owner, space=TOKEN_STAKE_SPACE)]
+    pub token_stake: Account<'info, TokenStake>,
     #[account(mut)]
-    pub user: Signer<'info>,
-    #[account(seeds=[b"pause_config"], bump)]
-    pub pause_config: Account<'info, PauseConfig>,
+    pub owner: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Withdraw<'info> {
-    #[account(mut, has_one = owner)]
-    pub vault: Account<'info, UserVault>,
+pub struct StakeTokens<'info> {
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    pub owner: Signer<'info>,
+    #[account(mut, seeds=[TOKEN_STAKE_SEED, vault.key().as_ref()], bump = token_stake.bump, has_one = vault)]
+    pub token_stake: Account<'info, TokenStake>,
+    #[account(seeds=[STAKING_CONFIG_SEED], bump = staking_config.bump)]
+    pub staking_config: Account<'info, StakingConfig>,
+    /// CHECK: the owner's token account; the token program checks its authority and mint
     #[account(mut)]
+    pub source: UncheckedAccount<'info>,
+    /// CHECK: the escrow recorded in the staking config
+    #[account(mut, address = staking_config.escrow)]
+    pub escrow: UncheckedAccount<'info>,
+    /// CHECK: compared against the SPL Token program id by `token_transfer`
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
     pub owner: Signer<'info>,
-    #[account(seeds=[b"pause_config"], bump)]
-    pub pause_config: Account<'info, PauseConfig>,
+    #[account(mut, seeds=[TOKEN_STAKE_SEED, vault.key().as_ref()], bump = token_stake.bump, has_one = vault)]
+    pub token_stake: Account<'info, TokenStake>,
+    #[account(seeds=[STAKING_CONFIG_SEED], bump = staking_config.bump)]
+    pub staking_config: Account<'info, StakingConfig>,
 }
 
 #[derive(Accounts)]
-pub struct BetAndSettle<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, UserVault>,
+pub struct WithdrawUnstaked<'info> {
+    #[account(has_one = owner, constraint = vault.load()?.version == VAULT_VERSION @ VaultError::VersionMismatch)]
+    pub vault: AccountLoader<'info, UserVault>,
+    pub owner: Signer<'info>,
+    #[account(mut, seeds=[TOKEN_STAKE_SEED, vault.key().as_ref()], bump = token_stake.bump, has_one = vault)]
+    pub token_stake: Account<'info, TokenStake>,
+    #[account(seeds=[STAKING_CONFIG_SEED], bump = staking_config.bump)]
+    pub staking_config: Account<'info, StakingConfig>,
+    /// CHECK: the escrow recorded in the staking config
+    #[account(mut, address = staking_config.escrow)]
+    pub escrow: UncheckedAccount<'info>,
+    /// CHECK: any token account of the staking mint; the token program checks the mint
     #[account(mut)]
-    pub house_vault: Account<'info, HouseVault>,
-    pub authority: Signer<'info>,
-    #[account(seeds=[b"pause_config"], bump)]
-    pub pause_config: Account<'info, PauseConfig>,
+    pub destination: UncheckedAccount<'info>,
+    /// CHECK: compared against the SPL Token program id by `token_transfer`
+    pub token_program: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
-pub struct BatchSettle<'info> {
+pub struct InitializeUpgradeGovernance<'info> {
+    #[account(init, seeds=[UPGRADE_GOVERNANCE_SEED], bump, payer=authority, space=UPGRADE_GOVERNANCE_SPACE)]
+    pub upgrade_governance: Account<'info, UpgradeGovernance>,
+    /// CHECK: this program's ProgramData, read by `load_program_data`
+    #[account(seeds=[ID.as_ref()], bump, seeds::program = bpf_loader_upgradeable::ID)]
+    pub program_data: UncheckedAccount<'info>,
     #[account(mut)]
-    pub house_vault: Account<'info, HouseVault>,
     pub authority: Signer<'info>,
-    #[account(seeds=[b"pause_config"], bump)]
-    pub pause_config: Account<'info, PauseConfig>,
-    // User vaults will be passed as remaining_accounts
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct StartMaintenancePause<'info> {
-    #[account(mut, seeds=[b"pause_config"], bump)]
-    pub pause_config: Account<'info, PauseConfig>,
+pub struct SetUpgradeGovernance<'info> {
+    #[account(mut, seeds=[UPGRADE_GOVERNANCE_SEED], bump = upgrade_governance.bump)]
+    pub upgrade_governance: Account<'info, UpgradeGovernance>,
     pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct EmergencyPause<'info> {
-    #[account(mut, seeds=[b"pause_config"], bump)]
-    pub pause_config: Account<'info, PauseConfig>,
+pub struct TransferUpgradeAuthority<'info> {
+    /// CHECK: this program's ProgramData; the loader checks `authority` against it
+    #[account(mut, seeds=[ID.as_ref()], bump, seeds::program = bpf_loader_upgradeable::ID)]
+    pub program_data: UncheckedAccount<'info>,
     pub authority: Signer<'info>,
+    /// CHECK: must be the multisig; checked in the handler
+    pub new_authority: UncheckedAccount<'info>,
+    /// CHECK: the upgradeable BPF loader, invoked to change the authority
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable: UncheckedAccount<'info>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct Unpause<'info> {
-    #[account(mut, seeds=[b"pause_config"], bump)]
-    pub pause_config: Account<'info, PauseConfig>,
+pub struct AnnounceUpgrade<'info> {
+    #[account(mut, seeds=[UPGRADE_GOVERNANCE_SEED], bump = upgrade_governance.bump)]
+    pub upgrade_governance: Account<'info, UpgradeGovernance>,
+    /// CHECK: only its address is recorded; must be a loader-owned buffer
+    #[account(owner = bpf_loader_upgradeable::ID)]
+    pub buffer: UncheckedAccount<'info>,
     pub authority: Signer<'info>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct GetPauseStatus<'info> {
-    #[account(seeds=[b"pause_config"], bump)]
-    pub pause_config: Account<'info, PauseConfig>,
+pub struct RecordUpgrade<'info> {
+    #[account(mut, seeds=[UPGRADE_GOVERNANCE_SEED], bump = upgrade_governance.bump)]
+    pub upgrade_governance: Account<'info, UpgradeGovernance>,
+    /// CHECK: this program's ProgramData, read by `load_program_data`
+    #[account(seeds=[ID.as_ref()], bump, seeds::program = bpf_loader_upgradeable::ID)]
+    pub program_data: UncheckedAccount<'info>,
+    #[account(mut, seeds=[GLOBAL_CONFIG_SEED], bump = global_config.bump)]
+    pub global_config: Account<'info, GlobalConfig>,
 }
 
-#[derive(Accounts)]
-pub struct ChangeAuthority<'info> {
-    #[account(mut, seeds=[b"house_vault"], bump)]
-    pub house_vault: Account<'info, HouseVault>,
-    pub authority: Signer<'info>,
+// Events
+//
+// Every event has a fixed layout (no strings or vectors) that starts with
+// `version`, so decoders can dispatch on it before reading the body.
+
+/// Layout version stamped on every event; bump when any event's fields change
+pub const EVENT_VERSION: u8 = 2;
+
+/// Off-chain bet ids are carried in events as the first 16 bytes of their keccak hash
+pub fn bet_id_hash(bet_id: &str) -> [u8; 16] {
+    let mut hash = [0u8; 16];
+    hash.copy_from_slice(&keccak::hash(bet_id.as_bytes()).to_bytes()[..16]);
+    hash
+}
+
+/// Reject gem payloads that are not 7 counts or award more of a type than `MAX_GEMS_PER_SETTLEMENT`
+fn check_gem_data(gem_data: &[u8]) -> Result<()> {
+    require!(gem_data.len() == MAX_GEMS_PER_SETTLEMENT.len(), VaultError::BadGemData);
+    require!(
+        gem_data.iter().zip(MAX_GEMS_PER_SETTLEMENT.iter()).all(|(count, max)| count <= max),
+        VaultError::GemCountOutOfRange
+    );
+    Ok(())
+}
+
+fn compact_gems(gem_data: &[u8]) -> Result<[u8; 7]> {
+    gem_data.try_into().map_err(|_| error!(VaultError::BadGemData))
+}
+
+#[event]
+pub struct Deposited {
+    pub version: u8,
+    pub seq: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub new_balance: u64,
+    /// Solana Pay reference, or the posted VAA of a bridged deposit; zeroed when
+    /// the deposit had none, absent before version 2
+    pub reference: [u8; 32],
+}
+
+/// A deposit, withdrawal or payout above `ComplianceConfig::large_transfer_threshold`
+#[event]
+pub struct LargeTransfer {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    /// `TRANSFER_DEPOSIT`, `TRANSFER_WITHDRAWAL` or `TRANSFER_PAYOUT`
+    pub kind: u8,
+    pub amount: u64,
+    /// Start of the vault's current 24h window
+    pub window_start: i64,
+    /// Deposits, withdrawals and payouts through the vault within that window, this one included
+    pub window_total: u64,
+}
+
+#[event]
+pub struct VaultRegionSet {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub previous_region: u8,
+    pub region: u8,
+}
+
+/// One fact in an audit page: what it is, the address it concerns and its value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct AuditEntry {
+    pub label: String,
+    /// Default when the fact concerns no particular address
+    pub address: Pubkey,
+    /// Lamports, counts or settings; 0 for address-only facts, 1/0 for flags
+    pub amount: u64,
+}
+
+/// One page of `export_audit_page`
+#[event]
+pub struct AuditPage {
+    pub version: u8,
+    pub seq: u64,
+    /// Slot the snapshot was taken at
+    pub slot: u64,
+    pub page: u8,
+    pub page_count: u8,
+    pub entries: Vec<AuditEntry>,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub version: u8,
+    pub seq: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+}
+
+#[event]
+pub struct BetSettled {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub bet_id_hash: [u8; 16],
+    pub game_id: u64,
+    pub stake: u64,
+    pub payout: u64,
+    pub gem_data: [u8; 7],
+}
+
+#[event]
+pub struct PayoutScheduled {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub bet_id_hash: [u8; 16],
+    pub paid_now: u64,
+    pub streamed: u64,
+    pub tranches: u16,
+    pub interval_secs: i64,
+}
+
+#[event]
+pub struct StakingRebatePaid {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub bet_id_hash: [u8; 16],
+    pub wagered: u64,
+    pub rebate_bps: u16,
+    pub rebate: u64,
+}
+
+#[event]
+pub struct PayoutTrancheClaimed {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub bet_id_hash: [u8; 16],
+    pub amount: u64,
+    pub claimed: u64,
+    pub total: u64,
+}
+
+#[event]
+pub struct GemsCredited {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub gems: [u8; 7],
+    pub boost_multiplier: u16,
+}
+
+#[event]
+pub struct DisputeFiled {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub bet_id_hash: [u8; 16],
+    pub amount: u64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub bet_id_hash: [u8; 16],
+    pub amount: u64,
+    /// Part of `amount` paid to the vault; the rest went back to the house
+    pub award: u64,
+}
+
+/// Part of the stake of an insured pending bet refunded by the insurance fund after it lost
+#[event]
+pub struct InsuranceRefunded {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub bet_id_hash: [u8; 16],
+    pub stake: u64,
+    pub premium: u64,
+    pub refund: u64,
+}
+
+/// A side bet settled with the parent bet identified by `parent_bet_id_hash`
+#[event]
+pub struct SideBetSettled {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub parent_bet_id_hash: [u8; 16],
+    /// Position among the parent's side bets
+    pub index: u8,
+    pub game_id: u64,
+    pub stake: u64,
+    pub payout: u64,
+}
+
+/// A seat's stake and payout in a settled table round
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct TableSeatResult {
+    pub vault: Pubkey,
+    pub committed: u64,
+    pub payout: u64,
+}
+
+/// A table round paid out to its seats, after the rake
+#[event]
+pub struct TableRoundSettled {
+    pub version: u8,
+    pub seq: u64,
+    pub round_id: u64,
+    pub game_id: u64,
+    pub rake: u64,
+    pub seats: Vec<TableSeatResult>,
+}
+
+/// A follower's copy of a leader's bet, settled against the follower's vault
+#[event]
+pub struct BetMirrored {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub leader: Pubkey,
+    pub bet_id_hash: [u8; 16],
+    pub game_id: u64,
+    pub stake: u64,
+    pub payout: u64,
+}
+
+/// A bracket match decided by the settler
+#[event]
+pub struct TournamentMatchSettled {
+    pub version: u8,
+    pub seq: u64,
+    pub tournament_id: u64,
+    pub round: u8,
+    pub match_index: u16,
+    pub winner: Pubkey,
+    pub loser: Pubkey,
+}
+
+/// An entrant's final standing and the prize it was paid
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct TournamentStanding {
+    pub vault: Pubkey,
+    pub standing: u8,
+    pub prize: u64,
+}
+
+/// A completed bracket paid out, one standing per entrant in seeding order
+#[event]
+pub struct TournamentPrizesPaid {
+    pub version: u8,
+    pub seq: u64,
+    pub tournament_id: u64,
+    pub game_id: u64,
+    pub standings: Vec<TournamentStanding>,
+}
+
+/// A jackpot tier (0 mini, 1 major, 2 grand) paid into the vault whose bet hit it
+#[event]
+pub struct JackpotAwarded {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub bet_id_hash: [u8; 16],
+    pub game_id: u64,
+    pub tier: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PvpBetSettled {
+    pub version: u8,
+    pub seq: u64,
+    pub bet_id_hash: [u8; 16],
+    pub game_id: u64,
+    pub creator_vault: Pubkey,
+    pub opponent_vault: Pubkey,
+    /// Winning vault, default for a draw
+    pub winner: Pubkey,
+    /// Escrowed by each side
+    pub stake: u64,
+    pub rake: u64,
+}
+
+#[event]
+pub struct GemBurned {
+    pub version: u8,
+    pub seq: u64,
+    pub vault: Pubkey,
+    pub gem_type: u8,
+    pub boost_multiplier: u16,
+}
+
+#[event]
+pub struct PromoClawback {
+    pub version: u8,
+    pub seq: u64,
+    pub user: Pubkey,
+    pub bonus_amount: u64,
+    pub amount: u64,
+    pub reason_code: u8,
+}
+
+/// An upgrade the multisig has committed to; `buffer_hash` covers the buffer's program bytes
+#[event]
+pub struct UpgradeAnnounced {
+    pub version: u8,
+    pub seq: u64,
+    pub buffer: Pubkey,
+    pub buffer_hash: [u8; 32],
+    pub earliest_upgrade_slot: u64,
+}
+
+/// A deployment seen in the program data; the announcement fields are zero if none was pending
+#[event]
+pub struct UpgradeRecorded {
+    pub version: u8,
+    pub seq: u64,
+    pub buffer_hash: [u8; 32],
+    pub earliest_upgrade_slot: u64,
+    pub deployed_slot: u64,
+    pub on_schedule: bool,
 }
 
 // Error definitions
@@ -594,4 +9072,943 @@ pub enum VaultError {
     MaintenancePaused,
     #[msg("Emergency pause is active")]
     EmergencyPaused,
-}
\ No newline at end of file
+    #[msg("Withdrawal requests are only accepted while operations are paused")]
+    NotPaused,
+    #[msg("Withdrawal request is not at the head of the queue")]
+    WithdrawalOutOfOrder,
+    #[msg("Unknown game category")]
+    InvalidGameCategory,
+    #[msg("Token account is not the platform token's or not held by the right owner")]
+    WrongStakingToken,
+    #[msg("Rebate tiers must rise in both stake and rebate, within the caps")]
+    InvalidRebateTiers,
+    #[msg("Unstaked tokens are still cooling down")]
+    StakeCoolingDown,
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Promo pool has insufficient funds")]
+    PromoPoolInsufficient,
+    #[msg("Bonus has not expired yet")]
+    BonusNotExpired,
+    #[msg("Invalid gem type")]
+    InvalidGemType,
+    #[msg("Not enough gems in inventory")]
+    InsufficientGems,
+    #[msg("A gem boost is already pending")]
+    BoostAlreadyActive,
+    #[msg("Raffle entries are closed")]
+    RaffleClosed,
+    #[msg("Raffle has not been drawn yet")]
+    RaffleOpen,
+    #[msg("Raffle has already been drawn or paid")]
+    RaffleAlreadyDrawn,
+    #[msg("Raffle has no entries")]
+    RaffleEmpty,
+    #[msg("Entry does not hold the winning ticket")]
+    NotRaffleWinner,
+    #[msg("Vault does not meet reward eligibility thresholds")]
+    RewardIneligible,
+    #[msg("Promo window is still active")]
+    PromoActive,
+    #[msg("Bonus has already been cleared")]
+    BonusCleared,
+    #[msg("No elapsed maintenance window to resume from")]
+    MaintenanceInProgress,
+    #[msg("Receipt is still within its retention period")]
+    ReceiptRetained,
+    #[msg("Announcement URI is too long")]
+    AnnouncementTooLong,
+    #[msg("Invalid announcement severity")]
+    InvalidSeverity,
+    #[msg("The same vault appears more than once in the batch")]
+    DuplicateVault,
+    #[msg("Bet ID is too long")]
+    BetIdTooLong,
+    #[msg("Payout exceeds the game's maximum multiplier")]
+    PayoutExceedsLimit,
+    #[msg("No room for another game limit")]
+    GameLimitsFull,
+    #[msg("Too many settlements for this vault this second")]
+    BetRateLimited,
+    #[msg("Account layout is outdated; call migrate_vault to upgrade it")]
+    VersionMismatch,
+    #[msg("Audit invariant violated")]
+    InvariantViolated,
+    #[msg("Settlements in this transaction exceed the payout limit")]
+    TransactionPayoutExceeded,
+    #[msg("Batch argument vectors differ in length")]
+    LengthMismatch,
+    #[msg("Batch contains no settlements")]
+    EmptyBatch,
+    #[msg("Gem data must be exactly 7 bytes")]
+    BadGemData,
+    #[msg("Gem count exceeds the maximum for its type")]
+    GemCountOutOfRange,
+    #[msg("Remaining accounts do not match the number of settlements")]
+    AccountCountMismatch,
+    #[msg("Settlement cannot share a transaction with a withdraw from the same vault")]
+    BundledWithdraw,
+    #[msg("Settlement has neither a stake nor a payout")]
+    EmptySettlement,
+    #[msg("Address is blacklisted")]
+    Blacklisted,
+    #[msg("Blacklist is full")]
+    BlacklistFull,
+    #[msg("The current registry page still has room")]
+    RegistryPageNotFull,
+    #[msg("Vaults must be registered on the current registry page")]
+    WrongRegistryPage,
+    #[msg("No room for another game program")]
+    GameProgramsFull,
+    #[msg("Reference account does not match the deposit reference")]
+    ReferenceMismatch,
+    #[msg("Trophy base URI too long")]
+    TrophyUriTooLong,
+    #[msg("Trophy config passed without the Bubblegum accounts")]
+    TrophyAccountsMissing,
+    #[msg("Merkle tree or Bubblegum program is not the configured one")]
+    WrongTrophyTree,
+    #[msg("No room for another bridge emitter")]
+    BridgeEmittersFull,
+    #[msg("Not a verified Wormhole VAA carrying a deposit")]
+    InvalidVaa,
+    #[msg("VAA emitter is not a registered deposit contract")]
+    UnknownBridgeEmitter,
+    #[msg("Insufficient funds in bridge pool")]
+    BridgePoolInsufficient,
+    #[msg("Withdraw intent has expired")]
+    IntentExpired,
+    #[msg("No matching ed25519 signature by the vault owner precedes this instruction")]
+    InvalidIntentSignature,
+    #[msg("No room for another attestor")]
+    AttestorsFull,
+    #[msg("Withdrawal above the KYC threshold needs a valid attestation")]
+    KycRequired,
+    #[msg("No room for another region rule")]
+    RegionRulesFull,
+    #[msg("Stake not allowed for this game in the vault's region")]
+    RegionRestricted,
+    #[msg("Not the current terms of service")]
+    InvalidTerms,
+    #[msg("The current terms of service must be accepted before betting")]
+    TermsNotAccepted,
+    #[msg("The vault's registered device key must co-sign")]
+    DeviceSignatureRequired,
+    #[msg("Cool-off is shorter than the platform minimum")]
+    CoolOffTooShort,
+    #[msg("Self-set limit was raised too recently")]
+    LimitRaiseTooSoon,
+    #[msg("The dispute window for this bet has closed")]
+    DisputeWindowClosed,
+    #[msg("This bet has already been disputed")]
+    AlreadyDisputed,
+    #[msg("Deposit must be screened by the configured screening program")]
+    ScreeningProgramMissing,
+    #[msg("No such audit page")]
+    InvalidAuditPage,
+    #[msg("PvP bet has already been accepted")]
+    PvpBetAccepted,
+    #[msg("PvP bet has not been accepted")]
+    PvpBetOpen,
+    #[msg("Winner must be one of the PvP bet's vaults")]
+    InvalidPvpWinner,
+    #[msg("Open PvP challenges need the PvP queue")]
+    PvpQueueMissing,
+    #[msg("PvP queue is full")]
+    PvpQueueFull,
+    #[msg("PvP bet has expired")]
+    PvpBetExpired,
+    #[msg("PvP bet has not expired")]
+    PvpBetNotExpired,
+    #[msg("Betting on this crash round has closed")]
+    CrashBettingClosed,
+    #[msg("Betting on this crash round is still open")]
+    CrashBettingOpen,
+    #[msg("Crash round has already been revealed")]
+    CrashRoundRevealed,
+    #[msg("Crash round has not been revealed")]
+    CrashRoundNotRevealed,
+    #[msg("Revealed seed does not match the crash round commitment")]
+    CrashSeedMismatch,
+    #[msg("Revealed seed does not match the raffle commitment")]
+    RaffleSeedMismatch,
+    #[msg("The bet's event has not started")]
+    EventNotStarted,
+    #[msg("The bet's event has started")]
+    EventStarted,
+    #[msg("Pending bet has not expired")]
+    PendingBetNotExpired,
+    #[msg("Too many side bets in one settlement")]
+    TooManySideBets,
+    #[msg("Insured bet requires the insurance fund account")]
+    InsuranceFundMissing,
+    #[msg("Bet insurance is not offered")]
+    InsuranceUnavailable,
+    #[msg("Insurance premium is below the configured rate")]
+    InsurancePremiumTooLow,
+    #[msg("Insurance fund cannot cover the refund")]
+    InsuranceFundInsufficient,
+    #[msg("Stake is outside the game's configured bounds")]
+    StakeOutOfRange,
+    #[msg("Account is not the game config of this game")]
+    WrongGameConfig,
+    #[msg("Account is not the vault's promo account")]
+    WrongPromoAccount,
+    #[msg("Table round has no room for these seats")]
+    TableFull,
+    #[msg("Vault is not seated at this table round")]
+    NotSeated,
+    #[msg("Table payouts and rake must add up to the pot")]
+    TablePayoutMismatch,
+    #[msg("Vault has free spins of a different value outstanding")]
+    FreeSpinValueMismatch,
+    #[msg("Vault has no free spins")]
+    NoFreeSpins,
+    #[msg("A vault cannot follow itself")]
+    CannotFollowSelf,
+    #[msg("Vault does not follow this leader")]
+    NotFollowing,
+    #[msg("Too many followers to mirror in one instruction")]
+    TooManyFollowers,
+    #[msg("Brackets hold a power of two entrants, up to the maximum, with a prize per standing at most")]
+    BracketSizeInvalid,
+    #[msg("No such match in the current round")]
+    NoSuchMatch,
+    #[msg("Match result already posted")]
+    MatchAlreadyPosted,
+    #[msg("Winner is not in this match")]
+    NotInMatch,
+    #[msg("Not every match of the round has a result")]
+    RoundIncomplete,
+    #[msg("Tournament is already complete")]
+    TournamentOver,
+    #[msg("Tournament is still in progress")]
+    TournamentInProgress,
+    #[msg("Jackpot tiers are 0 (mini), 1 (major) and 2 (grand)")]
+    InvalidJackpotTier,
+    #[msg("Randomness does not hit this jackpot tier")]
+    JackpotNotHit,
+    #[msg("This bet has already won this jackpot tier")]
+    JackpotAlreadyPaid,
+    #[msg("Not the VRF randomness account for this draw")]
+    WrongRandomnessAccount,
+    #[msg("VRF randomness has not been fulfilled yet")]
+    RandomnessPending,
+    #[msg("Receipt is for another game")]
+    ReceiptGameMismatch,
+    #[msg("An upgrade is already announced; cancel it first")]
+    UpgradeAlreadyAnnounced,
+    #[msg("No upgrade is announced")]
+    NoUpgradeAnnounced,
+    #[msg("Earliest upgrade slot gives less than the required notice")]
+    UpgradeNoticeTooShort,
+    #[msg("No deployment since the last recorded upgrade")]
+    UpgradeAlreadyRecorded,
+    #[msg("Deposit is below the minimum")]
+    DepositBelowMinimum,
+    #[msg("Stake is below the minimum")]
+    StakeBelowMinimum,
+    #[msg("Daily net-win cap reached; try again when the window rolls over")]
+    DailyWinCapReached,
+    #[msg("Payout is above the streaming threshold; settle it with bet_and_settle")]
+    PayoutMustStream,
+    #[msg("A payout schedule must be passed exactly when the payout streams")]
+    PayoutScheduleMismatch,
+    #[msg("No payout tranche is due yet")]
+    NoTrancheDue,
+    #[msg("Vault is not the one the receipt was settled against")]
+    ReceiptVaultMismatch,
+    #[msg("Heartbeat is current and the house covers its exposure")]
+    HealthCheckPassed,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn transfer_of_entire_balance_leaves_zero() {
+        assert_eq!(checked_transfer(100, 0, 100).unwrap(), (0, 100));
+    }
+
+    #[test]
+    fn transfer_of_zero_at_limits_is_noop() {
+        assert_eq!(checked_transfer(0, u64::MAX, 0).unwrap(), (0, u64::MAX));
+    }
+
+    #[test]
+    fn transfer_one_lamport_over_balance_is_insufficient() {
+        assert_eq!(checked_transfer(99, 0, 100).unwrap_err(), VaultError::InsufficientFunds.into());
+        assert_eq!(checked_transfer(0, 0, 1).unwrap_err(), VaultError::InsufficientFunds.into());
+    }
+
+    #[test]
+    fn transfer_into_full_account_overflows() {
+        assert_eq!(checked_transfer(1, u64::MAX, 1).unwrap_err(), VaultError::Overflow.into());
+        assert_eq!(checked_transfer(u64::MAX, 1, u64::MAX).unwrap_err(), VaultError::Overflow.into());
+    }
+
+    #[test]
+    fn transfer_max_into_empty_account() {
+        assert_eq!(checked_transfer(u64::MAX, 0, u64::MAX).unwrap(), (0, u64::MAX));
+    }
+
+    #[test]
+    fn event_seq_overflow_is_rejected() {
+        let mut config = GlobalConfig {
+            event_seq: u64::MAX - 1,
+            bump: 0,
+            version: GLOBAL_CONFIG_VERSION,
+            min_deposit: 0,
+            min_stake: 0,
+        };
+        assert_eq!(config.next_seq().unwrap(), u64::MAX);
+        assert_eq!(config.next_seq().unwrap_err(), VaultError::Overflow.into());
+    }
+
+    #[test]
+    fn daily_win_cap_refuses_bets_until_the_window_rolls() {
+        let now = 1_700_000_000;
+        let mut vault: UserVault = anchor_lang::__private::bytemuck::Zeroable::zeroed();
+        vault.check_win_cap(now, 500).unwrap();
+        vault.record_net_win(now, 100, 700).unwrap();
+        assert_eq!(vault.check_win_cap(now + 1, 500).unwrap_err(), VaultError::DailyWinCapReached.into());
+        vault.check_win_cap(now + 1, 0).unwrap();
+
+        vault.daily_win_cap = 1_000;
+        vault.check_win_cap(now + 1, 500).unwrap();
+        vault.record_net_win(now + 2, 0, 400).unwrap();
+        assert_eq!(vault.check_win_cap(now + 3, 500).unwrap_err(), VaultError::DailyWinCapReached.into());
+
+        vault.check_win_cap(now + WIN_WINDOW_SECS, 500).unwrap();
+        assert_eq!({ vault.win_window_net }, 0);
+        vault.record_net_win(now + WIN_WINDOW_SECS, 400, 0).unwrap();
+        assert_eq!({ vault.win_window_net }, -400);
+    }
+
+    #[test]
+    fn global_config_upgrade_zero_fills_the_minimums() {
+        let mut legacy = GlobalConfig::DISCRIMINATOR.to_vec();
+        legacy.extend_from_slice(&42u64.to_le_bytes());
+        legacy.push(254);
+        assert_eq!(GlobalConfig::stored_version(&legacy), 0);
+
+        let upgraded = GlobalConfig::upgrade(&legacy, 0).unwrap();
+        let config = GlobalConfig::try_deserialize(&mut &upgraded[..]).unwrap();
+        assert_eq!(upgraded.len(), GLOBAL_CONFIG_SPACE);
+        assert_eq!((config.event_seq, config.bump, config.version), (42, 254, GLOBAL_CONFIG_VERSION));
+        assert_eq!((config.min_deposit, config.min_stake), (0, 0));
+    }
+
+    #[test]
+    fn dust_deposits_and_stakes_are_refused() {
+        let config = GlobalConfig {
+            event_seq: 0,
+            bump: 0,
+            version: GLOBAL_CONFIG_VERSION,
+            min_deposit: 1_000,
+            min_stake: 100,
+        };
+        config.check_deposit(1_000).unwrap();
+        assert_eq!(config.check_deposit(999).unwrap_err(), VaultError::DepositBelowMinimum.into());
+        config.check_stake(100).unwrap();
+        config.check_stake(0).unwrap();
+        assert_eq!(config.check_stake(99).unwrap_err(), VaultError::StakeBelowMinimum.into());
+    }
+
+    #[test]
+    fn stats_volume_overflow_is_rejected() {
+        let mut stats = GlobalStats {
+            total_users: 0,
+            total_bets: 0,
+            total_volume: u64::MAX,
+            biggest_win: 0,
+            biggest_win_vault: Pubkey::default(),
+            bump: 0,
+            settle_calls: 0,
+            batch_calls: 0,
+            deposit_calls: 0,
+            withdraw_calls: u64::MAX,
+            version: GLOBAL_STATS_VERSION,
+        };
+        stats.record_bet(Pubkey::default(), 0, 0).unwrap();
+        assert_eq!(stats.record_bet(Pubkey::default(), 1, 0).unwrap_err(), VaultError::Overflow.into());
+        stats.record_call(Invocation::Deposit).unwrap();
+        assert_eq!({ stats.deposit_calls }, 1);
+        assert_eq!(stats.record_call(Invocation::Withdraw).unwrap_err(), VaultError::Overflow.into());
+    }
+
+    #[test]
+    fn global_stats_upgrade_zero_fills_the_counters() {
+        let mut legacy = GlobalStats::DISCRIMINATOR.to_vec();
+        legacy.extend_from_slice(&[7; 8 + 8 + 8 + 8 + 32 + 1]);
+        assert_eq!(GlobalStats::stored_version(&legacy), 0);
+
+        let upgraded = GlobalStats::upgrade(&legacy, 0).unwrap();
+        assert_eq!(upgraded.len(), GLOBAL_STATS_SPACE);
+        assert_eq!(upgraded[..legacy.len()], legacy[..]);
+        assert!(upgraded[legacy.len()..GlobalStats::VERSION_OFFSET].iter().all(|&b| b == 0));
+        assert_eq!(GlobalStats::stored_version(&upgraded), GLOBAL_STATS_VERSION);
+    }
+
+    #[test]
+    fn pvp_rake_comes_out_of_decided_pots_only() {
+        let bet = PvpBet {
+            creator: Pubkey::new_unique(),
+            creator_vault: Pubkey::new_unique(),
+            opponent_vault: Pubkey::new_unique(),
+            bet_id_hash: [0; 16],
+            game_id: 1,
+            stake: 1_000,
+            rake_bps: 250,
+            created_at: 0,
+            accepted_at: 1,
+            expires_at: 0,
+            bump: 0,
+        };
+        assert_eq!(bet.payouts(Some(bet.creator_vault)).unwrap(), (1_950, 0, 50));
+        assert_eq!(bet.payouts(Some(bet.opponent_vault)).unwrap(), (0, 1_950, 50));
+        assert_eq!(bet.payouts(None).unwrap(), (1_000, 1_000, 0));
+        assert_eq!(bet.payouts(Some(bet.creator)).unwrap_err(), VaultError::InvalidPvpWinner.into());
+    }
+
+    #[test]
+    fn table_rake_comes_out_of_the_whole_pot() {
+        let seat = |committed| TableSeat { vault: Pubkey::new_unique(), committed };
+        let mut round = TableRound {
+            round_id: 1,
+            game_id: 1,
+            payer: Pubkey::new_unique(),
+            rake_bps: 500,
+            opened_at: 0,
+            seats: vec![seat(1_000), seat(2_000), seat(1_000)],
+            bump: 0,
+        };
+        assert_eq!((round.pot().unwrap(), round.rake().unwrap()), (4_000, 200));
+        let raiser = round.seats[1].vault;
+        round.seat_mut(&raiser).unwrap().committed += 2_000;
+        assert_eq!((round.pot().unwrap(), round.rake().unwrap()), (6_000, 300));
+        assert_eq!(round.seat_mut(&Pubkey::new_unique()).unwrap_err(), VaultError::NotSeated.into());
+    }
+
+    #[test]
+    fn mirrored_bets_scale_to_the_follower_cap() {
+        let follow = CopyFollow { vault: Pubkey::new_unique(), leader: Pubkey::new_unique(), max_stake: 250, bump: 0 };
+        assert_eq!(follow.mirror(1_000, 3_000).unwrap(), (250, 750));
+        assert_eq!(follow.mirror(100, 333).unwrap(), (100, 333));
+        assert_eq!(follow.mirror(1_000, 1).unwrap(), (250, 0));
+        assert_eq!(follow.mirror(1_000, 0).unwrap(), (250, 0));
+    }
+
+    #[test]
+    fn bracket_standings_follow_the_round_each_entrant_lost() {
+        let entrants: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let mut bracket = Tournament {
+            tournament_id: 1,
+            game_id: 1,
+            payer: Pubkey::new_unique(),
+            round: 1,
+            entrants: entrants.clone(),
+            eliminated_in: vec![0; 4],
+            prizes: vec![1_000, 400, 100],
+            bump: 0,
+        };
+        assert_eq!(bracket.total_prizes().unwrap(), 1_600);
+        assert_eq!(bracket.record_result(0, &entrants[1]).unwrap(), entrants[0]);
+        assert_eq!(bracket.record_result(0, &entrants[1]).unwrap_err(), VaultError::MatchAlreadyPosted.into());
+        assert_eq!(bracket.advance().unwrap_err(), VaultError::RoundIncomplete.into());
+        assert_eq!(bracket.record_result(1, &entrants[0]).unwrap_err(), VaultError::NotInMatch.into());
+        bracket.record_result(1, &entrants[2]).unwrap();
+        bracket.advance().unwrap();
+
+        assert_eq!(bracket.record_result(1, &entrants[2]).unwrap_err(), VaultError::NoSuchMatch.into());
+        assert_eq!(bracket.record_result(0, &entrants[2]).unwrap(), entrants[1]);
+        bracket.advance().unwrap();
+        assert!(bracket.is_complete());
+        let prizes: Vec<u64> = (0..4).map(|seed| bracket.prize(seed)).collect();
+        assert_eq!(prizes, vec![100, 400, 1_000, 100]);
+    }
+
+    #[test]
+    fn jackpot_tiers_accrue_and_restart_from_their_seed() {
+        let mut jackpot =
+            Jackpot { game_id: 1, vrf_authority: Pubkey::new_unique(), tiers: Default::default(), bump: 0 };
+        assert_eq!(jackpot.set_tier(0, 100, 1_000, 10).unwrap(), 1_000);
+        assert_eq!(jackpot.set_tier(2, 50, 100_000, 0).unwrap(), 100_000);
+        assert_eq!(jackpot.set_tier(1, 9_900, 0, 0).unwrap_err(), VaultError::InvalidAmount.into());
+        assert_eq!(jackpot.set_tier(3, 0, 0, 0).unwrap_err(), VaultError::InvalidJackpotTier.into());
+
+        assert_eq!(jackpot.contribute(10_000).unwrap(), 150);
+        assert_eq!((jackpot.tiers[0].pot, jackpot.tiers[2].pot), (1_100, 100_050));
+        assert_eq!(jackpot.reset_tier(0).unwrap(), (1_100, 1_000));
+        assert_eq!((jackpot.tiers[0].pot, jackpot.tiers[0].hits), (1_000, 1));
+
+        // A disabled tier never hits, whatever the randomness
+        assert!((0..=255u8).all(|byte| !jackpot.is_hit(2, &[byte; 64])));
+        assert!((0..=255u8).any(|byte| jackpot.is_hit(0, &[byte; 64])));
+
+        // One draw per bet and tier
+        let (key, bet) = (Pubkey::new_unique(), bet_id_hash("bet-1"));
+        assert_eq!(Jackpot::vrf_seed(&key, &bet, 0), Jackpot::vrf_seed(&key, &bet, 0));
+        assert_ne!(Jackpot::vrf_seed(&key, &bet, 0), Jackpot::vrf_seed(&key, &bet, 1));
+        assert_ne!(Jackpot::vrf_seed(&key, &bet, 0), Jackpot::vrf_seed(&key, &bet_id_hash("bet-2"), 0));
+    }
+
+    #[test]
+    fn raffle_draw_needs_the_committed_seed() {
+        let seed = [7u8; 32];
+        let mut raffle = Raffle {
+            raffle_id: 1,
+            entry_gem_type: 0,
+            prize: 1_000,
+            closes_at: 0,
+            draw_authority: Pubkey::new_unique(),
+            commitment: keccak::hash(&seed).to_bytes(),
+            total_tickets: 0,
+            winning_ticket: 0,
+            drawn: false,
+            paid: false,
+            bump: 0,
+        };
+        let key = Pubkey::new_unique();
+        assert_eq!(raffle.draw(&key, &seed).unwrap_err(), VaultError::RaffleEmpty.into());
+        raffle.total_tickets = 10;
+        assert_eq!(raffle.draw(&key, &[8u8; 32]).unwrap_err(), VaultError::RaffleSeedMismatch.into());
+        raffle.draw(&key, &seed).unwrap();
+        assert!(raffle.drawn && raffle.winning_ticket < 10);
+        assert_eq!(raffle.draw(&key, &seed).unwrap_err(), VaultError::RaffleAlreadyDrawn.into());
+    }
+
+    #[test]
+    fn pending_bets_cash_out_only_before_the_event_starts() {
+        let bet = PendingBet {
+            vault: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            bet_id_hash: bet_id_hash("bet-1"),
+            game_id: 1,
+            event_id: 7,
+            odds: 250,
+            stake: 100,
+            placed_at: 1_000,
+            expiry: 2_000,
+            expires_at: 3_000,
+            insurance_premium: 0,
+            insurance_refund: 0,
+            bump: 0,
+        };
+        assert!(bet.check_cashout_open(1_999).is_ok());
+        assert_eq!(bet.check_cashout_open(2_000).unwrap_err(), VaultError::EventStarted.into());
+        assert_eq!(bet.check_cashout_open(3_000).unwrap_err(), VaultError::EventStarted.into());
+    }
+
+    #[test]
+    fn a_receipt_can_be_disputed_once_within_the_window() {
+        let mut receipt = BetReceipt {
+            vault: Pubkey::new_unique(),
+            bet_id_hash: bet_id_hash("bet-1"),
+            game_id: 1,
+            stake: 100,
+            payout: 0,
+            settled_at: 1_000,
+            jackpot_tiers_paid: 0,
+            disputed: false,
+            bump: 0,
+        };
+        assert_eq!(receipt.file_dispute(1_500, 0).unwrap_err(), VaultError::DisputeWindowClosed.into());
+        assert_eq!(receipt.file_dispute(1_601, 600).unwrap_err(), VaultError::DisputeWindowClosed.into());
+        receipt.file_dispute(1_600, 600).unwrap();
+        assert!(receipt.disputed);
+        assert_eq!(receipt.file_dispute(1_600, 600).unwrap_err(), VaultError::AlreadyDisputed.into());
+    }
+
+    #[test]
+    fn disputes_freeze_only_the_house_surplus_over_its_exposure() {
+        assert!(check_house_surplus(1_000, 600, 400).is_ok());
+        assert_eq!(check_house_surplus(1_000, 601, 400).unwrap_err(), VaultError::HouseInsufficient.into());
+        assert_eq!(check_house_surplus(1_000, u64::MAX, 1).unwrap_err(), VaultError::Overflow.into());
+    }
+
+    #[test]
+    fn pity_boost_needs_the_full_losing_streak() {
+        let mut config = PityConfig { min_losses: 0, multiplier: 100, bump: 0 };
+        assert_eq!(config.set(3, 99).unwrap_err(), VaultError::InvalidAmount.into());
+        config.set(3, 250).unwrap();
+        assert_eq!(config.boost(2, &[2, 0, 0, 0, 0, 0, 200]), None);
+        assert_eq!(config.boost(3, &[2, 0, 0, 0, 0, 0, 200]), Some(vec![5, 0, 0, 0, 0, 0, 255]));
+        config.set(0, 250).unwrap();
+        assert_eq!(config.boost(u16::MAX, &[2; 7]), None);
+    }
+
+    #[test]
+    fn versioned_layouts_match_their_structs() {
+        assert_eq!(UserVault::SPACE, 8 + std::mem::size_of::<UserVault>());
+        assert_eq!(HouseVault::SPACE, 8 + std::mem::size_of::<HouseVault>());
+        assert_eq!(UserVault::VERSION_OFFSET, 8 + std::mem::offset_of!(UserVault, version));
+        assert_eq!(HouseVault::VERSION_OFFSET, 8 + std::mem::offset_of!(HouseVault, version));
+    }
+
+    #[test]
+    fn gem_data_is_range_checked_per_type() {
+        assert!(check_gem_data(&MAX_GEMS_PER_SETTLEMENT).is_ok());
+        assert_eq!(check_gem_data(&[0; 6]).unwrap_err(), VaultError::BadGemData.into());
+        let mut corrupted = MAX_GEMS_PER_SETTLEMENT;
+        corrupted[6] += 1;
+        assert_eq!(check_gem_data(&corrupted).unwrap_err(), VaultError::GemCountOutOfRange.into());
+    }
+
+    #[test]
+    fn elapsed_maintenance_no_longer_counts_as_paused() {
+        let mut config = PauseConfig {
+            multisig_authority: Pubkey::default(),
+            admin_authority: Pubkey::default(),
+            flags: PAUSE_MAINTENANCE,
+            maintenance_start_time: 1_700_000_000,
+            maintenance_duration_hours: 2,
+            version: PAUSE_CONFIG_VERSION,
+            bump: 0,
+        };
+        assert!(config.is_paused(1_700_000_000 + 7_199));
+        assert!(!config.is_paused(1_700_000_000 + 7_200));
+        config.set_flag(PAUSE_EMERGENCY, true);
+        assert!(config.is_paused(1_700_000_000 + 7_200));
+    }
+
+    #[test]
+    fn heartbeat_goes_overdue_after_its_interval() {
+        let monitor = HealthMonitor {
+            operator: Pubkey::new_unique(),
+            interval_secs: 600,
+            last_heartbeat: 1_700_000_000,
+            house_balance: 0,
+            open_exposure: 0,
+            degraded: false,
+            bump: 0,
+        };
+        assert!(!monitor.is_degraded(1_700_000_600));
+        assert!(monitor.is_degraded(1_700_000_601));
+        assert!(HealthMonitor { degraded: true, ..monitor }.is_degraded(1_700_000_000));
+    }
+
+    #[test]
+    fn house_exposure_release_saturates() {
+        let mut house: HouseVault = anchor_lang::__private::bytemuck::Zeroable::zeroed();
+        house.add_exposure(500).unwrap();
+        house.release_exposure(200);
+        assert_eq!({ house.open_exposure }, 300);
+        house.release_exposure(1_000);
+        assert_eq!({ house.open_exposure }, 0);
+        house.open_exposure = u64::MAX;
+        assert_eq!(house.add_exposure(1).unwrap_err(), VaultError::Overflow.into());
+    }
+
+    fn bonus(bonus_amount: u64, playthrough: u64) -> DepositBonus {
+        DepositBonus {
+            vault: Pubkey::default(),
+            deposit_amount: bonus_amount,
+            bonus_amount,
+            wager_requirement: bonus_amount * playthrough,
+            wagered: 0,
+            expires_at: 0,
+            cleared: false,
+            remaining: bonus_amount,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn forfeit_leaves_deposits_made_after_the_bonus_was_lost() {
+        let mut bonus = bonus(1_000, 5);
+        assert_eq!(bonus.record_loss(600, 0), 600);
+        assert_eq!(bonus.record_loss(100, 300), 0);
+        assert_eq!(bonus.record_loss(900, 0), 400);
+        assert_eq!(bonus.remaining, 0);
+        // A fresh 5_000 deposit sits above rent, but none of it is bonus
+        assert_eq!(bonus.reclaimable(5_000), 0);
+    }
+
+    #[test]
+    fn clawback_reclaims_only_the_bonus_still_held() {
+        let mut bonus = bonus(1_000, 5);
+        // Wagering past the bonus amount leaves it uncleared and partly held
+        bonus.wagered = 2_500;
+        bonus.record_loss(400, 0);
+        assert_eq!(bonus.reclaimable(10_000), 600);
+        // Lost entirely, then a deposit: nothing to claw back
+        bonus.record_loss(700, 0);
+        assert_eq!(bonus.reclaimable(10_000), 0);
+    }
+
+    #[test]
+    fn settlement_losses_spend_the_bonus_before_a_forfeit() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = Vec::new();
+        bonus(1_000, 5).try_serialize(&mut data).unwrap();
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        let mut vault: UserVault = anchor_lang::__private::bytemuck::Zeroable::zeroed();
+        vault.locked_amount = 1_000;
+
+        // A loss settled through any path (batch, table, pending…) comes out of the bonus
+        settle_bonus(&info, &mut vault, 600, 0).unwrap();
+        assert_eq!({ vault.locked_amount }, 400);
+        let state = load_optional::<DepositBonus>(&info).unwrap().unwrap();
+        assert_eq!((state.remaining, state.wagered), (400, 600));
+        // So forfeiting afterwards reclaims only what is left, never the player's deposit
+        assert_eq!(state.reclaimable(10_000), 400);
+
+        // No bonus record: nothing to spend
+        let (mut empty_lamports, mut empty) = (0, Vec::new());
+        let none = AccountInfo::new(&key, false, true, &mut empty_lamports, &mut empty, &crate::ID, false, 0);
+        settle_bonus(&none, &mut vault, 600, 0).unwrap();
+        assert_eq!({ vault.locked_amount }, 400);
+    }
+
+    #[test]
+    fn a_new_loss_back_window_carries_the_unclaimed_balance_over() {
+        let promo = |promo_id, end_time| LossBackPromo { promo_id, start_time: 0, end_time, rate_bps: 1_000, bump: 0 };
+        let mut loss_back =
+            LossBack { vault: Pubkey::default(), promo_id: 0, window_end: 0, accrued: 0, carried: 0, bump: 0 };
+
+        loss_back.accrue(&promo(1, 100), 50).unwrap();
+        assert_eq!(loss_back.claimable(99), 0);
+        assert_eq!(loss_back.claimable(100), 50);
+
+        // The first window's 50 stays owed, and claimable, while the second one accrues
+        loss_back.accrue(&promo(2, 300), 20).unwrap();
+        assert_eq!((loss_back.carried, loss_back.accrued, loss_back.window_end), (50, 20, 300));
+        assert_eq!(loss_back.claimable(200), 50);
+        assert_eq!(loss_back.claimable(300), 70);
+    }
+
+    #[test]
+    fn house_volume_is_broken_down_by_category() {
+        let mut house: HouseVault = anchor_lang::__private::bytemuck::Zeroable::zeroed();
+        house.record_volume(GAME_CATEGORY_SLOTS, 100).unwrap();
+        house.record_volume(GAME_CATEGORY_ORIGINALS, 40).unwrap();
+        house.record_volume(GAME_CATEGORY_SLOTS, 0).unwrap();
+        assert_eq!({ house.category_volume }, [100, 0, 40]);
+        assert_eq!({ house.total_volume }, 140);
+        let unknown = house.record_volume(GAME_CATEGORIES as u8, 1).unwrap_err();
+        assert_eq!(unknown, VaultError::InvalidGameCategory.into());
+        assert_eq!({ house.total_volume }, 140);
+    }
+
+    #[test]
+    fn staking_rebate_follows_the_highest_tier_reached() {
+        let mut config = StakingConfig {
+            mint: Pubkey::default(),
+            escrow: Pubkey::default(),
+            cooldown_secs: 0,
+            tiers: Vec::new(),
+            bump: 0,
+        };
+        let tier = |min_staked, rebate_bps| RebateTier { min_staked, rebate_bps };
+        config.set(86_400, vec![tier(1_000, 10), tier(5_000, 25)]).unwrap();
+        assert_eq!(config.rebate_bps(999), 0);
+        assert_eq!(config.rebate_bps(1_000), 10);
+        assert_eq!(config.rebate_bps(u64::MAX), 25);
+        assert_eq!(config.rebate(5_000, 1_000_000), 2_500);
+        assert_eq!(config.rebate(0, 1_000_000), 0);
+
+        let unordered = config.set(0, vec![tier(5_000, 25), tier(1_000, 10)]).unwrap_err();
+        assert_eq!(unordered, VaultError::InvalidRebateTiers.into());
+        let too_generous = config.set(0, vec![tier(1_000, MAX_REBATE_BPS + 1)]).unwrap_err();
+        assert_eq!(too_generous, VaultError::InvalidRebateTiers.into());
+        assert_eq!(config.tiers.len(), 2);
+    }
+
+    #[test]
+    fn vault_upgrade_zero_fills_new_fields() {
+        let legacy = vec![7; VAULT_VERSION_OFFSET];
+        assert_eq!(UserVault::stored_version(&legacy), 0);
+        let upgraded = UserVault::upgrade(&legacy, 0).unwrap();
+        assert_eq!(upgraded.len(), USER_VAULT_SPACE);
+        assert_eq!(upgraded[..VAULT_VERSION_OFFSET], legacy[..]);
+        assert_eq!(UserVault::stored_version(&upgraded), VAULT_VERSION);
+        assert!(upgraded[VAULT_VERSION_OFFSET + 1..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn pause_config_upgrade_repacks_the_legacy_layout() {
+        let multisig = Pubkey::new_unique();
+        let mut legacy = PauseConfig::DISCRIMINATOR.to_vec();
+        legacy.extend_from_slice(multisig.as_ref());
+        legacy.extend_from_slice(&[0; 32]);
+        legacy.push(1);
+        legacy.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        legacy.extend_from_slice(&[4, 0, 9]);
+        assert_eq!(legacy.len(), LEGACY_PAUSE_CONFIG_LEN);
+        assert_eq!(PauseConfig::stored_version(&legacy), 0);
+
+        let upgraded = PauseConfig::upgrade(&legacy, 0).unwrap();
+        let config = PauseConfig::try_deserialize(&mut &upgraded[..]).unwrap();
+        assert_eq!(upgraded.len(), PAUSE_CONFIG_SPACE);
+        assert_eq!(PauseConfig::stored_version(&upgraded), PAUSE_CONFIG_VERSION);
+        assert_eq!(config.multisig_authority, multisig);
+        assert!(config.maintenance_pause() && !config.emergency_pause());
+        assert_eq!((config.maintenance_start_time, config.version, config.bump), (1_700_000_000, 2, 9));
+        assert_eq!(PauseConfig::upgrade(&upgraded, 1).unwrap_err(), VaultError::VersionMismatch.into());
+    }
+
+    #[test]
+    fn game_config_caps_wins_below_the_fair_multiplier() {
+        let mut config =
+            GameConfig { game_id: 7, house_edge_bps: 0, max_multiplier: 0, min_stake: 0, max_stake: 0, bump: 0 };
+        config.set_rules(100, 10_000, 10, 1_000).unwrap();
+        config.check_settlement(1_000, 99_000).unwrap();
+        assert_eq!(config.check_settlement(1_000, 99_001).unwrap_err(), VaultError::PayoutExceedsLimit.into());
+        assert_eq!(config.check_settlement(9, 0).unwrap_err(), VaultError::StakeOutOfRange.into());
+        assert_eq!(config.check_settlement(1_001, 0).unwrap_err(), VaultError::StakeOutOfRange.into());
+        assert!(config.set_rules(10_000, 10_000, 0, 0).is_err());
+    }
+
+    #[test]
+    fn insurance_premium_must_meet_the_rate() {
+        let mut fund = InsuranceFund {
+            premium_bps: 500,
+            refund_bps: 5_000,
+            reserved: 0,
+            total_premiums: 0,
+            total_refunds: 0,
+            bump: 0,
+        };
+        assert_eq!(fund.refund_for(1_000, 50).unwrap(), 500);
+        assert_eq!(fund.refund_for(1_000, 49).unwrap_err(), VaultError::InsurancePremiumTooLow.into());
+        fund.refund_bps = 0;
+        assert_eq!(fund.refund_for(1_000, 50).unwrap_err(), VaultError::InsuranceUnavailable.into());
+    }
+
+    #[test]
+    fn payout_schedule_vests_tranche_by_tranche() {
+        let mut schedule = PayoutSchedule {
+            vault: Pubkey::new_unique(),
+            bet_id_hash: [0; 16],
+            total: 1_000,
+            claimed: 0,
+            tranches: 3,
+            start_time: 1_700_000_000,
+            interval_secs: 100,
+            rent_payer: Pubkey::new_unique(),
+            bump: 0,
+        };
+        assert_eq!(schedule.claimable(1_700_000_099), 0);
+        assert_eq!(schedule.claimable(1_700_000_100), 333);
+        schedule.claimed = 333;
+        assert_eq!(schedule.claimable(1_700_000_250), 333);
+        assert_eq!(schedule.claimable(1_700_000_300), 667);
+        assert_eq!(schedule.claimable(i64::MAX), 667);
+        assert_eq!(schedule.claimable(0), 0);
+    }
+
+    #[test]
+    fn crash_point_is_deterministic_and_at_least_one_x() {
+        for round_id in 0..500u64 {
+            let seed = keccak::hash(&round_id.to_be_bytes()).to_bytes();
+            let point = crash_point(&seed, round_id);
+            assert!(point >= 100);
+            assert_eq!(point, crash_point(&seed, round_id));
+        }
+        assert_eq!(crash_payout(1_000, 250).unwrap(), 2_500);
+    }
+
+    #[test]
+    fn posted_vaa_requires_guardian_magic() {
+        let mut payload = vec![BRIDGE_DEPOSIT_PAYLOAD_ID];
+        payload.extend_from_slice(Pubkey::new_unique().as_ref());
+        payload.extend_from_slice(&5_000u64.to_be_bytes());
+
+        let mut data = b"vaa".to_vec();
+        data.resize(PostedVaa::SEQUENCE_OFFSET, 0);
+        data.extend_from_slice(&7u64.to_le_bytes());
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&[9; 32]);
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend_from_slice(&payload);
+
+        let vaa = PostedVaa::parse(&data).unwrap();
+        assert_eq!((vaa.sequence, vaa.emitter_chain, vaa.emitter_address), (7, 2, [9; 32]));
+        assert_eq!(parse_bridge_deposit(&vaa.payload).unwrap().1, 5_000);
+
+        data[..3].copy_from_slice(b"msg");
+        assert_eq!(PostedVaa::parse(&data).unwrap_err(), VaultError::InvalidVaa.into());
+    }
+
+    #[test]
+    fn region_rules_cap_stakes_per_game() {
+        let config = ComplianceConfig {
+            kyc_withdraw_threshold: 0,
+            large_transfer_threshold: 0,
+            region_rules: vec![
+                RegionRule { region: 3, game_id: None, max_stake: 1_000 },
+                RegionRule { region: 3, game_id: Some(7), max_stake: 0 },
+            ],
+            terms_hash: [0; 32],
+            terms_required: false,
+            screening_program: Pubkey::default(),
+            screening_threshold: 0,
+            attestors: Vec::new(),
+            bump: 0,
+        };
+        assert!(config.check_region(3, 1, 1_000).is_ok());
+        assert_eq!(config.check_region(3, 1, 1_001).unwrap_err(), VaultError::RegionRestricted.into());
+        assert_eq!(config.check_region(3, 7, 1).unwrap_err(), VaultError::RegionRestricted.into());
+        assert!(config.check_region(3, 7, 0).is_ok());
+        assert!(config.check_region(4, 7, 5_000).is_ok());
+    }
+
+    #[test]
+    fn snapshot_ring_overwrites_oldest() {
+        let mut snapshots = StatsSnapshots { interval_secs: 1, last_recorded: 0, next: 0, entries: Vec::new(), bump: 0 };
+        let snapshot = |timestamp| StatsSnapshot { timestamp, total_users: 0, total_bets: 0, total_volume: 0, house_balance: 0 };
+        for timestamp in 1..=STATS_SNAPSHOT_SLOTS as i64 + 2 {
+            snapshots.record(snapshot(timestamp));
+        }
+        assert_eq!(snapshots.entries.len(), STATS_SNAPSHOT_SLOTS);
+        assert_eq!(snapshots.entries[0].timestamp, STATS_SNAPSHOT_SLOTS as i64 + 1);
+        assert_eq!(snapshots.entries[1].timestamp, STATS_SNAPSHOT_SLOTS as i64 + 2);
+        assert_eq!(snapshots.entries[2].timestamp, 3);
+        assert_eq!(snapshots.next, 2);
+        assert_eq!(snapshots.last_recorded, STATS_SNAPSHOT_SLOTS as i64 + 2);
+    }
+
+    /// One settlement against vault `vault % vaults.len()`
+    fn settlement() -> impl Strategy<Value = (usize, u64, u64)> {
+        let amount = prop_oneof![0..1_000_000_000u64, Just(0u64), Just(u64::MAX), any::<u64>()];
+        (any::<usize>(), amount.clone(), amount)
+    }
+
+    proptest! {
+        // Settlement fuzz target: random vault balances and batches of bets,
+        // settled in order the way batch_settle does. Whatever succeeds or
+        // fails, lamports are only ever moved between house and vaults.
+        #[test]
+        fn settlements_conserve_lamports(
+            house in any::<u64>(),
+            vaults in prop::collection::vec(any::<u64>(), 1..=10),
+            batch in prop::collection::vec(settlement(), 1..=10),
+        ) {
+            let total = |house: u64, vaults: &[u64]| house as u128 + vaults.iter().map(|&v| v as u128).sum::<u128>();
+            let expected = total(house, &vaults);
+            let (mut house, mut vaults) = (house, vaults);
+
+            for (vault, stake, payout) in batch {
+                let vault = vault % vaults.len();
+                match settle_balances(vaults[vault], house, stake, payout) {
+                    Ok((vault_after, house_after)) => {
+                        let house_pays = if stake == 0 { payout } else { payout.saturating_sub(stake) };
+                        let vault_pays = if stake == 0 { 0 } else { stake.saturating_sub(payout) };
+                        prop_assert_eq!(vault_after as u128, vaults[vault] as u128 + house_pays as u128 - vault_pays as u128);
+                        vaults[vault] = vault_after;
+                        house = house_after;
+                    }
+                    Err(err) => prop_assert!(
+                        err == VaultError::InsufficientFunds.into()
+                            || err == VaultError::HouseInsufficient.into()
+                            || err == VaultError::Overflow.into()
+                    ),
+                }
+                prop_assert_eq!(total(house, &vaults), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Accounts seeded into the validator's genesis
+//!
+//! Global PDAs are written as the init instructions would leave them, so a
+//! scenario can start from any state without replaying the setup
+//! transactions (several of which only the admin or multisig may sign).
+
+use anchor_lang::{AccountSerialize, Discriminator};
+use solana_sdk::account::Account;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+
+use smart_vault_v2::{
+    Blacklist, DepositBonus, GameLimits, GlobalConfig, GlobalStats, HouseVault, PauseConfig, PromoPool, UserVault,
+    BLACKLIST_SPACE, DEPOSIT_BONUS_SPACE, GAME_CATEGORIES, GAME_LIMITS_SPACE, GLOBAL_CONFIG_SPACE, GLOBAL_CONFIG_VERSION,
+    GLOBAL_STATS_VERSION, HOUSE_VERSION, PAUSE_CONFIG_SPACE, PROMO_POOL_SPACE, VAULT_VERSION,
+};
+use sols_bet_sdk::{pda, PROGRAM_ID, VAULT_VERSION_OFFSET};
+
+/// Lamports every player wallet and vault starts with
+pub const PLAYER_FUNDS: u64 = 10 * LAMPORTS_PER_SOL;
+
+/// Lamports the house vault starts with
+pub const HOUSE_FUNDS: u64 = 1_000 * LAMPORTS_PER_SOL;
+
+pub fn admin() -> Pubkey {
+    "4y1oXmheqD5VNScoNwLH17WQQExXSxBasH6TTwCb4iN5".parse().unwrap()
+}
+
+pub fn multisig() -> Pubkey {
+    "BMprzPNF9FTni4mJWwCJnk91ZzhKdxGCx7BwPckMRzBt".parse().unwrap()
+}
+
+/// System-owned account holding `lamports`
+pub fn wallet(lamports: u64) -> Account {
+    Account::new(lamports, 0, &solana_sdk::system_program::ID)
+}
+
+/// Program-owned account holding `data`, zero-padded to `space` bytes and at least rent-exempt
+pub fn program_account(mut data: Vec<u8>, space: usize, lamports: u64) -> Account {
+    data.resize(space, 0);
+    Account {
+        lamports: lamports.max(Rent::default().minimum_balance(space)),
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn borsh_account<T: AccountSerialize>(account: &T, space: usize) -> Account {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    program_account(data, space, 0)
+}
+
+pub fn zero_copy_account<T: Discriminator + bytemuck::Pod>(account: &T, lamports: u64) -> Account {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(account));
+    let space = data.len();
+    program_account(data, space, lamports)
+}
+
+/// A fresh current-layout vault of `owner`
+pub fn user_vault(owner: &Pubkey) -> UserVault {
+    UserVault {
+        owner: *owner,
+        bump: pda::vault(owner).1,
+        locked_amount: 0,
+        active_games: 0,
+        accum_wager: 0,
+        version: VAULT_VERSION,
+        created_at: 0,
+        lifetime_wager: 0,
+        rate_window_start: 0,
+        rate_window_bets: 0,
+        transfer_window_start: 0,
+        transfer_window_total: 0,
+        region: 0,
+        terms_hash: [0; 32],
+        terms_accepted_at: 0,
+        free_spins: 0,
+        free_spin_value: 0,
+        loss_streak: 0,
+        win_window_start: 0,
+        win_window_net: 0,
+        daily_win_cap: 0,
+    }
+}
+
+/// `owner`'s wallet and current-layout vault, both funded with [`PLAYER_FUNDS`]
+pub fn player(owner: &Pubkey) -> Vec<(Pubkey, Account)> {
+    vec![
+        (*owner, wallet(PLAYER_FUNDS)),
+        (pda::vault(owner).0, zero_copy_account(&user_vault(owner), PLAYER_FUNDS)),
+    ]
+}
+
+/// Like [`player`], with `bonus` more lamports in the vault locked by an expired, uncleared deposit bonus
+pub fn bonus_player(owner: &Pubkey, bonus: u64) -> Vec<(Pubkey, Account)> {
+    let vault = pda::vault(owner).0;
+    let record = DepositBonus {
+        vault,
+        deposit_amount: bonus,
+        bonus_amount: bonus,
+        wager_requirement: 10 * bonus,
+        wagered: 0,
+        expires_at: 0,
+        cleared: false,
+        remaining: bonus,
+        bump: pda::deposit_bonus(&vault).1,
+    };
+    let state = UserVault { locked_amount: bonus, ..user_vault(owner) };
+    vec![
+        (*owner, wallet(PLAYER_FUNDS)),
+        (vault, zero_copy_account(&state, PLAYER_FUNDS + bonus)),
+        (pda::deposit_bonus(&vault).0, borsh_account(&record, DEPOSIT_BONUS_SPACE)),
+    ]
+}
+
+/// `owner`'s wallet and a vault stored at the version before the current one
+///
+/// The data ends right after the version byte, as a vault created before any
+/// of the later fields existed, so `migrate_vault` has to grow it.
+pub fn legacy_player(owner: &Pubkey) -> Vec<(Pubkey, Account)> {
+    let mut data = UserVault::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&user_vault(owner)));
+    data.truncate(VAULT_VERSION_OFFSET + 1);
+    data[VAULT_VERSION_OFFSET] = VAULT_VERSION - 1;
+    let space = data.len();
+    vec![(*owner, wallet(PLAYER_FUNDS)), (pda::vault(owner).0, program_account(data, space, PLAYER_FUNDS))]
+}
+
+/// Authorities, house vault and global PDAs, with generous game limits and `pause` as the pause state
+pub fn globals(pause: PauseConfig) -> Vec<(Pubkey, Account)> {
+    let house = HouseVault {
+        bump: pda::house_vault().1,
+        multisig_authority: multisig(),
+        admin_authority: admin(),
+        total_volume: 0,
+        version: HOUSE_VERSION,
+        open_exposure: 0,
+        category_volume: [0; GAME_CATEGORIES],
+    };
+    let stats = GlobalStats {
+        total_users: 0,
+        total_bets: 0,
+        total_volume: 0,
+        biggest_win: 0,
+        biggest_win_vault: Pubkey::default(),
+        bump: pda::global_stats().1,
+        settle_calls: 0,
+        batch_calls: 0,
+        deposit_calls: 0,
+        withdraw_calls: 0,
+        version: GLOBAL_STATS_VERSION,
+    };
+    let limits = GameLimits {
+        default_max_multiplier: 10_000,
+        max_unstaked_payout: 100 * LAMPORTS_PER_SOL,
+        max_bets_per_second: 0,
+        max_transaction_payout: 0,
+        max_daily_net_win: 0,
+        payout_stream_threshold: 0,
+        payout_stream_tranches: 0,
+        payout_stream_interval_secs: 0,
+        games: Vec::new(),
+        bump: pda::game_limits().1,
+    };
+    let config = GlobalConfig {
+        event_seq: 0,
+        bump: pda::global_config().1,
+        version: GLOBAL_CONFIG_VERSION,
+        min_deposit: 0,
+        min_stake: 0,
+    };
+    let blacklist = Blacklist { addresses: Vec::new(), bump: pda::blacklist().1 };
+    let promo_pool = PromoPool { bump: pda::promo_pool().1, total_granted: 0, total_forfeited: 0 };
+
+    vec![
+        (admin(), wallet(100 * LAMPORTS_PER_SOL)),
+        (multisig(), wallet(100 * LAMPORTS_PER_SOL)),
+        (pda::house_vault().0, zero_copy_account(&house, HOUSE_FUNDS)),
+        (pda::global_stats().0, zero_copy_account(&stats, 0)),
+        (pda::pause_config().0, borsh_account(&pause, PAUSE_CONFIG_SPACE)),
+        (pda::global_config().0, borsh_account(&config, GLOBAL_CONFIG_SPACE)),
+        (pda::game_limits().0, borsh_account(&limits, GAME_LIMITS_SPACE)),
+        (pda::blacklist().0, borsh_account(&blacklist, BLACKLIST_SPACE)),
+        (pda::promo_pool().0, borsh_account(&promo_pool, PROMO_POOL_SPACE)),
+    ]
+}
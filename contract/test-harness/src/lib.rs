@@ -0,0 +1,173 @@
+//! Local-validator fixture and scenario harness for Smart Vault V2
+//!
+//! [`Harness::start`] boots a `solana-test-validator` running the SBF build
+//! of the program (run `anchor build` first) with the global PDAs already
+//! initialized and a funded house and player vaults in place. The
+//! [`scenarios`] module builds the transactions of the common flows, shared
+//! by the integration tests here and the keeper's end-to-end tests.
+//!
+//! The admin and multisig keys are hardcoded in the program, so what they
+//! sign is simulated with signature checks off: [`Harness::simulate`] returns
+//! the logs and the post-state of the accounts a scenario watches. Scenarios
+//! signed by a player or the payer can be sent for real with [`Harness::send`].
+
+pub mod fixtures;
+pub mod scenarios;
+
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::client_error::ClientError;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_test_validator::{TestValidator, TestValidatorGenesis};
+
+use smart_vault_v2::{PauseConfig, PAUSE_CONFIG_VERSION};
+use sols_bet_sdk::{pda, PROGRAM_ID};
+
+use crate::scenarios::Scenario;
+
+/// Initial chain state; the default is unpaused with no players
+#[derive(Clone, Debug, Default)]
+pub struct Setup {
+    /// Players with a funded current-layout vault
+    pub players: usize,
+    /// Players whose vault still uses the previous layout
+    pub legacy_players: usize,
+    /// `PAUSE_EMERGENCY` | `PAUSE_MAINTENANCE`
+    pub pause_flags: u8,
+    pub maintenance_start_time: u32,
+    pub maintenance_duration_hours: u8,
+    /// Lamports of an expired, uncleared deposit bonus locked in each player's vault (0 = none)
+    pub player_bonus: u64,
+}
+
+/// A running local validator seeded from a [`Setup`]
+pub struct Harness {
+    pub validator: TestValidator,
+    pub rpc: RpcClient,
+    /// The validator's funded mint keypair, fee payer of permissionless instructions
+    pub payer: Keypair,
+    pub players: Vec<Keypair>,
+    pub legacy_players: Vec<Keypair>,
+}
+
+/// Result of a simulated scenario
+pub struct Outcome {
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units: u64,
+    /// Post-state of [`Scenario::watch`], in that order; empty when the transaction failed
+    pub accounts: Vec<Option<Account>>,
+}
+
+impl Outcome {
+    /// Panic with the program logs unless the scenario succeeded
+    pub fn assert_ok(&self) -> &Self {
+        if let Some(err) = &self.err {
+            panic!("scenario failed: {err}\n{}", self.logs.join("\n"));
+        }
+        self
+    }
+
+    /// Lamports of the `index`th watched account afterwards
+    pub fn lamports(&self, index: usize) -> u64 {
+        self.accounts[index].as_ref().map_or(0, |account| account.lamports)
+    }
+}
+
+impl Harness {
+    /// Start a validator with the program and the accounts of `setup`
+    ///
+    /// The program is loaded from `SBF_OUT_DIR` (or `BPF_OUT_DIR`), defaulting
+    /// to the workspace's `target/deploy`.
+    pub fn start(setup: Setup) -> Self {
+        if std::env::var_os("SBF_OUT_DIR").is_none() && std::env::var_os("BPF_OUT_DIR").is_none() {
+            std::env::set_var("SBF_OUT_DIR", concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy"));
+        }
+
+        let players: Vec<Keypair> = (0..setup.players).map(|_| Keypair::new()).collect();
+        let legacy_players: Vec<Keypair> = (0..setup.legacy_players).map(|_| Keypair::new()).collect();
+        let pause = PauseConfig {
+            multisig_authority: fixtures::multisig(),
+            admin_authority: fixtures::admin(),
+            flags: setup.pause_flags,
+            maintenance_start_time: setup.maintenance_start_time,
+            maintenance_duration_hours: setup.maintenance_duration_hours,
+            version: PAUSE_CONFIG_VERSION,
+            bump: pda::pause_config().1,
+        };
+
+        let mut accounts = fixtures::globals(pause);
+        accounts.extend(players.iter().flat_map(|player| match setup.player_bonus {
+            0 => fixtures::player(&player.pubkey()),
+            bonus => fixtures::bonus_player(&player.pubkey(), bonus),
+        }));
+        accounts.extend(legacy_players.iter().flat_map(|player| fixtures::legacy_player(&player.pubkey())));
+
+        let (validator, payer) = TestValidatorGenesis::default()
+            .add_program("smart_vault_v2", PROGRAM_ID)
+            .add_accounts(accounts.into_iter().map(|(address, account)| (address, AccountSharedData::from(account))))
+            .start();
+        let rpc = RpcClient::new_with_commitment(validator.rpc_url(), CommitmentConfig::confirmed());
+        Self { validator, rpc, payer, players, legacy_players }
+    }
+
+    /// Simulate `scenario` without signature checks and return its logs and watched accounts
+    pub fn simulate(&self, scenario: &Scenario) -> Outcome {
+        let message = Message::new(&scenario.instructions, Some(&scenario.signer));
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: false,
+            replace_recent_blockhash: true,
+            commitment: Some(CommitmentConfig::confirmed()),
+            accounts: Some(RpcSimulateTransactionAccountsConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                addresses: scenario.watch.iter().map(ToString::to_string).collect(),
+            }),
+            ..RpcSimulateTransactionConfig::default()
+        };
+        let result = self
+            .rpc
+            .simulate_transaction_with_config(&Transaction::new_unsigned(message), config)
+            .unwrap_or_else(|err| panic!("simulating {}: {err}", scenario.name))
+            .value;
+        Outcome {
+            err: result.err,
+            logs: result.logs.unwrap_or_default(),
+            units: result.units_consumed.unwrap_or_default(),
+            accounts: result
+                .accounts
+                .unwrap_or_default()
+                .into_iter()
+                .map(|account| account.and_then(|account| account.decode()))
+                .collect(),
+        }
+    }
+
+    /// Sign `scenario` with the harness keypair of its signer and send it
+    pub fn send(&self, scenario: &Scenario) -> Result<Signature, ClientError> {
+        let signer = self
+            .keypair(&scenario.signer)
+            .unwrap_or_else(|| panic!("{}: {} is not a harness keypair", scenario.name, scenario.signer));
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction =
+            Transaction::new_signed_with_payer(&scenario.instructions, Some(&scenario.signer), &[signer], blockhash);
+        self.rpc.send_and_confirm_transaction(&transaction)
+    }
+
+    /// Current state of `address`
+    pub fn account(&self, address: &Pubkey) -> Account {
+        self.rpc.get_account(address).unwrap_or_else(|err| panic!("fetching {address}: {err}"))
+    }
+
+    fn keypair(&self, address: &Pubkey) -> Option<&Keypair> {
+        std::iter::once(&self.payer)
+            .chain(&self.players)
+            .chain(&self.legacy_players)
+            .find(|keypair| keypair.pubkey() == *address)
+    }
+}
@@ -0,0 +1,126 @@
+//! Transactions of the flows integration tests exercise
+//!
+//! Each builder returns a [`Scenario`]: the instructions, who signs them and
+//! the accounts whose post-state the test checks. Settlements and pauses are
+//! signed by the hardcoded admin or multisig, so run them with
+//! [`crate::Harness::simulate`]; player-signed ones can also be sent.
+
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+use sols_bet_sdk::instructions::{self, Settlement, SettlementExtras};
+use sols_bet_sdk::pda;
+
+use crate::fixtures::{admin, multisig};
+
+pub struct Scenario {
+    pub name: String,
+    /// Fee payer and only signer
+    pub signer: Pubkey,
+    pub instructions: Vec<Instruction>,
+    /// Accounts whose post-state [`crate::Outcome::accounts`] returns, in this order
+    pub watch: Vec<Pubkey>,
+}
+
+fn settlement(owner: &Pubkey, bet: &str, stake: u64, payout: u64) -> Settlement {
+    Settlement {
+        owner: *owner,
+        stake,
+        payout,
+        bet_id: bet.to_string(),
+        game_id: 1,
+        category: 0,
+        gem_data: vec![0; 7],
+    }
+}
+
+/// Admin settles a bet of `owner` paying `payout` for `stake`; watches the vault, then the house
+pub fn win(owner: &Pubkey, stake: u64, payout: u64) -> Scenario {
+    let bet = settlement(owner, "harness-win", stake, payout);
+    Scenario {
+        name: "win".into(),
+        signer: admin(),
+        instructions: vec![instructions::bet_and_settle(&admin(), &bet, SettlementExtras::default())],
+        watch: vec![pda::vault(owner).0, pda::house_vault().0],
+    }
+}
+
+/// Admin settles a lost bet of `owner`; watches the vault, then the house
+pub fn loss(owner: &Pubkey, stake: u64) -> Scenario {
+    let bet = settlement(owner, "harness-loss", stake, 0);
+    Scenario {
+        name: "loss".into(),
+        signer: admin(),
+        instructions: vec![instructions::bet_and_settle(&admin(), &bet, SettlementExtras::default())],
+        watch: vec![pda::vault(owner).0, pda::house_vault().0],
+    }
+}
+
+/// Admin batch-settles one bet per owner, doubling the stake of even positions and losing the odd ones
+///
+/// Watches every vault in `owners` order, then the house.
+pub fn batch(owners: &[Pubkey], stake: u64) -> Scenario {
+    let settlements: Vec<Settlement> = owners
+        .iter()
+        .enumerate()
+        .map(|(i, owner)| {
+            let payout = if i % 2 == 0 { 2 * stake } else { 0 };
+            settlement(owner, &format!("harness-batch-{i}"), stake, payout)
+        })
+        .collect();
+    let mut watch: Vec<Pubkey> = owners.iter().map(|owner| pda::vault(owner).0).collect();
+    watch.push(pda::house_vault().0);
+    Scenario {
+        name: "batch".into(),
+        signer: admin(),
+        instructions: vec![instructions::batch_settle(&admin(), &settlements)],
+        watch,
+    }
+}
+
+/// Admin batch-settles a lost bet of `owner`, then forfeits their expired bonus; watches the vault, then the promo pool
+pub fn bonus_loss_then_forfeit(owner: &Pubkey, stake: u64) -> Scenario {
+    let bet = settlement(owner, "harness-bonus-loss", stake, 0);
+    let vault = pda::vault(owner).0;
+    Scenario {
+        name: "bonus_loss_then_forfeit".into(),
+        signer: admin(),
+        instructions: vec![instructions::batch_settle(&admin(), &[bet]), instructions::forfeit_bonus(&vault)],
+        watch: vec![vault, pda::promo_pool().0],
+    }
+}
+
+/// Multisig emergency pause, or admin maintenance pause; watches the pause config
+pub fn pause(emergency: bool) -> Scenario {
+    let (signer, instruction) = if emergency {
+        (multisig(), instructions::emergency_pause(&multisig()))
+    } else {
+        (admin(), instructions::start_maintenance_pause(&admin()))
+    };
+    Scenario {
+        name: if emergency { "emergency_pause" } else { "maintenance_pause" }.into(),
+        signer,
+        instructions: vec![instruction],
+        watch: vec![pda::pause_config().0],
+    }
+}
+
+/// `owner` deposits `amount` into their vault; watches the vault
+pub fn deposit(owner: &Pubkey, amount: u64) -> Scenario {
+    Scenario {
+        name: "deposit".into(),
+        signer: *owner,
+        instructions: vec![instructions::deposit(owner, amount)],
+        watch: vec![pda::vault(owner).0],
+    }
+}
+
+/// `owner` brings their vault to the current layout; watches the vault
+pub fn migration(owner: &Pubkey) -> Scenario {
+    Scenario {
+        name: "migration".into(),
+        signer: *owner,
+        instructions: vec![instructions::migrate_vault(owner)],
+        watch: vec![pda::vault(owner).0],
+    }
+}
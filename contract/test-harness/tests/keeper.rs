@@ -0,0 +1,63 @@
+//! Keeper cranks end to end: due instructions found over RPC, sent and applied
+//!
+//! Needs the SBF build: run `anchor build`, then
+//! `cargo test --manifest-path test-harness/Cargo.toml`.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use solana_sdk::signature::Signer;
+
+use smart_vault_v2::PAUSE_MAINTENANCE;
+use sols_bet_keeper::cranks::{AutomationTick, Crank, ExpiredPendingBets, ExpiredPvpBets, MaintenanceResume};
+use sols_bet_sdk::{decode_account, pda, PauseConfig};
+use sols_bet_test_harness::scenarios::Scenario;
+use sols_bet_test_harness::{Harness, Setup};
+
+fn now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+#[test]
+fn maintenance_resume_lifts_an_elapsed_window() {
+    let harness = Harness::start(Setup {
+        pause_flags: PAUSE_MAINTENANCE,
+        maintenance_start_time: (now() - 5 * 3600) as u32,
+        maintenance_duration_hours: 4,
+        ..Setup::default()
+    });
+
+    let instructions = MaintenanceResume.due(&harness.rpc, now()).unwrap();
+    assert_eq!(instructions.len(), 1);
+    let scenario = Scenario {
+        name: MaintenanceResume.name().into(),
+        signer: harness.payer.pubkey(),
+        instructions,
+        watch: vec![pda::pause_config().0],
+    };
+    harness.send(&scenario).unwrap();
+
+    let pause: PauseConfig = decode_account(&harness.account(&pda::pause_config().0).data).unwrap();
+    assert!(!pause.maintenance_pause());
+    assert!(MaintenanceResume.due(&harness.rpc, now()).unwrap().is_empty());
+}
+
+#[test]
+fn maintenance_resume_waits_for_the_window() {
+    let harness = Harness::start(Setup {
+        pause_flags: PAUSE_MAINTENANCE,
+        maintenance_start_time: now() as u32,
+        maintenance_duration_hours: 4,
+        ..Setup::default()
+    });
+
+    assert!(MaintenanceResume.due(&harness.rpc, now()).unwrap().is_empty());
+}
+
+#[test]
+fn snapshot_and_expiry_cranks_idle_on_a_fresh_deploy() {
+    let harness = Harness::start(Setup { players: 1, ..Setup::default() });
+
+    assert!(AutomationTick.due(&harness.rpc, now()).unwrap().is_empty());
+    assert!(ExpiredPendingBets.due(&harness.rpc, now()).unwrap().is_empty());
+    assert!(ExpiredPvpBets.due(&harness.rpc, now()).unwrap().is_empty());
+}
@@ -0,0 +1,100 @@
+//! Scenario flows against a local validator
+//!
+//! Needs the SBF build: run `anchor build`, then
+//! `cargo test --manifest-path test-harness/Cargo.toml`.
+
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signer;
+
+use smart_vault_v2::{UserVault, DEPOSIT_BONUS_SPACE, PAUSE_EMERGENCY, PROMO_POOL_SPACE, VAULT_VERSION};
+use sols_bet_sdk::constants::USER_VAULT_SPACE;
+use sols_bet_sdk::{decode_account, PauseConfig, VAULT_VERSION_OFFSET};
+use sols_bet_test_harness::fixtures::{HOUSE_FUNDS, PLAYER_FUNDS};
+use sols_bet_test_harness::{scenarios, Harness, Setup};
+
+const STAKE: u64 = LAMPORTS_PER_SOL / 10;
+
+#[test]
+fn win_moves_the_net_payout_from_the_house() {
+    let harness = Harness::start(Setup { players: 1, ..Setup::default() });
+    let owner = harness.players[0].pubkey();
+
+    let outcome = harness.simulate(&scenarios::win(&owner, STAKE, 3 * STAKE));
+    outcome.assert_ok();
+    assert_eq!(outcome.lamports(0), PLAYER_FUNDS + 2 * STAKE);
+    assert_eq!(outcome.lamports(1), HOUSE_FUNDS - 2 * STAKE);
+}
+
+#[test]
+fn loss_moves_the_stake_to_the_house() {
+    let harness = Harness::start(Setup { players: 1, ..Setup::default() });
+    let owner = harness.players[0].pubkey();
+
+    let outcome = harness.simulate(&scenarios::loss(&owner, STAKE));
+    outcome.assert_ok();
+    assert_eq!(outcome.lamports(0), PLAYER_FUNDS - STAKE);
+    assert_eq!(outcome.lamports(1), HOUSE_FUNDS + STAKE);
+}
+
+#[test]
+fn batch_settles_every_vault() {
+    let harness = Harness::start(Setup { players: 4, ..Setup::default() });
+    let owners: Vec<_> = harness.players.iter().map(Signer::pubkey).collect();
+
+    let outcome = harness.simulate(&scenarios::batch(&owners, STAKE));
+    outcome.assert_ok();
+    for index in 0..owners.len() {
+        let expected = if index % 2 == 0 { PLAYER_FUNDS + STAKE } else { PLAYER_FUNDS - STAKE };
+        assert_eq!(outcome.lamports(index), expected, "vault {index}");
+    }
+    assert_eq!(outcome.lamports(owners.len()), HOUSE_FUNDS);
+}
+
+#[test]
+fn batch_losses_spend_the_bonus_before_a_forfeit() {
+    let bonus = LAMPORTS_PER_SOL;
+    let harness = Harness::start(Setup { players: 1, player_bonus: bonus, ..Setup::default() });
+    let owner = harness.players[0].pubkey();
+
+    let outcome = harness.simulate(&scenarios::bonus_loss_then_forfeit(&owner, STAKE));
+    outcome.assert_ok();
+    // The loss came out of the bonus, so the forfeit leaves the deposit whole and unlocked
+    assert_eq!(outcome.lamports(0), PLAYER_FUNDS);
+    let data = &outcome.accounts[0].as_ref().unwrap().data;
+    let vault: UserVault = bytemuck::pod_read_unaligned(&data[8..8 + std::mem::size_of::<UserVault>()]);
+    assert_eq!({ vault.locked_amount }, 0);
+    let rent = Rent::default();
+    let pool = rent.minimum_balance(PROMO_POOL_SPACE) + rent.minimum_balance(DEPOSIT_BONUS_SPACE) + bonus - STAKE;
+    assert_eq!(outcome.lamports(1), pool);
+}
+
+#[test]
+fn emergency_pause_sets_the_flag() {
+    let harness = Harness::start(Setup::default());
+
+    let outcome = harness.simulate(&scenarios::pause(true));
+    outcome.assert_ok();
+    let pause: PauseConfig = decode_account(&outcome.accounts[0].as_ref().unwrap().data).unwrap();
+    assert!(pause.emergency_pause());
+}
+
+#[test]
+fn deposit_is_refused_while_paused() {
+    let harness = Harness::start(Setup { players: 1, pause_flags: PAUSE_EMERGENCY, ..Setup::default() });
+    let owner = harness.players[0].pubkey();
+
+    assert!(harness.send(&scenarios::deposit(&owner, STAKE)).is_err());
+}
+
+#[test]
+fn migration_grows_a_legacy_vault_to_the_current_layout() {
+    let harness = Harness::start(Setup { legacy_players: 1, ..Setup::default() });
+    let owner = harness.legacy_players[0].pubkey();
+    let scenario = scenarios::migration(&owner);
+
+    harness.send(&scenario).unwrap();
+    let vault = harness.account(&scenario.watch[0]);
+    assert_eq!(vault.data.len(), USER_VAULT_SPACE);
+    assert_eq!(vault.data[VAULT_VERSION_OFFSET], VAULT_VERSION);
+}
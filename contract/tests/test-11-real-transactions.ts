@@ -77,7 +77,8 @@ const SMART_VAULT_IDL = {
                 { "name": "systemProgram", "isMut": false, "isSigner": false }
             ],
             "args": [
-                { "name": "amount", "type": "u64" }
+                { "name": "amount", "type": "u64" },
+                { "name": "reference", "type": { "option": { "array": ["u8", 32] } } }
             ]
         },
         {
@@ -332,11 +333,10 @@ class RealTransactionTester {
                 // Create deposit transaction with the correct wallet
                 const programWithWallet = this.getProgramWithWallet(wallet);
                 const tx = await programWithWallet.methods
-                    .deposit(new anchor.BN(depositLamports))
+                    .deposit(new anchor.BN(depositLamports), null)
                     .accounts({
                         vault: wallet.vaultPda,
                         owner: wallet.keypair.publicKey,
-                        user: wallet.keypair.publicKey,
                         systemProgram: SystemProgram.programId,
                     })
                     .rpc();
@@ -134,10 +134,10 @@ const SMART_VAULT_IDL = {
                 "fields": [
                     { "name": "multisigAuthority", "type": "publicKey" },
                     { "name": "adminAuthority", "type": "publicKey" },
-                    { "name": "maintenancePause", "type": "bool" },
-                    { "name": "maintenanceStartTime", "type": "i64" },
+                    { "name": "flags", "type": "u8" },
+                    { "name": "maintenanceStartTime", "type": "u32" },
                     { "name": "maintenanceDurationHours", "type": "u8" },
-                    { "name": "emergencyPause", "type": "bool" },
+                    { "name": "version", "type": "u8" },
                     { "name": "bump", "type": "u8" }
                 ]
             }
@@ -497,16 +497,15 @@ class HouseSetupTester {
             
                         console.log(`      Multisig Authority: ${pauseConfig.multisigAuthority.toBase58()}`);
              console.log(`      Admin Authority: ${pauseConfig.adminAuthority.toBase58()}`);
-             console.log(`      Maintenance Pause: ${pauseConfig.maintenancePause}`);
+             console.log(`      Maintenance Pause: ${(pauseConfig.flags & 2) !== 0}`);
              console.log(`      Maintenance Start Time: ${pauseConfig.maintenanceStartTime}`);
              console.log(`      Maintenance Duration Hours: ${pauseConfig.maintenanceDurationHours}`);
-             console.log(`      Emergency Pause: ${pauseConfig.emergencyPause}`);
+             console.log(`      Emergency Pause: ${(pauseConfig.flags & 1) !== 0}`);
              
              // Verify configuration
              assert(pauseConfig.multisigAuthority.equals(this.context.multisigAuthority), "Multisig authority mismatch");
              assert(pauseConfig.adminAuthority.equals(this.context.adminAuthority), "Admin authority mismatch");
-             assert(!pauseConfig.maintenancePause, "Maintenance pause should start false");
-             assert(!pauseConfig.emergencyPause, "Emergency pause should start false");
+             assert(pauseConfig.flags === 0, "Pause flags should start cleared");
             
                          console.log("   ✅ Pause config data verified");
             
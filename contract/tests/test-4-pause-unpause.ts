@@ -421,7 +421,7 @@ class PauseUnpauseTester {
         // Check current pause state
         try {
             const pauseConfig = await this.program.account.pauseConfig.fetch(this.pauseConfigPda);
-            const isPaused = pauseConfig.emergencyPause;
+            const isPaused = (pauseConfig.flags & 1) !== 0;
 
             console.log(`   📊 Current pause state: ${isPaused ? 'PAUSED' : 'ACTIVE'}`);
 
@@ -465,11 +465,10 @@ class PauseUnpauseTester {
             const depositAmount = 0.001 * LAMPORTS_PER_SOL;
 
             await this.program.methods
-                .deposit(new anchor.BN(depositAmount))
+                .deposit(new anchor.BN(depositAmount), null)
                 .accounts({
                     vault: this.testWallet.vaultPda,
                     owner: this.testWallet.keypair.publicKey,
-                    user: this.testWallet.keypair.publicKey,
                     systemProgram: SystemProgram.programId,
                 })
                 .signers([this.testWallet.keypair])
@@ -513,6 +512,7 @@ class PauseUnpauseTester {
                     new anchor.BN(0), // Total loss
                     betId,
                     new anchor.BN(gameId),
+                    0, // Slots
                     Buffer.from(gemData)
                 )
                 .accounts({
@@ -562,6 +562,7 @@ class PauseUnpauseTester {
                     new anchor.BN(creditAmount),
                     betId,
                     new anchor.BN(gameId),
+                    0, // Slots
                     Buffer.from(gemData)
                 )
                 .accounts({
@@ -630,8 +631,8 @@ class PauseUnpauseTester {
             const pauseConfig = await this.program.account.pauseConfig.fetch(this.pauseConfigPda);
 
             console.log(`   📊 Final State:`);
-            console.log(`      Emergency Pause: ${pauseConfig.emergencyPause}`);
-            console.log(`      Maintenance Pause: ${pauseConfig.maintenancePause}`);
+            console.log(`      Emergency Pause: ${(pauseConfig.flags & 1) !== 0}`);
+            console.log(`      Maintenance Pause: ${(pauseConfig.flags & 2) !== 0}`);
             console.log(`      Multisig Authority: ${(pauseConfig.multisigAuthority as PublicKey).toBase58()}`);
             console.log(`      Admin Authority: ${(pauseConfig.adminAuthority as PublicKey).toBase58()}`);
 
@@ -644,8 +645,8 @@ class PauseUnpauseTester {
             console.log(`      Vault: ${(vaultBalance / LAMPORTS_PER_SOL).toFixed(6)} SOL`);
 
             this.results.finalState = {
-                emergencyPause: pauseConfig.emergencyPause,
-                maintenancePause: pauseConfig.maintenancePause,
+                emergencyPause: (pauseConfig.flags & 1) !== 0,
+                maintenancePause: (pauseConfig.flags & 2) !== 0,
                 multisigAuthority: (pauseConfig.multisigAuthority as PublicKey).toBase58(),
                 adminAuthority: (pauseConfig.adminAuthority as PublicKey).toBase58(),
                 testWalletBalance: walletBalance / LAMPORTS_PER_SOL,
@@ -39,7 +39,9 @@ export function useSmartVault() {
         "accounts": [
           { "name": "vault", "isMut": true, "isSigner": false },
           { "name": "user", "isMut": true, "isSigner": true },
-          { "name": "systemProgram", "isMut": false, "isSigner": false }
+          { "name": "systemProgram", "isMut": false, "isSigner": false },
+          { "name": "globalStats", "isMut": true, "isSigner": false },
+          { "name": "blacklist", "isMut": false, "isSigner": false }
         ],
         "args": []
       },
@@ -48,11 +50,21 @@ export function useSmartVault() {
         "accounts": [
           { "name": "vault", "isMut": true, "isSigner": false },
           { "name": "owner", "isMut": true, "isSigner": true },
-          { "name": "user", "isMut": true, "isSigner": false },
           { "name": "pauseConfig", "isMut": false, "isSigner": false },
-          { "name": "systemProgram", "isMut": false, "isSigner": false }
+          { "name": "systemProgram", "isMut": false, "isSigner": false },
+          { "name": "globalConfig", "isMut": true, "isSigner": false },
+          { "name": "globalStats", "isMut": true, "isSigner": false },
+          { "name": "blacklist", "isMut": false, "isSigner": false },
+          { "name": "complianceConfig", "isMut": false, "isSigner": false },
+          { "name": "screeningProgram", "isMut": false, "isSigner": false, "isOptional": true },
+          { "name": "screeningState", "isMut": false, "isSigner": false, "isOptional": true },
+          { "name": "eventAuthority", "isMut": false, "isSigner": false },
+          { "name": "program", "isMut": false, "isSigner": false }
         ],
-        "args": [{ "name": "amount", "type": "u64" }]
+        "args": [
+          { "name": "amount", "type": "u64" },
+          { "name": "reference", "type": { "option": { "array": ["u8", 32] } } }
+        ]
       },
       {
         "name": "withdraw",
@@ -60,7 +72,14 @@ export function useSmartVault() {
           { "name": "vault", "isMut": true, "isSigner": false },
           { "name": "owner", "isMut": true, "isSigner": true },
           { "name": "pauseConfig", "isMut": false, "isSigner": false },
-          { "name": "systemProgram", "isMut": false, "isSigner": false }
+          { "name": "globalConfig", "isMut": true, "isSigner": false },
+          { "name": "globalStats", "isMut": true, "isSigner": false },
+          { "name": "complianceConfig", "isMut": false, "isSigner": false },
+          { "name": "kycAttestation", "isMut": false, "isSigner": false, "isOptional": true },
+          { "name": "withdrawGuard", "isMut": false, "isSigner": false },
+          { "name": "device", "isMut": false, "isSigner": true, "isOptional": true },
+          { "name": "eventAuthority", "isMut": false, "isSigner": false },
+          { "name": "program", "isMut": false, "isSigner": false }
         ],
         "args": [{ "name": "amount", "type": "u64" }]
       }
@@ -72,15 +91,39 @@ export function useSmartVault() {
           "kind": "struct",
           "fields": [
             { "name": "owner", "type": "publicKey" },
-            { "name": "balance", "type": "u64" },
+            { "name": "bump", "type": "u8" },
             { "name": "lockedAmount", "type": "u64" },
-            { "name": "activeGames", "type": "u32" }
+            { "name": "activeGames", "type": "u32" },
+            { "name": "accumWager", "type": "u64" },
+            { "name": "version", "type": "u8" },
+            { "name": "createdAt", "type": "i64" },
+            { "name": "lifetimeWager", "type": "u64" },
+            { "name": "rateWindowStart", "type": "i64" },
+            { "name": "rateWindowBets", "type": "u32" },
+            { "name": "transferWindowStart", "type": "i64" },
+            { "name": "transferWindowTotal", "type": "u64" },
+            { "name": "region", "type": "u8" },
+            { "name": "termsHash", "type": { "array": ["u8", 32] } },
+            { "name": "termsAcceptedAt", "type": "i64" },
+            { "name": "freeSpins", "type": "u32" },
+            { "name": "freeSpinValue", "type": "u64" },
+            { "name": "lossStreak", "type": "u16" },
+            { "name": "winWindowStart", "type": "i64" },
+            { "name": "winWindowNet", "type": "i64" },
+            { "name": "dailyWinCap", "type": "u64" }
           ]
         }
       }
     ]
   } as anchor.Idl;
 
+  // Program PDA of `seeds`
+  const findPDA = (...seeds: (string | Buffer)[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      seeds.map((seed) => (typeof seed === 'string' ? Buffer.from(seed) : seed)),
+      SMART_VAULT_PROGRAM_ID,
+    )[0];
+
   const { connection } = useConnection();
 
   useEffect(() => {
@@ -151,6 +194,8 @@ export function useSmartVault() {
         vault: userVault,
         user: publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
+        globalStats: findPDA('global_stats'),
+        blacklist: findPDA('blacklist'),
         })
         .transaction();
 
@@ -277,12 +322,19 @@ export function useSmartVault() {
       );
 
       // Create a unique transaction with fresh blockhash to prevent duplication
-      const transaction = await program.methods.deposit(new anchor.BN(lamports)).accounts({
+      const transaction = await program.methods.deposit(new anchor.BN(lamports), null).accounts({
         vault: vaultPubkey,
         owner: publicKey,
-        user: publicKey,
         pauseConfig: pauseConfigPDA,
         systemProgram: anchor.web3.SystemProgram.programId,
+        globalConfig: findPDA('global_config'),
+        globalStats: findPDA('global_stats'),
+        blacklist: findPDA('blacklist'),
+        complianceConfig: findPDA('compliance_config'),
+        screeningProgram: null,
+        screeningState: null,
+        eventAuthority: findPDA('__event_authority'),
+        program: SMART_VAULT_PROGRAM_ID,
       }).transaction();
 
       // Add a small random delay to prevent rapid successive transactions
@@ -473,7 +525,14 @@ export function useSmartVault() {
           vault: vaultPubkey,
           owner: publicKey,
           pauseConfig: pauseConfigPDA,
-          systemProgram: anchor.web3.SystemProgram.programId,
+          globalConfig: findPDA('global_config'),
+          globalStats: findPDA('global_stats'),
+          complianceConfig: findPDA('compliance_config'),
+          kycAttestation: null,
+          withdrawGuard: findPDA('withdraw_guard', vaultPubkey.toBuffer()),
+          device: null,
+          eventAuthority: findPDA('__event_authority'),
+          program: SMART_VAULT_PROGRAM_ID,
         }).transaction();
 
         // Add unique memo instruction with attempt number
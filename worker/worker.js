@@ -137,21 +137,24 @@ function createInstructionDataWithMixedTypes(methodName, stake, payout, multipli
 // All operations now use createBetAndSettleInstruction with appropriate stake/payout values
 
 // Create instruction data for betAndSettle with String and u64 parameters
-function createBatchSettleInstructionData(stakes, payouts, betIds, gameIds, gemDatas) {
+function createBatchSettleInstructionData(owners, stakes, payouts, betIds, gameIds, categories, gemDatas) {
   const hash = crypto.createHash('sha256');
   hash.update(`global:batch_settle`);
   const disc = hash.digest().slice(0, 8);
   
   // Proper Borsh encoding for batch_settle parameters:
-  // Vec<u64> stakes, Vec<u64> payouts, Vec<String> bet_ids, Vec<u64> game_ids, Vec<Vec<u8>> gem_datas
+  // Vec<Pubkey> owners, Vec<u64> stakes, Vec<u64> payouts, Vec<String> bet_ids, Vec<u64> game_ids,
+  // Vec<u8> categories, Vec<Vec<u8>> gem_datas
   
   // Calculate total size needed
   let totalSize = 8; // discriminator
+  totalSize += 4 + owners.length * 32; // Vec<Pubkey> owners
   totalSize += 4 + stakes.length * 8; // Vec<u64> stakes  
   totalSize += 4 + payouts.length * 8; // Vec<u64> payouts
   totalSize += 4; // Vec<String> bet_ids length
   betIds.forEach(id => totalSize += 4 + Buffer.byteLength(id, 'utf8')); // String lengths + content
   totalSize += 4 + gameIds.length * 8; // Vec<u64> game_ids
+  totalSize += 4 + categories.length; // Vec<u8> categories
   totalSize += 4; // Vec<Vec<u8>> gem_datas length  
   gemDatas.forEach(data => totalSize += 4 + data.length); // Vec<u8> lengths + content
   
@@ -162,6 +165,14 @@ function createBatchSettleInstructionData(stakes, payouts, betIds, gameIds, gemD
   disc.copy(buffer, offset);
   offset += 8;
   
+  // Vec<Pubkey> owners
+  buffer.writeUInt32LE(owners.length, offset);
+  offset += 4;
+  owners.forEach(owner => {
+    owner.toBuffer().copy(buffer, offset);
+    offset += 32;
+  });
+  
   // Vec<u64> stakes
   buffer.writeUInt32LE(stakes.length, offset);
   offset += 4;
@@ -197,6 +208,14 @@ function createBatchSettleInstructionData(stakes, payouts, betIds, gameIds, gemD
     offset += 8;
   });
   
+  // Vec<u8> categories
+  buffer.writeUInt32LE(categories.length, offset);
+  offset += 4;
+  categories.forEach(category => {
+    buffer.writeUInt8(category, offset);
+    offset += 1;
+  });
+  
   // Vec<Vec<u8>> gem_datas
   buffer.writeUInt32LE(gemDatas.length, offset);
   offset += 4;
@@ -210,12 +229,12 @@ function createBatchSettleInstructionData(stakes, payouts, betIds, gameIds, gemD
   return buffer;
 }
 
-function createBetAndSettleInstructionData(stake, payout, betId, gameId, gemData = [0,0,0,0,0,0,0]) {
+function createBetAndSettleInstructionData(stake, payout, betId, gameId, category, gemData = [0,0,0,0,0,0,0]) {
   const hash = crypto.createHash('sha256');
   hash.update(`global:bet_and_settle`);
   const disc = hash.digest().slice(0, 8);
   
-  // bet_and_settle: stake(u64) + payout(u64) + bet_id(String) + game_id(u64) + gem_data(Vec<u8>)
+  // bet_and_settle: stake(u64) + payout(u64) + bet_id(String) + game_id(u64) + category(u8) + gem_data(Vec<u8>)
   const betIdBytes = Buffer.from(betId, 'utf8');
   const betIdLength = betIdBytes.length;
   
@@ -226,7 +245,7 @@ function createBetAndSettleInstructionData(stake, payout, betId, gameId, gemData
     gemDataArray.fill(0, gemDataArray.length, 7);
   }
   
-  const buf = Buffer.alloc(8 + 8 + 8 + 4 + betIdLength + 8 + 4 + 7);
+  const buf = Buffer.alloc(8 + 8 + 8 + 4 + betIdLength + 8 + 1 + 4 + 7);
   let offset = 0;
   
   // Discriminator (8 bytes)
@@ -251,6 +270,10 @@ function createBetAndSettleInstructionData(stake, payout, betId, gameId, gemData
   buf.writeBigUInt64LE(BigInt(parseInt(gameId) || 0), offset);
   offset += 8;
   
+  // category: u8 (1 byte)
+  buf.writeUInt8(category, offset);
+  offset += 1;
+  
   // gem_data: Vec<u8> (length + bytes)
   buf.writeUInt32LE(7, offset); // Always 7 bytes
   offset += 4;
@@ -258,13 +281,13 @@ function createBetAndSettleInstructionData(stake, payout, betId, gameId, gemData
     buf.writeUInt8(byte, offset + i);
   });
   
-  console.log(`🔍 [DEBUG] betAndSettle - stake: ${stake}, payout: ${payout}, betId: ${betId}, gameId: ${gameId}, gemData: ${gemDataArray}`);
+  console.log(`🔍 [DEBUG] betAndSettle - stake: ${stake}, payout: ${payout}, betId: ${betId}, gameId: ${gameId}, category: ${category}, gemData: ${gemDataArray}`);
   console.log(`🔍 [DEBUG] Data: ${buf.toString('hex')}`);
   
   return buf;
 }
 
-function createBetAndSettleInstruction(userVaultPDA, houseVaultPDA, authority, stake, payout, betId, gameId) {
+function createBetAndSettleInstruction(userVaultPDA, authority, stake, payout, betId, gameId) {
   // Validate amounts
   if (!Number.isSafeInteger(stake) || stake < 0) {
     throw new Error("Stake must be non-negative u64");
@@ -280,17 +303,9 @@ function createBetAndSettleInstruction(userVaultPDA, houseVaultPDA, authority, s
   }
   
   return new TransactionInstruction({
-    keys: [
-      { pubkey: userVaultPDA, isSigner: false, isWritable: true },
-      { pubkey: houseVaultPDA, isSigner: false, isWritable: true },
-      { pubkey: authority, isSigner: true, isWritable: false },
-      { pubkey: pauseConfigPDA, isSigner: false, isWritable: false },
-      { pubkey: anchor.web3.SystemProgram.programId, isSigner: false, isWritable: false },
-      { pubkey: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
-      { pubkey: anchor.web3.SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
-    ],
+    keys: betAndSettleKeys(userVaultPDA, authority, gameId),
     programId: SMART_VAULT_PROGRAM_ID,
-    data: createBetAndSettleInstructionData(stake, payout, betId, gameId),
+    data: createBetAndSettleInstructionData(stake, payout, betId, gameId, WORKER_GAME_CATEGORY),
   });
 }
 
@@ -306,6 +321,91 @@ const [pauseConfigPDA] = anchor.web3.PublicKey.findProgramAddressSync(
   SMART_VAULT_PROGRAM_ID
 );
 
+// Program PDA of `seeds`
+function findPDA(...seeds) {
+  return anchor.web3.PublicKey.findProgramAddressSync(seeds.map(seed => Buffer.from(seed)), SMART_VAULT_PROGRAM_ID)[0];
+}
+
+// game_id as the little-endian u64 the per-game PDAs are seeded with
+function gameIdSeed(gameId) {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(BigInt(parseInt(gameId) || 0));
+  return buf;
+}
+
+const globalConfigPDA = findPDA("global_config");
+const globalStatsPDA = findPDA("global_stats");
+const gameLimitsPDA = findPDA("game_limits");
+const blacklistPDA = findPDA("blacklist");
+const complianceConfigPDA = findPDA("compliance_config");
+const lossBackPromoPDA = findPDA("lossback_promo");
+const eventAuthorityPDA = findPDA("__event_authority");
+
+// Every game this worker settles comes from the slots provider (GAME_CATEGORY_SLOTS)
+const WORKER_GAME_CATEGORY = 0;
+
+// Settlements per batch_settle: each adds its vault, game config, bonus and loss-back record
+// to the 12 context accounts, and a legacy transaction fits only two within 1232 bytes
+const MAX_BATCH_SETTLEMENTS = 2;
+
+// Anchor takes the program id in the slot of an optional account left out
+const OMITTED = { pubkey: SMART_VAULT_PROGRAM_ID, isSigner: false, isWritable: false };
+
+const readonly = pubkey => ({ pubkey, isSigner: false, isWritable: false });
+const writable = pubkey => ({ pubkey, isSigner: false, isWritable: true });
+
+// Accounts of bet_and_settle, in the order of the program's BetAndSettle context
+function betAndSettleKeys(userVaultPDA, authority, gameId) {
+  return [
+    writable(userVaultPDA),
+    writable(houseVaultPDA),
+    { pubkey: authority, isSigner: true, isWritable: true },
+    readonly(pauseConfigPDA),
+    writable(findPDA("bonus", userVaultPDA.toBuffer())),
+    OMITTED, // gem_inventory
+    readonly(lossBackPromoPDA),
+    writable(findPDA("lossback", userVaultPDA.toBuffer())),
+    OMITTED, // pity_config
+    OMITTED, // bet_receipt
+    writable(globalConfigPDA),
+    readonly(anchor.web3.SystemProgram.programId),
+    writable(globalStatsPDA),
+    readonly(gameLimitsPDA),
+    readonly(anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY),
+    readonly(blacklistPDA),
+    readonly(complianceConfigPDA),
+    readonly(findPDA("game_config", gameIdSeed(gameId))),
+    writable(findPDA("jackpot", gameIdSeed(gameId))),
+    // game_programs, the trophy accounts, payout_schedule, staking_config and token_stake
+    ...Array(11).fill(OMITTED),
+    readonly(eventAuthorityPDA),
+    readonly(SMART_VAULT_PROGRAM_ID),
+  ];
+}
+
+// Accounts of batch_settle: the BatchSettle context, then the vaults, their game configs, bonuses and loss-back records
+function batchSettleKeys(userVaultPDAs, authority, gameIds) {
+  return [
+    writable(houseVaultPDA),
+    { pubkey: authority, isSigner: true, isWritable: false },
+    readonly(pauseConfigPDA),
+    writable(globalConfigPDA),
+    writable(globalStatsPDA),
+    readonly(gameLimitsPDA),
+    readonly(anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY),
+    readonly(blacklistPDA),
+    OMITTED, // game_programs
+    readonly(complianceConfigPDA),
+    readonly(lossBackPromoPDA),
+    readonly(eventAuthorityPDA),
+    readonly(SMART_VAULT_PROGRAM_ID),
+    ...userVaultPDAs.map(writable),
+    ...gameIds.map(gameId => readonly(findPDA("game_config", gameIdSeed(gameId)))),
+    ...userVaultPDAs.map(vault => writable(findPDA("bonus", vault.toBuffer()))),
+    ...userVaultPDAs.map(vault => writable(findPDA("lossback", vault.toBuffer()))),
+  ];
+}
+
 console.log(`🏠 House vault PDA: ${houseVaultPDA.toBase58()}`);
 console.log(`⏸️ Pause config PDA: ${pauseConfigPDA.toBase58()}`);
 console.log(`✅ Basic setup completed successfully!`);
@@ -1457,15 +1557,10 @@ async function executeSingleBlockchainTransaction(transactionData, requestId) {
   const startTime = Date.now();
   
   // Build manual bet and settle transaction
-  const betSettleData = createBetAndSettleInstructionData(stake_lamports, payout_lamports, bet_id, game_id);
+  const betSettleData = createBetAndSettleInstructionData(stake_lamports, payout_lamports, bet_id, game_id, WORKER_GAME_CATEGORY);
   
   const betSettleIx = new anchor.web3.TransactionInstruction({
-    keys: [
-      { pubkey: userVaultPDA, isSigner: false, isWritable: true },
-      { pubkey: houseVaultPDA, isSigner: false, isWritable: true },
-      { pubkey: systemKeypair.publicKey, isSigner: true, isWritable: false },
-      { pubkey: pauseConfigPDA, isSigner: false, isWritable: false },
-    ],
+    keys: betAndSettleKeys(userVaultPDA, systemKeypair.publicKey, game_id),
     programId: SMART_VAULT_PROGRAM_ID,
     data: betSettleData,
   });
@@ -1489,8 +1584,8 @@ async function executeBatchBlockchainTransactions(transactionsData, requestId) {
   
   // Using manual instruction building instead of Anchor program
   
-  if (transactionsData.length === 0 || transactionsData.length > 10) {
-    throw new Error('Batch size must be between 1 and 10');
+  if (transactionsData.length === 0 || transactionsData.length > MAX_BATCH_SETTLEMENTS) {
+    throw new Error(`Batch size must be between 1 and ${MAX_BATCH_SETTLEMENTS}`);
   }
   
   // Prepare batch data
@@ -1515,27 +1610,26 @@ async function executeBatchBlockchainTransactions(transactionsData, requestId) {
     betIds.push(tx.bet_id);
     gameIds.push(tx.game_id);
     gemDatas.push(tx.gem_data);
-    userVaultPDAs.push({
-      pubkey: new anchor.web3.PublicKey(tx.user_vault_pda),
-      isWritable: true,
-      isSigner: false
-    });
+    userVaultPDAs.push(new anchor.web3.PublicKey(tx.user_vault_pda));
   }
   
   const startTime = Date.now();
   
-  // Build manual batch settle transaction
-  const batchSettleData = createBatchSettleInstructionData(stakes, payouts, betIds, gameIds, gemDatas);
+  // batch_settle names each vault's owner; it is the first field after the 8-byte discriminator
+  const vaultAccounts = await provider.connection.getMultipleAccountsInfo(userVaultPDAs);
+  const owners = vaultAccounts.map((account, i) => {
+    if (!account) {
+      throw new Error(`User vault not found on chain: ${userVaultPDAs[i].toBase58()}`);
+    }
+    return new anchor.web3.PublicKey(account.data.subarray(8, 40));
+  });
+  const categories = transactionsData.map(() => WORKER_GAME_CATEGORY);
   
-  const accounts = [
-    { pubkey: houseVaultPDA, isSigner: false, isWritable: true },
-    { pubkey: systemKeypair.publicKey, isSigner: true, isWritable: false },
-    { pubkey: pauseConfigPDA, isSigner: false, isWritable: false },
-    ...userVaultPDAs.map(vault => ({ pubkey: vault.pubkey, isSigner: false, isWritable: true }))
-  ];
+  // Build manual batch settle transaction
+  const batchSettleData = createBatchSettleInstructionData(owners, stakes, payouts, betIds, gameIds, categories, gemDatas);
   
   const batchSettleIx = new anchor.web3.TransactionInstruction({
-    keys: accounts,
+    keys: batchSettleKeys(userVaultPDAs, systemKeypair.publicKey, gameIds),
     programId: SMART_VAULT_PROGRAM_ID,
     data: batchSettleData,
   });
@@ -1576,15 +1670,10 @@ async function executeAtomicSettlement(userVaultPDA, stakeAmount, payoutAmount,
   const startTime = Date.now();
   
   // Build manual bet and settle transaction
-  const betSettleData = createBetAndSettleInstructionData(stakeAmount, payoutAmount, betId, gameId);
+  const betSettleData = createBetAndSettleInstructionData(stakeAmount, payoutAmount, betId, gameId, WORKER_GAME_CATEGORY);
   
   const betSettleIx = new anchor.web3.TransactionInstruction({
-    keys: [
-      { pubkey: userVaultPDA, isSigner: false, isWritable: true },
-      { pubkey: houseVaultPDA, isSigner: false, isWritable: true },
-      { pubkey: systemKeypair.publicKey, isSigner: true, isWritable: false },
-      { pubkey: pauseConfigPDA, isSigner: false, isWritable: false },
-    ],
+    keys: betAndSettleKeys(userVaultPDA, systemKeypair.publicKey, gameId),
     programId: SMART_VAULT_PROGRAM_ID,
     data: betSettleData,
   });
@@ -2243,7 +2332,7 @@ async function processBlockchainQueue() {
   try {
     // Get pending transactions
     const { data: pendingTxs, error: fetchError } = await supabase.rpc('get_pending_blockchain_transactions', {
-      p_limit: 10
+      p_limit: MAX_BATCH_SETTLEMENTS
     });
     
     if (fetchError) {